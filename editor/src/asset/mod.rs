@@ -98,6 +98,7 @@ struct ContextMenu {
     menu: RcUiNodeHandle,
     open: Handle<UiNode>,
     duplicate: Handle<UiNode>,
+    create_variant: Handle<UiNode>,
     copy_path: Handle<UiNode>,
     copy_file_name: Handle<UiNode>,
     show_in_explorer: Handle<UiNode>,
@@ -151,6 +152,7 @@ impl ContextMenu {
         let show_in_explorer;
         let open;
         let duplicate;
+        let create_variant;
         let copy_path;
         let copy_file_name;
         let dependencies;
@@ -170,6 +172,12 @@ impl ContextMenu {
                                 .build(ctx);
                             duplicate
                         })
+                        .with_child({
+                            create_variant = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Create Variant"))
+                                .build(ctx);
+                            create_variant
+                        })
                         .with_child({
                             copy_path = MenuItemBuilder::new(WidgetBuilder::new())
                                 .with_content(MenuItemContent::text("Copy Full Path"))
@@ -211,6 +219,7 @@ impl ContextMenu {
             menu,
             open,
             duplicate,
+            create_variant,
             copy_path,
             delete,
             show_in_explorer,
@@ -288,6 +297,22 @@ impl ContextMenu {
                             }
                         }
                     }
+                } else if message.destination() == self.create_variant {
+                    if let Some(model) = item.resource::<Model>() {
+                        if let (Some(parent), Some(stem)) =
+                            (item.path.parent(), item.path.file_stem())
+                        {
+                            let stem = format!("{}_Variant", stem.to_string_lossy());
+                            let mut path = parent.join(format!("{stem}.rgs"));
+                            if path.exists() {
+                                path = make_unique_path(parent, &stem, "rgs");
+                            }
+                            Log::verify(fyrox::asset::ResourceData::save(
+                                &mut Model::new_variant(&model),
+                                &path,
+                            ));
+                        }
+                    }
                 } else if message.destination() == self.copy_path {
                     if let Ok(canonical_path) = item.path.canonicalize() {
                         put_path_to_clipboard(engine, canonical_path.as_os_str())
@@ -20,6 +20,7 @@
 
 use crate::{
     fyrox::{
+        asset::pack::{PackWriter, PackedResourceIo},
         core::{
             log::{Log, LogMessage, MessageKind},
             pool::Handle,
@@ -61,7 +62,7 @@ use std::{
     ffi::OsStr,
     fmt::{Display, Formatter},
     fs,
-    io::{self, BufRead, BufReader},
+    io::{self, BufRead, BufReader, BufWriter},
     path::{Path, PathBuf},
     process::Stdio,
     sync::{
@@ -80,6 +81,8 @@ struct ExportOptions {
     target_platform: TargetPlatform,
     destination_folder: PathBuf,
     include_used_assets: bool,
+    /// Put all the assets into a single pack file instead of copying them as loose files. PC only.
+    pack_assets: bool,
     assets_folders: Vec<PathBuf>,
     ignored_extensions: Vec<String>,
     #[reflect(hidden)]
@@ -97,6 +100,7 @@ impl Default for ExportOptions {
             destination_folder: "./build/".into(),
             assets_folders: vec!["./data/".into()],
             include_used_assets: false,
+            pack_assets: false,
             ignored_extensions: vec!["log".to_string()],
             build_targets: vec!["default".to_string()],
             selected_build_target: 0,
@@ -171,6 +175,45 @@ where
     Ok(())
 }
 
+fn pack_assets(
+    folders: &[PathBuf],
+    ignored_extensions: &[String],
+    destination_folder: &Path,
+) -> Result<(), String> {
+    let pack_path = destination_folder.join(PackedResourceIo::DEFAULT_FILE_NAME);
+
+    Log::info(format!(
+        "Trying to pack the assets into {}...",
+        pack_path.display()
+    ));
+
+    let file = fs::File::create(&pack_path)
+        .map_err(|e| format!("Unable to create {}. Reason: {e:?}", pack_path.display()))?;
+    let mut writer = PackWriter::new(BufWriter::new(file)).map_err(|e| e.to_string())?;
+
+    let filter = |path: &Path| {
+        path.extension().map_or(true, |ext| {
+            !ignored_extensions
+                .iter()
+                .any(|ignored| OsStr::new(ignored) == ext)
+        })
+    };
+
+    for folder in folders {
+        let count = writer
+            .add_directory(folder, &filter)
+            .map_err(|e| format!("Unable to pack {}. Reason: {e:?}", folder.display()))?;
+        Log::info(format!(
+            "{count} files from {} were successfully packed.",
+            folder.display()
+        ));
+    }
+
+    writer.finish().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
 fn make_command(program: &str) -> std::process::Command {
     let mut command = std::process::Command::new(program);
     // Remove the `RUSTFLAGS` environment variable, which could be added to the child process
@@ -546,7 +589,18 @@ fn export(export_options: ExportOptions, cancel_flag: Arc<AtomicBool>) -> Result
 
     // Copy assets
     match export_options.target_platform {
+        TargetPlatform::PC if export_options.pack_assets => {
+            pack_assets(
+                &export_options.assets_folders,
+                &export_options.ignored_extensions,
+                &export_options.destination_folder,
+            )?;
+        }
         TargetPlatform::PC | TargetPlatform::WebAssembly => {
+            if export_options.pack_assets {
+                Log::warn("Asset packing is supported on PC only, the assets will be copied.");
+            }
+
             Log::info("Trying to copy the assets...");
 
             for folder in export_options.assets_folders {
@@ -564,6 +618,10 @@ fn export(export_options: ExportOptions, cancel_flag: Arc<AtomicBool>) -> Result
             }
         }
         TargetPlatform::Android => {
+            if export_options.pack_assets {
+                Log::warn("Asset packing is supported on PC only, the assets will be copied.");
+            }
+
             // Asset management on Android is quite annoying, because all other target platforms
             // uses the workspace manifest path as a root directory and all paths in code/assets
             // stored relatively to it. On Android, however, all your assets must be in unified
@@ -65,7 +65,11 @@ use crate::{
     configurator::Configurator,
     export::ExportWindow,
     fyrox::{
-        asset::{io::FsResourceIo, manager::ResourceManager, untyped::ResourceKind},
+        asset::{
+            io::{FsResourceIo, ResourceIo},
+            manager::ResourceManager,
+            untyped::ResourceKind,
+        },
         core::{
             algebra::{Matrix3, Vector2},
             color::Color,
@@ -75,6 +79,7 @@ use crate::{
             pool::Handle,
             task::TaskPool,
             uuid::Uuid,
+            visitor::{VisitError, Visitor},
             watcher::FileSystemWatcher,
             TypeUuidProvider,
         },
@@ -114,9 +119,12 @@ use crate::{
             Material, MaterialResource,
         },
         plugin::{dylib::DyLibDynamicPlugin, DynamicPlugin, Plugin, PluginContainer},
-        resource::texture::{
-            CompressionOptions, TextureImportOptions, TextureMinificationFilter, TextureResource,
-            TextureResourceExtension,
+        resource::{
+            model::Model,
+            texture::{
+                CompressionOptions, TextureImportOptions, TextureMinificationFilter,
+                TextureResource, TextureResourceExtension,
+            },
         },
         scene::{graph::GraphUpdateSwitches, mesh::Mesh, Scene, SceneLoader},
         utils::{translate_cursor_icon, translate_event},
@@ -141,8 +149,9 @@ use crate::{
     plugins::{
         absm::AbsmEditor, absm::AbsmEditorPlugin, animation::AnimationEditorPlugin,
         collider::ColliderPlugin, curve_editor::CurveEditorPlugin, material::MaterialPlugin,
-        path_fixer::PathFixerPlugin, ragdoll::RagdollPlugin, settings::SettingsPlugin,
-        stats::UiStatisticsPlugin, tilemap::TileMapEditorPlugin,
+        path_fixer::PathFixerPlugin, physics_recording::PhysicsRecordingPlugin,
+        ragdoll::RagdollPlugin, settings::SettingsPlugin, stats::UiStatisticsPlugin,
+        tilemap::TileMapEditorPlugin,
     },
     scene::{
         commands::{
@@ -698,6 +707,12 @@ impl Editor {
         })
         .unwrap();
 
+        engine
+            .resource_manager
+            .state()
+            .import_cache
+            .set_directory(settings.general.import_cache_directory());
+
         let (message_sender, message_receiver) = mpsc::channel();
         let message_sender = MessageSender(message_sender);
 
@@ -967,6 +982,7 @@ impl Editor {
                 .with(UiStatisticsPlugin::default())
                 .with(CurveEditorPlugin::default())
                 .with(PathFixerPlugin::default())
+                .with(PhysicsRecordingPlugin::default())
                 .with(inspector_plugin),
             // Apparently, some window managers (like Wayland), does not send `Focused` event after the window
             // was created. So we must assume that the editor is focused by default, otherwise editor's thread
@@ -1888,18 +1904,26 @@ impl Editor {
         if let Some(ext) = scene_path.extension() {
             if ext == "rgs" {
                 let engine = &mut self.engine;
-                let result = {
-                    block_on(SceneLoader::from_file(
-                        &scene_path,
-                        &FsResourceIo,
-                        engine.serialization_context.clone(),
-                        engine.resource_manager.clone(),
-                    ))
-                };
+                let result = block_on(FsResourceIo.load_file(&scene_path))
+                    .map_err(VisitError::from)
+                    .and_then(|data| {
+                        let mut visitor = Visitor::load_from_memory(&data)?;
+                        let loader = SceneLoader::load(
+                            "Scene",
+                            engine.serialization_context.clone(),
+                            engine.resource_manager.clone(),
+                            &mut visitor,
+                            Some(scene_path.clone()),
+                        )?;
+                        // Keep the link to the base prefab, if the scene is a prefab variant.
+                        let mut variant_base = None;
+                        let _ = Model::visit_variant_base(&mut variant_base, &mut visitor);
+                        Ok((loader, variant_base))
+                    });
                 match result {
-                    Ok(loader) => {
-                        let scene = block_on(loader.0.finish());
-                        let entry = EditorSceneEntry::new_game_scene(
+                    Ok((loader, variant_base)) => {
+                        let scene = block_on(loader.finish());
+                        let mut entry = EditorSceneEntry::new_game_scene(
                             scene,
                             Some(scene_path),
                             engine,
@@ -1908,6 +1932,9 @@ impl Editor {
                             &self.scene_viewer,
                             self.highlighter.clone(),
                         );
+                        if let Some(game_scene) = entry.controller.downcast_mut::<GameScene>() {
+                            game_scene.variant_base = variant_base;
+                        }
                         self.add_scene(entry);
                     }
                     Err(e) => {
@@ -2492,6 +2519,12 @@ impl Editor {
         }
 
         if self.settings.try_save() {
+            self.engine
+                .resource_manager
+                .state()
+                .import_cache
+                .set_directory(self.settings.general.import_cache_directory());
+
             let ui = self.engine.user_interfaces.first_mut();
             if let Some(style) = self.styles.get(&self.settings.general.style) {
                 if style != ui.style() {
@@ -898,6 +898,37 @@ impl<N: Debug + 'static> CommandTrait for RemoveLayerCommand<N> {
     }
 }
 
+#[derive(Debug)]
+pub struct SetLayerAdditiveCommand<N: Debug + 'static> {
+    pub absm_node_handle: Handle<N>,
+    pub layer_index: usize,
+    pub additive: bool,
+}
+
+impl<N: Debug + 'static> SetLayerAdditiveCommand<N> {
+    fn swap(&mut self, context: &mut dyn CommandContext) {
+        let layer =
+            &mut fetch_machine(context, self.absm_node_handle).layers_mut()[self.layer_index];
+        let prev = layer.is_additive();
+        layer.set_additive(self.additive);
+        self.additive = prev;
+    }
+}
+
+impl<N: Debug + 'static> CommandTrait for SetLayerAdditiveCommand<N> {
+    fn name(&mut self, _context: &dyn CommandContext) -> String {
+        "Set Layer Additive".to_string()
+    }
+
+    fn execute(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut dyn CommandContext) {
+        self.swap(context)
+    }
+}
+
 #[derive(Debug)]
 pub struct SetLayerMaskCommand<N: Debug + 'static> {
     pub absm_node_handle: Handle<N>,
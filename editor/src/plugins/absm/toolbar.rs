@@ -40,7 +40,10 @@ use crate::fyrox::{
 };
 use crate::plugins::absm::{
     animation_container_ref,
-    command::{AddLayerCommand, RemoveLayerCommand, SetLayerMaskCommand, SetLayerNameCommand},
+    command::{
+        AddLayerCommand, RemoveLayerCommand, SetLayerAdditiveCommand, SetLayerMaskCommand,
+        SetLayerNameCommand,
+    },
     fetch_selection, machine_container_ref,
     selection::AbsmSelection,
 };
@@ -67,6 +70,7 @@ pub struct Toolbar {
     pub add_layer: Handle<UiNode>,
     pub remove_layer: Handle<UiNode>,
     pub edit_mask: Handle<UiNode>,
+    pub additive: Handle<UiNode>,
     pub node_selector: Handle<UiNode>,
 }
 
@@ -84,6 +88,7 @@ impl Toolbar {
         let add_layer;
         let remove_layer;
         let edit_mask;
+        let additive;
         let panel = StackPanelBuilder::new(
             WidgetBuilder::new()
                 .with_child({
@@ -164,6 +169,26 @@ impl Toolbar {
                     )
                     .build(ctx);
                     edit_mask
+                })
+                .with_child({
+                    additive = CheckBoxBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Add the pose of the layer on top of the previous layers, \
+                                instead of blending with them.",
+                            )),
+                    )
+                    .with_content(
+                        TextBuilder::new(
+                            WidgetBuilder::new().with_vertical_alignment(VerticalAlignment::Center),
+                        )
+                        .with_text("Additive")
+                        .build(ctx),
+                    )
+                    .build(ctx);
+                    additive
                 }),
         )
         .with_orientation(Orientation::Horizontal)
@@ -177,6 +202,7 @@ impl Toolbar {
             add_layer,
             remove_layer,
             edit_mask,
+            additive,
             node_selector: Handle::NONE,
         }
     }
@@ -205,6 +231,21 @@ impl Toolbar {
                 } else {
                     ToolbarAction::LeavePreviewMode
                 };
+            } else if message.destination() == self.additive
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(layer_index) = selection.layer {
+                    if machine_container_ref(graph, selection.absm_node_handle)
+                        .and_then(|machine| machine.layers().get(layer_index))
+                        .is_some_and(|layer| layer.is_additive() != *value)
+                    {
+                        sender.do_command(SetLayerAdditiveCommand {
+                            absm_node_handle: selection.absm_node_handle,
+                            layer_index,
+                            additive: *value,
+                        });
+                    }
+                }
             }
         } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
             if message.destination() == self.layers
@@ -411,6 +452,14 @@ impl Toolbar {
                         layer.name().to_string(),
                     ),
                 );
+                send_sync_message(
+                    ui,
+                    CheckBoxMessage::checked(
+                        self.additive,
+                        MessageDirection::ToWidget,
+                        Some(layer.is_additive()),
+                    ),
+                );
             }
         }
     }
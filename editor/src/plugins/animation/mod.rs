@@ -406,6 +406,7 @@ impl AnimationEditor {
                                     name: "Unnamed".to_string(),
                                     time: *time,
                                     enabled: true,
+                                    payload: Default::default(),
                                 }),
                             });
                         }
@@ -764,8 +765,11 @@ impl AnimationEditor {
                                 .iter()
                                 .find(|track| &track.id() == track_id)
                             {
-                                for (index, track_curve) in
-                                    track.data_container().curves_ref().iter().enumerate()
+                                for (index, track_curve) in track
+                                    .data_container()
+                                    .dequantized_curves()
+                                    .iter()
+                                    .enumerate()
                                 {
                                     if !selected_curves
                                         .iter()
@@ -779,22 +783,24 @@ impl AnimationEditor {
                         SelectedEntity::Curve(curve_id) => {
                             if let Some((index, selected_curve)) =
                                 animation_tracks_data.tracks().iter().find_map(|t| {
-                                    t.data_container().curves_ref().iter().enumerate().find_map(
-                                        |(i, c)| {
+                                    t.data_container()
+                                        .dequantized_curves()
+                                        .iter()
+                                        .enumerate()
+                                        .find_map(|(i, c)| {
                                             if &c.id() == curve_id {
-                                                Some((i, c))
+                                                Some((i, c.clone()))
                                             } else {
                                                 None
                                             }
-                                        },
-                                    )
+                                        })
                                 })
                             {
                                 if !selected_curves
                                     .iter()
                                     .any(|(_, curve)| curve.id == selected_curve.id)
                                 {
-                                    selected_curves.push((index, selected_curve.clone()));
+                                    selected_curves.push((index, selected_curve));
                                 }
                             }
                         }
@@ -803,7 +809,7 @@ impl AnimationEditor {
                 }
                 let mut background_curves = Vec::<Curve>::new();
                 for track in animation_tracks_data.tracks() {
-                    for curve in track.data_container().curves_ref() {
+                    for curve in track.data_container().dequantized_curves().iter() {
                         if !selected_curves.iter().any(|(_, c)| c.id == curve.id) {
                             background_curves.push(curve.clone());
                         }
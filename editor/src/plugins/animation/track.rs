@@ -1414,7 +1414,7 @@ impl TrackList {
 
                         let curves = model_track
                             .data_container()
-                            .curves_ref()
+                            .dequantized_curves()
                             .iter()
                             .enumerate()
                             .map(|(i, curve)| {
@@ -64,6 +64,7 @@ use crate::{
         scene::{
             base::BaseBuilder,
             collider::{Collider, ColliderShape},
+            debug::Line,
             dim2,
             node::Node,
             sprite::Sprite,
@@ -244,7 +245,9 @@ fn make_shape_gizmo(
             ColliderShape::Triangle(_) => Box::new(TriangleShapeGizmo::new(root, visible, scene)),
             ColliderShape::Trimesh(_)
             | ColliderShape::Heightfield(_)
-            | ColliderShape::Polyhedron(_) => Box::new(DummyShapeGizmo),
+            | ColliderShape::Polyhedron(_)
+            | ColliderShape::ConvexDecomposition(_)
+            | ColliderShape::Compound(_) => Box::new(DummyShapeGizmo),
         }
     } else if let Some(collider) = scene
         .graph
@@ -277,6 +280,50 @@ lazy_static! {
     };
 }
 
+/// Draws edges of convex hulls generated by convex decomposition of the collider shape, each hull
+/// has its own color.
+fn draw_convex_decompositions(collider: Handle<Node>, scene: &mut Scene) {
+    const COLORS: [Color; 6] = [
+        Color::RED,
+        Color::GREEN,
+        Color::BLUE,
+        Color::ORANGE,
+        Color::CYAN,
+        Color::MAGENTA,
+    ];
+
+    let Some(collider_ref) = scene.graph.try_get_of_type::<Collider>(collider) else {
+        return;
+    };
+
+    let collider_transform = scene.graph.isometric_global_transform(collider);
+
+    let mut color_index = 0;
+    for decomposition in collider_ref.convex_decompositions().iter() {
+        let transform = collider_transform * decomposition.transform.to_homogeneous();
+        for hull in decomposition.hulls.iter() {
+            let color = COLORS[color_index % COLORS.len()];
+            color_index += 1;
+
+            let vertex = |index: u32| {
+                transform
+                    .transform_point(&hull.vertices[index as usize].into())
+                    .coords
+            };
+
+            for triangle in hull.triangles.iter() {
+                for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+                    scene.drawing_context.add_line(Line {
+                        begin: vertex(triangle[a]),
+                        end: vertex(triangle[b]),
+                        color,
+                    });
+                }
+            }
+        }
+    }
+}
+
 fn make_handle(scene: &mut Scene, root: Handle<Node>, visible: bool) -> Handle<Node> {
     let mut material = Material::from_shader(GIZMO_SHADER.clone());
 
@@ -625,6 +672,8 @@ impl InteractionMode for ColliderShapeInteractionMode {
             old_gizmo.destroy(scene);
         }
 
+        draw_convex_decompositions(self.collider, scene);
+
         self.move_gizmo.set_visible(
             &mut scene.graph,
             self.shape_gizmo.is_vector_handle(self.selected_handle),
@@ -43,6 +43,10 @@ use crate::{
     fyrox::{
         asset::{untyped::UntypedResource, Resource},
         core::pool::{ErasedHandle, Handle},
+        generic_animation::{
+            compression::AnimationCompressionOptions,
+            signal::{AnimationSignalPayload, AnimationSignalProperty, AnimationSignalValue},
+        },
         gui::{
             self,
             font::FontResource,
@@ -75,8 +79,10 @@ use crate::{
                 ColorGradingLut, Exposure, OrthographicProjection, PerspectiveProjection,
                 Projection, SkyBox,
             },
+            character::CharacterAutostep,
             collider::{
-                BallShape, BitMask, CapsuleShape, ColliderShape, ConeShape, ConvexPolyhedronShape,
+                BallShape, BitMask, CapsuleShape, ColliderShape, CompoundPartShape, CompoundShape,
+                CompoundShapePart, ConeShape, ConvexDecompositionShape, ConvexPolyhedronShape,
                 CuboidShape, CylinderShape, GeometrySource, HeightfieldShape, InteractionGroups,
                 SegmentShape, TriangleShape, TrimeshShape,
             },
@@ -100,6 +106,7 @@ use crate::{
                 },
                 ParticleSystemRng,
             },
+            physics_material::{PhysicsMaterial, PhysicsMaterialResource},
             ragdoll::Limb,
             rigidbody::RigidBodyType,
             sound::{
@@ -117,6 +124,7 @@ use crate::{
             tilemap::TileCollider,
             tilemap::{tileset::TileSet, Tile},
             transform::Transform,
+            vehicle::VehicleWheel,
         },
     },
     message::MessageSender,
@@ -335,8 +343,16 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_vec_collection::<Option<TileMapBrushResource>>();
     container.register_inheritable_inspectable::<TileMapBrush>();
 
+    container.insert(ResourceFieldPropertyEditorDefinition::<PhysicsMaterial>::new(sender.clone()));
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<PhysicsMaterialResource>,
+    >::new());
+
     container.register_inheritable_inspectable::<ColorGradingLut>();
     container.register_inheritable_inspectable::<InteractionGroups>();
+    container.register_inheritable_inspectable::<CharacterAutostep>();
+    container.register_inheritable_vec_collection::<VehicleWheel>();
+    container.register_inheritable_inspectable::<VehicleWheel>();
 
     container.register_inheritable_enum::<JointParams, _>();
     container.register_inheritable_enum::<dim2::joint::JointParams, _>();
@@ -348,6 +364,29 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<RevoluteJoint>();
     container.register_inheritable_inspectable::<PrismaticJoint>();
     container.register_inheritable_inspectable::<dim2::joint::PrismaticJoint>();
+    container.register_inheritable_inspectable::<GenericJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::GenericJoint>();
+    container.register_inheritable_inspectable::<RopeJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::RopeJoint>();
+    container.register_inheritable_inspectable::<SpringJoint>();
+    container.register_inheritable_inspectable::<dim2::joint::SpringJoint>();
+    container.register_inheritable_inspectable::<GenericJointAxis>();
+    container.register_inheritable_inspectable::<JointMotor>();
+
+    {
+        use crate::fyrox::scene::animation::ik::{IkBone, IkChain, IkSolver};
+        container.register_inheritable_inspectable::<IkBone>();
+        container.register_inheritable_vec_collection::<IkBone>();
+        container.register_inheritable_inspectable::<IkChain>();
+        container.register_inheritable_vec_collection::<IkChain>();
+        container.register_inheritable_enum::<IkSolver, _>();
+    }
+
+    {
+        use crate::fyrox::scene::animation::retarget::BoneMapping;
+        container.register_inheritable_inspectable::<BoneMapping>();
+        container.register_inheritable_vec_collection::<BoneMapping>();
+    }
 
     container.register_inheritable_inspectable::<Base>();
     container.register_inheritable_inspectable::<BaseLight>();
@@ -397,12 +436,19 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_enum::<TextureMinificationFilter, _>();
     container.register_inheritable_enum::<Projection, _>();
     container.register_inheritable_enum::<ColliderShape, _>();
+    container.register_inheritable_enum::<CompoundPartShape, _>();
     container.register_inheritable_enum::<PropertyValue, _>();
     container.register_inheritable_enum::<Mobility, _>();
     container.register_inheritable_enum::<RigidBodyType, _>();
     container.register_inheritable_enum::<Exposure, _>();
     container.register_inheritable_enum::<FrustumSplitOptions, _>();
     container.register_inheritable_enum::<MaterialSearchOptions, _>();
+    container.register_inheritable_inspectable::<AnimationCompressionOptions>();
+    container.register_inheritable_option::<AnimationCompressionOptions>();
+    container.register_inheritable_enum::<AnimationSignalValue, _>();
+    container.register_inheritable_inspectable::<AnimationSignalProperty>();
+    container.register_inheritable_vec_collection::<AnimationSignalProperty>();
+    container.register_inheritable_inspectable::<AnimationSignalPayload>();
     container.register_inheritable_enum::<DistanceModel, _>();
     container.register_inheritable_enum::<sound::Renderer, _>();
     container.register_inheritable_enum::<RenderPath, _>();
@@ -433,6 +479,10 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<dim2::collider::HeightfieldShape>();
     container.register_inheritable_inspectable::<dim2::collider::TileMapShape>();
     container.register_inheritable_inspectable::<ConvexPolyhedronShape>();
+    container.register_inheritable_inspectable::<ConvexDecompositionShape>();
+    container.register_inheritable_inspectable::<CompoundShape>();
+    container.register_inheritable_vec_collection::<CompoundShapePart>();
+    container.register_inheritable_inspectable::<CompoundShapePart>();
     container.insert(SpriteSheetFramesContainerEditorDefinition);
 
     container.insert(SurfaceDataPropertyEditorDefinition {
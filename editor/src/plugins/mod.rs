@@ -25,6 +25,7 @@ pub mod curve_editor;
 pub mod inspector;
 pub mod material;
 pub mod path_fixer;
+pub mod physics_recording;
 pub mod ragdoll;
 pub mod settings;
 pub mod stats;
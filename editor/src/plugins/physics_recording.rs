@@ -0,0 +1,385 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Physics recording viewer allows you to record physics of the current scene (both 3D and 2D), export
+//! the recording to a file, load a physics recording (for example, the one that was attached to a bug
+//! report) and scrub through its frames. The selected frame is drawn on top of the current scene.
+
+use crate::{
+    fyrox::{
+        core::{futures::executor::block_on, log::Log, pool::Handle},
+        gui::{
+            button::{ButtonBuilder, ButtonMessage},
+            check_box::{CheckBoxBuilder, CheckBoxMessage},
+            file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
+            grid::{Column, GridBuilder, Row},
+            menu::MenuItemMessage,
+            message::{MessageDirection, UiMessage},
+            scroll_bar::{ScrollBarBuilder, ScrollBarMessage},
+            text::{TextBuilder, TextMessage},
+            widget::{WidgetBuilder, WidgetMessage},
+            window::{WindowBuilder, WindowMessage, WindowTitle},
+            HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
+        },
+        scene::{graph::physics_recorder::PhysicsRecording, Scene},
+    },
+    menu::create_menu_item,
+    plugin::EditorPlugin,
+    scene::GameScene,
+    send_sync_message,
+    utils::create_file_selector,
+    Editor,
+};
+use std::path::{Path, PathBuf};
+
+#[derive(Default)]
+pub struct PhysicsRecordingPlugin {
+    open_viewer: Handle<UiNode>,
+    window: Handle<UiNode>,
+    file_selector: Handle<UiNode>,
+    export_file_selector: Handle<UiNode>,
+    record: Handle<UiNode>,
+    export: Handle<UiNode>,
+    load: Handle<UiNode>,
+    info: Handle<UiNode>,
+    position: Handle<UiNode>,
+    recording: Option<PhysicsRecording>,
+    frame: usize,
+}
+
+impl PhysicsRecordingPlugin {
+    fn open_window(&mut self, editor: &mut Editor) {
+        let ui = editor.engine.user_interfaces.first_mut();
+        let ctx = &mut ui.build_ctx();
+
+        self.file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text("Select a physics recording")),
+        )
+        .with_filter(Filter::new(|p: &Path| {
+            p.is_dir() || p.extension().is_some_and(|ext| ext == "physrec")
+        }))
+        .build(ctx);
+        self.export_file_selector = create_file_selector(
+            ctx,
+            "physrec",
+            FileBrowserMode::Save {
+                default_file_name: PathBuf::from("recording.physrec"),
+            },
+        );
+
+        self.record = CheckBoxBuilder::new(
+            WidgetBuilder::new()
+                .on_column(1)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_content(
+            TextBuilder::new(
+                WidgetBuilder::new().with_vertical_alignment(VerticalAlignment::Center),
+            )
+            .with_text("Record")
+            .build(ctx),
+        )
+        .checked(Some(false))
+        .build(ctx);
+        self.export = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .on_column(2)
+                .with_width(80.0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_text("Export...")
+        .build(ctx);
+        self.load = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .on_column(3)
+                .with_width(80.0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_text("Load...")
+        .build(ctx);
+        self.info = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_column(0)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .with_text("No recording loaded.")
+        .build(ctx);
+        self.position = ScrollBarBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .with_enabled(false)
+                .with_margin(Thickness::uniform(1.0)),
+        )
+        .with_min(0.0)
+        .with_max(0.0)
+        .with_step(1.0)
+        .with_value_precision(0)
+        .build(ctx);
+
+        self.window = WindowBuilder::new(WidgetBuilder::new().with_width(500.0).with_height(80.0))
+            .with_title(WindowTitle::text("Physics Recording"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            GridBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_child(self.info)
+                                    .with_child(self.record)
+                                    .with_child(self.export)
+                                    .with_child(self.load),
+                            )
+                            .add_column(Column::stretch())
+                            .add_column(Column::auto())
+                            .add_column(Column::auto())
+                            .add_column(Column::auto())
+                            .add_row(Row::stretch())
+                            .build(ctx),
+                        )
+                        .with_child(self.position),
+                )
+                .add_column(Column::stretch())
+                .add_row(Row::strict(24.0))
+                .add_row(Row::strict(22.0))
+                .build(ctx),
+            )
+            .open(false)
+            .build(ctx);
+
+        ui.send_message(WindowMessage::open_and_align(
+            self.window,
+            MessageDirection::ToWidget,
+            editor.scene_viewer.frame(),
+            HorizontalAlignment::Right,
+            VerticalAlignment::Bottom,
+            Thickness::uniform(1.0),
+            false,
+            true,
+        ));
+    }
+
+    fn close_window(&mut self, ui: &UserInterface) {
+        for widget in [self.window, self.file_selector, self.export_file_selector] {
+            ui.send_message(WidgetMessage::remove(widget, MessageDirection::ToWidget));
+        }
+        self.window = Handle::NONE;
+        self.file_selector = Handle::NONE;
+        self.export_file_selector = Handle::NONE;
+        self.recording = None;
+        self.frame = 0;
+    }
+
+    fn load_recording(&mut self, path: &Path, ui: &UserInterface) {
+        let recording = match block_on(PhysicsRecording::load(path)) {
+            Ok(recording) => recording,
+            Err(error) => {
+                Log::err(format!(
+                    "Unable to load physics recording from {}. Reason: {error:?}",
+                    path.display()
+                ));
+                return;
+            }
+        };
+
+        let max = recording.frames.len().saturating_sub(1) as f32;
+        self.recording = Some(recording);
+        self.frame = 0;
+
+        send_sync_message(
+            ui,
+            ScrollBarMessage::max_value(self.position, MessageDirection::ToWidget, max),
+        );
+        send_sync_message(
+            ui,
+            ScrollBarMessage::value(self.position, MessageDirection::ToWidget, 0.0),
+        );
+        ui.send_message(WidgetMessage::enabled(
+            self.position,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        self.sync_info(ui);
+    }
+
+    fn open_file_selector(file_selector: Handle<UiNode>, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open_modal(
+            file_selector,
+            MessageDirection::ToWidget,
+            true,
+            true,
+        ));
+        match std::env::current_dir() {
+            Ok(dir) => ui.send_message(FileSelectorMessage::root(
+                file_selector,
+                MessageDirection::ToWidget,
+                Some(dir),
+            )),
+            Err(error) => Log::err(format!(
+                "Unable to get current directory. Reason: {error:?}"
+            )),
+        }
+    }
+
+    fn set_recording_enabled(scene: &mut Scene, enabled: bool) {
+        scene.graph.physics.recorder.set_enabled(enabled);
+        scene.graph.physics2d.recorder.set_enabled(enabled);
+    }
+
+    fn export_recording(scene: &Scene, path: &Path) {
+        // A scene usually uses only one kind of physics, pick the one that has something recorded.
+        let mut recording = scene.graph.physics.recorder.recording();
+        if recording.frames.is_empty() {
+            recording = scene.graph.physics2d.recorder.recording();
+        }
+        if recording.frames.is_empty() {
+            Log::warn("Physics recording is empty, nothing to export.");
+            return;
+        }
+        match recording.save(path) {
+            Ok(_) => Log::info(format!(
+                "Physics recording was exported to {}.",
+                path.display()
+            )),
+            Err(error) => Log::err(format!(
+                "Unable to export physics recording to {}. Reason: {error:?}",
+                path.display()
+            )),
+        }
+    }
+
+    fn sync_info(&self, ui: &UserInterface) {
+        let text = match self
+            .recording
+            .as_ref()
+            .and_then(|recording| recording.frames.get(self.frame).map(|f| (recording, f)))
+        {
+            Some((recording, frame)) => format!(
+                "Frame {}/{} - {:.3}s\nBodies: {} Contacts: {}",
+                self.frame + 1,
+                recording.frames.len(),
+                frame.time,
+                frame.bodies.len(),
+                frame.contacts.len()
+            ),
+            None => "Recording is empty.".to_string(),
+        };
+        ui.send_message(TextMessage::text(
+            self.info,
+            MessageDirection::ToWidget,
+            text,
+        ));
+    }
+}
+
+impl EditorPlugin for PhysicsRecordingPlugin {
+    fn on_start(&mut self, editor: &mut Editor) {
+        let ui = editor.engine.user_interfaces.first_mut();
+        let ctx = &mut ui.build_ctx();
+        self.open_viewer = create_menu_item("Physics Recording", vec![], ctx);
+        ui.send_message(MenuItemMessage::add_item(
+            editor.menu.utils_menu.menu,
+            MessageDirection::ToWidget,
+            self.open_viewer,
+        ));
+    }
+
+    fn on_ui_message(&mut self, message: &mut UiMessage, editor: &mut Editor) {
+        if let Some(MenuItemMessage::Click) = message.data() {
+            if message.destination() == self.open_viewer && self.window.is_none() {
+                self.open_window(editor);
+            }
+        }
+
+        if self.window.is_none() {
+            return;
+        }
+
+        let scene = editor
+            .scenes
+            .current_scene_controller_ref()
+            .and_then(|controller| controller.downcast_ref::<GameScene>())
+            .map(|game_scene| game_scene.scene)
+            .and_then(|scene| editor.engine.scenes.try_get_mut(scene));
+        let ui = editor.engine.user_interfaces.first_mut();
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.load {
+                Self::open_file_selector(self.file_selector, ui);
+            } else if message.destination() == self.export {
+                Self::open_file_selector(self.export_file_selector, ui);
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.record
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(scene) = scene {
+                    Self::set_recording_enabled(scene, *value);
+                }
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.file_selector {
+                self.load_recording(path, ui);
+            } else if message.destination() == self.export_file_selector {
+                if let Some(scene) = scene {
+                    Self::export_recording(scene, path);
+                }
+            }
+        } else if let Some(ScrollBarMessage::Value(value)) = message.data() {
+            if message.destination() == self.position
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.frame = value.round().max(0.0) as usize;
+                self.sync_info(ui);
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window {
+                if let Some(scene) = scene {
+                    Self::set_recording_enabled(scene, false);
+                }
+                self.close_window(ui);
+            }
+        }
+    }
+
+    fn on_post_update(&mut self, editor: &mut Editor) {
+        // Scene drawing context is cleared on every update, so the frame must be drawn after it.
+        let Some(frame) = self
+            .recording
+            .as_ref()
+            .and_then(|recording| recording.frames.get(self.frame))
+        else {
+            return;
+        };
+
+        let Some(game_scene) = editor
+            .scenes
+            .current_scene_controller_ref()
+            .and_then(|controller| controller.downcast_ref::<GameScene>())
+        else {
+            return;
+        };
+
+        frame.draw(&mut editor.engine.scenes[game_scene.scene].drawing_context);
+    }
+}
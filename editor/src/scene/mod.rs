@@ -58,7 +58,7 @@ use crate::{
             shader::ShaderResource, shader::ShaderResourceExtension, Material, MaterialResource,
         },
         resource::{
-            model::{Model, ModelResourceExtension},
+            model::{Model, ModelResource, ModelResourceExtension},
             texture::{Texture, TextureKind, TextureResource, TextureResourceExtension},
         },
         scene::{
@@ -148,6 +148,8 @@ pub struct GameScene {
     pub serialization_context: Arc<SerializationContext>,
     pub grid: Handle<Node>,
     pub settings_receiver: Receiver<SettingsMessage>,
+    // Base prefab of the scene, if the scene is a prefab variant. It is saved together with the scene.
+    pub variant_base: Option<ModelResource>,
 }
 
 lazy_static! {
@@ -233,6 +235,9 @@ impl GameScene {
                 // Update only editor's camera.
                 node_overrides: Some(Default::default()),
                 paused: false,
+                // World partition cells must not be streamed in the editor, otherwise they will
+                // be saved together with the scene.
+                streaming: false,
             },
             sender,
             camera_state: Default::default(),
@@ -242,6 +247,7 @@ impl GameScene {
             serialization_context: engine.serialization_context.clone(),
             grid,
             settings_receiver,
+            variant_base: None,
         }
     }
 
@@ -269,8 +275,15 @@ impl GameScene {
 
         let mut visitor = Visitor::new();
         pure_scene.save("Scene", &mut visitor).unwrap();
+        Model::visit_variant_base(&mut self.variant_base, &mut visitor).unwrap();
 
-        if let Err(e) = visitor.save_binary(path) {
+        let result = if settings.general.save_scenes_in_text_form {
+            visitor.save_text_to_file(path)
+        } else {
+            visitor.save_binary(path)
+        };
+
+        if let Err(e) = result {
             Err(format!("Failed to save scene! Reason: {e}"))
         } else {
             if settings.debugging.save_scene_in_text_form {
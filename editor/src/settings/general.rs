@@ -20,6 +20,7 @@
 
 use crate::fyrox::core::{reflect::prelude::*, type_traits::prelude::*, uuid_provider};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
 #[derive(
@@ -87,6 +88,36 @@ pub struct GeneralSettings {
 
     #[serde(default = "default_style")]
     pub style: EditorStyle,
+
+    #[reflect(
+        description = "Forces the editor to save scenes and UI scenes in text form instead of binary. \
+    Text scenes can be diffed and merged by version control systems, but they take more space and \
+    load slower."
+    )]
+    #[serde(default)]
+    pub save_scenes_in_text_form: bool,
+
+    #[reflect(
+        description = "Enables or disables the import cache. When enabled, processed textures and \
+    models are stored in the .cache/import folder of the project and reused on the next load, \
+    unless the source file or its import options were changed."
+    )]
+    #[serde(default = "default_use_import_cache")]
+    pub use_import_cache: bool,
+}
+
+impl GeneralSettings {
+    /// Path of the import cache directory, relative to the project root.
+    pub const IMPORT_CACHE_DIRECTORY: &'static str = ".cache/import";
+
+    pub fn import_cache_directory(&self) -> Option<PathBuf> {
+        self.use_import_cache
+            .then(|| PathBuf::from(Self::IMPORT_CACHE_DIRECTORY))
+    }
+}
+
+fn default_use_import_cache() -> bool {
+    true
 }
 
 fn default_style() -> EditorStyle {
@@ -149,6 +180,8 @@ impl Default for GeneralSettings {
             generate_previews: default_generate_previews(),
             max_log_entries: default_max_log_entries(),
             style: EditorStyle::Dark,
+            save_scenes_in_text_form: false,
+            use_import_cache: default_use_import_cache(),
         }
     }
 }
@@ -38,6 +38,7 @@ use crate::{
             math::Rect,
             pool::{ErasedHandle, Handle},
             reflect::Reflect,
+            visitor::{Visit, Visitor},
         },
         engine::Engine,
         fxhash::FxHashSet,
@@ -264,7 +265,17 @@ impl SceneController for UiScene {
         settings: &Settings,
         _engine: &mut Engine,
     ) -> Result<String, String> {
-        match self.ui.save(path) {
+        let result = if settings.general.save_scenes_in_text_form {
+            let mut visitor = Visitor::new();
+            self.ui
+                .visit("Ui", &mut visitor)
+                .and_then(|_| visitor.save_text_to_file(path))
+                .map(|_| visitor)
+        } else {
+            self.ui.save(path)
+        };
+
+        match result {
             Ok(visitor) => {
                 if settings.debugging.save_scene_in_text_form {
                     let text = visitor.save_text();
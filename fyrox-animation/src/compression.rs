@@ -0,0 +1,155 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Animation compression reduces the amount of memory used by animations by removing redundant keys and by
+//! storing the rest of the keys in a compact form. See [`AnimationCompressionOptions`] docs for more info.
+
+use crate::{
+    core::{reflect::prelude::*, type_traits::prelude::*},
+    value::ValueBinding,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    ops::AddAssign,
+};
+
+/// A set of options for animation compression. Compression consists of two steps: keyframe reduction and optional
+/// quantization. Keyframe reduction removes every key that can be removed without changing the animation by more
+/// than the respective tolerance (see [`crate::container::TrackDataContainer::reduce_keys`]). Quantization stores
+/// the remaining keys in a compact form (see [`crate::container::TrackDataContainer::quantize`]), quantized
+/// animations cannot be edited.
+///
+/// Animation compression could be applied to model resources on import, using respective import options:
+///
+/// ```text
+/// (
+///     material_search_options: RecursiveUp,
+///     animation_compression: Some((
+///         position_tolerance: 0.001,
+///         rotation_tolerance: 0.001,
+///         quantize: true,
+///     )),
+/// )
+/// ```
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize, TypeUuidProvider)]
+#[type_uuid(id = "09cb433b-3f18-48c7-bed1-348b75e8dacd")]
+#[serde(default)]
+pub struct AnimationCompressionOptions {
+    /// Maximum allowed error of positions (in units of the parent space of a node).
+    #[reflect(min_value = 0.0, step = 0.0001)]
+    pub position_tolerance: f32,
+
+    /// Maximum allowed error of rotations (in radians).
+    #[reflect(min_value = 0.0, step = 0.0001)]
+    pub rotation_tolerance: f32,
+
+    /// Maximum allowed error of scales.
+    #[reflect(min_value = 0.0, step = 0.0001)]
+    pub scale_tolerance: f32,
+
+    /// Maximum allowed error of all other numeric properties.
+    #[reflect(min_value = 0.0, step = 0.0001)]
+    pub property_tolerance: f32,
+
+    /// If `true`, the tracks will be quantized after keyframe reduction.
+    pub quantize: bool,
+}
+
+impl Default for AnimationCompressionOptions {
+    fn default() -> Self {
+        Self {
+            position_tolerance: 0.0005,
+            rotation_tolerance: 0.0005,
+            scale_tolerance: 0.0005,
+            property_tolerance: 0.0005,
+            quantize: false,
+        }
+    }
+}
+
+// Tolerances are compared bitwise, so the options could be `Eq` and could be a part of other `Eq`
+// types (such as import options).
+impl PartialEq for AnimationCompressionOptions {
+    fn eq(&self, other: &Self) -> bool {
+        self.position_tolerance.to_bits() == other.position_tolerance.to_bits()
+            && self.rotation_tolerance.to_bits() == other.rotation_tolerance.to_bits()
+            && self.scale_tolerance.to_bits() == other.scale_tolerance.to_bits()
+            && self.property_tolerance.to_bits() == other.property_tolerance.to_bits()
+            && self.quantize == other.quantize
+    }
+}
+
+impl Eq for AnimationCompressionOptions {}
+
+impl AnimationCompressionOptions {
+    /// Returns the tolerance for a track with the given binding.
+    pub fn tolerance(&self, binding: &ValueBinding) -> f32 {
+        match binding {
+            ValueBinding::Position => self.position_tolerance,
+            ValueBinding::Rotation => self.rotation_tolerance,
+            ValueBinding::Scale => self.scale_tolerance,
+            ValueBinding::Property { .. } => self.property_tolerance,
+        }
+    }
+}
+
+/// A report of animation compression, that tells how much memory was saved.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnimationCompressionReport {
+    /// Total amount of keys before compression.
+    pub keys_before: usize,
+    /// Total amount of keys after compression.
+    pub keys_after: usize,
+    /// Approximate amount of memory (in bytes) used by the tracks before compression.
+    pub memory_before: usize,
+    /// Approximate amount of memory (in bytes) used by the tracks after compression.
+    pub memory_after: usize,
+}
+
+impl AnimationCompressionReport {
+    /// Returns the amount of memory (in bytes) saved by compression.
+    pub fn saved_memory(&self) -> usize {
+        self.memory_before.saturating_sub(self.memory_after)
+    }
+}
+
+impl AddAssign for AnimationCompressionReport {
+    fn add_assign(&mut self, rhs: Self) {
+        self.keys_before += rhs.keys_before;
+        self.keys_after += rhs.keys_after;
+        self.memory_before += rhs.memory_before;
+        self.memory_after += rhs.memory_after;
+    }
+}
+
+impl Display for AnimationCompressionReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} keys -> {} keys, {:.1} KiB -> {:.1} KiB ({:.1} KiB saved)",
+            self.keys_before,
+            self.keys_after,
+            self.memory_before as f32 / 1024.0,
+            self.memory_after as f32 / 1024.0,
+            self.saved_memory() as f32 / 1024.0
+        )
+    }
+}
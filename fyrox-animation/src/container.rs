@@ -24,13 +24,14 @@
 use crate::{
     core::{
         algebra::{Vector2, Vector3, Vector4},
-        math::curve::Curve,
-        math::{quat_from_euler, RotationOrder},
+        math::curve::{Curve, CurveKey, CurveKeyKind},
+        math::{lerpf, quat_from_euler, RotationOrder},
         reflect::prelude::*,
         visitor::prelude::*,
     },
     value::TrackValue,
 };
+use std::borrow::Cow;
 
 /// The kind of track output value, the animation system works only with numeric properties and the number
 /// of variants is small.
@@ -87,6 +88,153 @@ pub enum InterpolationMode {
     ShortPath,
 }
 
+/// A compact, lossy representation of a curve, that consists of linear or constant keys. Each value is stored
+/// as a 16-bit integer in the range of values of the curve, which makes the curve several times smaller than
+/// the respective [`Curve`] at the cost of a tiny precision loss (1/65535 of the range of values of the curve).
+/// See [`TrackDataContainer::quantize`] for more info.
+#[derive(Visit, Reflect, Debug, Clone, Default, PartialEq)]
+pub struct QuantizedCurve {
+    locations: Vec<f32>,
+    values: Vec<u16>,
+    min: f32,
+    step: f32,
+    constant: bool,
+}
+
+impl QuantizedCurve {
+    /// Tries to create a quantized version of the given curve. Returns `None` if the curve has cubic keys or mixes
+    /// keys of different kinds, such curves cannot be quantized.
+    pub fn from_curve(curve: &Curve) -> Option<Self> {
+        let keys = curve.keys();
+        let interpolated = keys.split_last().map_or(&[][..], |(_, keys)| keys);
+        let constant = interpolated
+            .iter()
+            .all(|key| matches!(key.kind, CurveKeyKind::Constant));
+        let linear = interpolated
+            .iter()
+            .all(|key| matches!(key.kind, CurveKeyKind::Linear));
+        if !constant && !linear {
+            return None;
+        }
+
+        let (min, max) = keys.iter().fold((f32::MAX, f32::MIN), |(min, max), key| {
+            (min.min(key.value), max.max(key.value))
+        });
+        let step = if keys.is_empty() {
+            0.0
+        } else {
+            (max - min) / u16::MAX as f32
+        };
+
+        Some(Self {
+            locations: keys.iter().map(|key| key.location()).collect(),
+            values: keys
+                .iter()
+                .map(|key| {
+                    if step > 0.0 {
+                        ((key.value - min) / step).round() as u16
+                    } else {
+                        0
+                    }
+                })
+                .collect(),
+            min,
+            step,
+            constant,
+        })
+    }
+
+    /// Converts the quantized curve back to a [`Curve`].
+    pub fn to_curve(&self) -> Curve {
+        let kind = if self.constant {
+            CurveKeyKind::Constant
+        } else {
+            CurveKeyKind::Linear
+        };
+        Curve::from(
+            self.locations
+                .iter()
+                .enumerate()
+                .map(|(i, location)| CurveKey::new(*location, self.value(i), kind.clone()))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Returns the amount of keys in the curve.
+    pub fn keys_count(&self) -> usize {
+        self.locations.len()
+    }
+
+    /// Returns location of the last key of the curve.
+    pub fn max_location(&self) -> f32 {
+        self.locations.last().cloned().unwrap_or_default()
+    }
+
+    fn value(&self, index: usize) -> f32 {
+        self.min + self.values[index] as f32 * self.step
+    }
+
+    fn fetch_at<I>(&self, location: f32, interpolator: I) -> f32
+    where
+        I: FnOnce(f32, f32, f32) -> f32,
+    {
+        let (Some(first), Some(last)) = (self.locations.first(), self.locations.last()) else {
+            return 0.0;
+        };
+        if location <= *first {
+            self.value(0)
+        } else if location >= *last {
+            self.value(self.locations.len() - 1)
+        } else {
+            let right = self.locations.partition_point(|l| *l < location);
+            let left = right - 1;
+            if self.constant {
+                return self.value(left);
+            }
+            let t =
+                (location - self.locations[left]) / (self.locations[right] - self.locations[left]);
+            interpolator(self.value(left), self.value(right), t)
+        }
+    }
+
+    /// Returns a value of the curve at the given location.
+    pub fn value_at(&self, location: f32) -> f32 {
+        self.fetch_at(location, lerpf)
+    }
+
+    /// Returns an angle at the given location, the angles are interpolated using the shortest path.
+    pub fn angle_at(&self, location: f32) -> f32 {
+        self.fetch_at(location, |mut start, mut end, t| {
+            if (end - start).abs() > std::f32::consts::PI {
+                if end > start {
+                    start += std::f32::consts::TAU;
+                } else {
+                    end += std::f32::consts::TAU;
+                }
+            }
+            lerpf(start, end, t)
+        })
+    }
+
+    /// Returns approximate amount of memory (in bytes) used by the curve.
+    pub fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + std::mem::size_of_val(self.locations.as_slice())
+            + std::mem::size_of_val(self.values.as_slice())
+    }
+}
+
+fn value_error(a: &TrackValue, b: &TrackValue) -> f32 {
+    match (a, b) {
+        (TrackValue::Real(a), TrackValue::Real(b)) => (a - b).abs(),
+        (TrackValue::Vector2(a), TrackValue::Vector2(b)) => (a - b).norm(),
+        (TrackValue::Vector3(a), TrackValue::Vector3(b)) => (a - b).norm(),
+        (TrackValue::Vector4(a), TrackValue::Vector4(b)) => (a - b).norm(),
+        (TrackValue::UnitQuaternion(a), TrackValue::UnitQuaternion(b)) => a.angle_to(b),
+        _ => f32::MAX,
+    }
+}
+
 /// Container for a track data. Strictly speaking, it is just a set of parametric curves which can be
 /// fetched at a given time position simultaneously, producing a value of desired type. Which type of
 /// value is produced is defined by [`TrackValueKind`] enumeration. Usually a container contains up to
@@ -101,6 +249,9 @@ pub struct TrackDataContainer {
     /// Interpolation mode.
     #[visit(optional)] // Backward compatibility.
     pub mode: InterpolationMode,
+    #[visit(optional)] // Backward compatibility.
+    #[reflect(hidden)]
+    quantized: Vec<QuantizedCurve>,
 }
 
 impl TrackDataContainer {
@@ -118,32 +269,56 @@ impl TrackDataContainer {
                 .map(|_| Curve::default())
                 .collect(),
             mode: Default::default(),
+            quantized: Default::default(),
         }
     }
 
     /// Adds a new curve to the container. Keep in mind, that the actual useful amount of curves has soft limit
-    /// of four due to [`TrackValueKind`], any excessive curves will be ignored.
+    /// of four due to [`TrackValueKind`], any excessive curves will be ignored. Quantized container will be
+    /// dequantized first (see [`Self::dequantize`]).
     pub fn add_curve(&mut self, curve: Curve) {
+        self.dequantize();
         self.curves.push(curve)
     }
 
-    /// Tries to borrow a curve at a given index.
+    /// Tries to borrow a curve at a given index. Keep in mind, that the curves of a quantized container have no
+    /// keys, use [`Self::dequantized_curves`] to get the actual curves.
     pub fn curve(&self, index: usize) -> Option<&Curve> {
         self.curves.get(index)
     }
 
-    /// Tries to borrow a curve at a given index.
+    /// Tries to borrow a curve at a given index. Quantized container will be dequantized first (see
+    /// [`Self::dequantize`]).
     pub fn curve_mut(&mut self, index: usize) -> Option<&mut Curve> {
+        self.dequantize();
         self.curves.get_mut(index)
     }
 
-    /// Returns a reference to curves container.
+    /// Returns a reference to curves container. Keep in mind, that the curves of a quantized container have no
+    /// keys, use [`Self::dequantized_curves`] to get the actual curves.
     pub fn curves_ref(&self) -> &[Curve] {
         &self.curves
     }
 
-    /// Tries to borrow a curve at a given index.
+    /// Returns the curves of the container with their keys. Unlike [`Self::curves_ref`], this method restores
+    /// the keys of a quantized container (without dequantizing the container itself), in this case the curves
+    /// are copied.
+    pub fn dequantized_curves(&self) -> Cow<'_, [Curve]> {
+        if !self.is_quantized() {
+            return Cow::Borrowed(&self.curves);
+        }
+
+        let mut curves = self.curves.clone();
+        for (curve, quantized) in curves.iter_mut().zip(self.quantized.iter()) {
+            curve.keys = quantized.to_curve().keys;
+        }
+        Cow::Owned(curves)
+    }
+
+    /// Returns a mutable reference to curves container. Quantized container will be dequantized first (see
+    /// [`Self::dequantize`]).
     pub fn curves_mut(&mut self) -> &mut [Curve] {
+        self.dequantize();
         &mut self.curves
     }
 
@@ -242,6 +417,10 @@ impl TrackDataContainer {
     /// and cannot produce a desired value (for example, [`Vector3`] can be fetched only if the amount of curves
     /// is 3).
     pub fn fetch(&self, time: f32) -> Option<TrackValue> {
+        if !self.quantized.is_empty() {
+            return self.fetch_quantized(time);
+        }
+
         match self.kind {
             TrackValueKind::Real => Some(TrackValue::Real(self.curves.first()?.value_at(time))),
             TrackValueKind::Vector2 => self.fetch_vector2(time),
@@ -251,12 +430,254 @@ impl TrackDataContainer {
         }
     }
 
+    fn fetch_quantized(&self, time: f32) -> Option<TrackValue> {
+        let count = self.kind.components_count();
+        let curves = self.quantized.get(..count)?;
+
+        let mut components = [0.0; 4];
+        for (component, curve) in components.iter_mut().zip(curves) {
+            *component = match (self.kind, &self.mode) {
+                (TrackValueKind::UnitQuaternion, InterpolationMode::ShortPath) => {
+                    curve.angle_at(time)
+                }
+                _ => curve.value_at(time),
+            };
+        }
+
+        let [x, y, z, w] = components;
+        Some(match self.kind {
+            TrackValueKind::Real => TrackValue::Real(x),
+            TrackValueKind::Vector2 => TrackValue::Vector2(Vector2::new(x, y)),
+            TrackValueKind::Vector3 => TrackValue::Vector3(Vector3::new(x, y, z)),
+            TrackValueKind::Vector4 => TrackValue::Vector4(Vector4::new(x, y, z, w)),
+            TrackValueKind::UnitQuaternion => TrackValue::UnitQuaternion(quat_from_euler(
+                Vector3::new(x, y, z),
+                RotationOrder::XYZ,
+            )),
+        })
+    }
+
+    /// Removes every key, that can be removed without changing the values produced by the container by more than
+    /// the given tolerance. The error is measured using the produced values, not the individual curves: it is the
+    /// distance between the vectors for numeric values and the angle between the rotations for quaternions. This
+    /// is useful to reduce the size of densely sampled animations (for example, imported from FBX or glTF), which
+    /// often have lots of redundant keys. The first and the last keys of a curve are removed too, if the curve is
+    /// constant within the tolerance.
+    pub fn reduce_keys(&mut self, tolerance: f32) {
+        self.dequantize();
+
+        let original = self.clone();
+
+        // Check the error at the original keys and in the middle between them.
+        let mut locations = self
+            .curves
+            .iter()
+            .flat_map(|curve| curve.keys().iter().map(|key| key.location()))
+            .collect::<Vec<_>>();
+        locations.sort_by(|a, b| a.total_cmp(b));
+        locations.dedup();
+        let middles = locations
+            .windows(2)
+            .map(|pair| (pair[0] + pair[1]) * 0.5)
+            .collect::<Vec<_>>();
+        locations.extend(middles);
+        locations.sort_by(|a, b| a.total_cmp(b));
+
+        let count = self.kind.components_count().min(self.curves.len());
+        for curve_index in 0..count {
+            let mut key_index = 0;
+            while key_index < self.curves[curve_index].keys.len()
+                && self.curves[curve_index].keys.len() > 1
+            {
+                let keys = &self.curves[curve_index].keys;
+                let left = key_index
+                    .checked_sub(1)
+                    .map_or(f32::MIN, |i| keys[i].location());
+                let right = keys.get(key_index + 1).map_or(f32::MAX, |k| k.location());
+
+                let key = self.curves[curve_index].keys.remove(key_index);
+
+                let within_tolerance = locations
+                    .iter()
+                    .filter(|location| (left..=right).contains(*location))
+                    .all(
+                        |location| match (self.fetch(*location), original.fetch(*location)) {
+                            (Some(a), Some(b)) => value_error(&a, &b) <= tolerance,
+                            _ => false,
+                        },
+                    );
+
+                if !within_tolerance {
+                    self.curves[curve_index].keys.insert(key_index, key);
+                    key_index += 1;
+                }
+            }
+        }
+    }
+
+    /// Converts the curves of the container to a compact representation (see [`QuantizedCurve`]) and releases the
+    /// keys of the curves. Quantized containers are meant to be used at runtime only: [`Self::curves_ref`] returns
+    /// the curves without keys, and any mutable access to the curves (such as [`Self::curves_mut`]) dequantizes the
+    /// container. Use [`Self::dequantize`] to get the curves back explicitly, or [`Self::dequantized_curves`] to
+    /// read them without changing the container. Returns `false` if any of the curves cannot be quantized, in this
+    /// case the container remains unchanged.
+    pub fn quantize(&mut self) -> bool {
+        if self.is_quantized() {
+            return true;
+        }
+
+        let Some(quantized) = self
+            .curves
+            .iter()
+            .map(QuantizedCurve::from_curve)
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+
+        self.quantized = quantized;
+        for curve in self.curves.iter_mut() {
+            curve.keys = Vec::new();
+        }
+        true
+    }
+
+    /// Converts quantized curves of the container (if any) back to regular curves. See [`Self::quantize`] for more
+    /// info.
+    pub fn dequantize(&mut self) {
+        for (curve, quantized) in self.curves.iter_mut().zip(self.quantized.drain(..)) {
+            curve.keys = quantized.to_curve().keys;
+        }
+    }
+
+    /// Returns `true` if the container is quantized, `false` - otherwise.
+    pub fn is_quantized(&self) -> bool {
+        !self.quantized.is_empty()
+    }
+
+    /// Returns the total amount of keys in all curves of the container.
+    pub fn keys_count(&self) -> usize {
+        self.curves
+            .iter()
+            .map(|curve| curve.keys().len())
+            .chain(self.quantized.iter().map(|curve| curve.keys_count()))
+            .sum()
+    }
+
+    /// Returns approximate amount of memory (in bytes) used by the curves of the container.
+    pub fn memory_usage(&self) -> usize {
+        self.curves
+            .iter()
+            .map(|curve| {
+                std::mem::size_of::<Curve>()
+                    + curve.name().len()
+                    + std::mem::size_of_val(curve.keys())
+            })
+            .chain(self.quantized.iter().map(|curve| curve.memory_usage()))
+            .sum()
+    }
+
+    /// Converts the curves of the container, so they will produce deltas relative to the given reference value
+    /// (see [`TrackValue::make_additive`]). Numeric curves are simply shifted by the respective components of the
+    /// reference value, rotation curves are resampled at their key locations using linear interpolation. The
+    /// method does nothing if the reference value does not match the kind of the container.
+    pub fn make_additive(&mut self, reference: &TrackValue) {
+        self.dequantize();
+
+        let components = match (self.kind, reference) {
+            (TrackValueKind::Real, TrackValue::Real(v)) => vec![*v],
+            (TrackValueKind::Vector2, TrackValue::Vector2(v)) => v.as_slice().to_vec(),
+            (TrackValueKind::Vector3, TrackValue::Vector3(v)) => v.as_slice().to_vec(),
+            (TrackValueKind::Vector4, TrackValue::Vector4(v)) => v.as_slice().to_vec(),
+            (TrackValueKind::UnitQuaternion, TrackValue::UnitQuaternion(_)) => {
+                self.map_values(|mut value| {
+                    value.make_additive(reference);
+                    value
+                });
+                return;
+            }
+            _ => return,
+        };
+
+        for (curve, component) in self.curves.iter_mut().zip(components) {
+            for value in curve.keys_values() {
+                *value -= component;
+            }
+        }
+    }
+
+    /// Transforms every value that the container produces using the given function. The curves are resampled at
+    /// the union of their key locations and then rebuilt from the transformed values, which makes it possible to
+    /// apply transformations that mix components (for example, rotating a vector). Keys with constant
+    /// interpolation keep it, all other keys become linear. Rotations are converted back to Euler angles and the
+    /// container is switched to [`InterpolationMode::ShortPath`], because the angles could wrap around.
+    pub fn map_values<F>(&mut self, mut func: F)
+    where
+        F: FnMut(TrackValue) -> TrackValue,
+    {
+        self.dequantize();
+
+        let count = self.kind.components_count();
+        if self.curves.len() < count {
+            return;
+        }
+
+        let mut keys = self.curves[..count]
+            .iter()
+            .flat_map(|curve| curve.keys().iter())
+            .map(|key| {
+                let kind = if matches!(key.kind, CurveKeyKind::Constant) {
+                    CurveKeyKind::Constant
+                } else {
+                    CurveKeyKind::Linear
+                };
+                (key.location(), kind)
+            })
+            .collect::<Vec<_>>();
+        keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+        keys.dedup_by(|a, b| a.0 == b.0);
+
+        let samples = keys
+            .iter()
+            .filter_map(|(location, kind)| {
+                let components = match func(self.fetch(*location)?) {
+                    TrackValue::Real(v) => vec![v],
+                    TrackValue::Vector2(v) => v.as_slice().to_vec(),
+                    TrackValue::Vector3(v) => v.as_slice().to_vec(),
+                    TrackValue::Vector4(v) => v.as_slice().to_vec(),
+                    TrackValue::UnitQuaternion(v) => {
+                        let (x, y, z) = v.euler_angles();
+                        vec![x, y, z]
+                    }
+                };
+                Some((*location, kind.clone(), components))
+            })
+            .collect::<Vec<_>>();
+
+        for (i, curve) in self.curves[..count].iter_mut().enumerate() {
+            curve.clear();
+            for (location, kind, components) in samples.iter() {
+                if let Some(component) = components.get(i) {
+                    curve.add_key(CurveKey::new(*location, *component, kind.clone()));
+                }
+            }
+        }
+
+        if self.kind == TrackValueKind::UnitQuaternion {
+            self.mode = InterpolationMode::ShortPath;
+        }
+    }
+
     /// Find a right-most key on one of the curves in the container and returns its position. This position
     /// can be treated as a maximum "length" of the container.
     pub fn time_length(&self) -> f32 {
         let mut length = 0.0;
-        for curve in self.curves.iter() {
-            let max_location = curve.max_location();
+        for max_location in self
+            .curves
+            .iter()
+            .map(|curve| curve.max_location())
+            .chain(self.quantized.iter().map(|curve| curve.max_location()))
+        {
             if max_location > length {
                 length = max_location;
             }
@@ -264,3 +685,139 @@ impl TrackDataContainer {
         length
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        container::{TrackDataContainer, TrackValueKind},
+        core::math::curve::{Curve, CurveKey, CurveKeyKind},
+        value::TrackValue,
+    };
+
+    #[test]
+    fn test_make_rotation_additive() {
+        let mut container = TrackDataContainer::new(TrackValueKind::UnitQuaternion);
+        container.curves_mut()[0] = Curve::from(vec![
+            CurveKey::new(0.0, 0.5, CurveKeyKind::Linear),
+            CurveKey::new(1.0, 1.0, CurveKeyKind::Linear),
+        ]);
+        container.curves_mut()[1] =
+            Curve::from(vec![CurveKey::new(0.5, 0.3, CurveKeyKind::Linear)]);
+
+        let reference = container.fetch(0.0).unwrap();
+        let original = container.fetch(1.0).unwrap();
+        container.make_additive(&reference);
+
+        let Some(TrackValue::UnitQuaternion(delta)) = container.fetch(0.0) else {
+            unreachable!()
+        };
+        assert!(delta.angle() < 1.0e-5);
+
+        let mut restored = reference;
+        restored.add_with(&container.fetch(1.0).unwrap(), 1.0);
+        let (TrackValue::UnitQuaternion(restored), TrackValue::UnitQuaternion(original)) =
+            (restored, original)
+        else {
+            unreachable!()
+        };
+        assert!(restored.angle_to(&original) < 1.0e-4);
+    }
+
+    fn sampled_container(
+        kind: TrackValueKind,
+        func: impl Fn(usize, f32) -> f32,
+    ) -> TrackDataContainer {
+        let mut container = TrackDataContainer::new(kind);
+        for (i, curve) in container.curves_mut().iter_mut().enumerate() {
+            *curve = Curve::from(
+                (0..=100)
+                    .map(|n| {
+                        let t = n as f32 / 100.0;
+                        CurveKey::new(t, func(i, t), CurveKeyKind::Linear)
+                    })
+                    .collect::<Vec<_>>(),
+            );
+        }
+        container
+    }
+
+    fn max_error(a: &TrackDataContainer, b: &TrackDataContainer) -> f32 {
+        (0..=1000)
+            .map(|n| {
+                let t = n as f32 / 1000.0;
+                super::value_error(&a.fetch(t).unwrap(), &b.fetch(t).unwrap())
+            })
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_reduce_keys() {
+        // Linear motion along X, constant Y and Z.
+        let mut container =
+            sampled_container(
+                TrackValueKind::Vector3,
+                |i, t| {
+                    if i == 0 {
+                        t * 2.0
+                    } else {
+                        1.0
+                    }
+                },
+            );
+        container.reduce_keys(0.001);
+        let keys = container
+            .curves_ref()
+            .iter()
+            .map(|curve| curve.keys().len())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, [2, 1, 1]);
+
+        let original = sampled_container(TrackValueKind::UnitQuaternion, |i, t| {
+            (t * 6.0 + i as f32).sin()
+        });
+        let mut container = original.clone();
+        container.reduce_keys(0.01);
+        assert!(container.keys_count() < original.keys_count());
+        assert!(max_error(&container, &original) <= 0.01 + 1.0e-4);
+    }
+
+    #[test]
+    fn test_quantize() {
+        let original =
+            sampled_container(TrackValueKind::Vector3, |i, t| (t * 6.0 + i as f32).sin());
+        let mut container = original.clone();
+        assert!(container.quantize());
+        assert!(container.is_quantized());
+        assert_eq!(container.keys_count(), original.keys_count());
+        assert!(container.memory_usage() * 4 < original.memory_usage());
+        assert!(max_error(&container, &original) < 1.0e-4);
+        assert_eq!(container.time_length(), original.time_length());
+
+        container.dequantize();
+        assert!(!container.is_quantized());
+        assert!(max_error(&container, &original) < 1.0e-4);
+
+        // Curves are available with their keys while the container is quantized, mutable access dequantizes it.
+        assert!(container.quantize());
+        assert!(container
+            .curves_ref()
+            .iter()
+            .all(|curve| curve.keys().is_empty()));
+        let curves = container.dequantized_curves();
+        for (curve, original) in curves.iter().zip(original.curves_ref()) {
+            assert_eq!(curve.id(), original.id());
+            assert_eq!(curve.keys().len(), original.keys().len());
+        }
+        drop(curves);
+        assert_eq!(container.curves_mut().len(), original.curves_ref().len());
+        assert!(!container.is_quantized());
+        assert!(max_error(&container, &original) < 1.0e-4);
+
+        let mut cubic = TrackDataContainer::new(TrackValueKind::Real);
+        cubic.curves_mut()[0] = Curve::from(vec![
+            CurveKey::new(0.0, 0.0, CurveKeyKind::new_cubic(0.0, 0.0)),
+            CurveKey::new(1.0, 1.0, CurveKeyKind::new_cubic(0.0, 0.0)),
+        ]);
+        assert!(!cubic.quantize());
+    }
+}
@@ -35,6 +35,7 @@ use crate::{
         visitor::{Visit, VisitResult, Visitor},
         ImmutableString, NameProvider,
     },
+    compression::{AnimationCompressionOptions, AnimationCompressionReport},
     track::Track,
 };
 use fxhash::FxHashMap;
@@ -54,8 +55,12 @@ use crate::track::TrackBinding;
 pub use fyrox_core as core;
 use fyrox_resource::untyped::ResourceKind;
 pub use pose::{AnimationPose, NodePose};
-pub use signal::{AnimationEvent, AnimationSignal};
+pub use signal::{
+    AnimationEvent, AnimationSignal, AnimationSignalPayload, AnimationSignalProperty,
+    AnimationSignalValue,
+};
 
+pub mod compression;
 pub mod container;
 pub mod machine;
 pub mod pose;
@@ -114,6 +119,25 @@ impl AnimationTracksData {
     {
         self.tracks.retain(filter)
     }
+
+    /// Compresses every track using the given options (see [`AnimationCompressionOptions`] docs for more info)
+    /// and returns a report that tells how much memory was saved.
+    pub fn compress(&mut self, options: &AnimationCompressionOptions) -> AnimationCompressionReport {
+        let mut report = AnimationCompressionReport::default();
+        for track in self.tracks.iter_mut() {
+            let tolerance = options.tolerance(track.value_binding());
+            let container = track.data_container_mut();
+            report.keys_before += container.keys_count();
+            report.memory_before += container.memory_usage();
+            container.reduce_keys(tolerance);
+            if options.quantize {
+                container.quantize();
+            }
+            report.keys_after += container.keys_count();
+            report.memory_after += container.memory_usage();
+        }
+        report
+    }
 }
 
 impl Visit for AnimationTracksData {
@@ -135,6 +159,13 @@ impl ResourceData for AnimationTracksData {
     fn can_be_saved(&self) -> bool {
         true
     }
+
+    fn memory_usage(&self) -> usize {
+        self.tracks
+            .iter()
+            .map(|track| std::mem::size_of::<Track>() + track.data_container().memory_usage())
+            .sum()
+    }
 }
 
 /// A resource that holds animation tracks. This resource can be shared across multiple animations.
@@ -547,6 +578,7 @@ impl<T: EntityId> Animation<T> {
                 self.events.push_back(AnimationEvent {
                     signal_id: signal.id,
                     name: signal.name.clone(),
+                    payload: signal.payload.clone(),
                 });
             }
         }
@@ -961,18 +993,74 @@ impl<T: EntityId> Animation<T> {
             return;
         };
 
-        self.pose.reset();
-        for track in tracks_data.tracks.iter() {
-            let Some(binding) = self.track_bindings.get(&track.id()) else {
-                continue;
-            };
+        sample_pose(
+            tracks_data,
+            &self.track_bindings,
+            self.time_position,
+            &mut self.pose,
+        );
+    }
 
-            if binding.is_enabled() {
-                if let Some(bound_value) = track.fetch(self.time_position) {
-                    self.pose.add_to_node_pose(binding.target(), bound_value);
-                }
-            }
+    /// Calculates a pose of the animation at the given time position. Unlike [`Self::pose`], this method does not
+    /// depend on the current playback position. It could be useful to get a reference pose for
+    /// [`Self::make_additive`].
+    pub fn pose_at(&self, time: f32) -> AnimationPose<T> {
+        let mut pose = AnimationPose::default();
+        let state = self.tracks_data.state();
+        if let Some(tracks_data) = state.data_ref() {
+            sample_pose(tracks_data, &self.track_bindings, time, &mut pose);
         }
+        pose
+    }
+
+    /// Bakes the animation into additive form, so its tracks will produce deltas relative to the given reference
+    /// pose. Such animations are meant to be played on additive layers (see
+    /// [`machine::MachineLayer::set_additive`]) or to be used as additive inputs of blending nodes (see
+    /// [`machine::BlendPose::additive`]), which adds their motion on top of other animations. For example, a
+    /// breathing animation could be baked relative to its own first frame and then layered over locomotion:
+    ///
+    /// ```rust
+    /// # use fyrox_animation::Animation;
+    /// # use fyrox_core::pool::ErasedHandle;
+    /// fn bake_breathing(breathing: &mut Animation<ErasedHandle>) {
+    ///     let reference = breathing.pose_at(0.0);
+    ///     breathing.make_additive(&reference);
+    /// }
+    /// ```
+    ///
+    /// The reference pose could also be taken from some other animation, for example from an idle animation. Tracks
+    /// that have no respective value in the reference pose are removed. The baked tracks are stored in a new embedded
+    /// resource, so other animations that share the same tracks data are not affected.
+    pub fn make_additive(&mut self, reference: &AnimationPose<T>) {
+        let state = self.tracks_data.state();
+        let Some(tracks_data) = state.data_ref() else {
+            return;
+        };
+        let mut additive = tracks_data.clone();
+        drop(state);
+
+        additive.tracks.retain_mut(|track| {
+            let Some(reference_value) = self
+                .track_bindings
+                .get(&track.id())
+                .and_then(|binding| reference.poses().get(&binding.target()))
+                .and_then(|pose| {
+                    pose.values
+                        .values
+                        .iter()
+                        .find(|value| &value.binding == track.value_binding())
+                })
+            else {
+                return false;
+            };
+            track
+                .data_container_mut()
+                .make_additive(&reference_value.value);
+            true
+        });
+
+        self.tracks_data = Resource::new_ok(ResourceKind::Embedded, additive);
+        self.update_pose();
     }
 
     /// Returns current pose of the animation (a final result that can be applied to a scene graph).
@@ -1002,6 +1090,26 @@ impl<T: EntityId> Default for Animation<T> {
     }
 }
 
+fn sample_pose<T: EntityId>(
+    tracks_data: &AnimationTracksData,
+    track_bindings: &FxHashMap<Uuid, TrackBinding<T>>,
+    time: f32,
+    pose: &mut AnimationPose<T>,
+) {
+    pose.reset();
+    for track in tracks_data.tracks.iter() {
+        let Some(binding) = track_bindings.get(&track.id()) else {
+            continue;
+        };
+
+        if binding.is_enabled() {
+            if let Some(bound_value) = track.fetch(time) {
+                pose.add_to_node_pose(binding.target(), bound_value);
+            }
+        }
+    }
+}
+
 /// A container for animations. It is a tiny wrapper around [`Pool`], you should never create the container yourself,
 /// it is managed by the engine.
 #[derive(Debug, Clone, Reflect, PartialEq)]
@@ -48,6 +48,13 @@ pub enum Event<T: EntityId> {
 
     /// Occurs when active transition was changed.
     ActiveTransitionChanged(Handle<Transition<T>>),
+
+    /// Occurs when a transition has started. It is emitted right after the source state was left and the
+    /// destination state was entered.
+    TransitionStarted(Handle<Transition<T>>),
+
+    /// Occurs when a transition has finished blending and its destination state became active.
+    TransitionFinished(Handle<Transition<T>>),
 }
 
 /// A simple event queue with fixed capacity. It is used to store a fixed amount of events and discard any
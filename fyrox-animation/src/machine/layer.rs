@@ -89,6 +89,9 @@ pub struct MachineLayer<T: EntityId> {
 
     mask: LayerMask<T>,
 
+    #[visit(optional)] // Backward compatibility
+    additive: bool,
+
     #[reflect(hidden)]
     nodes: Pool<PoseNode<T>>,
 
@@ -181,6 +184,7 @@ impl<T: EntityId> MachineLayer<T> {
             events: FixedEventQueue::new(2048),
             debug: false,
             mask: Default::default(),
+            additive: false,
         }
     }
 
@@ -276,6 +280,12 @@ impl<T: EntityId> MachineLayer<T> {
     ///         Event::ActiveTransitionChanged(transition_handle) => {
     ///             // Occurs when active transition has changed.
     ///         }
+    ///         Event::TransitionStarted(transition_handle) => {
+    ///             // Occurs when a transition has started.
+    ///         }
+    ///         Event::TransitionFinished(transition_handle) => {
+    ///             // Occurs when a transition has finished.
+    ///         }
     ///     }
     /// }
     /// ```
@@ -536,6 +546,22 @@ impl<T: EntityId> MachineLayer<T> {
         self.weight
     }
 
+    /// Defines whether the layer is additive or not. Pose of an additive layer is added on top of the pose
+    /// produced by the previous layers (using the layer weight), instead of being blended with it. Additive
+    /// layers should play animations that were baked into additive form (see
+    /// [`crate::Animation::make_additive`]), for example breathing, recoil or leaning. By default the layer
+    /// is not additive.
+    #[inline]
+    pub fn set_additive(&mut self, additive: bool) {
+        self.additive = additive;
+    }
+
+    /// Returns `true` if the layer is additive, `false` - otherwise.
+    #[inline]
+    pub fn is_additive(&self) -> bool {
+        self.additive
+    }
+
     /// Sets new layer mask. See docs of [`LayerMask`] for more info about layer masks.
     #[inline]
     pub fn set_mask(&mut self, mask: LayerMask<T>) -> LayerMask<T> {
@@ -643,6 +669,7 @@ impl<T: EntityId> MachineLayer<T> {
                         self.active_transition = handle;
                         self.events
                             .push(Event::ActiveTransitionChanged(self.active_transition));
+                        self.events.push(Event::TransitionStarted(handle));
 
                         break;
                     }
@@ -668,6 +695,9 @@ impl<T: EntityId> MachineLayer<T> {
                 if transition.is_done() {
                     transition.reset();
 
+                    self.events
+                        .push(Event::TransitionFinished(self.active_transition));
+
                     self.active_transition = Handle::NONE;
                     self.events
                         .push(Event::ActiveTransitionChanged(self.active_transition));
@@ -355,11 +355,138 @@ impl<T: EntityId> Machine<T> {
 
         for layer in self.layers.iter_mut() {
             let weight = layer.weight();
+            let additive = layer.is_additive();
             let pose = layer.evaluate_pose(animations, &self.parameters, dt);
 
-            self.final_pose.blend_with(pose, weight);
+            if additive {
+                self.final_pose.add_with(pose, weight);
+            } else {
+                self.final_pose.blend_with(pose, weight);
+            }
         }
 
         &self.final_pose
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        container::{TrackDataContainer, TrackValueKind},
+        core::{
+            algebra::Vector3,
+            math::curve::{Curve, CurveKey, CurveKeyKind},
+            pool::ErasedHandle,
+        },
+        machine::{
+            Event, Machine, MachineLayer, Parameter, PlayAnimation, PoseNode, State, Transition,
+        },
+        track::{Track, TrackBinding},
+        value::{TrackValue, ValueBinding},
+        Animation, AnimationContainer,
+    };
+
+    fn make_animation(target: ErasedHandle, keys: &[(f32, f32)]) -> Animation<ErasedHandle> {
+        let mut container = TrackDataContainer::new(TrackValueKind::Vector3);
+        container.curves_mut()[0] = Curve::from(
+            keys.iter()
+                .map(|(t, v)| CurveKey::new(*t, *v, CurveKeyKind::Linear))
+                .collect::<Vec<_>>(),
+        );
+        let mut animation = Animation::default();
+        animation.add_track_with_binding(
+            TrackBinding::new(target),
+            Track::new(container, ValueBinding::Position),
+        );
+        animation.set_time_slice(0.0..1.0);
+        animation
+    }
+
+    fn make_layer(
+        animation: crate::core::pool::Handle<Animation<ErasedHandle>>,
+    ) -> MachineLayer<ErasedHandle> {
+        let mut layer = MachineLayer::new();
+        let node = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::new(animation)));
+        let state = layer.add_state(State::new("State", node));
+        layer.set_entry_state(state);
+        layer
+    }
+
+    #[test]
+    fn test_additive_layer() {
+        let target = ErasedHandle::new(1, 1);
+
+        let mut animations = AnimationContainer::new();
+        let base = animations.add(make_animation(target, &[(0.0, 10.0), (1.0, 10.0)]));
+        let mut lean = make_animation(target, &[(0.0, 1.0), (1.0, 3.0)]);
+        let reference = lean.pose_at(0.0);
+        lean.make_additive(&reference);
+        let lean = animations.add(lean);
+
+        let mut machine = Machine::new();
+        machine.add_layer(make_layer(base));
+        let mut additive_layer = make_layer(lean);
+        additive_layer.set_additive(true);
+        additive_layer.set_weight(0.5);
+        machine.add_layer(additive_layer);
+
+        // Move the additive animation to its end, where the delta is 2.0.
+        animations[lean].set_time_position(1.0);
+        let pose = machine.evaluate_pose(&mut animations, 0.0);
+        let value = &pose.poses()[&target].values.values[0].value;
+        assert_eq!(value, &TrackValue::Vector3(Vector3::new(11.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_transition_events() {
+        let target = ErasedHandle::new(1, 1);
+
+        let mut animations = AnimationContainer::new();
+        let idle = animations.add(make_animation(target, &[(0.0, 0.0), (1.0, 0.0)]));
+        let run = animations.add(make_animation(target, &[(0.0, 1.0), (1.0, 1.0)]));
+
+        let mut layer = make_layer(idle);
+        let idle_state = layer.entry_state();
+        let run_node = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::new(run)));
+        let run_state = layer.add_state(State::new("Run", run_node));
+        let transition = layer.add_transition(Transition::new(
+            "IdleToRun",
+            idle_state,
+            run_state,
+            0.5,
+            "Run",
+        ));
+
+        let mut machine = Machine::new();
+        machine.layers_mut()[0] = layer;
+        machine.set_parameter("Run", Parameter::Rule(true));
+
+        machine.evaluate_pose(&mut animations, 0.1);
+        let layer = &mut machine.layers_mut()[0];
+        let events = std::iter::from_fn(|| layer.pop_event()).collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            [
+                Event::StateLeave(idle_state),
+                Event::StateEnter(run_state),
+                Event::ActiveTransitionChanged(transition),
+                Event::TransitionStarted(transition),
+            ]
+        );
+
+        machine.evaluate_pose(&mut animations, 1.0);
+        let layer = &mut machine.layers_mut()[0];
+        let events = std::iter::from_fn(|| layer.pop_event()).collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            [
+                Event::TransitionFinished(transition),
+                Event::ActiveTransitionChanged(Default::default()),
+                Event::ActiveStateChanged {
+                    prev: idle_state,
+                    new: run_state,
+                },
+            ]
+        );
+    }
+}
@@ -50,6 +50,11 @@ pub struct BlendPose<T: EntityId> {
     /// A source of animation pose.
     #[reflect(hidden)]
     pub pose_source: Handle<PoseNode<T>>,
+
+    /// Defines whether the pose is additive or not. Additive poses are added on top of the result of blending
+    /// of all non-additive poses, see [`crate::AnimationPose::add_with`] for more info.
+    #[visit(optional)] // Backward compatibility
+    pub additive: bool,
 }
 
 impl<T: EntityId> TypeUuidProvider for BlendPose<T> {
@@ -64,6 +69,7 @@ impl<T: EntityId> BlendPose<T> {
         Self {
             weight,
             pose_source,
+            additive: false,
         }
     }
 
@@ -73,6 +79,7 @@ impl<T: EntityId> BlendPose<T> {
         Self {
             weight: PoseWeight::Constant(weight),
             pose_source,
+            additive: false,
         }
     }
 
@@ -82,8 +89,15 @@ impl<T: EntityId> BlendPose<T> {
         Self {
             weight: PoseWeight::Parameter(param_id.to_owned()),
             pose_source,
+            additive: false,
         }
     }
+
+    /// Marks the pose as additive. See [`Self::additive`] docs for more info.
+    pub fn with_additive(mut self, additive: bool) -> Self {
+        self.additive = additive;
+        self
+    }
 }
 
 /// Animation blend node. It takes multiple input poses and mixes them together into single pose with specified
@@ -146,7 +160,10 @@ impl<T: EntityId> AnimationPoseSource<T> for BlendAnimations<T> {
         dt: f32,
     ) -> Ref<AnimationPose<T>> {
         self.output_pose.borrow_mut().reset();
-        for blend_pose in self.pose_sources.iter() {
+        // Additive poses are applied on top of the blended ones.
+        let blend_poses = self.pose_sources.iter().filter(|p| !p.additive);
+        let additive_poses = self.pose_sources.iter().filter(|p| p.additive);
+        for blend_pose in blend_poses.chain(additive_poses) {
             let weight = match blend_pose.weight {
                 PoseWeight::Constant(value) => value,
                 PoseWeight::Parameter(ref param_id) => {
@@ -162,9 +179,12 @@ impl<T: EntityId> AnimationPoseSource<T> for BlendAnimations<T> {
                 .try_borrow(blend_pose.pose_source)
                 .map(|pose_source| pose_source.eval_pose(nodes, params, animations, dt))
             {
-                self.output_pose
-                    .borrow_mut()
-                    .blend_with(&pose_source, weight);
+                let mut output_pose = self.output_pose.borrow_mut();
+                if blend_pose.additive {
+                    output_pose.add_with(&pose_source, weight);
+                } else {
+                    output_pose.blend_with(&pose_source, weight);
+                }
             }
         }
         self.output_pose.borrow()
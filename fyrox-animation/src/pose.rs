@@ -87,6 +87,32 @@ impl<T: EntityId> AnimationPose<T> {
             .blend_with(&other.root_motion.clone().unwrap_or_default(), weight);
     }
 
+    /// Adds an additive animation pose to the current pose using a weight coefficient. Additive pose contains
+    /// deltas relative to some reference pose (see [`Self::make_additive`]), which are added on top of the
+    /// current pose. Unlike blending, node poses that are missing in the current pose are ignored, because
+    /// there is nothing to add the delta to. Root motion of the additive pose is ignored too.
+    pub fn add_with(&mut self, additive: &AnimationPose<T>, weight: f32) {
+        for (handle, additive_pose) in additive.poses.iter() {
+            if let Some(current_pose) = self.poses.get_mut(handle) {
+                current_pose.values.add_with(&additive_pose.values, weight);
+            }
+        }
+    }
+
+    /// Converts the current pose into an additive pose, that contains deltas relative to the given reference
+    /// pose. Values that are missing in the reference pose are removed.
+    pub fn make_additive(&mut self, reference: &AnimationPose<T>) {
+        self.poses.retain(|handle, pose| {
+            if let Some(reference_pose) = reference.poses.get(handle) {
+                pose.values.make_additive(&reference_pose.values);
+                !pose.values.values.is_empty()
+            } else {
+                false
+            }
+        });
+        self.root_motion = None;
+    }
+
     fn add_node_pose(&mut self, local_pose: NodePose<T>) {
         self.poses.insert(local_pose.node, local_pose);
     }
@@ -20,17 +20,149 @@
 
 //! Signal is a named marker on specific time position on the animation timeline. See [`AnimationSignal`] docs for more info.
 
-use crate::core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*};
+use crate::core::{
+    algebra::Vector3, reflect::prelude::*, uuid::Uuid, uuid_provider, visitor::prelude::*,
+};
 use fyrox_core::NameProvider;
+use strum_macros::{AsRefStr, EnumString, VariantNames};
 
 /// An event happened in an animation.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct AnimationEvent {
     /// An id of an animation event.
     pub signal_id: Uuid,
 
     /// Name of the signal emitted the event.
     pub name: String,
+
+    /// A copy of the payload of the signal emitted the event.
+    pub payload: AnimationSignalPayload,
+}
+
+/// A value of a single property of an animation signal payload.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, VariantNames, EnumString, AsRefStr)]
+pub enum AnimationSignalValue {
+    /// Boolean value, could be used as a flag (for example - to enable or disable a hit window).
+    Bool(bool),
+
+    /// Integer value.
+    Integer(i32),
+
+    /// Real number.
+    Number(f32),
+
+    /// Arbitrary string (for example - a name of a sound to play).
+    String(String),
+
+    /// Three-dimensional vector.
+    Vector3(Vector3<f32>),
+}
+
+uuid_provider!(AnimationSignalValue = "00368042-d357-4530-8c63-8a2a9018f2e0");
+
+impl Default for AnimationSignalValue {
+    fn default() -> Self {
+        Self::Bool(false)
+    }
+}
+
+/// A named value of an animation signal payload.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct AnimationSignalProperty {
+    /// Name of the property.
+    pub name: String,
+
+    /// Value of the property.
+    pub value: AnimationSignalValue,
+}
+
+uuid_provider!(AnimationSignalProperty = "4b812125-9f16-4b3d-bb0b-963947bad787");
+
+/// A set of named values attached to an animation signal. Every event produced by the signal carries a copy
+/// of the payload, so the receiver could react to the event without knowing anything about the signal itself.
+/// For example, a footstep signal could store the name of the foot and a sound volume.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct AnimationSignalPayload {
+    /// A list of payload properties.
+    pub properties: Vec<AnimationSignalProperty>,
+}
+
+uuid_provider!(AnimationSignalPayload = "609f57d3-4f29-4bc2-974e-1b1288a489ba");
+
+impl AnimationSignalPayload {
+    /// Adds a new property to the payload and returns the payload. Could be used to build payloads
+    /// in a chained manner.
+    pub fn with_property<S: AsRef<str>>(mut self, name: S, value: AnimationSignalValue) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Sets a new value of a property with the given name. The property will be added if it does not exist.
+    pub fn set<S: AsRef<str>>(&mut self, name: S, value: AnimationSignalValue) {
+        let name = name.as_ref();
+        if let Some(property) = self.properties.iter_mut().find(|p| p.name == name) {
+            property.value = value;
+        } else {
+            self.properties.push(AnimationSignalProperty {
+                name: name.to_owned(),
+                value,
+            });
+        }
+    }
+
+    /// Tries to find a value of a property with the given name.
+    pub fn get<S: AsRef<str>>(&self, name: S) -> Option<&AnimationSignalValue> {
+        let name = name.as_ref();
+        self.properties
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| &p.value)
+    }
+
+    /// Tries to fetch a boolean property with the given name.
+    pub fn get_bool<S: AsRef<str>>(&self, name: S) -> Option<bool> {
+        match self.get(name)? {
+            AnimationSignalValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Tries to fetch an integer property with the given name.
+    pub fn get_integer<S: AsRef<str>>(&self, name: S) -> Option<i32> {
+        match self.get(name)? {
+            AnimationSignalValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Tries to fetch a numeric property with the given name.
+    pub fn get_number<S: AsRef<str>>(&self, name: S) -> Option<f32> {
+        match self.get(name)? {
+            AnimationSignalValue::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Tries to fetch a string property with the given name.
+    pub fn get_string<S: AsRef<str>>(&self, name: S) -> Option<&str> {
+        match self.get(name)? {
+            AnimationSignalValue::String(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Tries to fetch a vector property with the given name.
+    pub fn get_vector3<S: AsRef<str>>(&self, name: S) -> Option<Vector3<f32>> {
+        match self.get(name)? {
+            AnimationSignalValue::Vector3(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the payload has no properties.
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
 }
 
 /// Signal is a named marker on specific time position on the animation timeline. Signal will emit an event if the animation playback
@@ -52,6 +184,10 @@ pub struct AnimationSignal {
 
     /// The flag defines whether the signal is enabled or not. Disabled signals won't produce any events.
     pub enabled: bool,
+
+    /// Additional data that will be passed to every event produced by the signal.
+    #[visit(optional)] // Backward compatibility
+    pub payload: AnimationSignalPayload,
 }
 
 impl NameProvider for AnimationSignal {
@@ -68,8 +204,15 @@ impl AnimationSignal {
             name: name.to_owned(),
             time,
             enabled: true,
+            payload: Default::default(),
         }
     }
+
+    /// Sets a new payload of the signal and returns the signal. Could be used to create signals in a chained manner.
+    pub fn with_payload(mut self, payload: AnimationSignalPayload) -> Self {
+        self.payload = payload;
+        self
+    }
 }
 
 impl Default for AnimationSignal {
@@ -79,6 +222,39 @@ impl Default for AnimationSignal {
             name: Default::default(),
             time: 0.0,
             enabled: true,
+            payload: Default::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{pool::ErasedHandle, uuid::Uuid},
+        signal::{AnimationSignal, AnimationSignalPayload, AnimationSignalValue},
+        Animation,
+    };
+
+    #[test]
+    fn test_signal_payload() {
+        let payload = AnimationSignalPayload::default()
+            .with_property("Foot", AnimationSignalValue::String("Left".to_string()))
+            .with_property("Volume", AnimationSignalValue::Number(0.5));
+
+        let mut animation = Animation::<ErasedHandle>::default();
+        animation.set_time_slice(0.0..1.0);
+        animation.add_signal(
+            AnimationSignal::new(Uuid::new_v4(), "Footstep", 0.25).with_payload(payload.clone()),
+        );
+
+        animation.tick(0.5);
+
+        let event = animation.pop_event().unwrap();
+        assert_eq!(event.name, "Footstep");
+        assert_eq!(event.payload, payload);
+        assert_eq!(event.payload.get_string("Foot"), Some("Left"));
+        assert_eq!(event.payload.get_number("Volume"), Some(0.5));
+        assert_eq!(event.payload.get_bool("Volume"), None);
+        assert!(animation.pop_event().is_none());
+    }
+}
@@ -233,6 +233,36 @@ impl TrackValue {
         }
     }
 
+    /// Adds an additive value (a delta, see [`Self::make_additive`]) scaled by the given weight to the current
+    /// value. Numeric values are simply added, rotations are combined. Adding is possible only if the types are
+    /// the same.
+    pub fn add_with(&mut self, additive: &Self, weight: f32) {
+        match (self, additive) {
+            (Self::Real(a), Self::Real(b)) => *a += *b * weight,
+            (Self::Vector2(a), Self::Vector2(b)) => *a += b.scale(weight),
+            (Self::Vector3(a), Self::Vector3(b)) => *a += b.scale(weight),
+            (Self::Vector4(a), Self::Vector4(b)) => *a += b.scale(weight),
+            (Self::UnitQuaternion(a), Self::UnitQuaternion(b)) => {
+                *a *= nlerp(UnitQuaternion::identity(), b, weight)
+            }
+            _ => (),
+        }
+    }
+
+    /// Converts the current value into a delta relative to the given reference value, so that adding the delta
+    /// to the reference value (see [`Self::add_with`]) produces the original value. Conversion is possible only if
+    /// the types are the same.
+    pub fn make_additive(&mut self, reference: &Self) {
+        match (self, reference) {
+            (Self::Real(a), Self::Real(b)) => *a -= *b,
+            (Self::Vector2(a), Self::Vector2(b)) => *a -= *b,
+            (Self::Vector3(a), Self::Vector3(b)) => *a -= *b,
+            (Self::Vector4(a), Self::Vector4(b)) => *a -= *b,
+            (Self::UnitQuaternion(a), Self::UnitQuaternion(b)) => *a = b.inverse() * *a,
+            _ => (),
+        }
+    }
+
     /// Tries to perform a numeric type casting of the current value to some other and returns a boxed value, that can
     /// be used to set the value using reflection.
     pub fn apply_to_any(&self, any: &mut dyn Any, value_type: ValueType) {
@@ -401,6 +431,13 @@ impl BoundValue {
         self.value.blend_with(&other.value, weight);
     }
 
+    /// Adds an additive value to the current value using the given weight. See [`TrackValue::add_with`] for
+    /// more info.
+    pub fn add_with(&mut self, additive: &Self, weight: f32) {
+        assert_eq!(self.binding, additive.binding);
+        self.value.add_with(&additive.value, weight);
+    }
+
     /// Sets a property of the given object.
     pub fn apply_to_object(
         &self,
@@ -440,6 +477,34 @@ impl BoundValueCollection {
             }
         }
     }
+
+    /// Tries to add each value of the other (additive) collection to a respective (by binding) value in the current
+    /// collection. See [`TrackValue::add_with`] docs for more info.
+    pub fn add_with(&mut self, additive: &Self, weight: f32) {
+        for value in self.values.iter_mut() {
+            if let Some(additive_value) =
+                additive.values.iter().find(|v| v.binding == value.binding)
+            {
+                value.add_with(additive_value, weight);
+            }
+        }
+    }
+
+    /// Converts each value of the current collection into a delta relative to a respective (by binding) value in
+    /// the reference collection. Values that have no respective reference value are removed. See
+    /// [`TrackValue::make_additive`] docs for more info.
+    pub fn make_additive(&mut self, reference: &Self) {
+        self.values.retain_mut(|value| {
+            if let Some(reference_value) =
+                reference.values.iter().find(|v| v.binding == value.binding)
+            {
+                value.value.make_additive(&reference_value.value);
+                true
+            } else {
+                false
+            }
+        });
+    }
 }
 
 /// Interpolates from `a` to `b` using nlerp, including an additional check to ensure
@@ -459,7 +524,10 @@ pub fn negate_unit_quaternion(a: &UnitQuaternion<f32>) -> UnitQuaternion<f32> {
 #[cfg(test)]
 mod test {
     use crate::value::{BoundValue, TrackValue, ValueBinding, ValueType};
-    use fyrox_core::reflect::prelude::*;
+    use fyrox_core::{
+        algebra::{UnitQuaternion, Vector3},
+        reflect::prelude::*,
+    };
 
     #[derive(Default, Reflect, Debug, PartialEq)]
     struct OtherStruct {
@@ -473,6 +541,33 @@ mod test {
         other_struct: OtherStruct,
     }
 
+    #[test]
+    fn test_additive_value() {
+        let reference =
+            TrackValue::UnitQuaternion(UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3));
+        let original =
+            TrackValue::UnitQuaternion(UnitQuaternion::from_euler_angles(0.5, -0.2, 0.0));
+
+        let mut delta = original.clone();
+        delta.make_additive(&reference);
+        let mut restored = reference.clone();
+        restored.add_with(&delta, 1.0);
+        let (TrackValue::UnitQuaternion(restored), TrackValue::UnitQuaternion(original)) =
+            (restored, original)
+        else {
+            unreachable!()
+        };
+        assert!(restored.angle_to(&original) < 1.0e-5);
+
+        let mut value = TrackValue::Vector3(Vector3::new(1.0, 2.0, 3.0));
+        value.make_additive(&TrackValue::Vector3(Vector3::new(1.0, 1.0, 1.0)));
+        assert_eq!(value, TrackValue::Vector3(Vector3::new(0.0, 1.0, 2.0)));
+
+        let mut base = TrackValue::Vector3(Vector3::new(5.0, 5.0, 5.0));
+        base.add_with(&value, 0.5);
+        assert_eq!(base, TrackValue::Vector3(Vector3::new(5.0, 5.5, 6.0)));
+    }
+
     #[test]
     fn test_apply_value() {
         let some_bool_value = BoundValue {
@@ -131,6 +131,44 @@ use syn::{parse_macro_input, DeriveInput};
 /// }
 /// # fn main() {}
 /// ```
+///
+/// # Versioning
+///
+/// `#[visit(version = 2, migrate = "function")]` on a type stores the version of the data in the
+/// region of the type and calls the given migration function for every version step when older
+/// data is read, before any field is visited. `migrate` is optional, and it must point to a
+/// function with the signature of `fyrox_core::visitor::MigrationFn`.
+///
+/// ```
+/// # use fyrox_core::visitor::{RegionMut, Visit, VisitResult, Visitor};
+/// #[derive(Visit)]
+/// #[visit(version = 1, migrate = "migrate_foo")]
+/// struct Foo {
+///     renamed: f32,
+/// }
+///
+/// fn migrate_foo(from_version: u32, region: &mut RegionMut) -> VisitResult {
+///     region.rename_field("Original", "Renamed")
+/// }
+/// # fn main() {}
+/// ```
+///
+/// would expand to something like:
+///
+/// ```
+/// # use fyrox_core::visitor::{RegionMut, Visit, VisitResult, Visitor};
+/// # struct Foo { renamed: f32 }
+/// # fn migrate_foo(from_version: u32, region: &mut RegionMut) -> VisitResult { Ok(()) }
+/// impl Visit for Foo {
+///     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+///         let mut region = visitor.enter_region(name)?;
+///         region.visit_version(1, Some(migrate_foo))?;
+///         self.renamed.visit("Renamed", &mut region)?;
+///         Ok(())
+///     }
+/// }
+/// # fn main() {}
+/// ```
 #[proc_macro_derive(Visit, attributes(visit))]
 pub fn visit(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -41,8 +41,15 @@ fn impl_visit_struct(
     field_args: &ast::Fields<args::FieldArgs>,
 ) -> TokenStream2 {
     let visit_fn_body = if field_args.style == ast::Style::Unit {
+        assert!(
+            ty_args.version.is_none(),
+            "unit structs have no data, there's nothing to version!"
+        );
+
         quote! { Ok(()) }
     } else {
+        let version_visit = utils::create_version_visit(ty_args);
+
         // `field.visit(..)?;` parts
         let field_visits = utils::create_field_visits(
             true,
@@ -56,6 +63,7 @@ fn impl_visit_struct(
                 Ok(x) => x,
                 Err(err) => return Err(err),
             };
+            #version_visit
             #(#field_visits)*
             Ok(())
         }
@@ -211,6 +219,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
         }
     });
 
+    let version_visit = utils::create_version_visit(ty_args);
+
     utils::create_impl(
         ty_args,
         variant_args.iter().flat_map(|v| v.fields.iter()).cloned(),
@@ -220,6 +230,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                  Err(err) => return Err(err),
              };
 
+             #version_visit
+
              let mut id = id(self);
              if let Err(err) = id.visit("Id", &mut region) {
                  return Err(err);
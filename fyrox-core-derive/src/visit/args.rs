@@ -29,6 +29,16 @@ pub struct TypeArgs {
     pub data: ast::Data<VariantArgs, FieldArgs>,
     #[darling(default)]
     pub optional: bool,
+    /// `#[visit(version = 3)]`
+    ///
+    /// Store the version of the data in the region of the type
+    #[darling(default)]
+    pub version: Option<u32>,
+    /// `#[visit(migrate = "path::to::function")]`
+    ///
+    /// Function that upgrades the data of older versions, requires `version`
+    #[darling(default)]
+    pub migrate: Option<Path>,
 }
 
 /// Parsed from struct's or enum variant's field
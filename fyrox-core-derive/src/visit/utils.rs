@@ -68,6 +68,26 @@ fn create_impl_generics(
     generics
 }
 
+/// `region.visit_version(<version>, <migrate>)?;`
+pub fn create_version_visit(ty_args: &args::TypeArgs) -> TokenStream2 {
+    match (ty_args.version, &ty_args.migrate) {
+        (Some(version), migrate) => {
+            let migrate = match migrate {
+                Some(migrate) => quote!(Some(#migrate)),
+                None => quote!(None),
+            };
+
+            quote! {
+                if let Err(err) = region.visit_version(#version, #migrate) {
+                    return Err(err);
+                }
+            }
+        }
+        (None, Some(_)) => panic!("`migrate` attribute requires `version` attribute!"),
+        (None, None) => quote!(),
+    }
+}
+
 /// `<prefix>field.visit("name", visitor)?;`
 pub fn create_field_visits<'a>(
     // false if enum variant
@@ -23,7 +23,7 @@
 mod basic;
 mod compat;
 
-use std::{env, fs::File, io::Write, path::PathBuf};
+use std::{env, path::PathBuf};
 
 use futures::executor::block_on;
 use fyrox_core::visitor::prelude::*;
@@ -49,8 +49,16 @@ pub fn save_load<T: Visit>(test_name: &str, data: &mut T, data_default: &mut T)
         data.visit("Data", &mut visitor).unwrap();
 
         visitor.save_binary(&bin).unwrap();
-        let mut file = File::create(txt).unwrap();
-        file.write_all(visitor.save_text().as_bytes()).unwrap();
+        visitor.save_text_to_file(&txt).unwrap();
+    }
+
+    // The text form must contain exactly the same data as the binary one.
+    {
+        let text = block_on(Visitor::load_from_file(&txt)).unwrap();
+        assert_eq!(
+            text.save_binary_to_vec().unwrap(),
+            std::fs::read(&bin).unwrap()
+        );
     }
 
     // Load the saved data to `data_default`
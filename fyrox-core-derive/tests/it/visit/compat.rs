@@ -20,7 +20,7 @@
 
 //! Fight the compatibility hell with attributes! .. someday :)
 
-use fyrox_core::visitor::prelude::*;
+use fyrox_core::visitor::{prelude::*, FieldKind, RegionMut};
 
 // Comment it out and make sure it panics
 // #[derive(Debug, Clone, PartialEq, Visit)]
@@ -45,3 +45,109 @@ fn rename() {
 
     assert_eq!(data, data_default);
 }
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub struct PlayerV0 {
+    pub health: f32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Visit)]
+#[visit(version = 2, migrate = "migrate_player")]
+pub struct Player {
+    pub hit_points: f32,
+    pub name: String,
+    pub armor: f32,
+}
+
+fn migrate_player(from_version: u32, region: &mut RegionMut) -> VisitResult {
+    match from_version {
+        // `health` was renamed to `hit_points`.
+        0 => region.rename_field("Health", "HitPoints"),
+        // `armor` was added, its initial value was a half of health.
+        1 => {
+            let Some(FieldKind::F32(hit_points)) = region.field("HitPoints").cloned() else {
+                return Err(VisitError::FieldTypeDoesNotMatch);
+            };
+            region.set_field("Armor", FieldKind::F32(hit_points / 2.0));
+            Ok(())
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Visit)]
+#[visit(version = 1, migrate = "migrate_shape")]
+pub enum Shape {
+    Circle { radius: f32 },
+    Square { size: f32 },
+}
+
+impl Default for Shape {
+    fn default() -> Self {
+        Self::Circle { radius: 0.0 }
+    }
+}
+
+fn migrate_shape(_from_version: u32, region: &mut RegionMut) -> VisitResult {
+    // Circles used to store diameter.
+    if region.field("Id") == Some(&FieldKind::U32(0)) {
+        if let Some(FieldKind::F32(diameter)) = region.field_mut("Radius") {
+            *diameter /= 2.0;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn versioned() {
+    let mut data = Player {
+        hit_points: 100.0,
+        name: "Name".to_string(),
+        armor: 25.0,
+    };
+    let mut data_default = Player::default();
+
+    super::save_load("versioned", &mut data, &mut data_default);
+
+    assert_eq!(data, data_default);
+}
+
+#[test]
+fn migrate_struct() {
+    let mut visitor = Visitor::new();
+    let mut old = PlayerV0 {
+        health: 100.0,
+        name: "Name".to_string(),
+    };
+    old.visit("Data", &mut visitor).unwrap();
+
+    let mut visitor = Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap();
+    let mut new = Player::default();
+    new.visit("Data", &mut visitor).unwrap();
+
+    assert_eq!(
+        new,
+        Player {
+            hit_points: 100.0,
+            name: "Name".to_string(),
+            armor: 50.0,
+        }
+    );
+}
+
+#[test]
+fn migrate_enum() {
+    let mut visitor = Visitor::new();
+    {
+        let mut region = visitor.enter_region("Data").unwrap();
+        0u32.visit("Id", &mut region).unwrap();
+        4.0f32.visit("Radius", &mut region).unwrap();
+    }
+
+    let mut visitor = Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap();
+    let mut shape = Shape::default();
+    shape.visit("Data", &mut visitor).unwrap();
+
+    assert_eq!(shape, Shape::Circle { radius: 2.0 });
+}
@@ -30,6 +30,11 @@
 
 pub use fyrox_core_derive::Visit;
 
+mod migration;
+mod text;
+
+pub use migration::{MigrationFn, RegionMut};
+
 pub mod prelude {
     //! Types to use `#[derive(Visit)]`
     pub use super::{Visit, VisitError, VisitResult, Visitor};
@@ -46,7 +51,6 @@ use crate::{
     replace_slashes,
 };
 
-use base64::Engine;
 use bitflags::bitflags;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use fxhash::FxHashMap;
@@ -63,6 +67,7 @@ use std::{
     ops::{Deref, DerefMut, Range},
     path::{Path, PathBuf},
     rc::Rc,
+    str::Utf8Error,
     string::FromUtf8Error,
     sync::{Arc, Mutex, RwLock},
     time::Duration,
@@ -74,6 +79,7 @@ use uuid::Uuid;
 /// of these types.
 /// Fields can be accessed from a visitor using [Visit::visit] on a variable with the
 /// same type as the field.
+#[derive(Debug, Clone, PartialEq)]
 pub enum FieldKind {
     Bool(bool),
     U8(u8),
@@ -293,153 +299,6 @@ impl<T: Pod> Visit for PodVecView<'_, T> {
     }
 }
 
-impl FieldKind {
-    fn as_string(&self) -> String {
-        match self {
-            Self::Bool(data) => format!("<bool = {data}>, "),
-            Self::U8(data) => format!("<u8 = {data}>, "),
-            Self::I8(data) => format!("<i8 = {data}>, "),
-            Self::U16(data) => format!("<u16 = {data}>, "),
-            Self::I16(data) => format!("<i16 = {data}>, "),
-            Self::U32(data) => format!("<u32 = {data}>, "),
-            Self::I32(data) => format!("<i32 = {data}>, "),
-            Self::U64(data) => format!("<u64 = {data}>, "),
-            Self::I64(data) => format!("<i64 = {data}>, "),
-            Self::F32(data) => format!("<f32 = {data}>, "),
-            Self::F64(data) => format!("<f64 = {data}>, "),
-            Self::Vector2F32(data) => format!("<vec2f32 = {}; {}>, ", data.x, data.y),
-            Self::Vector3F32(data) => format!("<vec3f32 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4F32(data) => {
-                format!(
-                    "<vec4f32 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2F64(data) => format!("<vec2f64 = {}; {}>, ", data.x, data.y),
-            Self::Vector3F64(data) => format!("<vec3f64 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4F64(data) => {
-                format!(
-                    "<vec4f64 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2I8(data) => format!("<vec2i8 = {}; {}>, ", data.x, data.y),
-            Self::Vector3I8(data) => format!("<vec3i8 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4I8(data) => {
-                format!(
-                    "<vec4i8 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2U8(data) => format!("<vec2u8 = {}; {}>, ", data.x, data.y),
-            Self::Vector3U8(data) => format!("<vec3u8 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4U8(data) => {
-                format!(
-                    "<vec4u8 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-
-            Self::Vector2I16(data) => format!("<vec2i16 = {}; {}>, ", data.x, data.y),
-            Self::Vector3I16(data) => format!("<vec3i16 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4I16(data) => {
-                format!(
-                    "<vec4i16 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2U16(data) => format!("<vec2u16 = {}; {}>, ", data.x, data.y),
-            Self::Vector3U16(data) => format!("<vec3u16 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4U16(data) => {
-                format!(
-                    "<vec4u16 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-
-            Self::Vector2I32(data) => format!("<vec2i32 = {}; {}>, ", data.x, data.y),
-            Self::Vector3I32(data) => format!("<vec3i32 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4I32(data) => {
-                format!(
-                    "<vec4i32 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2U32(data) => format!("<vec2u32 = {}; {}>, ", data.x, data.y),
-            Self::Vector3U32(data) => format!("<vec3u32 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4U32(data) => {
-                format!(
-                    "<vec4u32 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-
-            Self::Vector2I64(data) => format!("<vec2i64 = {}; {}>, ", data.x, data.y),
-            Self::Vector3I64(data) => format!("<vec3i64 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4I64(data) => {
-                format!(
-                    "<vec4i64 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-            Self::Vector2U64(data) => format!("<vec2u64 = {}; {}>, ", data.x, data.y),
-            Self::Vector3U64(data) => format!("<vec3u64 = {}; {}; {}>, ", data.x, data.y, data.z),
-            Self::Vector4U64(data) => {
-                format!(
-                    "<vec4u64 = {}; {}; {}; {}>, ",
-                    data.x, data.y, data.z, data.w
-                )
-            }
-
-            Self::UnitQuaternion(data) => {
-                format!("<quat = {}; {}; {}; {}>, ", data.i, data.j, data.k, data.w)
-            }
-            Self::Matrix4(data) => {
-                let mut out = String::from("<mat4 = ");
-                for f in data.iter() {
-                    out += format!("{f}; ").as_str();
-                }
-                out
-            }
-            Self::BinaryBlob(data) => {
-                let out = match String::from_utf8(data.clone()) {
-                    Ok(s) => s,
-                    Err(_) => base64::engine::general_purpose::STANDARD.encode(data),
-                };
-                format!("<data = {out}>, ")
-            }
-            Self::Matrix3(data) => {
-                let mut out = String::from("<mat3 = ");
-                for f in data.iter() {
-                    out += format!("{f}; ").as_str();
-                }
-                out
-            }
-            Self::Uuid(uuid) => {
-                format!("<uuid = {uuid}")
-            }
-            Self::UnitComplex(data) => {
-                format!("<complex = {}; {}>, ", data.re, data.im)
-            }
-            FieldKind::PodArray {
-                type_id,
-                element_size,
-                bytes,
-            } => {
-                let base64_encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
-                format!("<podarray = {type_id}; {element_size}; [{base64_encoded}]>")
-            }
-            Self::Matrix2(data) => {
-                let mut out = String::from("<mat2 = ");
-                for f in data.iter() {
-                    out += format!("{f}; ").as_str();
-                }
-                out
-            }
-        }
-    }
-}
-
 macro_rules! impl_field_data {
     ($type_name:ty, $($kind:tt)*) => {
         impl Visit for $type_name {
@@ -647,6 +506,22 @@ pub enum VisitError {
     PoisonedMutex,
     /// A FileLoadError was encountered while trying to decode Visitor data from a file.
     FileLoadError(FileLoadError),
+    /// Attempting to read data of a newer version than the code supports.
+    UnsupportedVersion {
+        /// Version of the data.
+        stored: u32,
+        /// Maximum supported version.
+        supported: u32,
+    },
+    /// Visitor data in text form is malformed.
+    InvalidText {
+        /// One-based number of the line where the error was found.
+        line: usize,
+        /// Description of the error.
+        reason: String,
+    },
+    /// Visitor data in text form is not valid UTF-8.
+    InvalidUtf8(Utf8Error),
 }
 
 impl Error for VisitError {}
@@ -671,6 +546,14 @@ impl Display for VisitError {
             Self::UnexpectedRcNullIndex => write!(f, "unexpected rc null index"),
             Self::PoisonedMutex => write!(f, "attempt to lock poisoned mutex"),
             Self::FileLoadError(e) => write!(f, "file load error: {e:?}"),
+            Self::UnsupportedVersion { stored, supported } => write!(
+                f,
+                "data version {stored} is newer than supported version {supported}"
+            ),
+            Self::InvalidText { line, reason } => {
+                write!(f, "invalid text data at line {line}: {reason}")
+            }
+            Self::InvalidUtf8(e) => write!(f, "invalid utf-8 data: {e}"),
         }
     }
 }
@@ -705,6 +588,12 @@ impl From<FromUtf8Error> for VisitError {
     }
 }
 
+impl From<Utf8Error> for VisitError {
+    fn from(e: Utf8Error) -> Self {
+        Self::InvalidUtf8(e)
+    }
+}
+
 impl From<String> for VisitError {
     fn from(s: String) -> Self {
         Self::User(s)
@@ -1123,10 +1012,6 @@ impl Field {
             },
         ))
     }
-
-    fn as_string(&self) -> String {
-        format!("{}{}", self.name, self.kind.as_string())
-    }
 }
 
 /// A node is a collection of [Fields](Field) that exists within a tree of nodes
@@ -1422,42 +1307,6 @@ impl Visitor {
         }
     }
 
-    fn print_node(
-        &self,
-        node_handle: Handle<VisitorNode>,
-        nesting: usize,
-        out_string: &mut String,
-    ) {
-        let offset = (0..nesting).map(|_| "\t").collect::<String>();
-        let node = self.nodes.borrow(node_handle);
-        *out_string += format!(
-            "{}{}[Fields={}, Children={}]: ",
-            offset,
-            node.name,
-            node.fields.len(),
-            node.children.len()
-        )
-        .as_str();
-        for field in node.fields.iter() {
-            *out_string += field.as_string().as_str();
-        }
-
-        *out_string += "\n";
-
-        for child_handle in node.children.iter() {
-            self.print_node(*child_handle, nesting + 1, out_string);
-        }
-    }
-
-    /// Create a String containing all the data of this Visitor.
-    /// The String is formatted to be human-readable with each node on its own line
-    /// and tabs to indent child nodes.
-    pub fn save_text(&self) -> String {
-        let mut out_string = String::new();
-        self.print_node(self.root, 0, &mut out_string);
-        out_string
-    }
-
     /// Write the data of this Visitor to the given writer.
     /// Begin by writing [Visitor::MAGIC].
     pub fn save_binary_to_memory<W: Write>(&self, mut writer: W) -> VisitResult {
@@ -1535,14 +1384,34 @@ impl Visitor {
     /// assuming that the file was created using [Visitor::save_binary].
     /// Return a [VisitError::NotSupportedFormat] if [Visitor::MAGIC] is not the first bytes read from the file.
     pub async fn load_binary<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
+        Self::load_binary_from_memory(&io::load_file(path).await?)
+    }
+
+    /// Create a visitor by reading data from the file at the given path. The file could be
+    /// created using either [Visitor::save_binary] or [Visitor::save_text_to_file], the format
+    /// is detected automatically by the header of the file.
+    pub async fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
         Self::load_from_memory(&io::load_file(path).await?)
     }
 
+    /// Create a visitor by decoding data from the given byte slice, assuming that the bytes are
+    /// in the format that would be produced by either [Visitor::save_binary_to_vec] or
+    /// [Visitor::save_text]. The format is detected automatically by the header of the data.
+    /// Return a [VisitError::NotSupportedFormat] if neither [Visitor::MAGIC] nor
+    /// [Visitor::TEXT_MAGIC] are the first bytes read from the slice.
+    pub fn load_from_memory(data: &[u8]) -> Result<Self, VisitError> {
+        if Self::is_text(data) {
+            Self::load_from_text(std::str::from_utf8(data)?)
+        } else {
+            Self::load_binary_from_memory(data)
+        }
+    }
+
     /// Create a visitor by decoding data from the given byte slice,
     /// assuming that the bytes are in the format that would be produced
     /// by [Visitor::save_binary_to_vec].
     /// Return a [VisitError::NotSupportedFormat] if [Visitor::MAGIC] is not the first bytes read from the slice.
-    pub fn load_from_memory(data: &[u8]) -> Result<Self, VisitError> {
+    pub fn load_binary_from_memory(data: &[u8]) -> Result<Self, VisitError> {
         let mut reader = Cursor::new(data);
         let mut magic: [u8; 4] = Default::default();
         reader.read_exact(&mut magic)?;
@@ -2278,35 +2147,32 @@ mod test {
     }
 
     #[test]
-    fn field_kind_as_string() {
-        assert_eq!(
-            FieldKind::Bool(true).as_string(),
-            "<bool = true>, ".to_string()
-        );
+    fn field_kind_as_text() {
+        assert_eq!(FieldKind::Bool(true).as_text(), "bool = true".to_string());
         assert_eq!(
-            FieldKind::BinaryBlob(Vec::<u8>::new()).as_string(),
-            "<data = >, ".to_string()
+            FieldKind::BinaryBlob(Vec::<u8>::new()).as_text(),
+            "str = \"\"".to_string()
         );
 
-        assert_eq!(FieldKind::F32(0.0).as_string(), "<f32 = 0>, ".to_string());
-        assert_eq!(FieldKind::F64(0.0).as_string(), "<f64 = 0>, ".to_string());
+        assert_eq!(FieldKind::F32(0.0).as_text(), "f32 = 0".to_string());
+        assert_eq!(FieldKind::F64(0.0).as_text(), "f64 = 0".to_string());
 
-        assert_eq!(FieldKind::I8(0).as_string(), "<i8 = 0>, ".to_string());
-        assert_eq!(FieldKind::I16(0).as_string(), "<i16 = 0>, ".to_string());
-        assert_eq!(FieldKind::I32(0).as_string(), "<i32 = 0>, ".to_string());
-        assert_eq!(FieldKind::I64(0).as_string(), "<i64 = 0>, ".to_string());
+        assert_eq!(FieldKind::I8(0).as_text(), "i8 = 0".to_string());
+        assert_eq!(FieldKind::I16(0).as_text(), "i16 = 0".to_string());
+        assert_eq!(FieldKind::I32(0).as_text(), "i32 = 0".to_string());
+        assert_eq!(FieldKind::I64(0).as_text(), "i64 = 0".to_string());
 
         assert_eq!(
-            FieldKind::Matrix2(Matrix2::default()).as_string(),
-            "<mat2 = 0; 0; 0; 0; ".to_string()
+            FieldKind::Matrix2(Matrix2::default()).as_text(),
+            "mat2 = 0; 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Matrix3(Matrix3::default()).as_string(),
-            "<mat3 = 0; 0; 0; 0; 0; 0; 0; 0; 0; ".to_string()
+            FieldKind::Matrix3(Matrix3::default()).as_text(),
+            "mat3 = 0; 0; 0; 0; 0; 0; 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Matrix4(Matrix4::default()).as_string(),
-            "<mat4 = 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; ".to_string()
+            FieldKind::Matrix4(Matrix4::default()).as_text(),
+            "mat4 = 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0; 0".to_string()
         );
         assert_eq!(
             FieldKind::PodArray {
@@ -2314,149 +2180,303 @@ mod test {
                 element_size: 0,
                 bytes: Vec::new()
             }
-            .as_string(),
-            "<podarray = 0; 0; []>".to_string()
+            .as_text(),
+            "podarray = 0; 0; ".to_string()
         );
 
-        assert_eq!(FieldKind::U8(0).as_string(), "<u8 = 0>, ".to_string());
-        assert_eq!(FieldKind::U16(0).as_string(), "<u16 = 0>, ".to_string());
-        assert_eq!(FieldKind::U32(0).as_string(), "<u32 = 0>, ".to_string());
-        assert_eq!(FieldKind::U64(0).as_string(), "<u64 = 0>, ".to_string());
+        assert_eq!(FieldKind::U8(0).as_text(), "u8 = 0".to_string());
+        assert_eq!(FieldKind::U16(0).as_text(), "u16 = 0".to_string());
+        assert_eq!(FieldKind::U32(0).as_text(), "u32 = 0".to_string());
+        assert_eq!(FieldKind::U64(0).as_text(), "u64 = 0".to_string());
 
         assert_eq!(
-            FieldKind::UnitComplex(UnitComplex::default()).as_string(),
-            "<complex = 1; 0>, ".to_string()
+            FieldKind::UnitComplex(UnitComplex::default()).as_text(),
+            "complex = 1; 0".to_string()
         );
         assert_eq!(
-            FieldKind::UnitQuaternion(UnitQuaternion::default()).as_string(),
-            "<quat = 0; 0; 0; 1>, ".to_string()
+            FieldKind::UnitQuaternion(UnitQuaternion::default()).as_text(),
+            "quat = 0; 0; 0; 1".to_string()
         );
         assert_eq!(
-            FieldKind::Uuid(Uuid::default()).as_string(),
-            "<uuid = 00000000-0000-0000-0000-000000000000".to_string()
+            FieldKind::Uuid(Uuid::default()).as_text(),
+            "uuid = 00000000-0000-0000-0000-000000000000".to_string()
         );
 
         assert_eq!(
-            FieldKind::Vector2F32(Vector2::new(0.0, 0.0)).as_string(),
-            "<vec2f32 = 0; 0>, ".to_string()
+            FieldKind::Vector2F32(Vector2::new(0.0, 0.0)).as_text(),
+            "vec2f32 = 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector2F64(Vector2::new(0.0, 0.0)).as_string(),
-            "<vec2f64 = 0; 0>, ".to_string()
+            FieldKind::Vector2F64(Vector2::new(0.0, 0.0)).as_text(),
+            "vec2f64 = 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector2U8(Vector2::new(0, 0)).as_string(),
-            "<vec2u8 = 0; 0>, ".to_string()
+            FieldKind::Vector2U8(Vector2::new(0, 0)).as_text(),
+            "vec2u8 = 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector2U16(Vector2::new(0, 0)).as_string(),
-            "<vec2u16 = 0; 0>, ".to_string()
+            FieldKind::Vector2U16(Vector2::new(0, 0)).as_text(),
+            "vec2u16 = 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector2U32(Vector2::new(0, 0)).as_string(),
-            "<vec2u32 = 0; 0>, ".to_string()
+            FieldKind::Vector2U32(Vector2::new(0, 0)).as_text(),
+            "vec2u32 = 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector2U64(Vector2::new(0, 0)).as_string(),
-            "<vec2u64 = 0; 0>, ".to_string()
+            FieldKind::Vector2U64(Vector2::new(0, 0)).as_text(),
+            "vec2u64 = 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector2I8(Vector2::new(0, 0)).as_string(),
-            "<vec2i8 = 0; 0>, ".to_string()
+            FieldKind::Vector2I8(Vector2::new(0, 0)).as_text(),
+            "vec2i8 = 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector2I16(Vector2::new(0, 0)).as_string(),
-            "<vec2i16 = 0; 0>, ".to_string()
+            FieldKind::Vector2I16(Vector2::new(0, 0)).as_text(),
+            "vec2i16 = 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector2I32(Vector2::new(0, 0)).as_string(),
-            "<vec2i32 = 0; 0>, ".to_string()
+            FieldKind::Vector2I32(Vector2::new(0, 0)).as_text(),
+            "vec2i32 = 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector2I64(Vector2::new(0, 0)).as_string(),
-            "<vec2i64 = 0; 0>, ".to_string()
+            FieldKind::Vector2I64(Vector2::new(0, 0)).as_text(),
+            "vec2i64 = 0; 0".to_string()
         );
 
         assert_eq!(
-            FieldKind::Vector3F32(Vector3::new(0.0, 0.0, 0.0)).as_string(),
-            "<vec3f32 = 0; 0; 0>, ".to_string()
+            FieldKind::Vector3F32(Vector3::new(0.0, 0.0, 0.0)).as_text(),
+            "vec3f32 = 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector3F64(Vector3::new(0.0, 0.0, 0.0)).as_string(),
-            "<vec3f64 = 0; 0; 0>, ".to_string()
+            FieldKind::Vector3F64(Vector3::new(0.0, 0.0, 0.0)).as_text(),
+            "vec3f64 = 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector3U8(Vector3::new(0, 0, 0)).as_string(),
-            "<vec3u8 = 0; 0; 0>, ".to_string()
+            FieldKind::Vector3U8(Vector3::new(0, 0, 0)).as_text(),
+            "vec3u8 = 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector3U16(Vector3::new(0, 0, 0)).as_string(),
-            "<vec3u16 = 0; 0; 0>, ".to_string()
+            FieldKind::Vector3U16(Vector3::new(0, 0, 0)).as_text(),
+            "vec3u16 = 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector3U32(Vector3::new(0, 0, 0)).as_string(),
-            "<vec3u32 = 0; 0; 0>, ".to_string()
+            FieldKind::Vector3U32(Vector3::new(0, 0, 0)).as_text(),
+            "vec3u32 = 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector3U64(Vector3::new(0, 0, 0)).as_string(),
-            "<vec3u64 = 0; 0; 0>, ".to_string()
+            FieldKind::Vector3U64(Vector3::new(0, 0, 0)).as_text(),
+            "vec3u64 = 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector3I8(Vector3::new(0, 0, 0)).as_string(),
-            "<vec3i8 = 0; 0; 0>, ".to_string()
+            FieldKind::Vector3I8(Vector3::new(0, 0, 0)).as_text(),
+            "vec3i8 = 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector3I16(Vector3::new(0, 0, 0)).as_string(),
-            "<vec3i16 = 0; 0; 0>, ".to_string()
+            FieldKind::Vector3I16(Vector3::new(0, 0, 0)).as_text(),
+            "vec3i16 = 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector3I32(Vector3::new(0, 0, 0)).as_string(),
-            "<vec3i32 = 0; 0; 0>, ".to_string()
+            FieldKind::Vector3I32(Vector3::new(0, 0, 0)).as_text(),
+            "vec3i32 = 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector3I64(Vector3::new(0, 0, 0)).as_string(),
-            "<vec3i64 = 0; 0; 0>, ".to_string()
+            FieldKind::Vector3I64(Vector3::new(0, 0, 0)).as_text(),
+            "vec3i64 = 0; 0; 0".to_string()
         );
 
         assert_eq!(
-            FieldKind::Vector4F32(Vector4::new(0.0, 0.0, 0.0, 0.0)).as_string(),
-            "<vec4f32 = 0; 0; 0; 0>, ".to_string()
+            FieldKind::Vector4F32(Vector4::new(0.0, 0.0, 0.0, 0.0)).as_text(),
+            "vec4f32 = 0; 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector4F64(Vector4::new(0.0, 0.0, 0.0, 0.0)).as_string(),
-            "<vec4f64 = 0; 0; 0; 0>, ".to_string()
+            FieldKind::Vector4F64(Vector4::new(0.0, 0.0, 0.0, 0.0)).as_text(),
+            "vec4f64 = 0; 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector4U8(Vector4::new(0, 0, 0, 0)).as_string(),
-            "<vec4u8 = 0; 0; 0; 0>, ".to_string()
+            FieldKind::Vector4U8(Vector4::new(0, 0, 0, 0)).as_text(),
+            "vec4u8 = 0; 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector4U16(Vector4::new(0, 0, 0, 0)).as_string(),
-            "<vec4u16 = 0; 0; 0; 0>, ".to_string()
+            FieldKind::Vector4U16(Vector4::new(0, 0, 0, 0)).as_text(),
+            "vec4u16 = 0; 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector4U32(Vector4::new(0, 0, 0, 0)).as_string(),
-            "<vec4u32 = 0; 0; 0; 0>, ".to_string()
+            FieldKind::Vector4U32(Vector4::new(0, 0, 0, 0)).as_text(),
+            "vec4u32 = 0; 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector4U64(Vector4::new(0, 0, 0, 0)).as_string(),
-            "<vec4u64 = 0; 0; 0; 0>, ".to_string()
+            FieldKind::Vector4U64(Vector4::new(0, 0, 0, 0)).as_text(),
+            "vec4u64 = 0; 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector4I8(Vector4::new(0, 0, 0, 0)).as_string(),
-            "<vec4i8 = 0; 0; 0; 0>, ".to_string()
+            FieldKind::Vector4I8(Vector4::new(0, 0, 0, 0)).as_text(),
+            "vec4i8 = 0; 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector4I16(Vector4::new(0, 0, 0, 0)).as_string(),
-            "<vec4i16 = 0; 0; 0; 0>, ".to_string()
+            FieldKind::Vector4I16(Vector4::new(0, 0, 0, 0)).as_text(),
+            "vec4i16 = 0; 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector4I32(Vector4::new(0, 0, 0, 0)).as_string(),
-            "<vec4i32 = 0; 0; 0; 0>, ".to_string()
+            FieldKind::Vector4I32(Vector4::new(0, 0, 0, 0)).as_text(),
+            "vec4i32 = 0; 0; 0; 0".to_string()
         );
         assert_eq!(
-            FieldKind::Vector4I64(Vector4::new(0, 0, 0, 0)).as_string(),
-            "<vec4i64 = 0; 0; 0; 0>, ".to_string()
+            FieldKind::Vector4I64(Vector4::new(0, 0, 0, 0)).as_text(),
+            "vec4i64 = 0; 0; 0; 0".to_string()
         );
     }
+
+    fn all_field_kinds() -> Vec<FieldKind> {
+        vec![
+            FieldKind::Bool(true),
+            FieldKind::U8(u8::MAX),
+            FieldKind::I8(i8::MIN),
+            FieldKind::U16(u16::MAX),
+            FieldKind::I16(i16::MIN),
+            FieldKind::U32(u32::MAX),
+            FieldKind::I32(i32::MIN),
+            FieldKind::U64(u64::MAX),
+            FieldKind::I64(i64::MIN),
+            FieldKind::F32(0.1),
+            FieldKind::F64(-1.0e-300),
+            FieldKind::UnitQuaternion(UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3)),
+            FieldKind::Matrix4(Matrix4::new(
+                1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0,
+                16.0,
+            )),
+            FieldKind::BinaryBlob("Some \"text\"\n\twith\\escapes\u{1}".as_bytes().to_vec()),
+            FieldKind::BinaryBlob(vec![0xFF, 0xFE, 0x00, 0x01]),
+            FieldKind::Matrix3(Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0)),
+            FieldKind::Uuid(Uuid::new_v4()),
+            FieldKind::UnitComplex(UnitComplex::new(0.123)),
+            FieldKind::PodArray {
+                type_id: 8,
+                element_size: 4,
+                bytes: vec![1, 2, 3, 4, 5, 6, 7, 8],
+            },
+            FieldKind::Matrix2(Matrix2::new(1.0, 2.0, 3.0, 4.0)),
+            FieldKind::Vector2F32(Vector2::new(0.1, f32::MAX)),
+            FieldKind::Vector3F32(Vector3::new(0.1, f32::MIN, f32::INFINITY)),
+            FieldKind::Vector4F32(Vector4::new(0.1, 0.2, 0.3, f32::NEG_INFINITY)),
+            FieldKind::Vector2F64(Vector2::new(0.1, 0.2)),
+            FieldKind::Vector3F64(Vector3::new(0.1, 0.2, 0.3)),
+            FieldKind::Vector4F64(Vector4::new(0.1, 0.2, 0.3, 0.4)),
+            FieldKind::Vector2U8(Vector2::new(1, 2)),
+            FieldKind::Vector3U8(Vector3::new(1, 2, 3)),
+            FieldKind::Vector4U8(Vector4::new(1, 2, 3, 4)),
+            FieldKind::Vector2I8(Vector2::new(-1, 2)),
+            FieldKind::Vector3I8(Vector3::new(-1, 2, 3)),
+            FieldKind::Vector4I8(Vector4::new(-1, 2, 3, 4)),
+            FieldKind::Vector2U16(Vector2::new(1, 2)),
+            FieldKind::Vector3U16(Vector3::new(1, 2, 3)),
+            FieldKind::Vector4U16(Vector4::new(1, 2, 3, 4)),
+            FieldKind::Vector2I16(Vector2::new(-1, 2)),
+            FieldKind::Vector3I16(Vector3::new(-1, 2, 3)),
+            FieldKind::Vector4I16(Vector4::new(-1, 2, 3, 4)),
+            FieldKind::Vector2U32(Vector2::new(1, 2)),
+            FieldKind::Vector3U32(Vector3::new(1, 2, 3)),
+            FieldKind::Vector4U32(Vector4::new(1, 2, 3, 4)),
+            FieldKind::Vector2I32(Vector2::new(-1, 2)),
+            FieldKind::Vector3I32(Vector3::new(-1, 2, 3)),
+            FieldKind::Vector4I32(Vector4::new(-1, 2, 3, 4)),
+            FieldKind::Vector2U64(Vector2::new(1, 2)),
+            FieldKind::Vector3U64(Vector3::new(1, 2, 3)),
+            FieldKind::Vector4U64(Vector4::new(1, 2, 3, 4)),
+            FieldKind::Vector2I64(Vector2::new(-1, 2)),
+            FieldKind::Vector3I64(Vector3::new(-1, 2, 3)),
+            FieldKind::Vector4I64(Vector4::new(-1, 2, 3, 4)),
+        ]
+    }
+
+    #[test]
+    fn text_round_trip_preserves_all_field_kinds() {
+        let mut visitor = Visitor::new();
+        {
+            let mut region = visitor.enter_region("Some Region").unwrap();
+            for (i, kind) in all_field_kinds().into_iter().enumerate() {
+                region
+                    .current_node()
+                    .fields
+                    .push(Field::new(&format!("Field{i}"), kind));
+            }
+            let mut nested = region.enter_region("Nested").unwrap();
+            let mut data = vec![1.0f32, 2.0, 3.0];
+            PodVecView::from_pod_vec(&mut data)
+                .visit("PodData", &mut nested)
+                .unwrap();
+            let mut string = String::from("multi\nline \"string\"");
+            string.visit("String", &mut nested).unwrap();
+        }
+
+        let text = visitor.save_text();
+        let loaded = Visitor::load_from_memory(text.as_bytes()).unwrap();
+
+        assert_eq!(loaded.save_text(), text);
+        assert_eq!(
+            loaded.save_binary_to_vec().unwrap(),
+            visitor.save_binary_to_vec().unwrap()
+        );
+    }
+
+    #[test]
+    fn text_visitor_reads_values() {
+        let mut visitor = Visitor::new();
+        let mut resource = Rc::new(Resource::new(ResourceKind::Model(Model { data: 555 })));
+        resource.visit("SharedResource", &mut visitor).unwrap();
+        let mut objects = vec![Foo::new(resource.clone()), Foo::new(resource)];
+        objects.visit("Objects", &mut visitor).unwrap();
+
+        let mut visitor = Visitor::load_from_text(&visitor.save_text()).unwrap();
+        let mut resource: Rc<Resource> = Rc::new(Default::default());
+        resource.visit("SharedResource", &mut visitor).unwrap();
+        let mut objects: Vec<Foo> = Vec::new();
+        objects.visit("Objects", &mut visitor).unwrap();
+
+        assert!(matches!(
+            resource.kind,
+            ResourceKind::Model(Model { data: 555 })
+        ));
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].bar, 123);
+        assert!(Rc::ptr_eq(
+            objects[0].shared_resource.as_ref().unwrap(),
+            &resource
+        ));
+    }
+
+    #[test]
+    fn malformed_text_is_rejected() {
+        let header = format!("{} {}\n", Visitor::TEXT_MAGIC, Visitor::TEXT_VERSION);
+        for text in [
+            "RG3D".to_string(),
+            format!("{header}__ROOT__ {{\n"),
+            format!("{header}__ROOT__ {{\n}}\n}}\n"),
+            format!("{header}__ROOT__ {{\n    Field: u8 = 256\n}}\n"),
+            format!("{header}__ROOT__ {{\n    Field: foo = 1\n}}\n"),
+            format!("{header}__ROOT__ {{\n    Field: vec3f32 = 1; 2\n}}\n"),
+            format!("{header}__ROOT__ {{\n    Field: str = \"unterminated\n}}\n"),
+            format!("{header}__ROOT__ {{\n}}\nOther {{\n}}\n"),
+        ] {
+            assert!(
+                Visitor::load_from_memory(text.as_bytes()).is_err(),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn non_utf8_text_is_rejected() {
+        let mut data = format!(
+            "{} {}\n__ROOT__ {{\n    Field: str = \"",
+            Visitor::TEXT_MAGIC,
+            Visitor::TEXT_VERSION
+        )
+        .into_bytes();
+        data.extend_from_slice(&[0xFF, 0xFE]);
+        data.extend_from_slice(b"\"\n}\n");
+        assert!(matches!(
+            Visitor::load_from_memory(&data),
+            Err(VisitError::InvalidUtf8(_))
+        ));
+    }
 }
@@ -0,0 +1,351 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Versioning and migration of serialized data.
+//!
+//! When the layout of a type changes (a field is renamed, moved to some other place, or its type
+//! is changed) the old data could still be loaded if the type stores its version in its region.
+//! When an older version is found in the data, migration functions are called for each version
+//! step, and they have full access to the raw data of the region before it is visited. See
+//! [`Visitor::visit_version`] for more info.
+//!
+//! ```rust
+//! # use fyrox_core::visitor::{prelude::*, RegionMut};
+//! #[derive(Visit, Default)]
+//! #[visit(version = 1, migrate = "migrate_player")]
+//! struct Player {
+//!     // This field was called `health` in version 0.
+//!     hit_points: f32,
+//! }
+//!
+//! fn migrate_player(from_version: u32, region: &mut RegionMut) -> VisitResult {
+//!     if from_version == 0 {
+//!         region.rename_field("Health", "HitPoints")?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    pool::{Handle, Pool},
+    visitor::{Field, FieldKind, Visit, VisitError, VisitResult, Visitor, VisitorNode},
+};
+
+/// A function that upgrades the data of a region from the given version to the next one. It is
+/// called once per version step, so the data of version 1 will be upgraded to version 3 by two
+/// calls with `from_version` equal to 1 and then 2.
+pub type MigrationFn = fn(from_version: u32, region: &mut RegionMut) -> VisitResult;
+
+/// Mutable access to the raw data of a region of a [`Visitor`] and all its descendant regions.
+/// It is used to migrate data from older versions before it is visited.
+pub struct RegionMut<'a> {
+    nodes: &'a mut Pool<VisitorNode>,
+    handle: Handle<VisitorNode>,
+}
+
+impl RegionMut<'_> {
+    fn node(&self) -> &VisitorNode {
+        self.nodes.borrow(self.handle)
+    }
+
+    fn node_mut(&mut self) -> &mut VisitorNode {
+        self.nodes.borrow_mut(self.handle)
+    }
+
+    fn find_child(&self, name: &str) -> Option<Handle<VisitorNode>> {
+        self.node()
+            .children
+            .iter()
+            .find(|child| self.nodes.borrow(**child).name == name)
+            .cloned()
+    }
+
+    /// Name of the region.
+    pub fn name(&self) -> &str {
+        &self.node().name
+    }
+
+    /// Returns an iterator over the names of the fields of the region.
+    pub fn field_names(&self) -> impl Iterator<Item = &str> {
+        self.node().fields.iter().map(|f| f.name.as_str())
+    }
+
+    /// Returns a reference to the data of a field with the given name, if any.
+    pub fn field(&self, name: &str) -> Option<&FieldKind> {
+        self.node()
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .map(|f| &f.kind)
+    }
+
+    /// Returns a reference to the data of a field with the given name, if any.
+    pub fn field_mut(&mut self, name: &str) -> Option<&mut FieldKind> {
+        self.node_mut()
+            .fields
+            .iter_mut()
+            .find(|f| f.name == name)
+            .map(|f| &mut f.kind)
+    }
+
+    /// Sets new data for a field with the given name. The field will be added if it does not
+    /// exist. Returns the previous data of the field, if any.
+    pub fn set_field(&mut self, name: &str, kind: FieldKind) -> Option<FieldKind> {
+        match self.field_mut(name) {
+            Some(existing) => Some(std::mem::replace(existing, kind)),
+            None => {
+                self.node_mut().fields.push(Field::new(name, kind));
+                None
+            }
+        }
+    }
+
+    /// Removes a field with the given name and returns its data, if any.
+    pub fn remove_field(&mut self, name: &str) -> Option<FieldKind> {
+        let fields = &mut self.node_mut().fields;
+        let index = fields.iter().position(|f| f.name == name)?;
+        Some(fields.remove(index).kind)
+    }
+
+    /// Renames a field. Fails if there's no field with the old name, or if there's a field with
+    /// the new name already.
+    pub fn rename_field(&mut self, old_name: &str, new_name: &str) -> VisitResult {
+        if self.field(new_name).is_some() {
+            return Err(VisitError::FieldAlreadyExists(new_name.to_owned()));
+        }
+        let field = self
+            .node_mut()
+            .fields
+            .iter_mut()
+            .find(|f| f.name == old_name)
+            .ok_or_else(|| VisitError::FieldDoesNotExist(old_name.to_owned()))?;
+        field.name = new_name.to_owned();
+        Ok(())
+    }
+
+    /// Returns an iterator over the names of the child regions of the region.
+    pub fn region_names(&self) -> impl Iterator<Item = &str> {
+        self.node()
+            .children
+            .iter()
+            .map(|child| self.nodes.borrow(*child).name.as_str())
+    }
+
+    /// Returns mutable access to a child region with the given name, if any.
+    pub fn region(&mut self, name: &str) -> Option<RegionMut<'_>> {
+        let handle = self.find_child(name)?;
+        Some(RegionMut {
+            nodes: self.nodes,
+            handle,
+        })
+    }
+
+    /// Adds a new empty child region with the given name. Fails if there's a region with the
+    /// same name already.
+    pub fn add_region(&mut self, name: &str) -> Result<RegionMut<'_>, VisitError> {
+        if self.find_child(name).is_some() {
+            return Err(VisitError::RegionAlreadyExists(name.to_owned()));
+        }
+        let handle = self.nodes.spawn(VisitorNode::new(name, self.handle));
+        self.node_mut().children.push(handle);
+        Ok(RegionMut {
+            nodes: self.nodes,
+            handle,
+        })
+    }
+
+    /// Removes a child region with the given name together with all its data. Returns `false`
+    /// if there's no such region.
+    pub fn remove_region(&mut self, name: &str) -> bool {
+        let Some(handle) = self.find_child(name) else {
+            return false;
+        };
+        self.node_mut().children.retain(|child| *child != handle);
+        let mut stack = vec![handle];
+        while let Some(handle) = stack.pop() {
+            let node = self.nodes.free(handle);
+            stack.extend(node.children);
+        }
+        true
+    }
+
+    /// Renames a child region. Fails if there's no region with the old name, or if there's a
+    /// region with the new name already.
+    pub fn rename_region(&mut self, old_name: &str, new_name: &str) -> VisitResult {
+        if self.find_child(new_name).is_some() {
+            return Err(VisitError::RegionAlreadyExists(new_name.to_owned()));
+        }
+        let handle = self
+            .find_child(old_name)
+            .ok_or_else(|| VisitError::RegionDoesNotExist(old_name.to_owned()))?;
+        self.nodes.borrow_mut(handle).name = new_name.to_owned();
+        Ok(())
+    }
+}
+
+impl Visitor {
+    /// Name of the field that is used to store the version of a region.
+    pub const VERSION_FIELD: &'static str = "__VERSION__";
+
+    /// Returns mutable access to the raw data of the current region. It could be used to migrate
+    /// data manually, prefer [`Visitor::visit_version`] whenever possible.
+    pub fn current_region_mut(&mut self) -> RegionMut<'_> {
+        RegionMut {
+            nodes: &mut self.nodes,
+            handle: self.current_node,
+        }
+    }
+
+    /// Writes or reads the version of the data of the current region. It must be called right
+    /// after entering a region, before any other data is visited.
+    ///
+    /// In write mode, it stores the given version in the region. In read mode, it reads the
+    /// stored version (the data that has no version is treated as version 0) and, if the stored
+    /// version is older than the given one, calls the migration function for every version step
+    /// to upgrade the data to the current version. Data of a newer version than the given one
+    /// can't be read and [`VisitError::UnsupportedVersion`] is returned.
+    pub fn visit_version(&mut self, version: u32, migration: Option<MigrationFn>) -> VisitResult {
+        if !self.reading {
+            let mut version = version;
+            return version.visit(Self::VERSION_FIELD, self);
+        }
+
+        let stored = match self.find_field(Self::VERSION_FIELD) {
+            Some(field) => match field.kind {
+                FieldKind::U32(stored) => stored,
+                _ => return Err(VisitError::FieldTypeDoesNotMatch),
+            },
+            None => 0,
+        };
+
+        if stored > version {
+            return Err(VisitError::UnsupportedVersion {
+                stored,
+                supported: version,
+            });
+        }
+
+        if stored < version {
+            if let Some(migration) = migration {
+                let mut region = self.current_region_mut();
+                for from_version in stored..version {
+                    migration(from_version, &mut region)?;
+                }
+            }
+            self.current_region_mut()
+                .set_field(Self::VERSION_FIELD, FieldKind::U32(version));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::visitor::{prelude::*, FieldKind, RegionMut};
+
+    fn save(version: Option<u32>, mut value: f32) -> Visitor {
+        let mut visitor = Visitor::new();
+        let mut region = visitor.enter_region("Data").unwrap();
+        if let Some(version) = version {
+            region.visit_version(version, None).unwrap();
+        }
+        value.visit("Value", &mut region).unwrap();
+        {
+            let mut nested = region.enter_region("Nested").unwrap();
+            value.visit("Value", &mut nested).unwrap();
+        }
+        drop(region);
+        Visitor::load_from_memory(&visitor.save_binary_to_vec().unwrap()).unwrap()
+    }
+
+    fn migrate(from_version: u32, region: &mut RegionMut) -> VisitResult {
+        match from_version {
+            0 => region.rename_field("Value", "NewValue"),
+            1 => {
+                let Some(FieldKind::F32(value)) = region.remove_field("NewValue") else {
+                    return Err(VisitError::FieldTypeDoesNotMatch);
+                };
+                region.set_field("NewValue", FieldKind::F64(value as f64 * 2.0));
+                region.rename_region("Nested", "Renamed")
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn load(visitor: &mut Visitor) -> Result<f64, VisitError> {
+        let mut region = visitor.enter_region("Data")?;
+        region.visit_version(2, Some(migrate))?;
+        let mut value = 0.0f64;
+        value.visit("NewValue", &mut region)?;
+        assert!(region.enter_region("Renamed").is_ok());
+        Ok(value)
+    }
+
+    #[test]
+    fn migrate_all_steps() {
+        let mut visitor = save(None, 1.5);
+        assert_eq!(load(&mut visitor).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn migrate_partially() {
+        let mut visitor = save(Some(1), 1.5);
+        // Emulate the data of version 1.
+        {
+            let mut region = visitor.enter_region("Data").unwrap();
+            region
+                .current_region_mut()
+                .rename_field("Value", "NewValue")
+                .unwrap();
+        }
+        assert_eq!(load(&mut visitor).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let mut visitor = save(Some(3), 1.5);
+        assert!(matches!(
+            load(&mut visitor),
+            Err(VisitError::UnsupportedVersion {
+                stored: 3,
+                supported: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn region_editing() {
+        let mut visitor = Visitor::new();
+        let mut region = visitor.current_region_mut();
+        region.add_region("A").unwrap().add_region("B").unwrap();
+        assert!(region.add_region("A").is_err());
+        region.set_field("X", FieldKind::U32(1));
+        assert_eq!(
+            region.set_field("X", FieldKind::U32(2)),
+            Some(FieldKind::U32(1))
+        );
+        assert_eq!(region.field_names().collect::<Vec<_>>(), ["X"]);
+        assert!(region.remove_region("A"));
+        assert!(!region.remove_region("A"));
+        assert_eq!(region.region_names().count(), 0);
+        assert_eq!(visitor.nodes.alive_count(), 1);
+    }
+}
@@ -0,0 +1,665 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Human-readable text representation of [`Visitor`] data.
+//!
+//! The format is line-based, so it plays well with version control systems: every field is
+//! stored on its own line and every region is a block delimited with braces. For example:
+//!
+//! ```text
+//! FYROX-VISITOR-TEXT 1
+//! __ROOT__ {
+//!     Scene {
+//!         Name: str = "Main"
+//!         Position: vec3f32 = 1; 2; 3
+//!     }
+//! }
+//! ```
+//!
+//! Names that contain anything except ASCII letters, digits and underscores are written as
+//! quoted strings. Floating-point numbers are written in their shortest form that parses back
+//! to exactly the same value, so text data has exactly the same precision as binary data.
+
+use crate::{
+    algebra::{
+        Complex, Matrix2, Matrix3, Matrix4, Quaternion, SVector, Scalar, UnitComplex,
+        UnitQuaternion,
+    },
+    pool::{Handle, Pool},
+    visitor::{
+        Blackboard, Field, FieldKind, VisitError, VisitResult, Visitor, VisitorFlags, VisitorNode,
+    },
+};
+use base64::Engine;
+use std::{
+    fmt::{Display, Write as _},
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    str::FromStr,
+};
+use uuid::Uuid;
+
+const INDENT: &str = "    ";
+
+fn is_bare_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn write_quoted(string: &str, out: &mut String) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{{{:x}}}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_name(name: &str, out: &mut String) {
+    if is_bare_name(name) {
+        out.push_str(name);
+    } else {
+        write_quoted(name, out);
+    }
+}
+
+fn write_components<'a, T, I>(components: I, out: &mut String)
+where
+    T: Display + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    for (i, component) in components.into_iter().enumerate() {
+        if i != 0 {
+            out.push_str("; ");
+        }
+        let _ = write!(out, "{component}");
+    }
+}
+
+impl FieldKind {
+    /// Returns a type name of the field, that is used to identify the field type in text form.
+    fn text_type_name(&self) -> &'static str {
+        match self {
+            Self::Bool(_) => "bool",
+            Self::U8(_) => "u8",
+            Self::I8(_) => "i8",
+            Self::U16(_) => "u16",
+            Self::I16(_) => "i16",
+            Self::U32(_) => "u32",
+            Self::I32(_) => "i32",
+            Self::U64(_) => "u64",
+            Self::I64(_) => "i64",
+            Self::F32(_) => "f32",
+            Self::F64(_) => "f64",
+            Self::UnitQuaternion(_) => "quat",
+            Self::Matrix4(_) => "mat4",
+            Self::BinaryBlob(data) => {
+                if std::str::from_utf8(data).is_ok() {
+                    "str"
+                } else {
+                    "data"
+                }
+            }
+            Self::Matrix3(_) => "mat3",
+            Self::Uuid(_) => "uuid",
+            Self::UnitComplex(_) => "complex",
+            Self::PodArray { .. } => "podarray",
+            Self::Matrix2(_) => "mat2",
+            Self::Vector2F32(_) => "vec2f32",
+            Self::Vector3F32(_) => "vec3f32",
+            Self::Vector4F32(_) => "vec4f32",
+            Self::Vector2F64(_) => "vec2f64",
+            Self::Vector3F64(_) => "vec3f64",
+            Self::Vector4F64(_) => "vec4f64",
+            Self::Vector2U8(_) => "vec2u8",
+            Self::Vector3U8(_) => "vec3u8",
+            Self::Vector4U8(_) => "vec4u8",
+            Self::Vector2I8(_) => "vec2i8",
+            Self::Vector3I8(_) => "vec3i8",
+            Self::Vector4I8(_) => "vec4i8",
+            Self::Vector2U16(_) => "vec2u16",
+            Self::Vector3U16(_) => "vec3u16",
+            Self::Vector4U16(_) => "vec4u16",
+            Self::Vector2I16(_) => "vec2i16",
+            Self::Vector3I16(_) => "vec3i16",
+            Self::Vector4I16(_) => "vec4i16",
+            Self::Vector2U32(_) => "vec2u32",
+            Self::Vector3U32(_) => "vec3u32",
+            Self::Vector4U32(_) => "vec4u32",
+            Self::Vector2I32(_) => "vec2i32",
+            Self::Vector3I32(_) => "vec3i32",
+            Self::Vector4I32(_) => "vec4i32",
+            Self::Vector2U64(_) => "vec2u64",
+            Self::Vector3U64(_) => "vec3u64",
+            Self::Vector4U64(_) => "vec4u64",
+            Self::Vector2I64(_) => "vec2i64",
+            Self::Vector3I64(_) => "vec3i64",
+            Self::Vector4I64(_) => "vec4i64",
+        }
+    }
+
+    fn write_text_value(&self, out: &mut String) {
+        match self {
+            Self::Bool(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::U8(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::I8(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::U16(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::I16(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::U32(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::I32(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::U64(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::I64(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::F32(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::F64(v) => {
+                let _ = write!(out, "{v}");
+            }
+            Self::UnitQuaternion(q) => write_components(&[q.i, q.j, q.k, q.w], out),
+            Self::Matrix4(m) => write_components(m.iter(), out),
+            Self::BinaryBlob(data) => match std::str::from_utf8(data) {
+                Ok(string) => write_quoted(string, out),
+                Err(_) => out.push_str(&base64::engine::general_purpose::STANDARD.encode(data)),
+            },
+            Self::Matrix3(m) => write_components(m.iter(), out),
+            Self::Uuid(uuid) => {
+                let _ = write!(out, "{uuid}");
+            }
+            Self::UnitComplex(c) => write_components(&[c.re, c.im], out),
+            Self::PodArray {
+                type_id,
+                element_size,
+                bytes,
+            } => {
+                let _ = write!(
+                    out,
+                    "{type_id}; {element_size}; {}",
+                    base64::engine::general_purpose::STANDARD.encode(bytes)
+                );
+            }
+            Self::Matrix2(m) => write_components(m.iter(), out),
+            Self::Vector2F32(v) => write_components(v.iter(), out),
+            Self::Vector3F32(v) => write_components(v.iter(), out),
+            Self::Vector4F32(v) => write_components(v.iter(), out),
+            Self::Vector2F64(v) => write_components(v.iter(), out),
+            Self::Vector3F64(v) => write_components(v.iter(), out),
+            Self::Vector4F64(v) => write_components(v.iter(), out),
+            Self::Vector2U8(v) => write_components(v.iter(), out),
+            Self::Vector3U8(v) => write_components(v.iter(), out),
+            Self::Vector4U8(v) => write_components(v.iter(), out),
+            Self::Vector2I8(v) => write_components(v.iter(), out),
+            Self::Vector3I8(v) => write_components(v.iter(), out),
+            Self::Vector4I8(v) => write_components(v.iter(), out),
+            Self::Vector2U16(v) => write_components(v.iter(), out),
+            Self::Vector3U16(v) => write_components(v.iter(), out),
+            Self::Vector4U16(v) => write_components(v.iter(), out),
+            Self::Vector2I16(v) => write_components(v.iter(), out),
+            Self::Vector3I16(v) => write_components(v.iter(), out),
+            Self::Vector4I16(v) => write_components(v.iter(), out),
+            Self::Vector2U32(v) => write_components(v.iter(), out),
+            Self::Vector3U32(v) => write_components(v.iter(), out),
+            Self::Vector4U32(v) => write_components(v.iter(), out),
+            Self::Vector2I32(v) => write_components(v.iter(), out),
+            Self::Vector3I32(v) => write_components(v.iter(), out),
+            Self::Vector4I32(v) => write_components(v.iter(), out),
+            Self::Vector2U64(v) => write_components(v.iter(), out),
+            Self::Vector3U64(v) => write_components(v.iter(), out),
+            Self::Vector4U64(v) => write_components(v.iter(), out),
+            Self::Vector2I64(v) => write_components(v.iter(), out),
+            Self::Vector3I64(v) => write_components(v.iter(), out),
+            Self::Vector4I64(v) => write_components(v.iter(), out),
+        }
+    }
+
+    /// Returns the field in `type = value` form, exactly as it is written in text form.
+    pub(super) fn as_text(&self) -> String {
+        let mut out = String::new();
+        out.push_str(self.text_type_name());
+        out.push_str(" = ");
+        self.write_text_value(&mut out);
+        out
+    }
+
+    fn parse_text(type_name: &str, value: &str) -> Result<Self, String> {
+        Ok(match type_name {
+            "bool" => Self::Bool(parse_scalar(value)?),
+            "u8" => Self::U8(parse_scalar(value)?),
+            "i8" => Self::I8(parse_scalar(value)?),
+            "u16" => Self::U16(parse_scalar(value)?),
+            "i16" => Self::I16(parse_scalar(value)?),
+            "u32" => Self::U32(parse_scalar(value)?),
+            "i32" => Self::I32(parse_scalar(value)?),
+            "u64" => Self::U64(parse_scalar(value)?),
+            "i64" => Self::I64(parse_scalar(value)?),
+            "f32" => Self::F32(parse_scalar(value)?),
+            "f64" => Self::F64(parse_scalar(value)?),
+            "quat" => {
+                let [i, j, k, w] = parse_components::<f32, 4>(value)?;
+                // Do not re-normalize the value, otherwise it won't be bit-exact.
+                Self::UnitQuaternion(UnitQuaternion::new_unchecked(Quaternion::new(w, i, j, k)))
+            }
+            "mat4" => Self::Matrix4(Matrix4::from_column_slice(&parse_components::<f32, 16>(
+                value,
+            )?)),
+            "str" => Self::BinaryBlob({
+                let (string, rest) = parse_quoted(value)?;
+                if !rest.trim().is_empty() {
+                    return Err(format!("unexpected trailing characters: {rest}"));
+                }
+                string.into_bytes()
+            }),
+            "data" => Self::BinaryBlob(parse_base64(value)?),
+            "mat3" => Self::Matrix3(Matrix3::from_column_slice(&parse_components::<f32, 9>(
+                value,
+            )?)),
+            "uuid" => Self::Uuid(Uuid::from_str(value).map_err(|e| e.to_string())?),
+            "complex" => {
+                let [re, im] = parse_components::<f32, 2>(value)?;
+                Self::UnitComplex(UnitComplex::new_unchecked(Complex::new(re, im)))
+            }
+            "podarray" => {
+                let mut parts = value.splitn(3, ';');
+                let mut next = || {
+                    parts
+                        .next()
+                        .map(str::trim)
+                        .ok_or_else(|| "pod array must have 3 components".to_string())
+                };
+                let type_id = parse_scalar(next()?)?;
+                let element_size = parse_scalar(next()?)?;
+                let bytes = parse_base64(next()?)?;
+                Self::PodArray {
+                    type_id,
+                    element_size,
+                    bytes,
+                }
+            }
+            "mat2" => Self::Matrix2(Matrix2::from_column_slice(&parse_components::<f32, 4>(
+                value,
+            )?)),
+            "vec2f32" => Self::Vector2F32(parse_vector(value)?),
+            "vec3f32" => Self::Vector3F32(parse_vector(value)?),
+            "vec4f32" => Self::Vector4F32(parse_vector(value)?),
+            "vec2f64" => Self::Vector2F64(parse_vector(value)?),
+            "vec3f64" => Self::Vector3F64(parse_vector(value)?),
+            "vec4f64" => Self::Vector4F64(parse_vector(value)?),
+            "vec2u8" => Self::Vector2U8(parse_vector(value)?),
+            "vec3u8" => Self::Vector3U8(parse_vector(value)?),
+            "vec4u8" => Self::Vector4U8(parse_vector(value)?),
+            "vec2i8" => Self::Vector2I8(parse_vector(value)?),
+            "vec3i8" => Self::Vector3I8(parse_vector(value)?),
+            "vec4i8" => Self::Vector4I8(parse_vector(value)?),
+            "vec2u16" => Self::Vector2U16(parse_vector(value)?),
+            "vec3u16" => Self::Vector3U16(parse_vector(value)?),
+            "vec4u16" => Self::Vector4U16(parse_vector(value)?),
+            "vec2i16" => Self::Vector2I16(parse_vector(value)?),
+            "vec3i16" => Self::Vector3I16(parse_vector(value)?),
+            "vec4i16" => Self::Vector4I16(parse_vector(value)?),
+            "vec2u32" => Self::Vector2U32(parse_vector(value)?),
+            "vec3u32" => Self::Vector3U32(parse_vector(value)?),
+            "vec4u32" => Self::Vector4U32(parse_vector(value)?),
+            "vec2i32" => Self::Vector2I32(parse_vector(value)?),
+            "vec3i32" => Self::Vector3I32(parse_vector(value)?),
+            "vec4i32" => Self::Vector4I32(parse_vector(value)?),
+            "vec2u64" => Self::Vector2U64(parse_vector(value)?),
+            "vec3u64" => Self::Vector3U64(parse_vector(value)?),
+            "vec4u64" => Self::Vector4U64(parse_vector(value)?),
+            "vec2i64" => Self::Vector2I64(parse_vector(value)?),
+            "vec3i64" => Self::Vector3I64(parse_vector(value)?),
+            "vec4i64" => Self::Vector4I64(parse_vector(value)?),
+            _ => return Err(format!("unknown field type {type_name}")),
+        })
+    }
+}
+
+fn parse_scalar<T>(value: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .trim()
+        .parse::<T>()
+        .map_err(|e| format!("unable to parse {value}: {e}"))
+}
+
+fn parse_components<T, const N: usize>(value: &str) -> Result<[T; N], String>
+where
+    T: FromStr + Default + Copy,
+    T::Err: Display,
+{
+    let mut components = [T::default(); N];
+    let mut count = 0;
+    for part in value.split(';') {
+        if count >= N {
+            return Err(format!("expected {N} components, got more: {value}"));
+        }
+        components[count] = parse_scalar(part)?;
+        count += 1;
+    }
+    if count != N {
+        return Err(format!("expected {N} components, got {count}: {value}"));
+    }
+    Ok(components)
+}
+
+fn parse_vector<T, const N: usize>(value: &str) -> Result<SVector<T, N>, String>
+where
+    T: FromStr + Default + Copy + Scalar,
+    T::Err: Display,
+{
+    Ok(SVector::from(parse_components::<T, N>(value)?))
+}
+
+fn parse_base64(value: &str) -> Result<Vec<u8>, String> {
+    base64::engine::general_purpose::STANDARD
+        .decode(value.trim())
+        .map_err(|e| e.to_string())
+}
+
+/// Parses a quoted string at the beginning of the given string and returns the unescaped
+/// string together with the rest of the input.
+fn parse_quoted(input: &str) -> Result<(String, &str), String> {
+    let mut chars = input.char_indices();
+    match chars.next() {
+        Some((_, '"')) => (),
+        _ => return Err(format!("expected quoted string, got {input}")),
+    }
+    let mut string = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((string, &input[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, '"')) => string.push('"'),
+                Some((_, '\\')) => string.push('\\'),
+                Some((_, 'n')) => string.push('\n'),
+                Some((_, 'r')) => string.push('\r'),
+                Some((_, 't')) => string.push('\t'),
+                Some((_, 'u')) => {
+                    if !matches!(chars.next(), Some((_, '{'))) {
+                        return Err("expected { after \\u".to_string());
+                    }
+                    let mut code = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) => break,
+                            Some((_, c)) => code.push(c),
+                            None => return Err("unterminated unicode escape".to_string()),
+                        }
+                    }
+                    let code = u32::from_str_radix(&code, 16).map_err(|e| e.to_string())?;
+                    string.push(
+                        char::from_u32(code)
+                            .ok_or_else(|| format!("invalid unicode character {code}"))?,
+                    );
+                }
+                other => {
+                    return Err(format!(
+                        "invalid escape sequence \\{}",
+                        other.map(|(_, c)| c).unwrap_or_default()
+                    ))
+                }
+            },
+            c => string.push(c),
+        }
+    }
+    Err("unterminated string".to_string())
+}
+
+/// Parses either a bare or a quoted name at the beginning of the given string and returns the
+/// name together with the rest of the input.
+fn parse_name(input: &str) -> Result<(String, &str), String> {
+    if input.starts_with('"') {
+        parse_quoted(input)
+    } else {
+        let end = input
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(input.len());
+        if end == 0 {
+            Err(format!("expected name, got {input}"))
+        } else {
+            Ok((input[..end].to_string(), &input[end..]))
+        }
+    }
+}
+
+enum Line {
+    RegionBegin(String),
+    RegionEnd,
+    Field(Field),
+}
+
+fn parse_line(line: &str) -> Result<Line, String> {
+    if line == "}" {
+        return Ok(Line::RegionEnd);
+    }
+
+    let (name, rest) = parse_name(line)?;
+    let rest = rest.trim_start();
+    if rest == "{" {
+        Ok(Line::RegionBegin(name))
+    } else if let Some(rest) = rest.strip_prefix(':') {
+        let rest = rest.trim_start();
+        let type_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or(rest.len());
+        let (type_name, rest) = rest.split_at(type_end);
+        let value = rest
+            .trim_start()
+            .strip_prefix('=')
+            .ok_or_else(|| format!("expected = after the type of {name} field"))?
+            .trim();
+        Ok(Line::Field(Field::new(
+            &name,
+            FieldKind::parse_text(type_name, value)?,
+        )))
+    } else {
+        Err(format!("expected {{ or : after {name}"))
+    }
+}
+
+impl Visitor {
+    /// Sequence of bytes that is automatically written at the start when a visitor is encoded
+    /// into text form. It is written by [Visitor::save_text], [Visitor::save_text_to_memory] and
+    /// [Visitor::save_text_to_file].
+    ///
+    /// [Visitor::load_from_memory] and [Visitor::load_from_file] use this sequence to tell the
+    /// text form from the binary one.
+    pub const TEXT_MAGIC: &'static str = "FYROX-VISITOR-TEXT";
+
+    /// Current version of the text format. It is written right after [Visitor::TEXT_MAGIC].
+    pub const TEXT_VERSION: u32 = 1;
+
+    fn print_node(&self, node_handle: Handle<VisitorNode>, nesting: usize, out: &mut String) {
+        let node = self.nodes.borrow(node_handle);
+
+        for _ in 0..nesting {
+            out.push_str(INDENT);
+        }
+        write_name(&node.name, out);
+        out.push_str(" {\n");
+
+        for field in node.fields.iter() {
+            for _ in 0..=nesting {
+                out.push_str(INDENT);
+            }
+            write_name(&field.name, out);
+            out.push_str(": ");
+            out.push_str(&field.kind.as_text());
+            out.push('\n');
+        }
+
+        for child_handle in node.children.iter() {
+            self.print_node(*child_handle, nesting + 1, out);
+        }
+
+        for _ in 0..nesting {
+            out.push_str(INDENT);
+        }
+        out.push_str("}\n");
+    }
+
+    /// Create a String containing all the data of this Visitor in a human-readable text form,
+    /// which can be loaded back using [Visitor::load_from_text]. Each field is placed on its
+    /// own line and child nodes are indented, which makes the data suitable for version
+    /// control systems.
+    pub fn save_text(&self) -> String {
+        let mut out = format!("{} {}\n", Self::TEXT_MAGIC, Self::TEXT_VERSION);
+        self.print_node(self.root, 0, &mut out);
+        out
+    }
+
+    /// Write the data of this Visitor to the given writer in the text form.
+    /// Begin by writing [Visitor::TEXT_MAGIC].
+    pub fn save_text_to_memory<W: Write>(&self, mut writer: W) -> VisitResult {
+        writer.write_all(self.save_text().as_bytes())?;
+        Ok(())
+    }
+
+    /// Create a file at the given path and write the data of this visitor into that file in
+    /// the text form, so that the data can be reconstructed using [Visitor::load_from_file].
+    /// Begin by writing [Visitor::TEXT_MAGIC].
+    pub fn save_text_to_file<P: AsRef<Path>>(&self, path: P) -> VisitResult {
+        let writer = BufWriter::new(File::create(path)?);
+        self.save_text_to_memory(writer)
+    }
+
+    /// Checks whether the given data starts with [Visitor::TEXT_MAGIC].
+    pub fn is_text(data: &[u8]) -> bool {
+        data.starts_with(Self::TEXT_MAGIC.as_bytes())
+    }
+
+    /// Create a visitor by parsing the given string, assuming that the string is in the format
+    /// that would be produced by [Visitor::save_text].
+    /// Return a [VisitError::NotSupportedFormat] if [Visitor::TEXT_MAGIC] is not at the
+    /// beginning of the string.
+    pub fn load_from_text(text: &str) -> Result<Self, VisitError> {
+        let mut lines = text.lines().enumerate();
+
+        let header = lines
+            .next()
+            .map(|(_, line)| line.trim())
+            .ok_or(VisitError::NotSupportedFormat)?;
+        let version = header
+            .strip_prefix(Self::TEXT_MAGIC)
+            .ok_or(VisitError::NotSupportedFormat)?
+            .trim();
+        if version.parse::<u32>() != Ok(Self::TEXT_VERSION) {
+            return Err(VisitError::InvalidText {
+                line: 1,
+                reason: format!("unsupported version {version}"),
+            });
+        }
+
+        let mut visitor = Self {
+            nodes: Pool::new(),
+            rc_map: Default::default(),
+            arc_map: Default::default(),
+            reading: true,
+            current_node: Handle::NONE,
+            root: Handle::NONE,
+            blackboard: Blackboard::new(),
+            flags: VisitorFlags::NONE,
+        };
+
+        let mut stack: Vec<Handle<VisitorNode>> = Vec::new();
+        for (index, line) in lines {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let error = |reason: String| VisitError::InvalidText {
+                line: index + 1,
+                reason,
+            };
+
+            match parse_line(line).map_err(error)? {
+                Line::RegionBegin(name) => {
+                    let parent = stack.last().cloned().unwrap_or_default();
+                    if parent.is_none() && visitor.root.is_some() {
+                        return Err(error("only one root region is allowed".to_string()));
+                    }
+                    let handle = visitor.nodes.spawn(VisitorNode::new(&name, parent));
+                    if parent.is_some() {
+                        visitor.nodes.borrow_mut(parent).children.push(handle);
+                    } else {
+                        visitor.root = handle;
+                    }
+                    stack.push(handle);
+                }
+                Line::RegionEnd => {
+                    if stack.pop().is_none() {
+                        return Err(error("unexpected }".to_string()));
+                    }
+                }
+                Line::Field(field) => {
+                    let Some(node) = stack.last() else {
+                        return Err(error("field outside of any region".to_string()));
+                    };
+                    visitor.nodes.borrow_mut(*node).fields.push(field);
+                }
+            }
+        }
+
+        if !stack.is_empty() {
+            return Err(VisitError::InvalidText {
+                line: text.lines().count(),
+                reason: "unexpected end of data, some regions are not closed".to_string(),
+            });
+        }
+        if visitor.root.is_none() {
+            return Err(VisitError::InvalidText {
+                line: text.lines().count(),
+                reason: "no root region".to_string(),
+            });
+        }
+
+        visitor.current_node = visitor.root;
+        Ok(visitor)
+    }
+}
@@ -33,7 +33,10 @@ use fyrox_core::{
     variable::{self, InheritableVariable},
     ComponentProvider, NameProvider,
 };
-use fyrox_resource::{untyped::UntypedResource, Resource, TypedResourceData};
+use fyrox_resource::{
+    event::ResourceEvent, manager::ResourceManager, untyped::UntypedResource, Resource,
+    TypedResourceData,
+};
 use std::any::Any;
 use std::cmp::Ordering;
 use std::fmt::Debug;
@@ -175,6 +178,18 @@ where
         node.as_reflect_mut(&mut |node| self.remap_handles_internal(node, &name, ignored_types));
     }
 
+    /// Same as [`Self::remap_handles`], but works with an arbitrary entity (for example, with a
+    /// detached property value of a node). `owner_name` is used only for diagnostic messages.
+    #[inline]
+    pub fn remap_handles_in(
+        &self,
+        entity: &mut dyn Reflect,
+        owner_name: &str,
+        ignored_types: &[TypeId],
+    ) {
+        self.remap_handles_internal(entity, owner_name, ignored_types)
+    }
+
     fn remap_handles_internal(
         &self,
         entity: &mut dyn Reflect,
@@ -431,6 +446,16 @@ fn reset_property_modified_flag(entity: &mut dyn Reflect, path: &str) {
     })
 }
 
+/// A property of a prefab instance, which value differs from the value of the respective property
+/// of the prefab (i.e. it is overridden in the instance).
+#[derive(Debug)]
+pub struct OverriddenProperty {
+    /// Path of the property (in the format of [`Reflect::resolve_path`]).
+    pub path: String,
+    /// Value of the property.
+    pub value: Box<dyn Reflect>,
+}
+
 pub trait AbstractSceneNode: ComponentProvider + Reflect + NameProvider {}
 
 impl<T: SceneGraphNode> AbstractSceneNode for T {}
@@ -594,6 +619,126 @@ pub trait SceneGraphNode: AbstractSceneNode + Clone + 'static {
         previous_value
     }
 
+    /// Collects every overridden property of the node, i.e. every inheritable property which value
+    /// was modified in comparison with the respective node of the parent resource (prefab). Returns
+    /// an empty vector if the node is not an instance of some resource.
+    fn overridden_properties(&self) -> Vec<OverriddenProperty> {
+        let mut properties = Vec::<OverriddenProperty>::new();
+
+        if self.resource().is_none() {
+            return properties;
+        }
+
+        self.as_reflect(&mut |node| {
+            node.enumerate_fields_recursively(
+                &mut |path, _, value| {
+                    value.as_inheritable_variable(&mut |variable| {
+                        if let Some(variable) = variable {
+                            if variable.is_modified()
+                                && properties.last().map_or(true, |last| last.path != path)
+                            {
+                                properties.push(OverriddenProperty {
+                                    path: path.to_string(),
+                                    value: variable.clone_value_box(),
+                                });
+                            }
+                        }
+                    })
+                },
+                &[TypeId::of::<UntypedResource>()],
+            )
+        });
+
+        properties
+    }
+
+    /// Reverts every overridden property of the node (see [`Self::overridden_properties`]) to
+    /// the value from the parent resource. Returns a set of previous values of the properties.
+    fn revert_overridden_properties(&mut self) -> Vec<OverriddenProperty> {
+        let mut previous_values = Vec::new();
+        for property in self.overridden_properties() {
+            if let Some(value) = self.revert_inheritable_property(&property.path) {
+                previous_values.push(OverriddenProperty {
+                    path: property.path,
+                    value,
+                });
+            }
+        }
+        previous_values
+    }
+
+    /// Applies the value of an overridden property to the respective node of the parent resource
+    /// (prefab) and makes the property of this node inherited again. If the parent resource is an
+    /// instance of some other resource itself (i.e. it is a prefab variant), the property becomes
+    /// overridden in the parent resource. The resource manager then notifies its subscribers that
+    /// the parent resource was changed (with [`ResourceEvent::Reloaded`]), so the other instances
+    /// of the resource could be synced with it. Keep in mind, that the parent resource is modified
+    /// only in memory, use [`Resource::save_back`] to write the changes to disk. Returns `true` if
+    /// the value was applied, `false` - otherwise.
+    fn apply_inheritable_property_to_resource(
+        &mut self,
+        path: &str,
+        resource_manager: &ResourceManager,
+    ) -> bool
+    where
+        Self::ResourceData: PrefabDataMut,
+    {
+        let Some(resource) = self.resource() else {
+            return false;
+        };
+
+        let mut value = None;
+        self.as_reflect(&mut |node| {
+            node.resolve_path(path, &mut |result| match result {
+                Ok(field) => field.as_inheritable_variable(&mut |variable| {
+                    if let Some(variable) = variable {
+                        if variable.is_modified() {
+                            value = Some(variable.clone_value_box());
+                        }
+                    } else {
+                        Log::err(format!("Property {path} is not inheritable!"))
+                    }
+                }),
+                Err(e) => Log::err(format!("Failed to resolve path {path}. Reason: {e:?}")),
+            })
+        });
+
+        if value.is_none() {
+            return false;
+        }
+
+        let mut applied = false;
+        if resource.is_ok() {
+            let mut resource_data = resource.data_ref();
+            if let Some(parent) = resource_data
+                .graph_mut()
+                .try_get_mut(self.original_handle_in_resource())
+            {
+                parent.as_reflect_mut(&mut |parent| {
+                    parent.set_field_by_path(path, value.take().unwrap(), &mut |result| {
+                        applied = result.is_ok();
+                        if !applied {
+                            Log::err(format!(
+                                "Failed to apply property {path} to the parent resource."
+                            ))
+                        }
+                    })
+                });
+            }
+        }
+
+        if applied {
+            reset_property_modified_flag(self, path);
+
+            resource_manager
+                .state()
+                .event_broadcaster
+                .broadcast(ResourceEvent::Reloaded(resource.into_untyped()));
+        }
+
+        applied
+    }
+
     /// Tries to borrow a component of given type.
     #[inline]
     fn component_ref<T: Any>(&self) -> Option<&T> {
@@ -622,6 +767,15 @@ pub trait PrefabData: TypedResourceData + 'static {
     fn mapping(&self) -> NodeMapping;
 }
 
+/// An extension of [`PrefabData`] for prefabs that could be modified in place. It is required to
+/// apply overridden properties of instances to their prefab (see
+/// [`SceneGraphNode::apply_inheritable_property_to_resource`]).
+pub trait PrefabDataMut: PrefabData {
+    /// Returns a mutable reference to the graph of the prefab. Changes made through this reference
+    /// are not propagated to the instances of the prefab on their own.
+    fn graph_mut(&mut self) -> &mut Self::Graph;
+}
+
 #[derive(Debug)]
 pub struct LinkScheme<N> {
     pub root: Handle<N>,
@@ -1051,6 +1205,21 @@ pub trait SceneGraph: BaseSceneGraph {
         self.traverse_iter(from).map(|(handle, _)| handle)
     }
 
+    /// Collects overridden properties (see [`SceneGraphNode::overridden_properties`]) of every
+    /// node in the hierarchy starting from the given node (usually it is a root node of a prefab
+    /// instance). Nodes without overridden properties are not included.
+    fn instance_overrides(
+        &self,
+        root: Handle<Self::Node>,
+    ) -> Vec<(Handle<Self::Node>, Vec<OverriddenProperty>)> {
+        self.traverse_iter(root)
+            .filter_map(|(handle, node)| {
+                let properties = node.overridden_properties();
+                (!properties.is_empty()).then_some((handle, properties))
+            })
+            .collect()
+    }
+
     /// This method checks integrity of the graph and restores it if needed. For example, if a node
     /// was added in a parent asset, then it must be added in the graph. Alternatively, if a node was
     /// deleted in a parent asset, then its instance must be deleted in the graph.
@@ -1390,8 +1559,8 @@ where
 #[cfg(test)]
 mod test {
     use crate::{
-        AbstractSceneGraph, AbstractSceneNode, BaseSceneGraph, NodeMapping, PrefabData, SceneGraph,
-        SceneGraphNode,
+        AbstractSceneGraph, AbstractSceneNode, BaseSceneGraph, NodeMapping, PrefabData,
+        PrefabDataMut, SceneGraph, SceneGraphNode,
     };
     use fyrox_core::pool::ErasedHandle;
     use fyrox_core::{
@@ -1523,6 +1692,12 @@ mod test {
         }
     }
 
+    impl PrefabDataMut for Graph {
+        fn graph_mut(&mut self) -> &mut Self::Graph {
+            self
+        }
+    }
+
     impl Index<Handle<Node>> for Graph {
         type Output = Node;
 
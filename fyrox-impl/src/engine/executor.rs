@@ -28,7 +28,8 @@ use crate::{
         task::TaskPool,
     },
     engine::{
-        Engine, EngineInitParams, GraphicsContext, GraphicsContextParams, SerializationContext,
+        Engine, EngineInitParams, FixedTimeStepSettings, GraphicsContext, GraphicsContextParams,
+        SerializationContext,
     },
     event::{Event, WindowEvent},
     event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
@@ -40,6 +41,7 @@ use clap::Parser;
 use fyrox_ui::constructor::new_widget_constructor_container;
 use std::{
     ops::{Deref, DerefMut},
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -48,6 +50,15 @@ use std::{
 struct Args {
     #[clap(short, long, default_value = None)]
     override_scene: Option<String>,
+
+    /// Prints a report about used and unused assets instead of running the game. Takes a list of
+    /// root resources (usually scenes) to start from.
+    #[clap(long, num_args = 1..)]
+    asset_report: Option<Vec<PathBuf>>,
+
+    /// Folders to search for unused assets. Used together with `--asset-report`.
+    #[clap(long, num_args = 1.., default_value = "data")]
+    assets_folders: Vec<PathBuf>,
 }
 
 /// Executor is a small wrapper that manages plugins and scripts for your game.
@@ -95,9 +106,11 @@ impl Executor {
     ) -> Self {
         let serialization_context = Arc::new(SerializationContext::new());
         let task_pool = Arc::new(TaskPool::new());
+        let resource_manager = ResourceManager::new(task_pool.clone());
+        mount_packed_assets(&resource_manager);
         let engine = Engine::new(EngineInitParams {
             graphics_context_params,
-            resource_manager: ResourceManager::new(task_pool.clone()),
+            resource_manager,
             serialization_context,
             task_pool,
             widget_constructors: Arc::new(new_widget_constructor_container()),
@@ -196,7 +209,9 @@ impl Executor {
         self.throttle_frame_interval
     }
 
-    /// Sets the desired update rate in frames per second.
+    /// Sets the desired update rate in frames per second. If fixed time step is enabled (see
+    /// [`Self::set_fixed_time_step`]), the physics runs at the fixed rate from its settings
+    /// regardless of the update rate.
     pub fn set_desired_update_rate(&mut self, update_rate: f32) {
         self.desired_update_rate = update_rate.abs();
     }
@@ -206,6 +221,14 @@ impl Executor {
         self.desired_update_rate
     }
 
+    /// Sets new fixed time step settings of the engine. The engine is still updated at the desired
+    /// update rate (see [`Self::set_desired_update_rate`]), but the physics, [`Plugin::fixed_update`]
+    /// and [`crate::script::ScriptTrait::on_fixed_update`] run at the fixed rate from the settings.
+    /// See [`FixedTimeStepSettings`] docs for more info.
+    pub fn set_fixed_time_step(&mut self, settings: FixedTimeStepSettings) {
+        self.engine.set_fixed_time_step_settings(settings);
+    }
+
     /// Adds new plugin to the executor, the plugin will be enabled only on [`Executor::run`].
     pub fn add_plugin<P>(&mut self, plugin: P)
     where
@@ -214,7 +237,10 @@ impl Executor {
         self.engine.add_plugin(plugin)
     }
 
-    /// Runs the executor - starts your game.
+    /// Runs the executor - starts your game. If the executable was started with
+    /// `--asset-report <scenes>` argument, then the game won't start, instead a report about used
+    /// and unused assets will be printed to the standard output (see
+    /// [`crate::asset::graph::ResourceUsageReport`] for more info).
     pub fn run(self) {
         let mut engine = self.engine;
         let event_loop = self.event_loop;
@@ -222,6 +248,21 @@ impl Executor {
         let throttle_threshold = self.throttle_threshold;
         let throttle_frame_interval = self.throttle_frame_interval;
 
+        let args = Args::try_parse().unwrap_or_default();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(roots) = args.asset_report {
+            let report = crate::core::futures::executor::block_on(
+                crate::asset::graph::ResourceUsageReport::new(
+                    &engine.resource_manager,
+                    &roots,
+                    &args.assets_folders,
+                ),
+            );
+            println!("{}", report.pretty_print());
+            return;
+        }
+
         if self.resource_hot_reloading {
             #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
             {
@@ -238,8 +279,6 @@ impl Executor {
             }
         }
 
-        let args = Args::try_parse().unwrap_or_default();
-
         engine.enable_plugins(args.override_scene.as_deref(), true, Some(&event_loop));
 
         let mut previous = Instant::now();
@@ -295,7 +334,8 @@ impl Executor {
                     previous = Instant::now();
                     lag += elapsed.as_secs_f32();
 
-                    // Update rate stabilization loop.
+                    // Update rate stabilization loop. If fixed time step is enabled, the engine
+                    // additionally splits each update into fixed steps for physics by itself.
                     while lag >= fixed_time_step {
                         let time_step;
                         if lag >= throttle_threshold
@@ -367,6 +407,41 @@ impl Executor {
     }
 }
 
+/// Mounts the pack file produced by the editor's export tool (if any) under the file system, so
+/// loose files could still override packed assets (useful for patches and mods).
+#[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+fn mount_packed_assets(resource_manager: &ResourceManager) {
+    use crate::asset::{
+        io::{FsResourceIo, OverlayResourceIo},
+        pack::PackedResourceIo,
+    };
+
+    let path = std::path::Path::new(PackedResourceIo::DEFAULT_FILE_NAME);
+    if !path.exists() {
+        return;
+    }
+
+    match PackedResourceIo::open(path) {
+        Ok(pack) => {
+            Log::info(format!("Packed assets {} were mounted.", path.display()));
+            resource_manager
+                .state()
+                .set_resource_io(Arc::new(OverlayResourceIo::new(vec![
+                    Arc::new(FsResourceIo),
+                    Arc::new(pack),
+                ])));
+        }
+        Err(err) => Log::err(format!(
+            "Unable to mount packed assets {}. Reason: {err:?}",
+            path.display()
+        )),
+    }
+}
+
+/// Packed assets are not supported on this platform.
+#[cfg(any(target_arch = "wasm32", target_os = "android"))]
+fn mount_packed_assets(_resource_manager: &ResourceManager) {}
+
 fn run_executor<F>(event_loop: EventLoop<()>, callback: F)
 where
     F: FnMut(Event<()>, &EventLoopWindowTarget<()>) + 'static,
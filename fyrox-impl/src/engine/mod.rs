@@ -38,6 +38,7 @@ use crate::{
     asset::{
         event::ResourceEvent,
         manager::{ResourceManager, ResourceWaitContext},
+        memory::ResourceMemoryStatistics,
         state::ResourceState,
         untyped::{ResourceKind, UntypedResource},
         Resource,
@@ -80,8 +81,15 @@ use crate::{
         texture::{self, loader::TextureLoader, Texture, TextureKind},
     },
     scene::{
+        animation::{
+            absm::{AbsmEventMessage, AnimationBlendingStateMachine},
+            retarget::{SkeletonMapping, SkeletonMappingLoader},
+            AnimationEventMessage, AnimationPlayer,
+        },
         base::NodeScriptMessage,
         camera::SkyBoxKind,
+        character::CharacterController,
+        dim2,
         graph::{GraphUpdateSwitches, NodePool},
         mesh::surface::{self, SurfaceData, SurfaceDataLoader},
         navmesh,
@@ -89,6 +97,7 @@ use crate::{
             constructor::{new_node_constructor_container, NodeConstructorContainer},
             Node,
         },
+        physics_material::{PhysicsMaterial, PhysicsMaterialLoader},
         sound::SoundEngine,
         tilemap::{
             brush::{TileMapBrush, TileMapBrushLoader},
@@ -168,14 +177,17 @@ pub struct PerformanceStatistics {
 
     /// Amount of time spent in plugins updating.
     pub plugins_time: Duration,
+
+    /// Memory usage of resources and eviction statistics.
+    pub resources: ResourceMemoryStatistics,
 }
 
 impl Display for PerformanceStatistics {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(
             f,
-            "Performance Statistics:\n\tUI: {:?}\n\tScripts: {:?}\n\tPlugins: {:?}",
-            self.ui_time, self.scripts_time, self.plugins_time
+            "Performance Statistics:\n\tUI: {:?}\n\tScripts: {:?}\n\tPlugins: {:?}\n{}",
+            self.ui_time, self.scripts_time, self.plugins_time, self.resources
         )
     }
 }
@@ -488,6 +500,14 @@ pub struct Engine {
     // Amount of time (in seconds) that passed from creation of the engine.
     elapsed_time: f32,
 
+    fixed_time_step: FixedTimeStepSettings,
+
+    // Amount of time (in seconds) that was not yet simulated by fixed steps.
+    fixed_time_accumulator: f32,
+
+    // Amount of time (in seconds) that was simulated by fixed steps.
+    fixed_elapsed_time: f32,
+
     /// A special container that is able to create nodes by their type UUID. Use a copy of this
     /// value whenever you need it as a parameter in other parts of the engine.
     pub serialization_context: Arc<SerializationContext>,
@@ -531,6 +551,21 @@ impl ScriptMessageDispatcher {
         }
     }
 
+    /// Returns `true` if the node is subscribed to receive messages of the given type `T`.
+    pub fn is_subscribed_to<T: 'static>(&self, receiver: Handle<Node>) -> bool {
+        self.type_groups
+            .get(&TypeId::of::<T>())
+            .is_some_and(|group| group.contains(&receiver))
+    }
+
+    fn subscribers<T: 'static>(&self) -> impl Iterator<Item = Handle<Node>> + '_ {
+        self.type_groups
+            .get(&TypeId::of::<T>())
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
     /// Unsubscribes a node from receiving any messages.
     pub fn unsubscribe(&mut self, receiver: Handle<Node>) {
         for group in self.type_groups.values_mut() {
@@ -670,6 +705,103 @@ impl ScriptMessageDispatcher {
     }
 }
 
+/// Delivers collision events of the last physics step to the scripts of the involved colliders and
+/// their parent rigid bodies (or character controllers). Each event is delivered to both sides, so
+/// the first collider of an event is always the one that belongs to the receiver.
+fn dispatch_collision_events(context: &mut ScriptContext) {
+    let collision_events = context
+        .scene
+        .graph
+        .physics
+        .collision_events()
+        .iter()
+        .chain(context.scene.graph.physics2d.collision_events())
+        .flat_map(|event| [event.clone(), event.swapped()])
+        .collect::<Vec<_>>();
+
+    for event in collision_events.iter() {
+        let graph = &context.scene.graph;
+        let body = graph
+            .try_get(event.collider1)
+            .map(|collider| collider.parent())
+            .filter(|parent| {
+                graph.try_get(*parent).is_some_and(|parent| {
+                    parent.is_rigid_body()
+                        || parent.is_rigid_body2d()
+                        || parent.cast::<CharacterController>().is_some()
+                        || parent
+                            .cast::<dim2::character::CharacterController>()
+                            .is_some()
+                })
+            })
+            .unwrap_or_default();
+
+        for handle in [event.collider1, body] {
+            context.handle = handle;
+
+            process_node_scripts(context, &mut |script, context| {
+                if script.started {
+                    script.on_collision(event, context);
+                }
+            });
+        }
+    }
+}
+
+/// Takes the events produced by animation players and animation blending state machines and sends them as
+/// script messages to the nodes that own them. Only the nodes subscribed to [`AnimationEventMessage`] or
+/// [`AbsmEventMessage`] are processed, events of any other node are left untouched, so they could still be
+/// polled manually.
+fn dispatch_animation_events(context: &mut ScriptContext) {
+    let dispatcher = &*context.message_dispatcher;
+
+    for handle in dispatcher.subscribers::<AnimationEventMessage>() {
+        let Some(animation_player) = context
+            .scene
+            .graph
+            .try_get_mut_of_type::<AnimationPlayer>(handle)
+        else {
+            continue;
+        };
+
+        for (animation, animation_ref) in animation_player
+            .animations_mut()
+            .get_value_mut_silent()
+            .pair_iter_mut()
+        {
+            for event in animation_ref.take_events() {
+                context
+                    .message_sender
+                    .send_to_target(handle, AnimationEventMessage { animation, event });
+            }
+        }
+    }
+
+    for handle in dispatcher.subscribers::<AbsmEventMessage>() {
+        let Some(absm) = context
+            .scene
+            .graph
+            .try_get_mut_of_type::<AnimationBlendingStateMachine>(handle)
+        else {
+            continue;
+        };
+
+        for (layer, layer_ref) in absm
+            .machine_mut()
+            .get_value_mut_silent()
+            .layers_mut()
+            .iter_mut()
+            .enumerate()
+        {
+            while let Some(event) = layer_ref.pop_event() {
+                context
+                    .message_sender
+                    .send_to_target(handle, AbsmEventMessage { layer, event });
+            }
+        }
+    }
+}
+
 /// Scripted scene is a handle to scene with some additional data associated with it.
 pub struct ScriptedScene {
     /// Handle of a scene.
@@ -711,6 +843,78 @@ impl ScriptProcessor {
             .push(resource_manager.state().get_wait_context());
     }
 
+    fn handle_fixed_update_scripts(
+        &mut self,
+        scenes: &mut SceneContainer,
+        plugins: &mut [PluginContainer],
+        resource_manager: &ResourceManager,
+        task_pool: &mut TaskPoolHandler,
+        graphics_context: &mut GraphicsContext,
+        user_interfaces: &mut UiContainer,
+        dt: f32,
+        elapsed_time: f32,
+    ) {
+        // Scripts are initialized and started in the regular update, so do nothing until everything
+        // is loaded.
+        if !self.wait_list.is_empty() {
+            return;
+        }
+
+        for scripted_scene in self.scripted_scenes.iter_mut() {
+            let Some(scene) = scenes.try_get_mut(scripted_scene.handle) else {
+                continue;
+            };
+
+            if !*scene.enabled {
+                continue;
+            }
+
+            let fixed_update_queue = scene
+                .graph
+                .pair_iter()
+                .filter(|(_, node)| node.is_globally_enabled())
+                .flat_map(|(handle, node)| {
+                    node.scripts
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, entry)| {
+                            entry.script.as_ref().is_some_and(|script| script.started)
+                        })
+                        .map(move |(index, _)| (handle, index))
+                })
+                .collect::<Vec<_>>();
+
+            let mut context = ScriptContext {
+                dt,
+                elapsed_time,
+                plugins: PluginsRefMut(plugins),
+                handle: Default::default(),
+                scene,
+                scene_handle: scripted_scene.handle,
+                resource_manager,
+                message_sender: &scripted_scene.message_sender,
+                message_dispatcher: &mut scripted_scene.message_dispatcher,
+                task_pool,
+                graphics_context,
+                user_interfaces,
+                script_index: 0,
+            };
+
+            for (handle, script_index) in fixed_update_queue {
+                context.handle = handle;
+                context.script_index = script_index;
+
+                process_node_script(script_index, &mut context, &mut |script, context| {
+                    if script.started {
+                        script.on_fixed_update(context);
+                    }
+                });
+            }
+
+            dispatch_collision_events(&mut context);
+        }
+    }
+
     fn handle_scripts(
         &mut self,
         scenes: &mut SceneContainer,
@@ -721,6 +925,7 @@ impl ScriptProcessor {
         user_interfaces: &mut UiContainer,
         dt: f32,
         elapsed_time: f32,
+        dispatch_collisions: bool,
     ) {
         self.wait_list
             .retain_mut(|context| !context.is_all_loaded());
@@ -887,6 +1092,28 @@ impl ScriptProcessor {
                 }
             }
 
+            let mut context = ScriptContext {
+                dt,
+                elapsed_time,
+                plugins: PluginsRefMut(plugins),
+                handle: Default::default(),
+                scene,
+                scene_handle: scripted_scene.handle,
+                resource_manager,
+                message_sender: &scripted_scene.message_sender,
+                message_dispatcher: &mut scripted_scene.message_dispatcher,
+                task_pool,
+                graphics_context,
+                user_interfaces,
+                script_index: 0,
+            };
+
+            if dispatch_collisions {
+                dispatch_collision_events(&mut context);
+            }
+
+            dispatch_animation_events(&mut context);
+
             // Dispatch script messages only when everything is initialized and updated. This has to
             // be done this way, because all those methods could spawn new messages. However, if a new
             // message is spawned directly in `on_message` the dispatcher will correctly handle it
@@ -1096,6 +1323,49 @@ impl Default for GraphicsContextParams {
     }
 }
 
+/// Settings of the fixed time step simulation loop. When enabled, the engine accumulates the time
+/// passed to [`Engine::update`] and splits it into fixed steps. Each fixed step performs the physics
+/// simulation (both 3D and 2D), then calls [`Plugin::fixed_update`] of every plugin and
+/// [`crate::script::ScriptTrait::on_fixed_update`] of every script. Everything else (nodes,
+/// animations, [`Plugin::update`], [`crate::script::ScriptTrait::on_update`], etc.) is still updated
+/// once per [`Engine::update`] call with the time step passed to it. This makes the simulation
+/// deterministic and independent of the frame rate. The elapsed time of fixed steps is tracked
+/// separately, see [`Engine::fixed_elapsed_time`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixedTimeStepSettings {
+    /// Enables or disables fixed time step. Default is `false`, which means that the physics is
+    /// simulated with the time step passed to [`Engine::update`].
+    pub enabled: bool,
+    /// The amount of fixed steps per second. Default is 60.
+    pub rate: f32,
+    /// The maximum amount of fixed steps per single [`Engine::update`] call. The remaining time is
+    /// discarded, which prevents the "spiral of death" when a fixed step takes more time than it
+    /// simulates. Default is 8.
+    pub max_substeps: usize,
+    /// Enables or disables interpolation of the dynamic rigid bodies between the last two physics
+    /// states. Without it, rigid bodies will move in a jerky way if the frame rate differs from the
+    /// fixed rate. Default is `true`.
+    pub interpolation: bool,
+}
+
+impl Default for FixedTimeStepSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rate: 60.0,
+            max_substeps: 8,
+            interpolation: true,
+        }
+    }
+}
+
+impl FixedTimeStepSettings {
+    /// Returns the duration (in seconds) of a single fixed step.
+    pub fn time_step(&self) -> f32 {
+        1.0 / self.rate.max(f32::EPSILON)
+    }
+}
+
 /// Engine initialization parameters.
 pub struct EngineInitParams {
     /// A set of parameters for graphics context initialization. Keep in mind that the engine **will not** initialize
@@ -1225,8 +1495,9 @@ pub(crate) fn initialize_resource_manager_loaders(
 ) {
     let model_loader = ModelLoader {
         resource_manager: resource_manager.clone(),
-        serialization_context,
+        serialization_context: serialization_context.clone(),
         default_import_options: Default::default(),
+        import_cache: resource_manager.state().import_cache.clone(),
     };
 
     let mut state = resource_manager.state();
@@ -1236,6 +1507,8 @@ pub(crate) fn initialize_resource_manager_loaders(
         let gltf_loader = super::resource::gltf::GltfLoader {
             resource_manager: resource_manager.clone(),
             default_import_options: Default::default(),
+            serialization_context,
+            import_cache: state.import_cache.clone(),
         };
         state.loaders.set(gltf_loader);
     }
@@ -1291,11 +1564,15 @@ pub(crate) fn initialize_resource_manager_loaders(
     state.constructors_container.add::<CustomTileCollider>();
     state.constructors_container.add::<AnimationTracksData>();
     state.constructors_container.add::<Style>();
+    state.constructors_container.add::<PhysicsMaterial>();
+    state.constructors_container.add::<SkeletonMapping>();
 
+    let import_cache = state.import_cache.clone();
     let loaders = &mut state.loaders;
     loaders.set(model_loader);
     loaders.set(TextureLoader {
         default_import_options: Default::default(),
+        import_cache,
     });
     loaders.set(SoundBufferLoader {
         default_import_options: Default::default(),
@@ -1318,6 +1595,8 @@ pub(crate) fn initialize_resource_manager_loaders(
         resource_manager: resource_manager.clone(),
     });
     state.loaders.set(StyleLoader);
+    state.loaders.set(PhysicsMaterialLoader);
+    state.loaders.set(SkeletonMappingLoader);
 }
 
 impl Engine {
@@ -1391,7 +1670,7 @@ impl Engine {
                 serialization_context.clone(),
             ),
             resource_manager,
-            scenes: SceneContainer::new(sound_engine.clone()),
+            scenes: SceneContainer::new(sound_engine.clone(), task_pool.clone()),
             sound_engine,
             user_interfaces,
             performance_statistics: Default::default(),
@@ -1401,6 +1680,9 @@ impl Engine {
             script_processor: Default::default(),
             plugins_enabled: false,
             elapsed_time: 0.0,
+            fixed_time_step: Default::default(),
+            fixed_time_accumulator: 0.0,
+            fixed_elapsed_time: 0.0,
             task_pool: TaskPoolHandler::new(task_pool),
         })
     }
@@ -1549,6 +1831,24 @@ impl Engine {
         self.elapsed_time
     }
 
+    /// Amount of time (in seconds) that was simulated by fixed steps (see [`FixedTimeStepSettings`]).
+    /// Unlike [`Self::elapsed_time`], it is advanced by each fixed step and it is the value that is
+    /// passed to [`Plugin::fixed_update`] and [`crate::script::ScriptTrait::on_fixed_update`].
+    pub fn fixed_elapsed_time(&self) -> f32 {
+        self.fixed_elapsed_time
+    }
+
+    /// Sets new fixed time step settings. See [`FixedTimeStepSettings`] docs for more info.
+    pub fn set_fixed_time_step_settings(&mut self, settings: FixedTimeStepSettings) {
+        self.fixed_time_step = settings;
+        self.fixed_time_accumulator = 0.0;
+    }
+
+    /// Returns current fixed time step settings. See [`FixedTimeStepSettings`] docs for more info.
+    pub fn fixed_time_step_settings(&self) -> &FixedTimeStepSettings {
+        &self.fixed_time_step
+    }
+
     /// Performs single update tick with given time delta. Engine internally will perform update
     /// of all scenes, sub-systems, user interface, etc. Must be called in order to get engine
     /// functioning.
@@ -1687,6 +1987,7 @@ impl Engine {
                                             &mut |_, _, _| {},
                                         )
                                         .0,
+                                    variant_base: None,
                                 },
                             );
 
@@ -1796,7 +2097,13 @@ impl Engine {
         lag: &mut f32,
         switches: FxHashMap<Handle<Scene>, GraphUpdateSwitches>,
     ) {
-        self.resource_manager.state().update(dt);
+        {
+            let mut resource_manager = self.resource_manager.state();
+            resource_manager.update(dt);
+            self.performance_statistics
+                .resources
+                .clone_from(resource_manager.memory_statistics());
+        }
         self.handle_model_events();
 
         let window_size = if let GraphicsContext::Initialized(ctx) = &mut self.graphics_context {
@@ -1808,6 +2115,27 @@ impl Engine {
             Vector2::new(1.0, 1.0)
         };
 
+        let mut interpolation_factor = None;
+        if self.fixed_time_step.enabled {
+            let time_step = self.fixed_time_step.time_step();
+            self.fixed_time_accumulator += dt;
+            let mut substeps = 0;
+            while self.fixed_time_accumulator >= time_step {
+                if substeps >= self.fixed_time_step.max_substeps {
+                    // Discard the time that cannot be simulated, otherwise the engine will try to
+                    // catch up forever.
+                    self.fixed_time_accumulator %= time_step;
+                    break;
+                }
+                self.fixed_update(time_step, window_target, lag, &switches);
+                self.fixed_time_accumulator -= time_step;
+                substeps += 1;
+            }
+            if self.fixed_time_step.interpolation {
+                interpolation_factor = Some(self.fixed_time_accumulator / time_step);
+            }
+        }
+
         for (handle, scene) in self.scenes.pair_iter_mut().filter(|(_, s)| *s.enabled) {
             let frame_size =
                 scene
@@ -1822,17 +2150,81 @@ impl Engine {
                         }
                     });
 
-            scene.update(
-                frame_size,
-                dt,
-                switches.get(&handle).cloned().unwrap_or_default(),
-            );
+            let mut switches = switches.get(&handle).cloned().unwrap_or_default();
+            if switches.streaming && !switches.paused {
+                scene.update_world_partition(&self.resource_manager);
+            }
+
+            if self.fixed_time_step.enabled {
+                // Physics is already simulated by fixed steps.
+                switches.physics = false;
+                switches.physics2d = false;
+            }
+            scene
+                .graph
+                .physics
+                .set_interpolation_factor(interpolation_factor);
+            scene
+                .graph
+                .physics2d
+                .set_interpolation_factor(interpolation_factor);
+
+            scene.update(frame_size, dt, switches);
         }
 
         self.update_plugins(dt, window_target, lag);
         self.handle_scripts(dt);
     }
 
+    fn fixed_update(
+        &mut self,
+        dt: f32,
+        window_target: &EventLoopWindowTarget<()>,
+        lag: &mut f32,
+        switches: &FxHashMap<Handle<Scene>, GraphUpdateSwitches>,
+    ) {
+        for (handle, scene) in self.scenes.pair_iter_mut().filter(|(_, s)| *s.enabled) {
+            let switches = switches.get(&handle).cloned().unwrap_or_default();
+            scene.graph.fixed_update(dt, &switches);
+        }
+
+        if self.plugins_enabled {
+            let mut context = PluginContext {
+                scenes: &mut self.scenes,
+                resource_manager: &self.resource_manager,
+                graphics_context: &mut self.graphics_context,
+                dt,
+                lag,
+                user_interfaces: &mut self.user_interfaces,
+                serialization_context: &self.serialization_context,
+                widget_constructors: &self.widget_constructors,
+                performance_statistics: &self.performance_statistics,
+                elapsed_time: self.fixed_elapsed_time,
+                script_processor: &self.script_processor,
+                async_scene_loader: &mut self.async_scene_loader,
+                window_target: Some(window_target),
+                task_pool: &mut self.task_pool,
+            };
+
+            for plugin in self.plugins.iter_mut() {
+                plugin.fixed_update(&mut context);
+            }
+        }
+
+        self.script_processor.handle_fixed_update_scripts(
+            &mut self.scenes,
+            &mut self.plugins,
+            &self.resource_manager,
+            &mut self.task_pool,
+            &mut self.graphics_context,
+            &mut self.user_interfaces,
+            dt,
+            self.fixed_elapsed_time,
+        );
+
+        self.fixed_elapsed_time += dt;
+    }
+
     /// Performs post update for the engine.
     ///
     /// Normally, this is called from `Engine::update()`.
@@ -1882,6 +2274,8 @@ impl Engine {
             &mut self.user_interfaces,
             dt,
             self.elapsed_time,
+            // Collision events are delivered right after each physics step in fixed time step mode.
+            !self.fixed_time_step.enabled,
         );
 
         self.performance_statistics.scripts_time = instant::Instant::now() - time;
@@ -2731,12 +3125,28 @@ mod test {
     use crate::{
         asset::manager::ResourceManager,
         core::{
-            pool::Handle, reflect::prelude::*, task::TaskPool, type_traits::prelude::*,
+            algebra::{Vector2, Vector3},
+            pool::Handle,
+            reflect::prelude::*,
+            task::TaskPool,
+            type_traits::prelude::*,
             visitor::prelude::*,
         },
         engine::{task::TaskPoolHandler, GraphicsContext, ScriptProcessor},
         graph::BaseSceneGraph,
-        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::{
+                physics::{CollisionEvent, CollisionEventKind},
+                GraphUpdateSwitches,
+            },
+            node::Node,
+            pivot::PivotBuilder,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+            Scene, SceneContainer,
+        },
         script::{
             ScriptContext, ScriptDeinitContext, ScriptMessageContext, ScriptMessagePayload,
             ScriptTrait,
@@ -2907,7 +3317,7 @@ mod test {
             script_index: 1,
         };
 
-        let mut scene_container = SceneContainer::new(Default::default());
+        let mut scene_container = SceneContainer::new(Default::default(), Default::default());
 
         let scene_handle = scene_container.add(scene);
 
@@ -2941,6 +3351,7 @@ mod test {
                 &mut user_interfaces,
                 0.0,
                 0.0,
+                true,
             );
 
             match iteration {
@@ -3085,7 +3496,7 @@ mod test {
             script_index: 0,
         };
 
-        let mut scene_container = SceneContainer::new(Default::default());
+        let mut scene_container = SceneContainer::new(Default::default(), Default::default());
 
         let scene_handle = scene_container.add(scene);
 
@@ -3106,6 +3517,7 @@ mod test {
                 &mut user_interfaces,
                 0.0,
                 0.0,
+                true,
             );
 
             match iteration {
@@ -3347,7 +3759,7 @@ mod test {
         .build(&mut scene.graph);
         assert_eq!(node_handle, Handle::new(1, 1));
 
-        let mut scene_container = SceneContainer::new(Default::default());
+        let mut scene_container = SceneContainer::new(Default::default(), Default::default());
 
         let scene_handle = scene_container.add(scene);
 
@@ -3369,6 +3781,7 @@ mod test {
                 &mut user_interfaces,
                 0.0,
                 0.0,
+                true,
             );
 
             match iteration {
@@ -3445,4 +3858,226 @@ mod test {
             }
         }
     }
+
+    #[derive(Debug, Clone, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+    #[type_uuid(id = "3f1a9c0e-5a63-4a53-9f0e-56a8c1a0d7e4")]
+    struct CollisionScript {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(Handle<Node>, CollisionEvent)>,
+    }
+
+    impl ScriptTrait for CollisionScript {
+        fn on_collision(&mut self, event: &CollisionEvent, ctx: &mut ScriptContext) {
+            self.sender.send((ctx.handle, event.clone())).unwrap();
+        }
+    }
+
+    type ReceivedCollisionEvents = Vec<(Handle<Node>, CollisionEvent)>;
+
+    fn simulate_falling_ball(
+        report_collision_events: bool,
+    ) -> (ReceivedCollisionEvents, [Handle<Node>; 3]) {
+        let resource_manager = ResourceManager::new(Arc::new(Default::default()));
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(10.0, 0.5, 10.0))
+            .build(&mut scene.graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[ground_collider]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut scene.graph);
+
+        let ball_collider = ColliderBuilder::new(
+            BaseBuilder::new().with_script(CollisionScript { sender: tx.clone() }),
+        )
+        .with_shape(ColliderShape::ball(0.5))
+        .with_report_collision_events(report_collision_events)
+        .build(&mut scene.graph);
+        let ball = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 1.5, 0.0))
+                        .build(),
+                )
+                .with_script(CollisionScript { sender: tx })
+                .with_children(&[ball_collider]),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default(), Default::default());
+
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+
+        script_processor.register_scripted_scene(scene_handle, &resource_manager);
+
+        let mut task_pool = TaskPoolHandler::new(Arc::new(TaskPool::new()));
+        let mut gc = GraphicsContext::Uninitialized(Default::default());
+        let mut user_interfaces = UiContainer::default();
+
+        for _ in 0..60 {
+            scene_container[scene_handle].graph.update(
+                Vector2::new(800.0, 600.0),
+                1.0 / 60.0,
+                Default::default(),
+            );
+
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Vec::new(),
+                &resource_manager,
+                &mut task_pool,
+                &mut gc,
+                &mut user_interfaces,
+                1.0 / 60.0,
+                0.0,
+                true,
+            );
+        }
+
+        (
+            rx.try_iter().collect(),
+            [ground_collider, ball_collider, ball],
+        )
+    }
+
+    #[test]
+    fn test_collision_events() {
+        let (events, [ground_collider, ball_collider, ball]) = simulate_falling_ball(true);
+        for receiver in [ball_collider, ball] {
+            let (_, event) = events
+                .iter()
+                .find(|(handle, event)| {
+                    *handle == receiver && event.kind == CollisionEventKind::ContactStarted
+                })
+                .unwrap();
+            assert_eq!(event.collider1, ball_collider);
+            assert_eq!(event.collider2, ground_collider);
+            assert!(!event.contact_points.is_empty());
+            assert!(event.total_impulse > 0.0);
+            assert!(event.normal.y < 0.0);
+        }
+
+        // Neither of the colliders asked for collision events.
+        let (events, _) = simulate_falling_ball(false);
+        assert!(events.is_empty());
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
+    #[type_uuid(id = "b0f5c3d2-7e1a-4c8e-9a44-2d6f1e8b3c57")]
+    struct FixedUpdateScript {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<f32>,
+    }
+
+    impl ScriptTrait for FixedUpdateScript {
+        fn on_fixed_update(&mut self, ctx: &mut ScriptContext) {
+            self.sender.send(ctx.dt).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_fixed_update() {
+        let resource_manager = ResourceManager::new(Arc::new(Default::default()));
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.5))
+            .build(&mut scene.graph);
+        let ball = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 10.0, 0.0))
+                        .build(),
+                )
+                .with_script(FixedUpdateScript { sender: tx })
+                .with_children(&[collider]),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default(), Default::default());
+        let scene_handle = scene_container.add(scene);
+
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(scene_handle, &resource_manager);
+
+        let mut task_pool = TaskPoolHandler::new(Arc::new(TaskPool::new()));
+        let mut gc = GraphicsContext::Uninitialized(Default::default());
+        let mut user_interfaces = UiContainer::default();
+
+        let fixed_step = 1.0 / 60.0;
+        let frame_switches = GraphUpdateSwitches {
+            physics: false,
+            physics2d: false,
+            ..Default::default()
+        };
+
+        let render = |scene_container: &mut SceneContainer, factor: f32| {
+            let graph = &mut scene_container[scene_handle].graph;
+            graph.physics.set_interpolation_factor(Some(factor));
+            graph.update(Vector2::new(800.0, 600.0), 0.0, frame_switches.clone());
+            graph[ball].local_transform().position().y
+        };
+
+        // Mimic the engine at 30 FPS with 60 fixed steps per second.
+        for _ in 0..10 {
+            for _ in 0..2 {
+                scene_container[scene_handle]
+                    .graph
+                    .fixed_update(fixed_step, &Default::default());
+
+                script_processor.handle_fixed_update_scripts(
+                    &mut scene_container,
+                    &mut Vec::new(),
+                    &resource_manager,
+                    &mut task_pool,
+                    &mut gc,
+                    &mut user_interfaces,
+                    fixed_step,
+                    0.0,
+                );
+            }
+
+            render(&mut scene_container, 0.5);
+
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Vec::new(),
+                &resource_manager,
+                &mut task_pool,
+                &mut gc,
+                &mut user_interfaces,
+                2.0 * fixed_step,
+                0.0,
+                false,
+            );
+        }
+
+        // The script is started at the end of the first frame, so it misses the first two steps.
+        let steps = rx.try_iter().collect::<Vec<_>>();
+        assert_eq!(steps.len(), 18);
+        assert!(steps.iter().all(|dt| *dt == fixed_step));
+
+        let half = render(&mut scene_container, 0.5);
+        let current = render(&mut scene_container, 1.0);
+        let previous = render(&mut scene_container, 0.0);
+        assert!(previous > current);
+        assert!((half - (previous + current) * 0.5).abs() < 1.0e-4);
+
+        // Interpolated poses must not rewind the simulation.
+        scene_container[scene_handle]
+            .graph
+            .fixed_update(fixed_step, &Default::default());
+        let next = render(&mut scene_container, 1.0);
+        assert!(next < current - 0.5 * (previous - current));
+    }
 }
@@ -281,6 +281,13 @@ pub trait Plugin: Downcast + Visit + Reflect {
     /// called after all Plugin and Script updates
     fn post_update(&mut self, #[allow(unused_variables)] context: &mut PluginContext) {}
 
+    /// Performs a single fixed step of the plugin. The method is called only if fixed time step is
+    /// enabled (see [`crate::engine::FixedTimeStepSettings`]), right after the physics step and
+    /// before [`crate::script::ScriptTrait::on_fixed_update`] of the scripts. It could be called
+    /// zero or multiple times per frame, [`PluginContext::dt`] is always equal to the fixed time
+    /// step.
+    fn fixed_update(&mut self, #[allow(unused_variables)] context: &mut PluginContext) {}
+
     /// The method is called when the main window receives an event from the OS. The main use of
     /// the method is to respond to some external events, for example an event from keyboard or
     /// gamepad. See [`Event`] docs for more info.
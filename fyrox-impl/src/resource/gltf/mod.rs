@@ -26,6 +26,7 @@ use gltf::Gltf;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use crate::asset::cache::{ImportCache, ImportCacheKey};
 use crate::asset::io::ResourceIo;
 use crate::asset::loader;
 use crate::asset::manager::ResourceManager;
@@ -35,11 +36,15 @@ use crate::core::algebra::{Matrix4, Unit};
 use crate::core::log::Log;
 use crate::core::pool::Handle;
 use crate::core::TypeUuidProvider;
+use crate::engine::SerializationContext;
 use crate::graph::BaseSceneGraph;
 use crate::graph::NodeMapping;
 use crate::gui::core::io::FileLoadError;
 use crate::material::MaterialResource;
-use crate::resource::model::{MaterialSearchOptions, Model, ModelImportOptions};
+use crate::resource::model::{
+    append_skeleton_mapping_source, apply_skeleton_mapping, compress_animations,
+    loader::load_cached_model, MaterialSearchOptions, Model, ModelImportOptions,
+};
 use crate::resource::texture::{TextureError, TextureResource};
 use crate::scene::animation::{AnimationContainer, AnimationPlayerBuilder};
 use crate::scene::base::BaseBuilder;
@@ -267,6 +272,18 @@ pub struct GltfLoader {
     pub resource_manager: ResourceManager,
     /// Import options control where this loader should search for additional resources.
     pub default_import_options: ModelImportOptions,
+    /// Serialization context is needed to load imported models from the import cache.
+    pub serialization_context: Arc<SerializationContext>,
+    /// Cache for imported glTF models. The content of the glTF file, its external buffers and its
+    /// import options are used as the key, external resources (such as textures) are stored as
+    /// references and resolved on every load. See [`ImportCache`] docs for more info.
+    pub import_cache: ImportCache,
+}
+
+impl GltfLoader {
+    /// Version of the glTF import pipeline. Must be increased every time when the loader starts
+    /// to produce different output for the same input, to invalidate stale import cache entries.
+    pub const IMPORT_CACHE_VERSION: u32 = 1;
 }
 
 impl loader::ResourceLoader for GltfLoader {
@@ -281,12 +298,42 @@ impl loader::ResourceLoader for GltfLoader {
     fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> loader::BoxedLoaderFuture {
         let resource_manager = self.resource_manager.clone();
         let default_import_options = self.default_import_options.clone();
+        let serialization_context = self.serialization_context.clone();
+        let import_cache = self.import_cache.clone();
 
         Box::pin(async move {
             let import_options = options::try_get_import_settings(&path, io.as_ref())
                 .await
                 .unwrap_or(default_import_options);
 
+            if import_cache.is_enabled() {
+                let source = import_cache_source(&path, &io, &resource_manager, &import_options)
+                    .await
+                    .map_err(LoadError::new)?;
+                let key = ImportCacheKey::new(
+                    "gltf",
+                    GltfLoader::IMPORT_CACHE_VERSION,
+                    &source,
+                    &import_options,
+                );
+
+                let model = load_cached_model(
+                    &path,
+                    key.as_ref(),
+                    serialization_context,
+                    resource_manager.clone(),
+                    &import_cache,
+                    async {
+                        load(path.clone(), io, resource_manager, import_options)
+                            .await
+                            .map_err(LoadError::new)
+                    },
+                )
+                .await?;
+
+                return Ok(loader::LoaderPayload::new(model));
+            }
+
             let model = load(path, io, resource_manager, import_options)
                 .await
                 .map_err(LoadError::new)?;
@@ -320,7 +367,7 @@ async fn load(
     let mut scene = Scene::new();
     let context = ImportContext {
         io,
-        resource_manager,
+        resource_manager: resource_manager.clone(),
         model_path: path.clone(),
         search_options: options.material_search_options,
     };
@@ -332,9 +379,44 @@ async fn load(
     scene.graph[root].set_name(root_name.clone());
     import_from_path(&mut scene.graph, &context).await?;
     node_names::resolve_name_conflicts(context.model_path.as_path(), &mut scene.graph);
+    if let Some(mapping_path) = options.skeleton_mapping.as_ref() {
+        apply_skeleton_mapping(&mut scene.graph, mapping_path, &resource_manager, &path).await;
+    }
+    if let Some(compression) = options.animation_compression.as_ref() {
+        compress_animations(&scene.graph, compression, &path);
+    }
     Ok(Model::new(NodeMapping::UseNames, scene))
 }
 
+/// Collects the data that affects the output of the importer: the content of the file and of its
+/// external buffers. External images are loaded as separate resources, so they're not included.
+async fn import_cache_source(
+    path: &Path,
+    io: &Arc<dyn ResourceIo>,
+    resource_manager: &ResourceManager,
+    options: &ModelImportOptions,
+) -> Result<Vec<u8>> {
+    let context = ImportContext {
+        io: io.clone(),
+        resource_manager: resource_manager.clone(),
+        model_path: path.to_path_buf(),
+        search_options: options.material_search_options.clone(),
+    };
+    let mut source = io.load_file(path).await?;
+    let gltf = Gltf::from_slice(&source)?;
+    for buf in gltf.document.buffers() {
+        if let gltf::buffer::Source::Uri(uri) = buf.source() {
+            if uri::parse_uri(uri).scheme == uri::Scheme::None {
+                let data = load_external_bin(uri, &context).await?;
+                source.extend_from_slice(&(data.len() as u64).to_le_bytes());
+                source.extend_from_slice(&data);
+            }
+        }
+    }
+    append_skeleton_mapping_source(&mut source, options, &**io).await;
+    Ok(source)
+}
+
 async fn import_from_path(graph: &mut Graph, context: &ImportContext) -> Result<()> {
     let file: Vec<u8> = context.io.load_file(context.model_path.as_path()).await?;
     import_from_slice(file.as_slice(), graph, context).await
@@ -22,6 +22,7 @@
 
 use crate::{
     asset::{
+        cache::{ImportCache, ImportCacheKey},
         io::ResourceIo,
         loader::{
             BoxedImportOptionsLoaderFuture, BoxedLoaderFuture, LoaderPayload, ResourceLoader,
@@ -29,12 +30,18 @@ use crate::{
         manager::ResourceManager,
         options::{try_get_import_settings, try_get_import_settings_opaque, BaseImportOptions},
     },
-    core::{uuid::Uuid, TypeUuidProvider},
+    core::{log::Log, uuid::Uuid, visitor::Visitor, TypeUuidProvider},
     engine::SerializationContext,
-    resource::model::{Model, ModelImportOptions},
+    graph::NodeMapping,
+    resource::model::{append_skeleton_mapping_source, Model, ModelImportOptions, ModelLoadError},
+    scene::SceneLoader,
 };
 use fyrox_resource::state::LoadError;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 /// Default implementation for model loading.
 pub struct ModelLoader {
@@ -45,6 +52,124 @@ pub struct ModelLoader {
     pub serialization_context: Arc<SerializationContext>,
     /// Default import options for model resources.
     pub default_import_options: ModelImportOptions,
+    /// Cache for imported FBX models. Only the content of the FBX file, its import options and
+    /// the content of the skeleton mapping file (if any) are used as the key, external resources
+    /// (such as textures) are stored as references and resolved on every load. See
+    /// [`ImportCache`] docs for more info.
+    pub import_cache: ImportCache,
+}
+
+impl ModelLoader {
+    /// Version of the FBX import pipeline. Must be increased every time when the loader starts
+    /// to produce different output for the same input, to invalidate stale import cache entries.
+    pub const IMPORT_CACHE_VERSION: u32 = 1;
+}
+
+async fn load_cached_fbx(
+    path: PathBuf,
+    io: &dyn ResourceIo,
+    serialization_context: Arc<SerializationContext>,
+    resource_manager: ResourceManager,
+    import_options: ModelImportOptions,
+    import_cache: ImportCache,
+) -> Result<Model, LoadError> {
+    let mut data = io
+        .load_file(&path)
+        .await
+        .map_err(|e| LoadError::new(ModelLoadError::Visit(e.into())))?;
+    append_skeleton_mapping_source(&mut data, &import_options, io).await;
+    let key = ImportCacheKey::new(
+        "fbx",
+        ModelLoader::IMPORT_CACHE_VERSION,
+        &data,
+        &import_options,
+    );
+
+    load_cached_model(
+        &path,
+        key.as_ref(),
+        serialization_context.clone(),
+        resource_manager.clone(),
+        &import_cache,
+        async {
+            Model::load(
+                &path,
+                io,
+                serialization_context,
+                resource_manager,
+                import_options,
+            )
+            .await
+            .map_err(LoadError::new)
+        },
+    )
+    .await
+}
+
+/// Tries to load a model from the import cache using the given key. If there's no valid cache
+/// entry, the model is imported using the given future and the result is written to the cache.
+/// Only the scene of the model is stored, external resources (such as textures) are stored as
+/// references and resolved on every load. The cache is not used at all if there's no key (see
+/// [`ImportCacheKey::new`]).
+pub(crate) async fn load_cached_model<F>(
+    path: &Path,
+    key: Option<&ImportCacheKey>,
+    serialization_context: Arc<SerializationContext>,
+    resource_manager: ResourceManager,
+    import_cache: &ImportCache,
+    import: F,
+) -> Result<Model, LoadError>
+where
+    F: Future<Output = Result<Model, LoadError>>,
+{
+    let Some(key) = key else {
+        return import.await;
+    };
+
+    if let Some(cached) = import_cache.load(key) {
+        let loader = Visitor::load_binary_from_memory(&cached).and_then(|mut visitor| {
+            SceneLoader::load(
+                "Scene",
+                serialization_context.clone(),
+                resource_manager.clone(),
+                &mut visitor,
+                Some(path.to_path_buf()),
+            )
+        });
+        match loader {
+            Ok(loader) => {
+                return Ok(Model::new(NodeMapping::UseNames, loader.finish().await));
+            }
+            Err(err) => Log::warn(format!(
+                "Unable to load {} from import cache, it will be re-imported. Reason: {err:?}",
+                path.display()
+            )),
+        }
+    }
+
+    let mut model = import.await?;
+
+    let mut visitor = Visitor::new();
+    match model
+        .get_scene_mut()
+        .save("Scene", &mut visitor)
+        .and_then(|_| visitor.save_binary_to_vec())
+    {
+        Ok(cached) => {
+            if let Err(err) = import_cache.store(key, &cached) {
+                Log::warn(format!(
+                    "Unable to write import cache entry for {}. Reason: {err}",
+                    path.display()
+                ));
+            }
+        }
+        Err(err) => Log::warn(format!(
+            "Unable to serialize {} for import cache. Reason: {err:?}",
+            path.display()
+        )),
+    }
+
+    Ok(model)
 }
 
 impl ResourceLoader for ModelLoader {
@@ -60,6 +185,7 @@ impl ResourceLoader for ModelLoader {
         let resource_manager = self.resource_manager.clone();
         let node_constructors = self.serialization_context.clone();
         let default_import_options = self.default_import_options.clone();
+        let import_cache = self.import_cache.clone();
 
         Box::pin(async move {
             let io = io.as_ref();
@@ -68,6 +194,23 @@ impl ResourceLoader for ModelLoader {
                 .await
                 .unwrap_or(default_import_options);
 
+            let is_fbx = path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("fbx"));
+            if is_fbx && import_cache.is_enabled() {
+                let model = load_cached_fbx(
+                    path,
+                    io,
+                    node_constructors,
+                    resource_manager,
+                    import_options,
+                    import_cache,
+                )
+                .await?;
+
+                return Ok(LoaderPayload::new(model));
+            }
+
             let model = Model::load(
                 path,
                 io,
@@ -40,7 +40,10 @@
 
 use crate::{
     asset::{
-        io::ResourceIo, manager::ResourceManager, options::ImportOptions, untyped::ResourceKind,
+        io::ResourceIo,
+        manager::ResourceManager,
+        options::ImportOptions,
+        untyped::{ResourceKind, UntypedResource},
         Resource, ResourceData, MODEL_RESOURCE_UUID,
     },
     core::{
@@ -55,15 +58,29 @@ use crate::{
         NameProvider, TypeUuidProvider,
     },
     engine::SerializationContext,
-    generic_animation::AnimationContainer,
-    graph::{BaseSceneGraph, NodeHandleMap, NodeMapping, PrefabData, SceneGraph, SceneGraphNode},
+    generic_animation::{
+        compression::{AnimationCompressionOptions, AnimationCompressionReport},
+        AnimationContainer,
+    },
+    graph::{
+        BaseSceneGraph, NodeHandleMap, NodeMapping, PrefabData, PrefabDataMut, SceneGraph,
+        SceneGraphNode,
+    },
     resource::fbx::{self, error::FbxError},
     scene::{
-        animation::Animation, base::SceneNodeId, graph::Graph, node::Node, transform::Transform,
+        animation::{
+            retarget::{Skeleton, SkeletonMapping},
+            Animation,
+        },
+        base::SceneNodeId,
+        graph::Graph,
+        mesh::Mesh,
+        node::Node,
+        transform::Transform,
         Scene, SceneLoader,
     },
 };
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use fyrox_core::algebra::Point3;
 use fyrox_core::math;
 use fyrox_ui::{UiNode, UserInterface};
@@ -85,6 +102,9 @@ pub struct Model {
     pub(crate) mapping: NodeMapping,
     #[visit(skip)]
     pub(crate) scene: Scene,
+    #[visit(skip)]
+    #[reflect(read_only)]
+    pub(crate) variant_base: Option<ModelResource>,
 }
 
 impl PrefabData for Model {
@@ -101,6 +121,13 @@ impl PrefabData for Model {
     }
 }
 
+impl PrefabDataMut for Model {
+    #[inline]
+    fn graph_mut(&mut self) -> &mut Self::Graph {
+        &mut self.scene.graph
+    }
+}
+
 impl TypeUuidProvider for Model {
     fn type_uuid() -> Uuid {
         MODEL_RESOURCE_UUID
@@ -438,6 +465,27 @@ pub trait ModelResourceExtension: Sized {
     /// Panics if there's no animation player in the given hierarchy (descendant nodes of `root`).
     fn retarget_animations(&self, root: Handle<Node>, graph: &mut Graph) -> Vec<Handle<Animation>>;
 
+    /// Same as [`Self::retarget_animations_directly`], but uses the given skeleton mapping to transfer
+    /// animations to a hierarchy with different proportions or rest pose. The rest pose of the source
+    /// skeleton is taken from the model resource, the rest pose of the target skeleton is taken using
+    /// [`Skeleton::from_instance`]. See [`SkeletonMapping`] docs for more info.
+    fn retarget_animations_with_mapping_directly(
+        &self,
+        root: Handle<Node>,
+        graph: &Graph,
+        mapping: &SkeletonMapping,
+    ) -> Vec<Animation>;
+
+    /// Same as [`Self::retarget_animations_with_mapping_directly`], but automatically adds retargetted
+    /// animations to a first animation player in the hierarchy of given `root`. Returns an empty vector
+    /// if there's no animation player in the hierarchy.
+    fn retarget_animations_with_mapping(
+        &self,
+        root: Handle<Node>,
+        graph: &mut Graph,
+        mapping: &SkeletonMapping,
+    ) -> Vec<Handle<Animation>>;
+
     /// Generates a set of unique IDs for every node in the model. Use this method in pair with
     /// [`ModelResource::begin_instantiation`].
     fn generate_ids(&self) -> FxHashMap<Handle<Node>, SceneNodeId>;
@@ -586,6 +634,66 @@ impl ModelResourceExtension for ModelResource {
         }
     }
 
+    fn retarget_animations_with_mapping_directly(
+        &self,
+        root: Handle<Node>,
+        graph: &Graph,
+        mapping: &SkeletonMapping,
+    ) -> Vec<Animation> {
+        let (animations, source) = {
+            let mut header = self.state();
+            let Some(model) = header.data() else {
+                return Default::default();
+            };
+            let model_graph = &model.scene.graph;
+            let animations = model_graph
+                .linear_iter()
+                .filter_map(|node| {
+                    node.component_ref::<InheritableVariable<AnimationContainer<Handle<Node>>>>()
+                })
+                .flat_map(|animations| animations.iter().cloned())
+                .collect::<Vec<_>>();
+            (
+                animations,
+                Skeleton::from_graph(model_graph, model_graph.get_root()),
+            )
+        };
+
+        // The resource must be unlocked at this point, because the target could be its instance.
+        let target = Skeleton::from_instance(graph, root);
+
+        animations
+            .iter()
+            .map(|animation| mapping.retarget(animation, &source, &target))
+            .collect()
+    }
+
+    fn retarget_animations_with_mapping(
+        &self,
+        root: Handle<Node>,
+        graph: &mut Graph,
+        mapping: &SkeletonMapping,
+    ) -> Vec<Handle<Animation>> {
+        let Some((animation_player, _)) = graph.find(root, &mut |n| {
+            n.component_ref::<InheritableVariable<AnimationContainer<Handle<Node>>>>()
+                .is_some()
+        }) else {
+            return Default::default();
+        };
+
+        let animations = self.retarget_animations_with_mapping_directly(root, graph, mapping);
+
+        let dest_animations = graph
+            .node_mut(animation_player)
+            .component_mut::<InheritableVariable<AnimationContainer<Handle<Node>>>>()
+            .unwrap();
+
+        animations
+            .into_iter()
+            .map(|animation| dest_animations.add(animation))
+            .collect()
+    }
+
     fn generate_ids(&self) -> FxHashMap<Handle<Node>, SceneNodeId> {
         let data = self.data_ref();
         data.scene
@@ -604,6 +712,7 @@ impl ResourceData for Model {
     fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
         let mut visitor = Visitor::new();
         self.scene.save("Scene", &mut visitor)?;
+        Self::visit_variant_base(&mut self.variant_base, &mut visitor)?;
         visitor.save_binary(path)?;
         Ok(())
     }
@@ -611,6 +720,33 @@ impl ResourceData for Model {
     fn can_be_saved(&self) -> bool {
         true
     }
+
+    fn memory_usage(&self) -> usize {
+        // External resources are registered in the resource manager and counted separately, so
+        // only embedded ones are counted here. Shared resources are counted once.
+        let mut counted = FxHashSet::default();
+        let mut usage = 0;
+        let mut count = |resource: UntypedResource| {
+            if resource.is_embedded() && counted.insert(resource.key()) {
+                usage += resource.memory_usage();
+            }
+        };
+        for node in self.scene.graph.linear_iter() {
+            if let Some(mesh) = node.cast::<Mesh>() {
+                for surface in mesh.surfaces() {
+                    count(surface.data_ref().clone().into_untyped());
+                }
+            }
+            if let Some(animations) =
+                node.component_ref::<InheritableVariable<AnimationContainer<Handle<Node>>>>()
+            {
+                for animation in animations.iter() {
+                    count(animation.tracks_data().clone().into_untyped());
+                }
+            }
+        }
+        usage
+    }
 }
 
 impl Default for Model {
@@ -618,6 +754,7 @@ impl Default for Model {
         Self {
             mapping: NodeMapping::UseNames,
             scene: Scene::new(),
+            variant_base: None,
         }
     }
 }
@@ -717,6 +854,98 @@ pub struct ModelImportOptions {
     /// See [`MaterialSearchOptions`] docs for more info.
     #[serde(default)]
     pub material_search_options: MaterialSearchOptions,
+
+    /// Compression options for the animations of the model. `None` means that the animations will be
+    /// imported as is. See [`AnimationCompressionOptions`] docs for more info.
+    #[serde(default)]
+    pub animation_compression: Option<AnimationCompressionOptions>,
+
+    /// Path to a [`SkeletonMapping`] resource, that will be used to rename the bones of the model to the
+    /// target names of the mapping (see [`SkeletonMapping::rename_bones`]). It is useful for animation-only
+    /// models, that use different naming of the bones than the characters they're made for. `None` means
+    /// that the bones will be imported as is.
+    ///
+    /// ## Important notes
+    ///
+    /// The names of the bones are changed at import, so the same mapping must not be used to retarget
+    /// animations from such model at runtime.
+    #[serde(default)]
+    pub skeleton_mapping: Option<PathBuf>,
+}
+
+/// Compresses every animation in the given graph and writes a report about saved memory to the log.
+pub(crate) fn compress_animations(
+    graph: &Graph,
+    options: &AnimationCompressionOptions,
+    path: &Path,
+) {
+    let mut report = AnimationCompressionReport::default();
+    for node in graph.linear_iter() {
+        if let Some(animations) =
+            node.component_ref::<InheritableVariable<AnimationContainer<Handle<Node>>>>()
+        {
+            for animation in animations.iter() {
+                let mut state = animation.tracks_data().state();
+                if let Some(tracks_data) = state.data() {
+                    report += tracks_data.compress(options);
+                }
+            }
+        }
+    }
+
+    if report.keys_before > 0 {
+        Log::info(format!(
+            "Animations of {} were compressed: {report}",
+            path.display()
+        ));
+    }
+}
+
+/// Renames the bones of the model in the given graph using the skeleton mapping at the given path and writes
+/// a report to the log.
+pub(crate) async fn apply_skeleton_mapping(
+    graph: &mut Graph,
+    mapping_path: &Path,
+    resource_manager: &ResourceManager,
+    path: &Path,
+) {
+    let mapping = match resource_manager
+        .request::<SkeletonMapping>(mapping_path)
+        .await
+    {
+        Ok(mapping) => mapping,
+        Err(error) => {
+            Log::err(format!(
+                "Unable to apply skeleton mapping {} to {}. Reason: {error:?}",
+                mapping_path.display(),
+                path.display()
+            ));
+            return;
+        }
+    };
+
+    let root = graph.get_root();
+    let count = mapping.data_ref().rename_bones(graph, root);
+    Log::info(format!(
+        "{count} bones of {} were renamed using skeleton mapping {}",
+        path.display(),
+        mapping_path.display()
+    ));
+}
+
+/// Appends the content of the skeleton mapping file (if any) to the given import cache source data, so
+/// changes in the mapping will invalidate the import cache.
+pub(crate) async fn append_skeleton_mapping_source(
+    data: &mut Vec<u8>,
+    options: &ModelImportOptions,
+    io: &dyn ResourceIo,
+) {
+    if let Some(mapping_path) = options.skeleton_mapping.as_ref() {
+        if let Ok(mapping) = io.load_file(mapping_path).await {
+            data.extend_from_slice(&(mapping.len() as u64).to_le_bytes());
+            data.extend_from_slice(&mapping);
+        }
+    }
 }
 
 impl ImportOptions for ModelImportOptions {}
@@ -763,7 +992,64 @@ impl Model {
     /// Creates a new Model instance using the given node mapping and the given scene. It could be
     /// used to create your own Model resources.
     pub fn new(mapping: NodeMapping, scene: Scene) -> Self {
-        Self { mapping, scene }
+        Self {
+            mapping,
+            scene,
+            variant_base: None,
+        }
+    }
+
+    /// Creates a new variant of the given prefab. A variant is a prefab, that consists of a single
+    /// instance of its base prefab. Every property of the instance that is not modified in the
+    /// variant will be inherited from the base prefab, so any change in the base prefab will be
+    /// reflected in the variant (and in every instance of the variant). Variants could be nested
+    /// - a variant could be created from another variant.
+    ///
+    /// The variant should be saved to a file (see [`ResourceData::save`]) and loaded as usual
+    /// prefab after that.
+    pub fn new_variant(base: &ModelResource) -> Self {
+        let mut scene = Scene::new();
+        base.instantiate(&mut scene);
+        Self {
+            mapping: NodeMapping::UseHandles,
+            scene,
+            variant_base: Some(base.clone()),
+        }
+    }
+
+    /// Returns the base prefab of the variant, or [`None`] if the prefab is not a variant. See
+    /// [`Self::new_variant`] for more info.
+    pub fn variant_base(&self) -> Option<ModelResource> {
+        self.variant_base.clone()
+    }
+
+    /// Reads or writes the base prefab of a variant (see [`Self::new_variant`]). Prefab files store
+    /// it next to the scene, so this method could be used to keep the link to the base prefab when
+    /// a variant is loaded or saved as a plain scene.
+    pub fn visit_variant_base(
+        variant_base: &mut Option<ModelResource>,
+        visitor: &mut Visitor,
+    ) -> VisitResult {
+        variant_base.visit("VariantBase", visitor)
+    }
+
+    /// Returns the chain of base prefabs of the variant, starting from the nearest one. The chain
+    /// is empty if the prefab is not a variant.
+    pub fn variant_chain(&self) -> Vec<ModelResource> {
+        let mut chain = Vec::new();
+        let mut base = self.variant_base();
+        while let Some(resource) = base {
+            base = if resource.is_ok() {
+                resource.data_ref().variant_base()
+            } else {
+                None
+            };
+            if chain.contains(&resource) {
+                break;
+            }
+            chain.push(resource);
+        }
+        chain
     }
 
     pub(crate) async fn load<P: AsRef<Path>>(
@@ -780,6 +1066,7 @@ impl Model {
             .to_string_lossy()
             .as_ref()
             .to_lowercase();
+        let mut variant_base = None;
         let (scene, mapping) = match extension.as_ref() {
             "fbx" => {
                 let mut scene = Scene::new();
@@ -789,31 +1076,50 @@ impl Model {
                 }
                 fbx::load_to_scene(
                     &mut scene,
-                    resource_manager,
+                    resource_manager.clone(),
                     io,
                     path.as_ref(),
                     &model_import_options,
                 )
                 .await?;
+                if let Some(mapping_path) = model_import_options.skeleton_mapping.as_ref() {
+                    apply_skeleton_mapping(
+                        &mut scene.graph,
+                        mapping_path,
+                        &resource_manager,
+                        path.as_ref(),
+                    )
+                    .await;
+                }
+                if let Some(options) = model_import_options.animation_compression.as_ref() {
+                    compress_animations(&scene.graph, options, path.as_ref());
+                }
                 // Set NodeMapping::UseNames as mapping here because FBX does not have
                 // any persistent unique ids, and we have to use names.
                 (scene, NodeMapping::UseNames)
             }
             // Scene can be used directly as model resource. Such scenes can be created in
             // Fyroxed.
-            "rgs" => (
-                SceneLoader::from_file(
-                    path.as_ref(),
-                    io,
-                    serialization_context,
-                    resource_manager.clone(),
-                )
-                .await?
-                .0
-                .finish()
-                .await,
-                NodeMapping::UseHandles,
-            ),
+            "rgs" => {
+                let data = io
+                    .load_file(path.as_ref())
+                    .await
+                    .map_err(VisitError::from)?;
+                let loader = {
+                    let mut visitor = Visitor::load_from_memory(&data)?;
+                    let loader = SceneLoader::load(
+                        "Scene",
+                        serialization_context,
+                        resource_manager.clone(),
+                        &mut visitor,
+                        Some(path.as_ref().to_path_buf()),
+                    )?;
+                    // Plain prefabs do not have the base.
+                    let _ = Self::visit_variant_base(&mut variant_base, &mut visitor);
+                    loader
+                };
+                (loader.finish().await, NodeMapping::UseHandles)
+            }
             // TODO: Add more formats.
             _ => {
                 return Err(ModelLoadError::NotSupported(format!(
@@ -822,7 +1128,11 @@ impl Model {
             }
         };
 
-        Ok(Self { scene, mapping })
+        Ok(Self {
+            scene,
+            mapping,
+            variant_base,
+        })
     }
 
     /// Returns shared reference to internal scene, there is no way to obtain
@@ -842,3 +1152,41 @@ impl Model {
         &mut self.scene
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::{untyped::ResourceKind, ResourceData},
+        core::algebra::Matrix4,
+        graph::NodeMapping,
+        resource::model::Model,
+        scene::{
+            base::BaseBuilder,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceResource},
+                MeshBuilder,
+            },
+            Scene,
+        },
+    };
+
+    #[test]
+    fn test_model_memory_usage() {
+        let data = SurfaceResource::new_ok(
+            ResourceKind::Embedded,
+            SurfaceData::make_cube(Matrix4::identity()),
+        );
+        let surface_usage = data.data_ref().memory_usage();
+        assert!(surface_usage > 0);
+
+        let mut scene = Scene::new();
+        // Shared surface data must be counted once.
+        for _ in 0..2 {
+            MeshBuilder::new(BaseBuilder::new())
+                .with_surfaces(vec![SurfaceBuilder::new(data.clone()).build()])
+                .build(&mut scene.graph);
+        }
+        let model = Model::new(NodeMapping::UseNames, scene);
+        assert_eq!(model.memory_usage(), surface_usage);
+    }
+}
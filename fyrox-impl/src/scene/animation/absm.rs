@@ -33,7 +33,7 @@ use crate::{
         visitor::prelude::*,
     },
     scene::{
-        animation::prelude::*,
+        animation::{ik::IkChain, prelude::*},
         base::{Base, BaseBuilder},
         graph::Graph,
         node::{Node, NodeTrait, UpdateContext},
@@ -78,6 +78,18 @@ pub type MachineLayer = crate::generic_animation::machine::MachineLayer<Handle<N
 pub type Transition = crate::generic_animation::machine::transition::Transition<Handle<Node>>;
 /// Scene specific animation blending state machine state.
 pub type State = crate::generic_animation::machine::state::State<Handle<Node>>;
+
+/// A script message with an event produced by a layer of an animation blending state machine (state enter/leave,
+/// transition start/finish, etc.). The engine sends such messages to the scripts of an
+/// [`AnimationBlendingStateMachine`] node, if the node is subscribed to this message type. Events of subscribed
+/// machines are taken from the layers, so they can't be fetched using [`MachineLayer::pop_event`] anymore.
+#[derive(Clone, Debug)]
+pub struct AbsmEventMessage {
+    /// An index of the layer that produced the event.
+    pub layer: usize,
+    /// Actual state machine event.
+    pub event: Event,
+}
 /// Scene specific animation blending state machine base pose node.
 pub type BasePoseNode = crate::generic_animation::machine::node::BasePoseNode<Handle<Node>>;
 /// Scene specific animation blending state machine state action.
@@ -217,6 +229,13 @@ impl LayerMaskExt for LayerMask {
 ///         .build(graph)
 /// }
 /// ```
+///
+/// # Inverse kinematics
+///
+/// The node can also adjust the pose produced by the state machine using a set of IK chains, see
+/// [`IkChain`] docs for more info. The chains are evaluated in the order they are listed, right after
+/// the pose was applied to the scene nodes. Weights of the chains could be driven by the weight
+/// parameters of the state machine.
 #[derive(Visit, Reflect, Clone, Debug, Default, ComponentProvider)]
 pub struct AnimationBlendingStateMachine {
     base: Base,
@@ -224,6 +243,8 @@ pub struct AnimationBlendingStateMachine {
     machine: InheritableVariable<Machine>,
     #[component(include)]
     animation_player: InheritableVariable<Handle<Node>>,
+    #[visit(optional)] // Backward compatibility
+    ik_chains: InheritableVariable<Vec<IkChain>>,
 }
 
 impl AnimationBlendingStateMachine {
@@ -253,6 +274,21 @@ impl AnimationBlendingStateMachine {
     pub fn animation_player(&self) -> Handle<Node> {
         *self.animation_player
     }
+
+    /// Sets new IK chains of the node. See [`IkChain`] docs for more info.
+    pub fn set_ik_chains(&mut self, ik_chains: Vec<IkChain>) {
+        self.ik_chains.set_value_and_mark_modified(ik_chains);
+    }
+
+    /// Returns a reference to the IK chains of the node.
+    pub fn ik_chains(&self) -> &[IkChain] {
+        &self.ik_chains
+    }
+
+    /// Returns a mutable reference to the IK chains of the node.
+    pub fn ik_chains_mut(&mut self) -> &mut Vec<IkChain> {
+        self.ik_chains.get_value_mut_and_mark_modified()
+    }
 }
 
 impl TypeUuidProvider for AnimationBlendingStateMachine {
@@ -326,6 +362,14 @@ impl NodeTrait for AnimationBlendingStateMachine {
             );
 
             pose.apply_internal(context.nodes);
+
+            for chain in self.ik_chains.iter() {
+                let weight = chain
+                    .weight
+                    .value(self.machine.parameters())
+                    .unwrap_or_default();
+                chain.solve(context.nodes, weight);
+            }
         }
     }
 
@@ -352,6 +396,7 @@ pub struct AnimationBlendingStateMachineBuilder {
     base_builder: BaseBuilder,
     machine: Machine,
     animation_player: Handle<Node>,
+    ik_chains: Vec<IkChain>,
 }
 
 impl AnimationBlendingStateMachineBuilder {
@@ -361,6 +406,7 @@ impl AnimationBlendingStateMachineBuilder {
             base_builder,
             machine: Default::default(),
             animation_player: Default::default(),
+            ik_chains: Default::default(),
         }
     }
 
@@ -376,12 +422,19 @@ impl AnimationBlendingStateMachineBuilder {
         self
     }
 
+    /// Sets the desired IK chains. See [`IkChain`] docs for more info.
+    pub fn with_ik_chains(mut self, ik_chains: Vec<IkChain>) -> Self {
+        self.ik_chains = ik_chains;
+        self
+    }
+
     /// Creates new node.
     pub fn build_node(self) -> Node {
         Node::new(AnimationBlendingStateMachine {
             base: self.base_builder.build_base(),
             machine: self.machine.into(),
             animation_player: self.animation_player.into(),
+            ik_chains: self.ik_chains.into(),
         })
     }
 
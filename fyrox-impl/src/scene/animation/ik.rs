@@ -0,0 +1,663 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Inverse kinematics (IK) allows you to procedurally adjust an animated pose of a skeleton, so that the end
+//! of a bone chain reaches a desired target. See [`IkChain`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector3},
+        math::Matrix4Ext,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    generic_animation::machine::parameter::PoseWeight,
+    scene::{graph::NodePool, node::Node},
+};
+use strum_macros::{AsRefStr, EnumString, VariantNames};
+
+/// A bone of an IK chain.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect, TypeUuidProvider)]
+#[type_uuid(id = "4d4f360d-a89b-4454-b7ec-fb057a5a9709")]
+pub struct IkBone {
+    /// A handle of the bone node.
+    pub node: Handle<Node>,
+
+    /// Maximum angle (in radians) the bone can be rotated by the solver away from its animated
+    /// rotation. `None` means that the rotation of the bone is unconstrained.
+    pub max_angle: Option<f32>,
+}
+
+impl IkBone {
+    /// Creates a new unconstrained bone.
+    pub fn new(node: Handle<Node>) -> Self {
+        Self {
+            node,
+            max_angle: None,
+        }
+    }
+
+    /// Creates a new bone, that can't be rotated away from its animated rotation by more than the
+    /// given angle (in radians).
+    pub fn limited(node: Handle<Node>, max_angle: f32) -> Self {
+        Self {
+            node,
+            max_angle: Some(max_angle),
+        }
+    }
+}
+
+/// An algorithm, that is used to solve an IK chain.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Visit,
+    Reflect,
+    TypeUuidProvider,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+)]
+#[type_uuid(id = "c757de36-5200-4111-86b7-b1f68cc4c776")]
+pub enum IkSolver {
+    /// Analytic solver for chains of exactly three bones (for example - upper arm, forearm and hand). It
+    /// always finds an exact solution (if the target is reachable) and it is the best choice for limbs.
+    #[default]
+    TwoBone,
+
+    /// Forward And Backward Reaching Inverse Kinematics - an iterative solver for chains of arbitrary
+    /// length. It produces natural looking results for long chains, such as tails or tentacles.
+    Fabrik {
+        /// Maximum amount of iterations.
+        iterations: u32,
+        /// Maximum distance between the end of the chain and the target at which the chain is
+        /// considered solved.
+        tolerance: f32,
+    },
+
+    /// Cyclic Coordinate Descent - an iterative solver for chains of arbitrary length. It tends to
+    /// bend the bones that are close to the end of the chain more, than the ones at the beginning.
+    Ccd {
+        /// Maximum amount of iterations.
+        iterations: u32,
+        /// Maximum distance between the end of the chain and the target at which the chain is
+        /// considered solved.
+        tolerance: f32,
+    },
+
+    /// Rotates the bones of the chain, so that the given local axis of the last bone points at the
+    /// target. The rotation is distributed evenly across the bones, which makes it suitable for head
+    /// tracking (neck + head) or weapon aiming (spine bones).
+    LookAt {
+        /// Local axis of the last bone, that should point at the target.
+        axis: Vector3<f32>,
+    },
+}
+
+impl IkSolver {
+    /// Creates a FABRIK solver with default settings.
+    pub fn fabrik() -> Self {
+        Self::Fabrik {
+            iterations: 10,
+            tolerance: 0.001,
+        }
+    }
+
+    /// Creates a CCD solver with default settings.
+    pub fn ccd() -> Self {
+        Self::Ccd {
+            iterations: 10,
+            tolerance: 0.001,
+        }
+    }
+}
+
+/// IK chain is a set of bones, that are adjusted by a solver in a way, that the end of the chain
+/// reaches the target. IK chains are evaluated by
+/// [`super::absm::AnimationBlendingStateMachine`] right after the pose produced by the state machine
+/// was applied to the bones, which makes it possible to fix up the animation procedurally - place
+/// feet on uneven terrain, put hands on a weapon grip, make a character look at something, etc.
+///
+/// The influence of a chain is defined by its weight, which could be either a constant or a name of
+/// a weight parameter of the state machine. This way the IK can be smoothly turned on and off, for
+/// example, it could be disabled while a character is in the air.
+///
+/// # Bones
+///
+/// Bones must be listed from the root of the chain to its end, every next bone must be a descendant
+/// of the previous one. Rotation of each bone could be limited, see [`IkBone`] docs for more info.
+///
+/// # Pole target
+///
+/// Pole target is an optional node, that defines the direction in which the chain should bend. For
+/// example, a pole target in front of a knee prevents a leg from bending backwards. It is used by all
+/// solvers except [`IkSolver::LookAt`].
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::pool::Handle,
+/// #     scene::{animation::ik::{IkBone, IkChain, IkSolver}, node::Node},
+/// #     generic_animation::machine::parameter::PoseWeight,
+/// # };
+/// fn make_leg_ik(
+///     thigh: Handle<Node>,
+///     shin: Handle<Node>,
+///     foot: Handle<Node>,
+///     foot_target: Handle<Node>,
+///     knee_pole: Handle<Node>,
+/// ) -> IkChain {
+///     IkChain {
+///         name: "LeftLeg".to_string(),
+///         bones: vec![IkBone::new(thigh), IkBone::new(shin), IkBone::new(foot)],
+///         target: foot_target,
+///         pole: knee_pole,
+///         weight: PoseWeight::Parameter("LeftLegIk".to_string()),
+///         solver: IkSolver::TwoBone,
+///         ..Default::default()
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, TypeUuidProvider)]
+#[type_uuid(id = "a54b1285-1a53-4965-b176-df3dc964e547")]
+pub struct IkChain {
+    /// Name of the chain.
+    pub name: String,
+
+    /// Whether the chain is enabled or not.
+    pub enabled: bool,
+
+    /// Bones of the chain, listed from the root to the end.
+    pub bones: Vec<IkBone>,
+
+    /// A node, which position is the target for the end of the chain.
+    pub target: Handle<Node>,
+
+    /// An optional node, that defines the direction in which the chain should bend.
+    pub pole: Handle<Node>,
+
+    /// Influence of the chain, `0.0` - the animated pose is left as is, `1.0` - the chain is fully
+    /// solved.
+    pub weight: PoseWeight,
+
+    /// An algorithm, that is used to solve the chain.
+    pub solver: IkSolver,
+}
+
+impl Default for IkChain {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            enabled: true,
+            bones: Default::default(),
+            target: Default::default(),
+            pole: Default::default(),
+            weight: PoseWeight::Constant(1.0),
+            solver: Default::default(),
+        }
+    }
+}
+
+const EPSILON: f32 = 1.0e-6;
+
+fn global_transform(nodes: &NodePool, mut handle: Handle<Node>) -> Matrix4<f32> {
+    // Global transforms of the nodes are outdated at this point, because the animation has just
+    // changed local transforms of the bones.
+    let mut transform = Matrix4::identity();
+    while let Some(node) = nodes.try_borrow(handle) {
+        transform = node.local_transform().matrix() * transform;
+        handle = node.parent();
+    }
+    transform
+}
+
+fn global_position(nodes: &NodePool, handle: Handle<Node>) -> Vector3<f32> {
+    global_transform(nodes, handle).position()
+}
+
+fn rotation_of(transform: &Matrix4<f32>) -> UnitQuaternion<f32> {
+    UnitQuaternion::from_matrix(&transform.basis())
+}
+
+// Scales the angle of the rotation by the given factor.
+fn scale_rotation(rotation: UnitQuaternion<f32>, factor: f32) -> UnitQuaternion<f32> {
+    UnitQuaternion::from_scaled_axis(rotation.scaled_axis() * factor)
+}
+
+// Rotates the bone by the given world-space rotation, respecting its angle limit.
+fn rotate_bone(
+    nodes: &mut NodePool,
+    bone: &IkBone,
+    delta: UnitQuaternion<f32>,
+    animated: UnitQuaternion<f32>,
+) {
+    let Some(node) = nodes.try_borrow(bone.node) else {
+        return;
+    };
+
+    let transform = node.local_transform();
+    let frame = rotation_of(&global_transform(nodes, node.parent())) * **transform.pre_rotation();
+    let mut rotation = frame.inverse() * delta * frame * **transform.rotation();
+
+    if let Some(max_angle) = bone.max_angle {
+        let deviation = animated.rotation_to(&rotation);
+        let angle = deviation.angle();
+        if angle > max_angle {
+            rotation = animated * scale_rotation(deviation, max_angle.max(0.0) / angle);
+        }
+    }
+
+    nodes[bone.node]
+        .local_transform_mut()
+        .set_rotation(rotation);
+}
+
+fn solve_two_bone(
+    positions: &mut [Vector3<f32>],
+    target: Vector3<f32>,
+    pole: Option<Vector3<f32>>,
+) {
+    let [a, b, c] = [positions[0], positions[1], positions[2]];
+    let upper = (b - a).norm();
+    let lower = (c - b).norm();
+
+    let to_target = target - a;
+    let Some(direction) = to_target.try_normalize(EPSILON) else {
+        return;
+    };
+    let distance = to_target
+        .norm()
+        .min(upper + lower - EPSILON)
+        .max((upper - lower).abs() + EPSILON);
+
+    // Bend the chain in the plane, that contains the target and the pole (or the current middle joint).
+    let hint = pole.unwrap_or(b) - a;
+    let bend = (hint - direction.scale(hint.dot(&direction)))
+        .try_normalize(EPSILON)
+        .unwrap_or_else(|| {
+            let axis = if direction.x.abs() < 0.9 {
+                Vector3::x()
+            } else {
+                Vector3::y()
+            };
+            direction.cross(&axis).normalize()
+        });
+
+    let cos = ((upper * upper + distance * distance - lower * lower) / (2.0 * upper * distance))
+        .clamp(-1.0, 1.0);
+    let sin = (1.0 - cos * cos).sqrt();
+
+    positions[1] = a + direction.scale(upper * cos) + bend.scale(upper * sin);
+    positions[2] = a + direction.scale(distance);
+}
+
+fn solve_fabrik(
+    positions: &mut [Vector3<f32>],
+    target: Vector3<f32>,
+    iterations: u32,
+    tolerance: f32,
+) {
+    let count = positions.len();
+    let lengths = positions
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).norm())
+        .collect::<Vec<_>>();
+    let root = positions[0];
+
+    let reach = |positions: &mut [Vector3<f32>], i: usize, j: usize, length: f32| {
+        if let Some(direction) = (positions[j] - positions[i]).try_normalize(EPSILON) {
+            positions[j] = positions[i] + direction.scale(length);
+        }
+    };
+
+    if (target - root).norm() >= lengths.iter().sum::<f32>() {
+        // The target is unreachable, stretch the chain towards it.
+        for i in 0..count - 1 {
+            if let Some(direction) = (target - positions[i]).try_normalize(EPSILON) {
+                positions[i + 1] = positions[i] + direction.scale(lengths[i]);
+            }
+        }
+        return;
+    }
+
+    for _ in 0..iterations {
+        if (positions[count - 1] - target).norm() <= tolerance {
+            break;
+        }
+
+        positions[count - 1] = target;
+        for i in (0..count - 1).rev() {
+            reach(positions, i + 1, i, lengths[i]);
+        }
+
+        positions[0] = root;
+        for (i, length) in lengths.iter().enumerate() {
+            reach(positions, i, i + 1, *length);
+        }
+    }
+}
+
+fn solve_ccd(
+    positions: &mut [Vector3<f32>],
+    target: Vector3<f32>,
+    iterations: u32,
+    tolerance: f32,
+) {
+    let end = positions.len() - 1;
+    for _ in 0..iterations {
+        if (positions[end] - target).norm() <= tolerance {
+            break;
+        }
+
+        for i in (0..end).rev() {
+            let pivot = positions[i];
+            let Some(rotation) =
+                UnitQuaternion::rotation_between(&(positions[end] - pivot), &(target - pivot))
+            else {
+                continue;
+            };
+            for position in positions[i + 1..].iter_mut() {
+                *position = pivot + rotation * (*position - pivot);
+            }
+        }
+    }
+}
+
+// Rotates every inner joint around the line between its neighbours, so it faces the pole target.
+fn apply_pole(positions: &mut [Vector3<f32>], pole: Vector3<f32>) {
+    for i in 1..positions.len() - 1 {
+        let origin = positions[i - 1];
+        let Some(axis) = (positions[i + 1] - origin).try_normalize(EPSILON) else {
+            continue;
+        };
+        let project = |point: Vector3<f32>| {
+            let offset = point - origin;
+            offset - axis.scale(offset.dot(&axis))
+        };
+        if let Some(rotation) =
+            UnitQuaternion::rotation_between(&project(positions[i]), &project(pole))
+        {
+            positions[i] = origin + rotation * (positions[i] - origin);
+        }
+    }
+}
+
+impl IkChain {
+    /// Solves the chain and modifies local rotations of its bones. `weight` defines the influence of
+    /// the solver, it is clamped to `[0.0; 1.0]` range. Local transforms of the bones must be up to
+    /// date, global transforms are not used.
+    pub fn solve(&self, nodes: &mut NodePool, weight: f32) {
+        let weight = weight.clamp(0.0, 1.0);
+        if !self.enabled
+            || weight <= 0.0
+            || self.bones.is_empty()
+            || !nodes.is_valid_handle(self.target)
+            || self
+                .bones
+                .iter()
+                .any(|bone| !nodes.is_valid_handle(bone.node))
+        {
+            return;
+        }
+
+        let target = global_position(nodes, self.target);
+        let animated = self
+            .bones
+            .iter()
+            .map(|bone| **nodes[bone.node].local_transform().rotation())
+            .collect::<Vec<_>>();
+
+        if let IkSolver::LookAt { axis } = self.solver {
+            let last = self.bones.len() - 1;
+            for (i, bone) in self.bones.iter().enumerate() {
+                let end = global_transform(nodes, self.bones[last].node);
+                let Some(delta) = UnitQuaternion::rotation_between(
+                    &(rotation_of(&end) * axis),
+                    &(target - end.position()),
+                ) else {
+                    continue;
+                };
+                let share = weight / (self.bones.len() - i) as f32;
+                rotate_bone(nodes, bone, scale_rotation(delta, share), animated[i]);
+            }
+            return;
+        }
+
+        if self.bones.len() < 2 || (self.solver == IkSolver::TwoBone && self.bones.len() != 3) {
+            return;
+        }
+
+        let mut positions = self
+            .bones
+            .iter()
+            .map(|bone| global_position(nodes, bone.node))
+            .collect::<Vec<_>>();
+        let end = positions[positions.len() - 1];
+        let target = end.lerp(&target, weight);
+
+        let pole = nodes
+            .is_valid_handle(self.pole)
+            .then(|| global_position(nodes, self.pole));
+
+        match self.solver {
+            IkSolver::TwoBone => solve_two_bone(&mut positions, target, pole),
+            IkSolver::Fabrik {
+                iterations,
+                tolerance,
+            } => solve_fabrik(&mut positions, target, iterations, tolerance),
+            IkSolver::Ccd {
+                iterations,
+                tolerance,
+            } => solve_ccd(&mut positions, target, iterations, tolerance),
+            IkSolver::LookAt { .. } => unreachable!(),
+        }
+
+        if let Some(pole) = pole {
+            apply_pole(&mut positions, pole);
+        }
+
+        // Convert the solution back to rotations, starting from the root, so every bone is rotated
+        // with respect to the already adjusted parent.
+        for (i, bone) in self.bones.iter().enumerate().take(self.bones.len() - 1) {
+            let current = global_position(nodes, bone.node);
+            let next = global_position(nodes, self.bones[i + 1].node);
+            if let Some(delta) = UnitQuaternion::rotation_between(
+                &(next - current),
+                &(positions[i + 1] - positions[i]),
+            ) {
+                rotate_bone(nodes, bone, delta, animated[i]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Vector2, Vector3},
+            pool::Handle,
+        },
+        generic_animation::machine::parameter::{Parameter, PoseWeight},
+        scene::{
+            animation::{
+                absm::{AnimationBlendingStateMachineBuilder, Machine},
+                ik::{IkBone, IkChain, IkSolver},
+                AnimationPlayerBuilder,
+            },
+            base::BaseBuilder,
+            graph::Graph,
+            node::Node,
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+    use fyrox_graph::BaseSceneGraph;
+
+    fn make_pivot(graph: &mut Graph, position: Vector3<f32>) -> Handle<Node> {
+        PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            ),
+        )
+        .build(graph)
+    }
+
+    // Creates a vertical chain of bones with unit length.
+    fn make_chain(graph: &mut Graph, count: usize) -> Vec<Handle<Node>> {
+        let mut bones = vec![make_pivot(graph, Vector3::default())];
+        for _ in 1..count {
+            let bone = make_pivot(graph, Vector3::new(0.0, 1.0, 0.0));
+            graph.link_nodes(bone, *bones.last().unwrap());
+            bones.push(bone);
+        }
+        bones
+    }
+
+    fn solve(graph: &mut Graph, chain: IkChain, machine: Machine) {
+        let animation_player = AnimationPlayerBuilder::new(BaseBuilder::new()).build(graph);
+        AnimationBlendingStateMachineBuilder::new(BaseBuilder::new())
+            .with_machine(machine)
+            .with_animation_player(animation_player)
+            .with_ik_chains(vec![chain])
+            .build(graph);
+        graph.update(Vector2::new(100.0, 100.0), 1.0 / 60.0, Default::default());
+        graph.update_hierarchical_data();
+    }
+
+    fn assert_near(a: Vector3<f32>, b: Vector3<f32>) {
+        assert!((a - b).norm() < 0.01, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_two_bone_ik() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 3);
+        let target = make_pivot(&mut graph, Vector3::new(1.0, 1.0, 0.0));
+        let pole = make_pivot(&mut graph, Vector3::new(2.0, 0.0, 0.0));
+
+        solve(
+            &mut graph,
+            IkChain {
+                bones: bones.iter().cloned().map(IkBone::new).collect(),
+                target,
+                pole,
+                ..Default::default()
+            },
+            Machine::new(),
+        );
+
+        assert_near(
+            graph[bones[2]].global_position(),
+            Vector3::new(1.0, 1.0, 0.0),
+        );
+        // The middle joint bends towards the pole.
+        assert_near(
+            graph[bones[1]].global_position(),
+            Vector3::new(1.0, 0.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn test_iterative_ik() {
+        for solver in [IkSolver::fabrik(), IkSolver::ccd()] {
+            let mut graph = Graph::new();
+            let bones = make_chain(&mut graph, 4);
+            let target = make_pivot(&mut graph, Vector3::new(1.5, 1.5, 0.5));
+
+            solve(
+                &mut graph,
+                IkChain {
+                    bones: bones.iter().cloned().map(IkBone::new).collect(),
+                    target,
+                    solver: solver.clone(),
+                    ..Default::default()
+                },
+                Machine::new(),
+            );
+
+            assert_near(
+                graph[bones[3]].global_position(),
+                Vector3::new(1.5, 1.5, 0.5),
+            );
+            // Bone lengths must be preserved.
+            for pair in bones.windows(2) {
+                let length =
+                    (graph[pair[1]].global_position() - graph[pair[0]].global_position()).norm();
+                assert!((length - 1.0).abs() < 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ik_weight_parameter() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 3);
+        let target = make_pivot(&mut graph, Vector3::new(1.0, 1.0, 0.0));
+
+        let mut machine = Machine::new();
+        machine.set_parameter("LegIk", Parameter::Weight(0.0));
+
+        solve(
+            &mut graph,
+            IkChain {
+                bones: bones.iter().cloned().map(IkBone::new).collect(),
+                target,
+                weight: PoseWeight::Parameter("LegIk".to_string()),
+                ..Default::default()
+            },
+            machine,
+        );
+
+        assert_near(
+            graph[bones[2]].global_position(),
+            Vector3::new(0.0, 2.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn test_look_at_ik_angle_limit() {
+        let mut graph = Graph::new();
+        let head = make_pivot(&mut graph, Vector3::default());
+        let target = make_pivot(&mut graph, Vector3::new(1.0, 0.0, 0.0));
+
+        solve(
+            &mut graph,
+            IkChain {
+                bones: vec![IkBone::limited(head, 0.5)],
+                target,
+                solver: IkSolver::LookAt { axis: Vector3::z() },
+                ..Default::default()
+            },
+            Machine::new(),
+        );
+
+        let look = graph[head].look_vector().normalize();
+        assert!((look.angle(&Vector3::z()) - 0.5).abs() < 0.001);
+        assert!(look.x > 0.0);
+    }
+}
@@ -33,7 +33,10 @@ use crate::{
         variable::InheritableVariable,
         visitor::prelude::*,
     },
-    generic_animation::value::{BoundValueCollection, TrackValue, ValueBinding},
+    generic_animation::{
+        value::{BoundValueCollection, TrackValue, ValueBinding},
+        AnimationEvent,
+    },
     scene::{
         base::{Base, BaseBuilder},
         graph::{Graph, NodePool},
@@ -45,6 +48,8 @@ use fyrox_graph::BaseSceneGraph;
 use std::ops::{Deref, DerefMut};
 
 pub mod absm;
+pub mod ik;
+pub mod retarget;
 pub mod spritesheet;
 
 /// Scene specific animation.
@@ -61,18 +66,64 @@ pub type NodePose = crate::generic_animation::NodePose<Handle<Node>>;
 /// Standard prelude for animations, that contains all most commonly used types and traits.
 pub mod prelude {
     pub use super::{
-        Animation, AnimationContainer, AnimationContainerExt, AnimationPlayer,
-        AnimationPlayerBuilder, AnimationPose, AnimationPoseExt, BoundValueCollectionExt, NodePose,
-        Track,
+        Animation, AnimationContainer, AnimationContainerExt, AnimationEventMessage,
+        AnimationPlayer, AnimationPlayerBuilder, AnimationPose, AnimationPoseExt,
+        BoundValueCollectionExt, NodePose, Track,
     };
     pub use crate::generic_animation::{
         container::{TrackDataContainer, TrackValueKind},
-        signal::AnimationSignal,
+        signal::{AnimationSignal, AnimationSignalPayload, AnimationSignalValue},
         value::{BoundValueCollection, TrackValue, ValueBinding, ValueType},
         AnimationEvent,
     };
 }
 
+/// A script message with an event produced by a signal of an animation. The engine sends such messages to
+/// the scripts of an [`AnimationPlayer`] node, if the node is subscribed to this message type. Keep in mind
+/// that the events are taken from the animations of subscribed players, so they can't be fetched using
+/// [`Animation::pop_event`] anymore.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::{reflect::prelude::*, visitor::prelude::*, type_traits::prelude::*},
+/// #     scene::animation::AnimationEventMessage,
+/// #     script::{ScriptContext, ScriptMessageContext, ScriptMessagePayload, ScriptTrait},
+/// # };
+/// #
+/// #[derive(Visit, Reflect, Default, Debug, Clone, TypeUuidProvider, ComponentProvider)]
+/// #[type_uuid(id = "f2a2a8d4-2b1c-4c3a-9a6e-6f3bb6d3c5a1")]
+/// struct Footsteps;
+///
+/// impl ScriptTrait for Footsteps {
+///     fn on_start(&mut self, ctx: &mut ScriptContext) {
+///         ctx.message_dispatcher
+///             .subscribe_to::<AnimationEventMessage>(ctx.handle);
+///     }
+///
+///     fn on_message(
+///         &mut self,
+///         message: &mut dyn ScriptMessagePayload,
+///         _ctx: &mut ScriptMessageContext,
+///     ) {
+///         if let Some(message) = message.downcast_ref::<AnimationEventMessage>() {
+///             if message.event.name == "Footstep" {
+///                 let _volume = message.event.payload.get_number("Volume").unwrap_or(1.0);
+///                 // Play a sound here.
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AnimationEventMessage {
+    /// A handle of the animation (in the container of the animation player) that produced the event.
+    pub animation: Handle<Animation>,
+    /// Actual animation event.
+    pub event: AnimationEvent,
+}
+
 /// Extension trait for [`AnimationContainer`].
 pub trait AnimationContainerExt {
     /// Updates all animations in the container and applies their poses to respective nodes. This method is intended to
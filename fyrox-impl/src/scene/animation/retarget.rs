@@ -0,0 +1,846 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Skeleton retargeting allows you to play animations made for one character on another character with
+//! different proportions and rest pose. See [`SkeletonMapping`] docs for more info.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        untyped::ResourceKind,
+        Resource, ResourceData,
+    },
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        io::FileLoadError,
+        log::Log,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    generic_animation::{
+        track::Track,
+        value::{TrackValue, ValueBinding},
+    },
+    graph::{BaseSceneGraph, SceneGraph},
+    scene::{
+        animation::{Animation, AnimationPlayer},
+        graph::Graph,
+        node::Node,
+        transform::Transform,
+    },
+};
+use fxhash::FxHashMap;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Names of the bones of a typical humanoid skeleton, that are used by [`SkeletonMapping::humanoid`]. The
+/// names follow the widespread naming convention used by Mixamo and many other tools.
+pub const HUMANOID_BONES: &[&str] = &[
+    "Hips",
+    "Spine",
+    "Spine1",
+    "Spine2",
+    "Neck",
+    "Head",
+    "LeftShoulder",
+    "LeftArm",
+    "LeftForeArm",
+    "LeftHand",
+    "RightShoulder",
+    "RightArm",
+    "RightForeArm",
+    "RightHand",
+    "LeftUpLeg",
+    "LeftLeg",
+    "LeftFoot",
+    "LeftToeBase",
+    "RightUpLeg",
+    "RightLeg",
+    "RightFoot",
+    "RightToeBase",
+];
+
+/// A bone of a [`Skeleton`] in its rest pose.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkeletonBone {
+    /// A handle of the bone node.
+    pub handle: Handle<Node>,
+    /// Name of the bone node.
+    pub name: String,
+    /// Local position of the bone in the rest pose.
+    pub position: Vector3<f32>,
+    /// Local rotation of the bone in the rest pose.
+    pub rotation: UnitQuaternion<f32>,
+    /// Local scale of the bone in the rest pose.
+    pub scale: Vector3<f32>,
+    /// Pre-rotation of the bone (see [`Transform::pre_rotation`]).
+    pub pre_rotation: UnitQuaternion<f32>,
+    /// Rest rotation of the parent bone relative to the root of the skeleton.
+    pub parent_rotation: UnitQuaternion<f32>,
+}
+
+/// Skeleton is a snapshot of the rest pose of a hierarchy of nodes. It is used as a source of information
+/// about proportions of a character during retargeting.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Skeleton {
+    bones: Vec<SkeletonBone>,
+}
+
+impl Skeleton {
+    /// Collects the descendant nodes of the given `root` using their current local transforms as the rest pose.
+    /// The root itself is not a bone, which means that its transform does not affect retargeting. Use this method
+    /// for hierarchies that are known to be in the rest pose, for example for the graph of a model resource or
+    /// for a freshly created instance of it.
+    pub fn from_graph(graph: &Graph, root: Handle<Node>) -> Self {
+        Self::collect(graph, root, false)
+    }
+
+    /// Same as [`Self::from_graph`], but takes the rest pose of every node that was instantiated from a model
+    /// resource from the resource itself. This is useful for instances that are being animated already.
+    ///
+    /// # Notes
+    ///
+    /// The method locks the model resources of the nodes, so it must not be called while any of them is locked.
+    pub fn from_instance(graph: &Graph, root: Handle<Node>) -> Self {
+        Self::collect(graph, root, true)
+    }
+
+    fn collect(graph: &Graph, root: Handle<Node>, use_resources: bool) -> Self {
+        let mut bones = Vec::new();
+        let mut stack = graph
+            .try_get(root)
+            .map(|root| {
+                root.children()
+                    .iter()
+                    .map(|child| (*child, UnitQuaternion::identity()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        while let Some((handle, parent_rotation)) = stack.pop() {
+            let Some(node) = graph.try_get(handle) else {
+                continue;
+            };
+            let transform = use_resources
+                .then(|| rest_transform(node))
+                .flatten()
+                .unwrap_or_else(|| node.local_transform().clone());
+            let bone = SkeletonBone {
+                handle,
+                name: node.name().to_string(),
+                position: **transform.position(),
+                rotation: **transform.rotation(),
+                scale: **transform.scale(),
+                pre_rotation: **transform.pre_rotation(),
+                parent_rotation,
+            };
+            let rotation = parent_rotation
+                * bone.pre_rotation
+                * bone.rotation
+                * transform.post_rotation().inverse();
+            stack.extend(node.children().iter().map(|child| (*child, rotation)));
+            bones.push(bone);
+        }
+        Self { bones }
+    }
+
+    /// Returns a reference to the bones of the skeleton.
+    pub fn bones(&self) -> &[SkeletonBone] {
+        &self.bones
+    }
+
+    /// Tries to find a bone by its node handle.
+    pub fn bone_by_handle(&self, handle: Handle<Node>) -> Option<&SkeletonBone> {
+        self.bones.iter().find(|bone| bone.handle == handle)
+    }
+
+    /// Tries to find a bone by its name.
+    pub fn bone_by_name(&self, name: &str) -> Option<&SkeletonBone> {
+        self.bones.iter().find(|bone| bone.name == name)
+    }
+}
+
+fn rest_transform(node: &Node) -> Option<Transform> {
+    let resource = node.resource()?;
+    let header = resource.state();
+    let model = header.data_ref()?;
+    model
+        .scene
+        .graph
+        .try_get(node.original_handle_in_resource())
+        .map(|original| original.local_transform().clone())
+}
+
+/// A pair of names of the bones, that correspond to each other in a source and a target skeleton.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect, TypeUuidProvider)]
+#[type_uuid(id = "c2457762-8308-48dc-8c73-ee301937aa1f")]
+pub struct BoneMapping {
+    /// Name of the bone in the source skeleton.
+    pub source: String,
+    /// Name of the bone in the target skeleton.
+    pub target: String,
+}
+
+impl BoneMapping {
+    /// Creates a new bone mapping.
+    pub fn new<S: AsRef<str>, T: AsRef<str>>(source: S, target: T) -> Self {
+        Self {
+            source: source.as_ref().to_string(),
+            target: target.as_ref().to_string(),
+        }
+    }
+}
+
+/// An error that may occur during skeleton mapping resource loading.
+#[derive(Debug)]
+pub enum SkeletonMappingError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for SkeletonMappingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            Self::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for SkeletonMappingError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for SkeletonMappingError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// Skeleton mapping defines how the bones of one skeleton correspond to the bones of another one and it is
+/// used to transfer (retarget) animations between characters with different proportions or rest poses.
+/// Without retargeting, an animation can only be played on a skeleton that is identical to the one it was
+/// made for, otherwise the limbs will be distorted.
+///
+/// Retargeting works with rotations relative to the rest pose: the rotation of each bone is taken as a
+/// deviation from the rest pose of the source skeleton and then applied to the rest pose of the target
+/// skeleton. This makes it possible to retarget animations between skeletons with different local axes of
+/// the bones, as long as the rest poses of both skeletons are similar (for example, both are in T-pose).
+/// Translations are scaled by the ratio of the bone lengths, so a shorter character will make shorter
+/// steps. Root motion could be additionally scaled using [`Self::root_motion_scale`].
+///
+/// Skeleton mapping is a resource, so it could be set up once and then shared across any number of
+/// characters.
+///
+/// ## Example
+///
+/// The following example retargets every animation of one character to another one at runtime. Both
+/// characters use Mixamo-like naming of the bones, but the bones of the source character have
+/// `mixamorig:` prefix.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::pool::Handle,
+/// #     scene::{animation::retarget::SkeletonMapping, graph::Graph, node::Node},
+/// # };
+/// fn retarget(
+///     graph: &mut Graph,
+///     source_root: Handle<Node>,
+///     source_animation_player: Handle<Node>,
+///     target_root: Handle<Node>,
+///     target_animation_player: Handle<Node>,
+/// ) {
+///     let mapping = SkeletonMapping::humanoid("mixamorig:", "");
+///     mapping.retarget_animation_player(
+///         graph,
+///         source_root,
+///         source_animation_player,
+///         target_root,
+///         target_animation_player,
+///     );
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, TypeUuidProvider)]
+#[type_uuid(id = "40bbd8fe-4d69-4935-9c05-9bac5f1ab6b0")]
+pub struct SkeletonMapping {
+    /// Pairs of corresponding bones.
+    pub bones: Vec<BoneMapping>,
+
+    /// If `true`, bones that are not listed in [`Self::bones`] will be matched by their names. Otherwise,
+    /// tracks of such bones will be removed from retargeted animations.
+    pub match_unlisted_by_name: bool,
+
+    /// If `true`, translations of the bones will be scaled by the ratio of the lengths of the respective
+    /// bones of the target and the source skeletons.
+    pub scale_translations: bool,
+
+    /// Additional scale of the translation of the root motion node (see
+    /// [`crate::generic_animation::RootMotionSettings`]).
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub root_motion_scale: f32,
+}
+
+impl Default for SkeletonMapping {
+    fn default() -> Self {
+        Self {
+            bones: Default::default(),
+            match_unlisted_by_name: true,
+            scale_translations: true,
+            root_motion_scale: 1.0,
+        }
+    }
+}
+
+struct BoneRetarget {
+    target: Handle<Node>,
+    pre_rotation: UnitQuaternion<f32>,
+    post_rotation: UnitQuaternion<f32>,
+    direction: UnitQuaternion<f32>,
+    source_position: Vector3<f32>,
+    target_position: Vector3<f32>,
+    translation_scale: f32,
+    source_scale: Vector3<f32>,
+    target_scale: Vector3<f32>,
+}
+
+impl BoneRetarget {
+    fn new(source: &SkeletonBone, target: &SkeletonBone, translation_scale: f32) -> Self {
+        // Rest rotation of the source parent expressed in the space of the target parent.
+        let direction = target.parent_rotation.inverse() * source.parent_rotation;
+        let pre_rotation = target.pre_rotation.inverse() * direction * source.pre_rotation;
+        let post_rotation = source.rotation.inverse() * pre_rotation.inverse() * target.rotation;
+        Self {
+            target: target.handle,
+            pre_rotation,
+            post_rotation,
+            direction,
+            source_position: source.position,
+            target_position: target.position,
+            translation_scale,
+            source_scale: source.scale,
+            target_scale: target.scale,
+        }
+    }
+
+    fn apply(&self, track: &mut Track) {
+        let binding = track.value_binding().clone();
+        track
+            .data_container_mut()
+            .map_values(|value| match (&binding, value) {
+                (ValueBinding::Position, TrackValue::Vector3(position)) => TrackValue::Vector3(
+                    self.target_position
+                        + self.direction
+                            * (position - self.source_position)
+                            * self.translation_scale,
+                ),
+                (ValueBinding::Rotation, TrackValue::UnitQuaternion(rotation)) => {
+                    TrackValue::UnitQuaternion(self.pre_rotation * rotation * self.post_rotation)
+                }
+                (ValueBinding::Scale, TrackValue::Vector3(scale)) => {
+                    TrackValue::Vector3(Vector3::from_fn(|i, _| {
+                        if self.source_scale[i].abs() > f32::EPSILON {
+                            scale[i] * self.target_scale[i] / self.source_scale[i]
+                        } else {
+                            scale[i]
+                        }
+                    }))
+                }
+                (_, value) => value,
+            });
+    }
+}
+
+impl SkeletonMapping {
+    /// Creates a mapping for typical humanoid skeletons (see [`HUMANOID_BONES`]). The prefixes are prepended
+    /// to the names of the bones of the respective skeletons, for example `mixamorig:`.
+    pub fn humanoid(source_prefix: &str, target_prefix: &str) -> Self {
+        Self {
+            bones: HUMANOID_BONES
+                .iter()
+                .map(|name| {
+                    BoneMapping::new(
+                        format!("{source_prefix}{name}"),
+                        format!("{target_prefix}{name}"),
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a new pair of corresponding bones to the mapping.
+    pub fn with_bone<S: AsRef<str>, T: AsRef<str>>(mut self, source: S, target: T) -> Self {
+        self.bones.push(BoneMapping::new(source, target));
+        self
+    }
+
+    /// Returns the name of the target bone, that corresponds to the given source bone.
+    pub fn target_name<'a>(&'a self, source: &'a str) -> Option<&'a str> {
+        match self.bones.iter().find(|bone| bone.source == source) {
+            Some(bone) => Some(bone.target.as_str()),
+            None => self.match_unlisted_by_name.then_some(source),
+        }
+    }
+
+    fn bone_retarget(
+        &self,
+        source_node: Handle<Node>,
+        source: &Skeleton,
+        target: &Skeleton,
+        is_root_motion_node: bool,
+    ) -> Option<BoneRetarget> {
+        let source_bone = source.bone_by_handle(source_node)?;
+        let Some(target_bone) = self
+            .target_name(&source_bone.name)
+            .and_then(|name| target.bone_by_name(name))
+        else {
+            Log::warn(format!(
+                "Unable to retarget animation tracks of {} bone, because there's no matching \
+                bone in the target skeleton. The tracks will be removed.",
+                source_bone.name
+            ));
+            return None;
+        };
+
+        let mut translation_scale = 1.0;
+        if self.scale_translations {
+            let source_length = source_bone.position.norm();
+            if source_length > f32::EPSILON {
+                translation_scale = target_bone.position.norm() / source_length;
+            }
+        }
+        if is_root_motion_node {
+            translation_scale *= self.root_motion_scale;
+        }
+
+        Some(BoneRetarget::new(
+            source_bone,
+            target_bone,
+            translation_scale,
+        ))
+    }
+
+    /// Renames the descendant nodes of the given `root` from the source names of the listed bones to their target
+    /// names. Nodes that are not listed in [`Self::bones`] keep their names. Unlike [`Self::retarget`], this method
+    /// does not adjust animations to the proportions of the target skeleton, it only makes the hierarchy match
+    /// the naming of the target skeleton. Returns the number of renamed nodes.
+    pub fn rename_bones(&self, graph: &mut Graph, root: Handle<Node>) -> usize {
+        let descendants = graph
+            .traverse_handle_iter(root)
+            .filter(|handle| *handle != root)
+            .collect::<Vec<_>>();
+        let mut count = 0;
+        for handle in descendants {
+            let node = &mut graph[handle];
+            if let Some(bone) = self.bones.iter().find(|bone| bone.source == node.name()) {
+                if bone.target != node.name() {
+                    node.set_name(&bone.target);
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Retargets the given animation from the `source` skeleton to the `target` skeleton. Tracks of the bones
+    /// that have no matching bone in the target skeleton are removed. The retargeted tracks are stored in a new
+    /// embedded resource, so the source animation is not affected.
+    pub fn retarget(
+        &self,
+        animation: &Animation,
+        source: &Skeleton,
+        target: &Skeleton,
+    ) -> Animation {
+        let mut retargeted = animation.clone();
+
+        let state = animation.tracks_data().state();
+        let Some(tracks_data) = state.data_ref() else {
+            return retargeted;
+        };
+        let mut tracks_data = tracks_data.clone();
+        drop(state);
+
+        let root_motion_node = animation.root_motion_settings_ref().map(|s| s.node);
+        let mut bones = FxHashMap::default();
+        tracks_data.tracks.retain_mut(|track| {
+            let Some(source_node) = animation
+                .track_bindings()
+                .get(&track.id())
+                .map(|binding| binding.target())
+            else {
+                return true;
+            };
+
+            let bone = bones.entry(source_node).or_insert_with(|| {
+                self.bone_retarget(
+                    source_node,
+                    source,
+                    target,
+                    root_motion_node == Some(source_node),
+                )
+            });
+
+            match bone {
+                Some(bone) => {
+                    if let Some(binding) = retargeted.track_bindings_mut().get_mut(&track.id()) {
+                        binding.set_target(bone.target);
+                    }
+                    bone.apply(track);
+                    true
+                }
+                None => {
+                    retargeted.track_bindings_mut().remove(&track.id());
+                    false
+                }
+            }
+        });
+
+        if let Some(settings) = retargeted.root_motion_settings_mut() {
+            settings.node = source
+                .bone_by_handle(settings.node)
+                .and_then(|bone| self.target_name(&bone.name))
+                .and_then(|name| target.bone_by_name(name))
+                .map(|bone| bone.handle)
+                .unwrap_or_default();
+        }
+
+        retargeted.set_tracks_data(Resource::new_ok(ResourceKind::Embedded, tracks_data));
+        retargeted
+    }
+
+    /// Retargets every animation of the source animation player to the target skeleton and adds the retargeted
+    /// animations to the target animation player. Rest poses of both skeletons are taken from the respective
+    /// model resources (see [`Skeleton::from_instance`]), so the characters could be animated already. Returns
+    /// handles of the new animations in the target animation player.
+    pub fn retarget_animation_player(
+        &self,
+        graph: &mut Graph,
+        source_root: Handle<Node>,
+        source_animation_player: Handle<Node>,
+        target_root: Handle<Node>,
+        target_animation_player: Handle<Node>,
+    ) -> Vec<Handle<Animation>> {
+        let source = Skeleton::from_instance(graph, source_root);
+        let target = Skeleton::from_instance(graph, target_root);
+
+        let Some(source_animation_player) =
+            graph.try_get_of_type::<AnimationPlayer>(source_animation_player)
+        else {
+            return Default::default();
+        };
+        let animations = source_animation_player
+            .animations()
+            .iter()
+            .map(|animation| self.retarget(animation, &source, &target))
+            .collect::<Vec<_>>();
+
+        let Some(target_animation_player) =
+            graph.try_get_mut_of_type::<AnimationPlayer>(target_animation_player)
+        else {
+            return Default::default();
+        };
+        let target_animations = target_animation_player.animations_mut();
+        animations
+            .into_iter()
+            .map(|animation| target_animations.add(animation))
+            .collect()
+    }
+
+    /// Load a skeleton mapping from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, SkeletonMappingError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut mapping = SkeletonMapping::default();
+        mapping.visit("SkeletonMapping", &mut visitor)?;
+        Ok(mapping)
+    }
+}
+
+impl ResourceData for SkeletonMapping {
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("SkeletonMapping", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+/// A loader for skeleton mapping resource.
+pub struct SkeletonMappingLoader;
+
+impl ResourceLoader for SkeletonMappingLoader {
+    fn extensions(&self) -> &[&str] {
+        &["skelmap"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <SkeletonMapping as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let mapping = SkeletonMapping::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(mapping))
+        })
+    }
+}
+
+/// Skeleton mapping resource.
+pub type SkeletonMappingResource = Resource<SkeletonMapping>;
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            math::curve::{Curve, CurveKey, CurveKeyKind},
+            pool::Handle,
+        },
+        generic_animation::{
+            track::{Track, TrackBinding},
+            value::{TrackValue, ValueBinding},
+        },
+        scene::{
+            animation::{
+                retarget::{Skeleton, SkeletonMapping},
+                Animation,
+            },
+            base::BaseBuilder,
+            graph::Graph,
+            node::Node,
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+    use fyrox_graph::BaseSceneGraph;
+
+    fn make_bone(
+        graph: &mut Graph,
+        name: &str,
+        parent: Handle<Node>,
+        position: Vector3<f32>,
+        rotation: UnitQuaternion<f32>,
+    ) -> Handle<Node> {
+        let bone = PivotBuilder::new(
+            BaseBuilder::new().with_name(name).with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .with_local_rotation(rotation)
+                    .build(),
+            ),
+        )
+        .build(graph);
+        graph.link_nodes(bone, parent);
+        bone
+    }
+
+    fn make_track(track: Track, values: [[f32; 3]; 2]) -> Track {
+        let mut track = track;
+        for (i, curve) in track
+            .data_container_mut()
+            .curves_mut()
+            .iter_mut()
+            .enumerate()
+        {
+            *curve = Curve::from(vec![
+                CurveKey::new(0.0, values[0][i], CurveKeyKind::Linear),
+                CurveKey::new(1.0, values[1][i], CurveKeyKind::Linear),
+            ]);
+        }
+        track
+    }
+
+    fn value(animation: &Animation, node: Handle<Node>, binding: ValueBinding) -> TrackValue {
+        animation.pose_at(1.0).poses()[&node]
+            .values
+            .values
+            .iter()
+            .find(|value| value.binding == binding)
+            .unwrap()
+            .value
+            .clone()
+    }
+
+    #[test]
+    fn test_retarget() {
+        let mut graph = Graph::new();
+        let root = graph.get_root();
+
+        let source_root = make_bone(
+            &mut graph,
+            "Source",
+            root,
+            Default::default(),
+            Default::default(),
+        );
+        let source_hips = make_bone(
+            &mut graph,
+            "mixamorig:Hips",
+            source_root,
+            Vector3::new(0.0, 1.0, 0.0),
+            Default::default(),
+        );
+        let source_spine = make_bone(
+            &mut graph,
+            "mixamorig:Spine",
+            source_hips,
+            Vector3::new(0.0, 1.0, 0.0),
+            Default::default(),
+        );
+
+        // Target skeleton is twice as large and its hips have different local axes.
+        let hips_rotation =
+            UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 90.0f32.to_radians());
+        let target_root = make_bone(
+            &mut graph,
+            "Target",
+            root,
+            Default::default(),
+            Default::default(),
+        );
+        let target_hips = make_bone(
+            &mut graph,
+            "Hips",
+            target_root,
+            Vector3::new(0.0, 2.0, 0.0),
+            hips_rotation,
+        );
+        let target_spine = make_bone(
+            &mut graph,
+            "Spine",
+            target_hips,
+            Vector3::new(0.0, 2.0, 0.0),
+            Default::default(),
+        );
+
+        let mut animation = Animation::default();
+        animation.add_track_with_binding(
+            TrackBinding::new(source_hips),
+            make_track(Track::new_position(), [[0.0, 1.0, 0.0], [0.0, 1.0, 1.0]]),
+        );
+        animation.add_track_with_binding(
+            TrackBinding::new(source_spine),
+            make_track(Track::new_rotation(), [[0.0, 0.0, 0.0], [0.5, 0.0, 0.0]]),
+        );
+
+        let source = Skeleton::from_graph(&graph, source_root);
+        let target = Skeleton::from_graph(&graph, target_root);
+        let mapping = SkeletonMapping::humanoid("mixamorig:", "");
+        let retargeted = mapping.retarget(&animation, &source, &target);
+
+        let TrackValue::Vector3(position) = value(&retargeted, target_hips, ValueBinding::Position)
+        else {
+            unreachable!()
+        };
+        assert!((position - Vector3::new(0.0, 2.0, 2.0)).norm() < 1.0e-4);
+
+        // Rotation of the spine relative to the rest pose must be the same in world space.
+        let TrackValue::UnitQuaternion(rotation) =
+            value(&retargeted, target_spine, ValueBinding::Rotation)
+        else {
+            unreachable!()
+        };
+        let delta = hips_rotation * rotation * hips_rotation.inverse();
+        let expected = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.5);
+        assert!(delta.angle_to(&expected) < 1.0e-4);
+
+        // Tracks of the unmapped bones are removed.
+        let mapping = SkeletonMapping {
+            match_unlisted_by_name: false,
+            ..Default::default()
+        }
+        .with_bone("mixamorig:Hips", "Hips");
+        let retargeted = mapping.retarget(&animation, &source, &target);
+        assert_eq!(retargeted.track_bindings().len(), 1);
+        assert_eq!(retargeted.tracks_data().data_ref().tracks().len(), 1);
+    }
+
+    #[test]
+    fn test_rename_bones() {
+        let mut graph = Graph::new();
+        let root = graph.get_root();
+
+        let model_root = make_bone(
+            &mut graph,
+            "mixamorig:Hips",
+            root,
+            Default::default(),
+            Default::default(),
+        );
+        let hips = make_bone(
+            &mut graph,
+            "mixamorig:Hips",
+            model_root,
+            Default::default(),
+            Default::default(),
+        );
+        let spine = make_bone(
+            &mut graph,
+            "mixamorig:Spine",
+            hips,
+            Default::default(),
+            Default::default(),
+        );
+        let other = make_bone(
+            &mut graph,
+            "Other",
+            hips,
+            Default::default(),
+            Default::default(),
+        );
+
+        let mapping = SkeletonMapping::humanoid("mixamorig:", "");
+        assert_eq!(mapping.rename_bones(&mut graph, model_root), 2);
+        // The root itself is not a bone.
+        assert_eq!(graph[model_root].name(), "mixamorig:Hips");
+        assert_eq!(graph[hips].name(), "Hips");
+        assert_eq!(graph[spine].name(), "Spine");
+        assert_eq!(graph[other].name(), "Other");
+    }
+}
@@ -24,10 +24,11 @@
 use crate::scene::node::constructor::NodeConstructor;
 use crate::{
     core::{
-        algebra::Vector3,
+        algebra::{UnitQuaternion, Vector3},
         log::Log,
         math::aabb::AxisAlignedBoundingBox,
         num_traits::{NumCast, One, ToPrimitive, Zero},
+        parking_lot::Mutex,
         pool::Handle,
         reflect::prelude::*,
         type_traits::prelude::*,
@@ -37,11 +38,13 @@ use crate::{
     },
     scene::{
         base::{Base, BaseBuilder},
+        character::CharacterController,
         graph::{
             physics::{CoefficientCombineRule, ContactPair, IntersectionPair, PhysicsWorld},
             Graph,
         },
         node::{Node, NodeTrait, SyncContext},
+        physics_material::{PhysicsMaterial, PhysicsMaterialResource},
         rigidbody::RigidBody,
         Scene,
     },
@@ -52,8 +55,9 @@ use fyrox_graph::constructor::ConstructorProvider;
 use fyrox_graph::{BaseSceneGraph, SceneGraphNode};
 use rapier3d::geometry::{self, ColliderHandle};
 use std::{
-    cell::Cell,
+    cell::{Cell, Ref, RefCell},
     ops::{Add, BitAnd, BitOr, Deref, DerefMut, Mul, Not, Shl},
+    sync::Arc,
 };
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
@@ -221,6 +225,169 @@ pub struct ConvexPolyhedronShape {
     pub geometry_source: GeometrySource,
 }
 
+/// A shape that is built by approximate convex decomposition (V-HACD) of a set of meshes. It allows
+/// dynamic rigid bodies to have concave geometry, which is not possible with [`TrimeshShape`].
+///
+/// # Performance
+///
+/// Decomposition is very expensive, so it is performed on the task pool of the engine (see
+/// [`crate::scene::graph::physics::PhysicsWorld::set_task_pool`]) and the collider does not have
+/// the shape until the decomposition is finished. Its results are cached on the
+/// collider, saved together with it and re-used until the parameters of the shape or the source
+/// geometry changes. This way the decomposition is performed only once, usually in the editor.
+#[derive(Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct ConvexDecompositionShape {
+    /// Geometry sources for the shape.
+    pub sources: Vec<GeometrySource>,
+    /// Resolution of the voxel grid used for decomposition. Higher values give more precise
+    /// hulls, but make the decomposition slower.
+    #[reflect(min_value = 16.0, max_value = 512.0)]
+    pub resolution: u32,
+    /// Maximum concavity allowed for each part. Lower values produce more hulls that fit the
+    /// source geometry better.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.001)]
+    pub concavity: f32,
+    /// Upper limit of hulls that could be generated.
+    #[reflect(min_value = 1.0)]
+    pub max_convex_hulls: u32,
+}
+
+impl Default for ConvexDecompositionShape {
+    fn default() -> Self {
+        Self {
+            sources: Default::default(),
+            resolution: 64,
+            concavity: 0.01,
+            max_convex_hulls: 1024,
+        }
+    }
+}
+
+/// Possible shapes of a [`CompoundShapePart`]. It is a subset of [`ColliderShape`], because
+/// triangle meshes, height fields and compound shapes cannot be nested in a compound shape.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, VariantNames)]
+pub enum CompoundPartShape {
+    /// See [`BallShape`] docs.
+    Ball(BallShape),
+    /// See [`CylinderShape`] docs.
+    Cylinder(CylinderShape),
+    /// See [`ConeShape`] docs.
+    Cone(ConeShape),
+    /// See [`CuboidShape`] docs.
+    Cuboid(CuboidShape),
+    /// See [`CapsuleShape`] docs.
+    Capsule(CapsuleShape),
+    /// See [`SegmentShape`] docs.
+    Segment(SegmentShape),
+    /// See [`TriangleShape`] docs.
+    Triangle(TriangleShape),
+    /// See [`ConvexPolyhedronShape`] docs.
+    Polyhedron(ConvexPolyhedronShape),
+    /// See [`ConvexDecompositionShape`] docs.
+    ConvexDecomposition(ConvexDecompositionShape),
+}
+
+uuid_provider!(CompoundPartShape = "fe6884cb-a9f2-48ae-a0e6-8cd6ed8024bb");
+
+impl Default for CompoundPartShape {
+    fn default() -> Self {
+        Self::Cuboid(Default::default())
+    }
+}
+
+impl From<CompoundPartShape> for ColliderShape {
+    fn from(shape: CompoundPartShape) -> Self {
+        match shape {
+            CompoundPartShape::Ball(v) => Self::Ball(v),
+            CompoundPartShape::Cylinder(v) => Self::Cylinder(v),
+            CompoundPartShape::Cone(v) => Self::Cone(v),
+            CompoundPartShape::Cuboid(v) => Self::Cuboid(v),
+            CompoundPartShape::Capsule(v) => Self::Capsule(v),
+            CompoundPartShape::Segment(v) => Self::Segment(v),
+            CompoundPartShape::Triangle(v) => Self::Triangle(v),
+            CompoundPartShape::Polyhedron(v) => Self::Polyhedron(v),
+            CompoundPartShape::ConvexDecomposition(v) => Self::ConvexDecomposition(v),
+        }
+    }
+}
+
+/// A part of a [`CompoundShape`].
+#[derive(Default, Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct CompoundShapePart {
+    /// Position of the part relative to the collider.
+    pub position: Vector3<f32>,
+    /// Rotation of the part relative to the collider.
+    pub rotation: UnitQuaternion<f32>,
+    /// Shape of the part.
+    pub shape: CompoundPartShape,
+}
+
+uuid_provider!(CompoundShapePart = "02d2ad97-002b-4b0c-915d-a2ba0c813485");
+
+/// A shape that consists of a set of convex shapes with their own local transforms.
+///
+/// # Notes
+///
+/// Convex decomposition parts produce multiple convex hulls, such hulls are flattened into the
+/// compound shape.
+#[derive(Default, Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct CompoundShape {
+    /// Parts of the shape.
+    pub parts: Vec<CompoundShapePart>,
+}
+
+/// A convex hull in the local coordinates of a collider.
+#[derive(Default, Clone, Debug, PartialEq, Visit)]
+pub struct ConvexHull {
+    /// Vertices of the hull.
+    pub vertices: Vec<Vector3<f32>>,
+    /// Triangles of the hull, each triangle is a triple of indices of the vertices.
+    pub triangles: Vec<[u32; 3]>,
+}
+
+/// A set of convex hulls generated for a [`ConvexDecompositionShape`].
+#[derive(Default, Clone, Debug)]
+pub struct ConvexDecomposition {
+    /// Transform of the hulls relative to the collider. It is not identity only for decompositions
+    /// that are parts of a [`CompoundShape`].
+    pub transform: Isometry3<f32>,
+    /// Generated convex hulls.
+    pub hulls: Vec<ConvexHull>,
+    // Hash of the decomposition parameters and the source geometry.
+    pub(crate) key: u64,
+}
+
+impl Visit for ConvexDecomposition {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.transform
+            .translation
+            .vector
+            .visit("Translation", &mut region)?;
+        self.transform.rotation.visit("Rotation", &mut region)?;
+        self.hulls.visit("Hulls", &mut region)?;
+        self.key.visit("Key", &mut region)?;
+
+        Ok(())
+    }
+}
+
+/// A convex decomposition that is being computed on the task pool.
+#[derive(Default, Clone, Debug)]
+pub(crate) struct PendingConvexDecomposition {
+    // Hash of the decomposition parameters and the source geometry.
+    pub(crate) key: u64,
+    // Generated convex hulls, `None` until the decomposition is finished.
+    pub(crate) hulls: Arc<Mutex<Option<Vec<ConvexHull>>>>,
+}
+
+impl PendingConvexDecomposition {
+    pub(crate) fn is_finished(&self) -> bool {
+        self.hulls.lock().is_some()
+    }
+}
+
 /// A set of bits used for pairwise collision filtering.
 #[derive(Clone, Copy, Default, PartialEq, Debug, Reflect, Eq)]
 pub struct BitMask(pub u32);
@@ -458,6 +625,10 @@ pub enum ColliderShape {
     Heightfield(HeightfieldShape),
     /// See [`ConvexPolyhedronShape`] docs.
     Polyhedron(ConvexPolyhedronShape),
+    /// See [`ConvexDecompositionShape`] docs.
+    ConvexDecomposition(ConvexDecompositionShape),
+    /// See [`CompoundShape`] docs.
+    Compound(CompoundShape),
 }
 
 uuid_provider!(ColliderShape = "2e627337-71ea-4b33-a5f1-be697f705a86");
@@ -544,6 +715,20 @@ impl ColliderShape {
     pub fn heightfield(geometry_source: GeometrySource) -> Self {
         Self::Heightfield(HeightfieldShape { geometry_source })
     }
+
+    /// Initializes a convex decomposition shape defined by a set of handles to mesh nodes, that
+    /// will be split into convex parts using default decomposition parameters.
+    pub fn convex_decomposition(geometry_sources: Vec<GeometrySource>) -> Self {
+        Self::ConvexDecomposition(ConvexDecompositionShape {
+            sources: geometry_sources,
+            ..Default::default()
+        })
+    }
+
+    /// Initializes a compound shape from a set of parts.
+    pub fn compound(parts: Vec<CompoundShapePart>) -> Self {
+        Self::Compound(CompoundShape { parts })
+    }
 }
 
 /// Collider is a geometric entity that can be attached to a rigid body to allow participate it
@@ -579,9 +764,31 @@ pub struct Collider {
     #[reflect(setter = "set_restitution_combine_rule")]
     pub(crate) restitution_combine_rule: InheritableVariable<CoefficientCombineRule>,
 
+    #[reflect(setter = "set_material")]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) material: InheritableVariable<Option<PhysicsMaterialResource>>,
+
+    #[reflect(setter = "set_report_collision_events")]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) report_collision_events: InheritableVariable<bool>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) native: Cell<ColliderHandle>,
+
+    // Key and content hash of the physics material, that was applied to the native collider the
+    // last time.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) applied_material: Cell<Option<(u64, u64)>>,
+
+    #[visit(optional)] // Backward compatibility
+    #[reflect(hidden)]
+    pub(crate) convex_decompositions: RefCell<Vec<ConvexDecomposition>>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) pending_convex_decompositions: RefCell<Vec<PendingConvexDecomposition>>,
 }
 
 impl Default for Collider {
@@ -597,7 +804,12 @@ impl Default for Collider {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            material: Default::default(),
+            report_collision_events: Default::default(),
             native: Cell::new(ColliderHandle::invalid()),
+            applied_material: Default::default(),
+            convex_decompositions: Default::default(),
+            pending_convex_decompositions: Default::default(),
         }
     }
 }
@@ -629,8 +841,13 @@ impl Clone for Collider {
             solver_groups: self.solver_groups.clone(),
             friction_combine_rule: self.friction_combine_rule.clone(),
             restitution_combine_rule: self.restitution_combine_rule.clone(),
+            material: self.material.clone(),
+            report_collision_events: self.report_collision_events.clone(),
             // Do not copy. The copy will have its own native representation (for example - Rapier's collider)
             native: Cell::new(ColliderHandle::invalid()),
+            applied_material: Default::default(),
+            convex_decompositions: self.convex_decompositions.clone(),
+            pending_convex_decompositions: self.pending_convex_decompositions.clone(),
         }
     }
 }
@@ -658,6 +875,19 @@ impl Collider {
         &self.shape
     }
 
+    /// Returns convex hulls generated for every [`ConvexDecompositionShape`] used by the collider
+    /// shape. The hulls are generated on the task pool when the native collider is (re)created,
+    /// so the list does not contain a decomposition until it is finished.
+    pub fn convex_decompositions(&self) -> Ref<'_, Vec<ConvexDecomposition>> {
+        self.convex_decompositions.borrow()
+    }
+
+    /// Returns `true` if at least one [`ConvexDecompositionShape`] used by the collider shape is
+    /// still being computed on the task pool.
+    pub fn is_convex_decomposition_pending(&self) -> bool {
+        !self.pending_convex_decompositions.borrow().is_empty()
+    }
+
     /// Returns a copy of the collider shape.
     pub fn shape_value(&self) -> ColliderShape {
         (*self.shape).clone()
@@ -782,6 +1012,20 @@ impl Collider {
         *self.is_sensor
     }
 
+    /// Defines whether the physics engine should report collision events (see
+    /// [`crate::script::ScriptTrait::on_collision`]) for the collider or not. An event is reported
+    /// if at least one of the two colliders has this flag set. Collision events are disabled by
+    /// default, because collecting them for every collider in a scene is quite expensive.
+    pub fn set_report_collision_events(&mut self, report: bool) -> bool {
+        self.report_collision_events
+            .set_value_and_mark_modified(report)
+    }
+
+    /// Returns `true` if the physics engine reports collision events for the collider.
+    pub fn is_report_collision_events(&self) -> bool {
+        *self.report_collision_events
+    }
+
     /// Sets the new friction combine rule. See [`CoefficientCombineRule`] docs for more info.
     ///
     /// # Performance
@@ -821,6 +1065,58 @@ impl Collider {
         *self.restitution_combine_rule
     }
 
+    /// Sets the new physics material of the collider. The material overrides friction, restitution,
+    /// their combine rules and density (if the material defines it) of the collider. `None` means
+    /// that the collider's own properties will be used. See [`PhysicsMaterial`] docs for more info.
+    pub fn set_material(
+        &mut self,
+        material: Option<PhysicsMaterialResource>,
+    ) -> Option<PhysicsMaterialResource> {
+        self.material.set_value_and_mark_modified(material)
+    }
+
+    /// Returns current physics material of the collider.
+    pub fn material(&self) -> Option<PhysicsMaterialResource> {
+        (*self.material).clone()
+    }
+
+    /// Returns surface properties that are actually used by the physics engine. These are the
+    /// properties of the physics material (if it is assigned and loaded) or the properties of the
+    /// collider itself otherwise. If the material does not define density, the density of the
+    /// collider is used.
+    pub fn effective_material(&self) -> PhysicsMaterial {
+        if let Some(material) = self.material.as_ref() {
+            if let Some(material) = material.data_ref().as_loaded_ref() {
+                return PhysicsMaterial {
+                    density: material.density.or(*self.density),
+                    ..material.clone()
+                };
+            }
+        }
+
+        PhysicsMaterial {
+            friction: *self.friction,
+            friction_combine_rule: *self.friction_combine_rule,
+            restitution: *self.restitution,
+            restitution_combine_rule: *self.restitution_combine_rule,
+            density: *self.density,
+            surface_tag: Default::default(),
+        }
+    }
+
+    // Returns the key and the content hash of the physics material, they change if the material
+    // is replaced or its properties are changed (including hot reloading).
+    pub(crate) fn material_state(&self) -> Option<(u64, u64)> {
+        self.material.as_ref().map(|material| {
+            let hash = material
+                .data_ref()
+                .as_loaded_ref()
+                .map(PhysicsMaterial::content_hash)
+                .unwrap_or_default();
+            (material.key(), hash)
+        })
+    }
+
     /// Returns an iterator that yields contact information for the collider.
     /// Contacts checks between two regular colliders
     pub fn contacts<'a>(
@@ -849,6 +1145,8 @@ impl Collider {
             || self.solver_groups.need_sync()
             || self.friction_combine_rule.need_sync()
             || self.restitution_combine_rule.need_sync()
+            || self.material.need_sync()
+            || self.report_collision_events.need_sync()
     }
 }
 
@@ -920,45 +1218,67 @@ impl NodeTrait for Collider {
         if scene
             .graph
             .try_get(self.parent())
-            .and_then(|p| p.component_ref::<RigidBody>())
+            .filter(|p| {
+                p.component_ref::<RigidBody>().is_some()
+                    || p.component_ref::<CharacterController>().is_some()
+            })
             .is_none()
         {
-            message += "3D Collider must be a direct child of a 3D Rigid Body node, \
-            otherwise it will not have any effect!";
+            message += "3D Collider must be a direct child of a 3D Rigid Body or a 3D Character \
+            Controller node, otherwise it will not have any effect!";
+        }
+
+        validate_shape(&self.shape, scene, &mut message);
+
+        if message.is_empty() {
+            Ok(())
+        } else {
+            Err(message)
         }
+    }
+}
 
-        match &*self.shape {
-            ColliderShape::Trimesh(trimesh) => {
-                for source in trimesh.sources.iter() {
-                    if !scene.graph.is_valid_handle(source.0) {
-                        message += &format!("Trimesh data source {} handle is invalid!", source.0);
-                    }
+fn validate_shape(shape: &ColliderShape, scene: &Scene, message: &mut String) {
+    match shape {
+        ColliderShape::Trimesh(trimesh) => {
+            for source in trimesh.sources.iter() {
+                if !scene.graph.is_valid_handle(source.0) {
+                    *message += &format!("Trimesh data source {} handle is invalid!", source.0);
                 }
             }
-            ColliderShape::Heightfield(heightfield) => {
-                if !scene.graph.is_valid_handle(heightfield.geometry_source.0) {
-                    message += &format!(
-                        "Heightfield data source {} handle is invalid!",
-                        heightfield.geometry_source.0
-                    );
-                }
+        }
+        ColliderShape::Heightfield(heightfield) => {
+            if !scene.graph.is_valid_handle(heightfield.geometry_source.0) {
+                *message += &format!(
+                    "Heightfield data source {} handle is invalid!",
+                    heightfield.geometry_source.0
+                );
             }
-            ColliderShape::Polyhedron(polyhedron) => {
-                if !scene.graph.is_valid_handle(polyhedron.geometry_source.0) {
-                    message += &format!(
-                        "Polyhedron data source {} handle is invalid!",
-                        polyhedron.geometry_source.0
+        }
+        ColliderShape::Polyhedron(polyhedron) => {
+            if !scene.graph.is_valid_handle(polyhedron.geometry_source.0) {
+                *message += &format!(
+                    "Polyhedron data source {} handle is invalid!",
+                    polyhedron.geometry_source.0
+                );
+            }
+        }
+        ColliderShape::ConvexDecomposition(decomposition) => {
+            for source in decomposition.sources.iter() {
+                if !scene.graph.is_valid_handle(source.0) {
+                    *message += &format!(
+                        "Convex decomposition data source {} handle is invalid!",
+                        source.0
                     );
                 }
             }
-            _ => (),
         }
-
-        if message.is_empty() {
-            Ok(())
-        } else {
-            Err(message)
+        ColliderShape::Compound(compound) => {
+            for part in compound.parts.iter() {
+                validate_shape(&part.shape.clone().into(), scene, message);
+            }
         }
+        _ => (),
     }
 }
 
@@ -974,6 +1294,8 @@ pub struct ColliderBuilder {
     solver_groups: InteractionGroups,
     friction_combine_rule: CoefficientCombineRule,
     restitution_combine_rule: CoefficientCombineRule,
+    material: Option<PhysicsMaterialResource>,
+    report_collision_events: bool,
 }
 
 impl ColliderBuilder {
@@ -990,6 +1312,8 @@ impl ColliderBuilder {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            material: None,
+            report_collision_events: false,
         }
     }
 
@@ -1047,6 +1371,18 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets desired physics material.
+    pub fn with_material(mut self, material: Option<PhysicsMaterialResource>) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Sets whether the physics engine should report collision events for the collider or not.
+    pub fn with_report_collision_events(mut self, report: bool) -> Self {
+        self.report_collision_events = report;
+        self
+    }
+
     /// Creates collider node, but does not add it to a graph.
     pub fn build_collider(self) -> Collider {
         Collider {
@@ -1060,7 +1396,12 @@ impl ColliderBuilder {
             solver_groups: self.solver_groups.into(),
             friction_combine_rule: self.friction_combine_rule.into(),
             restitution_combine_rule: self.restitution_combine_rule.into(),
+            material: self.material.into(),
+            report_collision_events: self.report_collision_events.into(),
             native: Cell::new(ColliderHandle::invalid()),
+            applied_material: Default::default(),
+            convex_decompositions: Default::default(),
+            pending_convex_decompositions: Default::default(),
         }
     }
 
@@ -1077,13 +1418,27 @@ impl ColliderBuilder {
 
 #[cfg(test)]
 mod test {
-    use crate::core::algebra::Vector2;
+    use crate::asset::untyped::ResourceKind;
+    use crate::core::algebra::{Matrix4, Point3, UnitQuaternion, Vector2, Vector3};
+    use crate::core::visitor::{Visit, Visitor};
     use crate::scene::{
         base::BaseBuilder,
-        collider::{ColliderBuilder, ColliderShape},
-        graph::Graph,
+        collider::{
+            Collider, ColliderBuilder, ColliderShape, CompoundPartShape, CompoundShapePart,
+            ConvexDecompositionShape, GeometrySource,
+        },
+        graph::{
+            physics::{Intersection, RayCastOptions},
+            Graph,
+        },
+        mesh::{
+            surface::{SurfaceBuilder, SurfaceData, SurfaceResource},
+            MeshBuilder,
+        },
+        physics_material::{PhysicsMaterial, PhysicsMaterialResource},
         rigidbody::{RigidBodyBuilder, RigidBodyType},
     };
+    use rapier3d::geometry::ColliderHandle;
 
     #[test]
     fn test_collider_intersect() {
@@ -1146,4 +1501,202 @@ mod test {
                 .count()
         );
     }
+
+    #[test]
+    fn test_convex_decomposition_and_compound() {
+        let mut graph = Graph::new();
+
+        // Two separate cubes form a concave shape that cannot be represented by a single hull.
+        let mut make_cube = |x: f32| {
+            MeshBuilder::new(BaseBuilder::new())
+                .with_surfaces(vec![SurfaceBuilder::new(SurfaceResource::new_ok(
+                    ResourceKind::Embedded,
+                    SurfaceData::make_cube(Matrix4::new_translation(&Vector3::new(x, 0.0, 0.0))),
+                ))
+                .build()])
+                .build(&mut graph)
+        };
+        let sources = vec![
+            GeometrySource(make_cube(-1.5)),
+            GeometrySource(make_cube(1.5)),
+        ];
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::convex_decomposition(sources.clone()))
+            .build(&mut graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        // There's no task pool, so the decomposition is computed right away when the native
+        // collider is created. The collider is synced before its rigid body, so it is created on
+        // the second update.
+        assert!(graph.physics.task_pool().is_none());
+        let update_and_check_decomposed = |graph: &mut Graph| {
+            for _ in 0..2 {
+                graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+            }
+            let collider_ref = graph[collider].as_collider();
+            assert_ne!(collider_ref.native.get(), ColliderHandle::invalid());
+            assert!(!collider_ref.is_convex_decomposition_pending());
+        };
+        update_and_check_decomposed(&mut graph);
+
+        let collider_ref = graph[collider].as_collider();
+        let hulls = {
+            let decompositions = collider_ref.convex_decompositions();
+            assert_eq!(decompositions.len(), 1);
+            assert!(decompositions[0].hulls.len() >= 2);
+            decompositions[0].hulls.clone()
+        };
+        let native = graph
+            .physics
+            .colliders
+            .get(collider_ref.native.get())
+            .unwrap();
+        assert_eq!(
+            native.shape().as_compound().unwrap().shapes().len(),
+            hulls.len()
+        );
+
+        // Generated hulls are saved together with the collider.
+        let mut visitor = Visitor::new();
+        graph[collider]
+            .as_collider_mut()
+            .visit("Collider", &mut visitor)
+            .unwrap();
+        let bytes = visitor.save_binary_to_vec().unwrap();
+        let mut visitor = Visitor::load_from_memory(&bytes).unwrap();
+        let mut loaded = Collider::default();
+        loaded.visit("Collider", &mut visitor).unwrap();
+        {
+            let decompositions = loaded.convex_decompositions();
+            assert_eq!(decompositions.len(), 1);
+            assert_eq!(decompositions[0].hulls, hulls);
+            assert_eq!(
+                decompositions[0].key,
+                graph[collider].as_collider().convex_decompositions()[0].key
+            );
+        }
+
+        // The same decomposition as a part of a compound shape must re-use cached hulls.
+        let offset = Vector3::new(0.0, 2.0, 0.0);
+        graph[collider]
+            .as_collider_mut()
+            .set_shape(ColliderShape::compound(vec![
+                CompoundShapePart::default(),
+                CompoundShapePart {
+                    position: offset,
+                    rotation: UnitQuaternion::identity(),
+                    shape: CompoundPartShape::ConvexDecomposition(ConvexDecompositionShape {
+                        sources,
+                        ..Default::default()
+                    }),
+                },
+            ]));
+
+        update_and_check_decomposed(&mut graph);
+
+        let collider_ref = graph[collider].as_collider();
+        let decompositions = collider_ref.convex_decompositions();
+        assert_eq!(decompositions.len(), 1);
+        assert_eq!(decompositions[0].hulls, hulls);
+        assert_eq!(decompositions[0].transform.translation.vector, offset);
+        let native = graph
+            .physics
+            .colliders
+            .get(collider_ref.native.get())
+            .unwrap();
+        assert_eq!(
+            native.shape().as_compound().unwrap().shapes().len(),
+            hulls.len() + 1
+        );
+    }
+
+    #[test]
+    fn test_physics_material() {
+        let mut graph = Graph::new();
+
+        let material = PhysicsMaterialResource::new_ok(
+            ResourceKind::Embedded,
+            PhysicsMaterial {
+                friction: 0.1,
+                restitution: 0.3,
+                density: Some(5.0),
+                surface_tag: "Ice".into(),
+                ..Default::default()
+            },
+        );
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(1.0, 1.0, 1.0))
+            .with_friction(1.0)
+            .with_density(Some(2.0))
+            .with_material(Some(material.clone()))
+            .build(&mut graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        let cast_ray = |graph: &Graph| {
+            let mut results = Vec::<Intersection>::new();
+            graph.physics.cast_ray(
+                RayCastOptions {
+                    ray_origin: Point3::new(0.0, 5.0, 0.0),
+                    ray_direction: Vector3::new(0.0, -1.0, 0.0),
+                    max_len: 10.0,
+                    groups: Default::default(),
+                    sort_results: true,
+                },
+                &mut results,
+            );
+            results[0].surface_tag.clone()
+        };
+        let native_friction = |graph: &Graph| {
+            graph
+                .physics
+                .colliders
+                .get(graph[collider].as_collider().native.get())
+                .unwrap()
+                .friction()
+        };
+        let native_density = |graph: &Graph| {
+            graph
+                .physics
+                .colliders
+                .get(graph[collider].as_collider().native.get())
+                .unwrap()
+                .density()
+        };
+
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+
+        assert_eq!(native_friction(&graph), 0.1);
+        assert_eq!(native_density(&graph), 5.0);
+        assert_eq!(cast_ray(&graph).as_str(), "Ice");
+
+        // The material overrides the properties of the collider, even if they're changed.
+        graph[collider].as_collider_mut().set_friction(0.9);
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        assert_eq!(native_friction(&graph), 0.1);
+
+        // Changes of the shared material (for example, when it is hot-reloaded) must be applied to
+        // the colliders that use it.
+        material.data_ref().as_loaded_mut().unwrap().friction = 0.7;
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        assert_eq!(native_friction(&graph), 0.7);
+
+        // Mutable access without actual changes must not be treated as a change.
+        let state = graph[collider].as_collider().material_state();
+        assert!(material.state().data().is_some());
+        let _ = material.data_ref().as_loaded_mut();
+        assert_eq!(graph[collider].as_collider().material_state(), state);
+
+        // Without a material the collider must use its own properties.
+        graph[collider].as_collider_mut().set_material(None);
+        graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        assert_eq!(native_friction(&graph), 0.9);
+        assert_eq!(native_density(&graph), 2.0);
+        assert!(cast_ray(&graph).is_empty());
+    }
 }
@@ -0,0 +1,643 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Structural difference between two graphs, that could be applied as a patch. See [`GraphDiff`]
+//! docs for more info.
+
+use crate::{
+    asset::untyped::UntypedResource,
+    core::{
+        log::Log,
+        pool::{Handle, PayloadContainer},
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    graph::{BaseSceneGraph, NodeHandleMap, SceneGraph, SceneGraphNode},
+    scene::{
+        base::SceneNodeId,
+        graph::Graph,
+        node::{container::NodeContainer, Node},
+        pivot::Pivot,
+    },
+};
+use fxhash::FxHashSet;
+use std::{
+    any::TypeId,
+    fmt::{Display, Formatter},
+};
+
+/// A changed property of a node. Its new value is stored in [`NodeChanges::node`], see
+/// [`NodeChanges::new_value`].
+#[derive(Debug, Default, Clone, Visit)]
+pub struct PropertyChange {
+    /// Path of the property (in the format of [`Reflect::resolve_path`]).
+    pub path: String,
+}
+
+/// A set of changed properties of a node.
+#[derive(Debug)]
+pub struct NodeChanges {
+    /// Id of the node.
+    pub id: SceneNodeId,
+    /// Name of the node.
+    pub name: String,
+    /// New name of the node, if it was changed. Names are not inheritable, so they're stored
+    /// separately.
+    pub new_name: Option<String>,
+    /// Changed properties.
+    pub properties: Vec<PropertyChange>,
+    /// A copy of the node from the "new" graph without any links to other nodes, it holds new
+    /// values of the changed properties. Values are stored as a part of the node, because only
+    /// entire nodes could be serialized.
+    pub node: Node,
+}
+
+impl Default for NodeChanges {
+    fn default() -> Self {
+        Self {
+            id: Default::default(),
+            name: Default::default(),
+            new_name: None,
+            properties: Default::default(),
+            node: placeholder_node(),
+        }
+    }
+}
+
+impl Visit for NodeChanges {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.id.visit("Id", &mut region)?;
+        self.name.visit("Name", &mut region)?;
+        self.new_name.visit("NewName", &mut region)?;
+        self.properties.visit("Properties", &mut region)?;
+        visit_node(&mut self.node, "Node", &mut region)
+    }
+}
+
+impl NodeChanges {
+    /// Returns a new value of the property at the given path, or `None` if the node has no
+    /// inheritable property at the path.
+    pub fn new_value(&self, path: &str) -> Option<Box<dyn Reflect>> {
+        let mut value = None;
+        (&self.node as &dyn Reflect).resolve_path(path, &mut |field| {
+            if let Ok(field) = field {
+                field.as_inheritable_variable(&mut |variable| {
+                    value = variable.map(|variable| variable.clone_value_box());
+                })
+            }
+        });
+        value
+    }
+}
+
+/// A node that was added to a graph.
+#[derive(Debug)]
+pub struct AddedNode {
+    /// Id of the node.
+    pub id: SceneNodeId,
+    /// Id of the parent node, `None` means the root node of the graph.
+    pub parent: Option<SceneNodeId>,
+    /// A copy of the node without any links to other nodes.
+    pub node: Node,
+}
+
+impl Default for AddedNode {
+    fn default() -> Self {
+        Self {
+            id: Default::default(),
+            parent: None,
+            node: placeholder_node(),
+        }
+    }
+}
+
+impl Visit for AddedNode {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.id.visit("Id", &mut region)?;
+        self.parent.visit("Parent", &mut region)?;
+        visit_node(&mut self.node, "Node", &mut region)
+    }
+}
+
+/// A node that was removed from a graph.
+#[derive(Debug, Default, Visit)]
+pub struct RemovedNode {
+    /// Id of the node.
+    pub id: SceneNodeId,
+    /// Name of the node.
+    pub name: String,
+}
+
+/// A node that was attached to another parent.
+#[derive(Debug, Default, Visit)]
+pub struct ReparentedNode {
+    /// Id of the node.
+    pub id: SceneNodeId,
+    /// Id of the new parent node, `None` means the root node of the graph.
+    pub parent: Option<SceneNodeId>,
+}
+
+/// Structural difference between two graphs (for example, two versions of a scene or a prefab).
+/// It contains added and removed nodes, nodes that were attached to other parents and changed
+/// properties of the nodes. The difference could be applied to a graph as a patch using
+/// [`Self::apply`], which transforms the "old" graph (or any other graph, which contains the
+/// same nodes) into the "new" one.
+///
+/// ## Matching
+///
+/// Nodes are matched by their [`SceneNodeId`], so the graphs must share the same ids for the
+/// same nodes. This is the case for a scene and its copy made with [`Graph::clone`] (or a copy
+/// saved to disk and loaded back), for example. Root nodes are always considered the same.
+///
+/// ## Properties
+///
+/// Only properties stored in [`crate::core::variable::InheritableVariable`] are compared, which
+/// is the case for every property of the built-in scene nodes (except names, which are compared
+/// separately). If a property that contains other inheritable properties has changed, only the
+/// outer property is stored in the diff.
+///
+/// ## Handles
+///
+/// Handles stored in added nodes and property values point to the nodes of the "new" graph,
+/// they're remapped to the nodes of the destination graph on [`Self::apply`] using node ids.
+///
+/// ## Serialization
+///
+/// The difference implements [`Visit`], so it could be saved to disk or sent over the network.
+/// Loading requires [`crate::engine::SerializationContext`] in the blackboard of the visitor,
+/// because the difference stores nodes.
+///
+/// ## Limitations
+///
+/// The order of children nodes is not tracked.
+#[derive(Debug, Default, Visit)]
+pub struct GraphDiff {
+    /// A set of added nodes. A parent node is always stored before its children.
+    pub added: Vec<AddedNode>,
+    /// A set of removed nodes.
+    pub removed: Vec<RemovedNode>,
+    /// A set of nodes that were attached to other parents.
+    pub reparented: Vec<ReparentedNode>,
+    /// A set of nodes with changed properties.
+    pub changed: Vec<NodeChanges>,
+    // Handles of the "new" graph and respective ids, used to remap handles.
+    handles: Vec<HandleId>,
+    new_root: Handle<Node>,
+}
+
+#[derive(Debug, Default, Clone, Visit)]
+struct HandleId {
+    handle: Handle<Node>,
+    id: SceneNodeId,
+}
+
+// Used only to fill nodes before deserialization.
+fn placeholder_node() -> Node {
+    Node::new(Pivot::default())
+}
+
+// Nodes must be visited through a container, because the type of a node must be known to load it.
+fn visit_node(node: &mut Node, name: &str, visitor: &mut Visitor) -> VisitResult {
+    let mut container = if visitor.is_reading() {
+        NodeContainer::default()
+    } else {
+        NodeContainer::new(std::mem::replace(node, placeholder_node()))
+    };
+    let result = container.visit(name, visitor);
+    if let Some(visited) = container.take() {
+        *node = visited;
+    }
+    result
+}
+
+fn detached_copy(node: &Node) -> Node {
+    let mut copy = node.clone_box();
+    copy.parent = Handle::NONE;
+    copy.children.clear();
+    copy
+}
+
+fn parent_id(graph: &Graph, node: &Node) -> Option<SceneNodeId> {
+    let parent = node.parent();
+    if parent == graph.get_root() {
+        None
+    } else {
+        graph.try_get(parent).map(|parent| parent.instance_id())
+    }
+}
+
+fn is_sub_path(path: &str, parent: &str) -> bool {
+    path.strip_prefix(parent)
+        .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+}
+
+fn diff_properties(old: &Node, new: &Node) -> Vec<PropertyChange> {
+    let ignored_types = [TypeId::of::<UntypedResource>()];
+
+    let mut paths = Vec::<String>::new();
+    (new as &dyn Reflect).enumerate_fields_recursively(
+        &mut |path, _, value| {
+            value.as_inheritable_variable(&mut |variable| {
+                if variable.is_some() && paths.last().map(|p| p.as_str()) != Some(path) {
+                    paths.push(path.to_string());
+                }
+            })
+        },
+        &ignored_types,
+    );
+
+    let mut changes = Vec::new();
+    let mut changed_parent: Option<String> = None;
+    for path in paths {
+        if changed_parent
+            .as_ref()
+            .is_some_and(|parent| is_sub_path(&path, parent))
+        {
+            // The entire outer property will be replaced.
+            continue;
+        }
+
+        (new as &dyn Reflect).resolve_path(&path, &mut |new_field| {
+            let Ok(new_field) = new_field else {
+                return;
+            };
+            new_field.as_inheritable_variable(&mut |new_variable| {
+                let Some(new_variable) = new_variable else {
+                    return;
+                };
+
+                let mut equals = false;
+                (old as &dyn Reflect).resolve_path(&path, &mut |old_field| {
+                    if let Ok(old_field) = old_field {
+                        old_field.as_inheritable_variable(&mut |old_variable| {
+                            if let Some(old_variable) = old_variable {
+                                equals = new_variable.value_equals(old_variable);
+                            }
+                        })
+                    }
+                });
+
+                if !equals {
+                    changes.push(PropertyChange { path: path.clone() });
+                    changed_parent = Some(path.clone());
+                }
+            })
+        });
+    }
+    changes
+}
+
+impl GraphDiff {
+    /// Calculates the difference between two graphs. See the type docs for more info.
+    pub fn new(old: &Graph, new: &Graph) -> Self {
+        let old_root = old.get_root();
+        let new_root = new.get_root();
+
+        let mut diff = Self {
+            new_root,
+            ..Default::default()
+        };
+
+        let mut visited = FxHashSet::default();
+        for (handle, new_node) in new.traverse_iter(new_root) {
+            if handle == new_root {
+                continue;
+            }
+
+            let id = new_node.instance_id();
+            visited.insert(id);
+            diff.handles.push(HandleId { handle, id });
+
+            let new_parent = parent_id(new, new_node);
+
+            let old_node = old
+                .id_to_node_handle(id)
+                .and_then(|old_handle| old.try_get(*old_handle))
+                .filter(|old_node| {
+                    // Nodes of different types cannot be compared.
+                    (*old_node as &dyn Reflect).type_name()
+                        == (new_node as &dyn Reflect).type_name()
+                });
+
+            if let Some(old_node) = old_node {
+                if parent_id(old, old_node) != new_parent {
+                    diff.reparented.push(ReparentedNode {
+                        id,
+                        parent: new_parent,
+                    });
+                }
+
+                let properties = diff_properties(old_node, new_node);
+                let new_name = (old_node.name() != new_node.name()).then(|| new_node.name_owned());
+                if !properties.is_empty() || new_name.is_some() {
+                    diff.changed.push(NodeChanges {
+                        id,
+                        name: old_node.name_owned(),
+                        new_name,
+                        properties,
+                        node: detached_copy(new_node),
+                    });
+                }
+            } else {
+                diff.added.push(AddedNode {
+                    id,
+                    parent: new_parent,
+                    node: detached_copy(new_node),
+                });
+            }
+        }
+
+        for (handle, old_node) in old.pair_iter() {
+            let id = old_node.instance_id();
+            let is_same_type = || {
+                new.id_to_node_handle(id)
+                    .and_then(|new_handle| new.try_get(*new_handle))
+                    .is_some_and(|new_node| {
+                        (new_node as &dyn Reflect).type_name()
+                            == (old_node as &dyn Reflect).type_name()
+                    })
+            };
+            if handle != old_root && (!visited.contains(&id) || !is_same_type()) {
+                diff.removed.push(RemovedNode {
+                    id,
+                    name: old_node.name_owned(),
+                });
+            }
+        }
+
+        diff
+    }
+
+    /// Returns `true` if the graphs are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.reparented.is_empty()
+            && self.changed.is_empty()
+    }
+
+    /// Applies the difference to the given graph. Every node that cannot be found in the graph
+    /// is skipped with a warning.
+    pub fn apply(self, graph: &mut Graph) {
+        let root = graph.get_root();
+
+        // Remove nodes first, but keep the ones that were just replaced by nodes of other types.
+        for removed in self.removed {
+            if let Some(handle) = graph.id_to_node_handle(removed.id).cloned() {
+                // Detach children first, they will be re-attached or removed later.
+                for child in graph[handle].children().to_vec() {
+                    graph.link_nodes(child, root);
+                }
+                graph.remove_node(handle);
+            } else {
+                Log::warn(format!(
+                    "Unable to remove node {} ({}), because it does not exist.",
+                    removed.name, removed.id.0
+                ));
+            }
+        }
+
+        let mut links = Vec::with_capacity(self.added.len() + self.reparented.len());
+        let mut added = Vec::with_capacity(self.added.len());
+        for added_node in self.added {
+            added.push(graph.add_node(added_node.node));
+            links.push((added_node.id, added_node.parent));
+        }
+        links.extend(
+            self.reparented
+                .into_iter()
+                .map(|reparented| (reparented.id, reparented.parent)),
+        );
+
+        let find = |graph: &Graph, id: Option<SceneNodeId>| match id {
+            Some(id) => graph.id_to_node_handle(id).cloned(),
+            None => Some(root),
+        };
+
+        for (id, parent) in links {
+            match (find(graph, Some(id)), find(graph, parent)) {
+                (Some(child), Some(parent)) => graph.link_nodes(child, parent),
+                _ => Log::warn(format!("Unable to link node {}.", id.0)),
+            }
+        }
+
+        let mut handle_map = NodeHandleMap::default();
+        handle_map.insert(self.new_root, root);
+        for HandleId { handle, id } in self.handles {
+            if let Some(target) = find(graph, Some(id)) {
+                handle_map.insert(handle, target);
+            }
+        }
+
+        let ignored_types = [TypeId::of::<UntypedResource>()];
+
+        for handle in added {
+            handle_map.remap_handles(&mut graph[handle], &ignored_types);
+        }
+
+        for changes in self.changed {
+            let Some(handle) = find(graph, Some(changes.id)) else {
+                Log::warn(format!(
+                    "Unable to change properties of node {} ({}), because it does not exist.",
+                    changes.name, changes.id.0
+                ));
+                continue;
+            };
+
+            let node = &mut graph[handle];
+            if let Some(new_name) = changes.new_name.clone() {
+                node.set_name(new_name);
+            }
+            for change in changes.properties.iter() {
+                let Some(mut value) = changes.new_value(&change.path) else {
+                    Log::warn(format!(
+                        "Unable to find a new value of property {} of node {}.",
+                        change.path, changes.name
+                    ));
+                    continue;
+                };
+
+                handle_map.remap_handles_in(&mut *value, &changes.name, &ignored_types);
+
+                (node as &mut dyn Reflect).set_field_by_path(&change.path, value, &mut |result| {
+                    if result.is_err() {
+                        Log::warn(format!(
+                            "Unable to set property {} of node {}.",
+                            change.path, changes.name
+                        ))
+                    }
+                });
+            }
+        }
+    }
+}
+
+impl Display for GraphDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for added in self.added.iter() {
+            writeln!(f, "+ {} ({})", added.node.name(), added.id.0)?;
+        }
+        for removed in self.removed.iter() {
+            writeln!(f, "- {} ({})", removed.name, removed.id.0)?;
+        }
+        for reparented in self.reparented.iter() {
+            match reparented.parent {
+                Some(parent) => writeln!(f, "> {} -> {}", reparented.id.0, parent.0)?,
+                None => writeln!(f, "> {} -> <root>", reparented.id.0)?,
+            }
+        }
+        for changes in self.changed.iter() {
+            writeln!(f, "* {} ({})", changes.name, changes.id.0)?;
+            if let Some(new_name) = changes.new_name.as_ref() {
+                writeln!(f, "    name = {new_name:?}")?;
+            }
+            for property in changes.properties.iter() {
+                match changes.new_value(&property.path) {
+                    Some(value) => writeln!(f, "    {} = {:?}", property.path, value)?,
+                    None => writeln!(f, "    {} = <unknown>", property.path)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, visitor::prelude::*},
+        engine::SerializationContext,
+        graph::{BaseSceneGraph, SceneGraph},
+        scene::{
+            base::BaseBuilder, diff::GraphDiff, graph::Graph, pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+    use std::sync::Arc;
+
+    fn make_graph() -> Graph {
+        let mut graph = Graph::new();
+        let a = PivotBuilder::new(BaseBuilder::new().with_name("A")).build(&mut graph);
+        let b = PivotBuilder::new(BaseBuilder::new().with_name("B")).build(&mut graph);
+        let c = PivotBuilder::new(BaseBuilder::new().with_name("C")).build(&mut graph);
+        graph.link_nodes(b, a);
+        graph.link_nodes(c, b);
+        graph
+    }
+
+    // Returns the "old" graph and the "new" one, made of the old one by adding, removing,
+    // reparenting and changing its nodes.
+    fn make_changed_graphs() -> (Graph, Graph) {
+        let old = make_graph();
+        let (mut new, _) = old.clone(
+            old.get_root(),
+            &mut |_, _| true,
+            &mut |_, _| {},
+            &mut |_, _, _| {},
+        );
+
+        assert!(GraphDiff::new(&old, &new).is_empty());
+
+        let a = new.find_by_name_from_root("A").unwrap().0;
+        let b = new.find_by_name_from_root("B").unwrap().0;
+        let c = new.find_by_name_from_root("C").unwrap().0;
+
+        // Reparent C to A and remove B.
+        new.link_nodes(c, a);
+        new.remove_node(b);
+
+        // Change properties of A.
+        new[a].set_name("NewA");
+        new[a]
+            .local_transform_mut()
+            .set_position(Vector3::new(1.0, 2.0, 3.0));
+
+        // Add a new node.
+        let d = PivotBuilder::new(
+            BaseBuilder::new().with_name("D").with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(4.0, 5.0, 6.0))
+                    .build(),
+            ),
+        )
+        .build(&mut new);
+        new.link_nodes(d, c);
+
+        (old, new)
+    }
+
+    fn check_patched(patched: &Graph, new: &Graph) {
+        assert!(GraphDiff::new(patched, new).is_empty());
+
+        let a = patched.find_by_name_from_root("NewA").unwrap().0;
+        assert_eq!(
+            **patched[a].local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+        assert!(patched.find_by_name_from_root("B").is_none());
+        let c = patched.find_by_name_from_root("C").unwrap().0;
+        assert_eq!(patched[c].parent(), a);
+        let d = patched.find_by_name_from_root("D").unwrap().0;
+        assert_eq!(patched[d].parent(), c);
+    }
+
+    #[test]
+    fn test_graph_diff() {
+        let (old, new) = make_changed_graphs();
+
+        let diff = GraphDiff::new(&old, &new);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.reparented.len(), 1);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].new_name.as_deref(), Some("NewA"));
+        assert_eq!(diff.changed[0].properties.len(), 1);
+
+        let mut patched = old;
+        diff.apply(&mut patched);
+        check_patched(&patched, &new);
+    }
+
+    #[test]
+    fn test_graph_diff_serialization() {
+        let (old, new) = make_changed_graphs();
+
+        let mut diff = GraphDiff::new(&old, &new);
+        let mut visitor = Visitor::new();
+        diff.visit("Diff", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_binary_from_memory(&data).unwrap();
+        visitor
+            .blackboard
+            .register(Arc::new(SerializationContext::new()));
+        let mut loaded = GraphDiff::default();
+        loaded.visit("Diff", &mut visitor).unwrap();
+        assert_eq!(loaded.to_string(), diff.to_string());
+
+        let mut patched = old;
+        loaded.apply(&mut patched);
+        check_patched(&patched, &new);
+    }
+}
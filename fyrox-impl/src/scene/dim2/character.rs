@@ -0,0 +1,565 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! 2D character controller is a kinematic physics entity, that moves a character through the
+//! world while correctly handling slopes, stairs and collisions with the environment. See
+//! [`CharacterController`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Vector2},
+        log::Log,
+        math::{aabb::AxisAlignedBoundingBox, m4x4_approx_eq},
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        character::CharacterAutostep,
+        dim2::collider::Collider,
+        graph::Graph,
+        node::{constructor::NodeConstructor, Node, NodeTrait, SyncContext, UpdateContext},
+        Scene,
+    },
+};
+use fyrox_graph::{constructor::ConstructorProvider, BaseSceneGraph, SceneGraph};
+use rapier2d::prelude::RigidBodyHandle;
+use std::{
+    cell::Cell,
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+};
+
+/// A collision between a character and its environment, that has happened during the last
+/// movement of a character.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CharacterCollision {
+    /// A handle of a collider, that was hit by the character.
+    pub collider: Handle<Node>,
+    /// World-space position of the character at the moment of the hit.
+    pub character_position: Vector2<f32>,
+    /// A translation, that was already applied to the character at the moment of the hit.
+    pub translation_applied: Vector2<f32>,
+    /// A translation, that was still waiting to be applied to the character at the moment of the
+    /// hit.
+    pub translation_remaining: Vector2<f32>,
+    /// World-space normal of the surface at the contact point.
+    pub normal: Vector2<f32>,
+    /// A fraction of the movement, at which the hit has happened.
+    pub toi: f32,
+}
+
+/// 2D character controller is a kinematic physics entity, that moves a character through the
+/// world while correctly handling slopes, stairs and collisions with the environment. It works
+/// exactly the same as its 3D counterpart, see [`crate::scene::character::CharacterController`]
+/// docs for more info. The first 2D collider, that is a direct child of the controller, defines
+/// the shape of the character.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::{algebra::Vector2, pool::Handle},
+/// #     graph::SceneGraph,
+/// #     scene::{dim2::character::CharacterController, graph::Graph, node::Node},
+/// # };
+/// fn jump(graph: &mut Graph, character: Handle<Node>, jump_height: f32) {
+///     if let Some(character) = graph.try_get_mut_of_type::<CharacterController>(character) {
+///         if character.is_grounded() {
+///             character.set_desired_translation(Vector2::new(0.0, jump_height));
+///         }
+///     }
+/// }
+/// ```
+#[derive(Visit, Reflect, ComponentProvider)]
+pub struct CharacterController {
+    base: Base,
+
+    #[reflect(min_value = 0.0, step = 0.005)]
+    #[reflect(setter = "set_offset")]
+    pub(crate) offset: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_slide")]
+    pub(crate) slide: InheritableVariable<bool>,
+
+    #[reflect(min_value = 0.0, max_value = 3.15, step = 0.01)]
+    #[reflect(setter = "set_max_slope_climb_angle")]
+    pub(crate) max_slope_climb_angle: InheritableVariable<f32>,
+
+    #[reflect(min_value = 0.0, max_value = 3.15, step = 0.01)]
+    #[reflect(setter = "set_min_slope_slide_angle")]
+    pub(crate) min_slope_slide_angle: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_autostep")]
+    pub(crate) autostep: InheritableVariable<CharacterAutostep>,
+
+    #[reflect(min_value = 0.0, step = 0.05)]
+    #[reflect(setter = "set_snap_to_ground")]
+    pub(crate) snap_to_ground: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_push_dynamic_bodies")]
+    pub(crate) push_dynamic_bodies: InheritableVariable<bool>,
+
+    #[reflect(min_value = 0.0, step = 0.05)]
+    #[reflect(setter = "set_mass")]
+    pub(crate) mass: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_inherit_platform_velocity")]
+    pub(crate) inherit_platform_velocity: InheritableVariable<bool>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) desired_translation: Vector2<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) effective_translation: Vector2<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) grounded: bool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) sliding_down_slope: bool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) platform_velocity: Vector2<f32>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) collisions: Vec<CharacterCollision>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) native: Cell<RigidBodyHandle>,
+}
+
+impl Debug for CharacterController {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CharacterController")
+    }
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        CharacterControllerBuilder::new(BaseBuilder::new()).build_character_controller()
+    }
+}
+
+impl Deref for CharacterController {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for CharacterController {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Clone for CharacterController {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            offset: self.offset.clone(),
+            slide: self.slide.clone(),
+            max_slope_climb_angle: self.max_slope_climb_angle.clone(),
+            min_slope_slide_angle: self.min_slope_slide_angle.clone(),
+            autostep: self.autostep.clone(),
+            snap_to_ground: self.snap_to_ground.clone(),
+            push_dynamic_bodies: self.push_dynamic_bodies.clone(),
+            mass: self.mass.clone(),
+            inherit_platform_velocity: self.inherit_platform_velocity.clone(),
+            desired_translation: self.desired_translation,
+            effective_translation: self.effective_translation,
+            grounded: self.grounded,
+            sliding_down_slope: self.sliding_down_slope,
+            platform_velocity: self.platform_velocity,
+            collisions: self.collisions.clone(),
+            // Do not copy. The copy will have its own native representation.
+            native: Cell::new(RigidBodyHandle::invalid()),
+        }
+    }
+}
+
+impl TypeUuidProvider for CharacterController {
+    fn type_uuid() -> Uuid {
+        uuid!("a344a856-15cb-4eaf-81fd-d40031d1b1ab")
+    }
+}
+
+impl CharacterController {
+    /// Sets a small gap (in meters) to preserve between the character and its surroundings. It
+    /// should not be too large to avoid visual artifacts, but it should not be zero to keep the
+    /// movement numerically stable.
+    pub fn set_offset(&mut self, offset: f32) -> f32 {
+        self.offset.set_value_and_mark_modified(offset)
+    }
+
+    /// Returns current gap between the character and its surroundings.
+    pub fn offset(&self) -> f32 {
+        *self.offset
+    }
+
+    /// Defines whether the character should slide along obstacles (walls, floor, etc.) or stop
+    /// when it hits them.
+    pub fn set_slide(&mut self, slide: bool) -> bool {
+        self.slide.set_value_and_mark_modified(slide)
+    }
+
+    /// Returns `true` if the character slides along obstacles, `false` - otherwise.
+    pub fn is_slide(&self) -> bool {
+        *self.slide
+    }
+
+    /// Sets the maximum angle (in radians) between the floor normal and the up vector, that the
+    /// character is able to climb.
+    pub fn set_max_slope_climb_angle(&mut self, angle: f32) -> f32 {
+        self.max_slope_climb_angle
+            .set_value_and_mark_modified(angle)
+    }
+
+    /// Returns the maximum angle (in radians) of a slope, that the character is able to climb.
+    pub fn max_slope_climb_angle(&self) -> f32 {
+        *self.max_slope_climb_angle
+    }
+
+    /// Sets the minimum angle (in radians) between the floor normal and the up vector, at which
+    /// the character starts to slide down automatically.
+    pub fn set_min_slope_slide_angle(&mut self, angle: f32) -> f32 {
+        self.min_slope_slide_angle
+            .set_value_and_mark_modified(angle)
+    }
+
+    /// Returns the minimum angle (in radians) of a slope, at which the character starts to slide
+    /// down automatically.
+    pub fn min_slope_slide_angle(&self) -> f32 {
+        *self.min_slope_slide_angle
+    }
+
+    /// Sets new auto-stepping settings. See [`CharacterAutostep`] docs for more info.
+    pub fn set_autostep(&mut self, autostep: CharacterAutostep) -> CharacterAutostep {
+        self.autostep.set_value_and_mark_modified(autostep)
+    }
+
+    /// Returns current auto-stepping settings.
+    pub fn autostep(&self) -> &CharacterAutostep {
+        &self.autostep
+    }
+
+    /// Sets the maximum distance (in meters) to the ground, at which the character will be
+    /// snapped to it. This prevents the character from "flying" off the ground when it moves
+    /// down a slope or stairs. Zero disables snapping.
+    pub fn set_snap_to_ground(&mut self, distance: f32) -> f32 {
+        self.snap_to_ground.set_value_and_mark_modified(distance)
+    }
+
+    /// Returns the maximum distance to the ground, at which the character will be snapped to it.
+    pub fn snap_to_ground(&self) -> f32 {
+        *self.snap_to_ground
+    }
+
+    /// Defines whether the character should push dynamic rigid bodies it collides with or not.
+    pub fn set_push_dynamic_bodies(&mut self, push: bool) -> bool {
+        self.push_dynamic_bodies.set_value_and_mark_modified(push)
+    }
+
+    /// Returns `true` if the character pushes dynamic rigid bodies, `false` - otherwise.
+    pub fn is_push_dynamic_bodies(&self) -> bool {
+        *self.push_dynamic_bodies
+    }
+
+    /// Sets the mass of the character, that is used to calculate impulses applied to dynamic
+    /// rigid bodies when the character pushes them.
+    pub fn set_mass(&mut self, mass: f32) -> f32 {
+        self.mass.set_value_and_mark_modified(mass)
+    }
+
+    /// Returns the mass of the character.
+    pub fn mass(&self) -> f32 {
+        *self.mass
+    }
+
+    /// Defines whether the character should move together with a moving platform (a kinematic or
+    /// dynamic rigid body) it stands on or not. The platform must not be steeper than the maximum
+    /// slope climb angle (see [`Self::set_max_slope_climb_angle`]).
+    pub fn set_inherit_platform_velocity(&mut self, inherit: bool) -> bool {
+        self.inherit_platform_velocity
+            .set_value_and_mark_modified(inherit)
+    }
+
+    /// Returns `true` if the character moves together with a moving platform it stands on.
+    pub fn is_inherit_platform_velocity(&self) -> bool {
+        *self.inherit_platform_velocity
+    }
+
+    /// Returns the velocity of a platform at the point the character was standing on it during
+    /// the last update. It is zero if the character wasn't standing on a moving platform. See
+    /// [`Self::set_inherit_platform_velocity`] for more info.
+    pub fn platform_velocity(&self) -> Vector2<f32> {
+        self.platform_velocity
+    }
+
+    /// Sets a desired translation (in world space) of the character for the next update. The
+    /// controller will try to move the character as far as possible in the given direction. The
+    /// translation is reset after each update, so it must be set every frame.
+    pub fn set_desired_translation(&mut self, translation: Vector2<f32>) {
+        self.desired_translation = translation;
+    }
+
+    /// Returns current desired translation of the character.
+    pub fn desired_translation(&self) -> Vector2<f32> {
+        self.desired_translation
+    }
+
+    /// Returns a translation, that was actually applied to the character during the last update.
+    /// It includes the movement of a platform the character stands on.
+    pub fn effective_translation(&self) -> Vector2<f32> {
+        self.effective_translation
+    }
+
+    /// Returns `true` if the character was standing on the ground after the last update, `false` -
+    /// otherwise.
+    pub fn is_grounded(&self) -> bool {
+        self.grounded
+    }
+
+    /// Returns `true` if the character was sliding down a slope during the last update, `false` -
+    /// otherwise. See [`Self::set_min_slope_slide_angle`] for more info.
+    pub fn is_sliding_down_slope(&self) -> bool {
+        self.sliding_down_slope
+    }
+
+    /// Returns a list of collisions, that have happened during the last update.
+    pub fn collisions(&self) -> &[CharacterCollision] {
+        &self.collisions
+    }
+}
+
+impl ConstructorProvider<Node, Graph> for CharacterController {
+    fn constructor() -> NodeConstructor {
+        NodeConstructor::new::<Self>()
+            .with_variant("Character Controller 2D", |_| {
+                CharacterControllerBuilder::new(
+                    BaseBuilder::new().with_name("Character Controller 2D"),
+                )
+                .build_node()
+                .into()
+            })
+            .with_group("Physics")
+    }
+}
+
+impl NodeTrait for CharacterController {
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn on_removed_from_graph(&mut self, graph: &mut Graph) {
+        graph.physics2d.remove_body(self.native.get());
+        self.native.set(RigidBodyHandle::invalid());
+
+        Log::info(format!(
+            "Native character controller was removed for node: {}",
+            self.name()
+        ));
+    }
+
+    fn sync_native(&self, self_handle: Handle<Node>, context: &mut SyncContext) {
+        context
+            .physics2d
+            .sync_to_character_controller_node(self_handle, self);
+    }
+
+    fn on_global_transform_changed(
+        &self,
+        new_global_transform: &Matrix4<f32>,
+        context: &mut SyncContext,
+    ) {
+        if !m4x4_approx_eq(new_global_transform, &self.global_transform()) {
+            context
+                .physics2d
+                .set_character_controller_position(self, new_global_transform);
+        }
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        // The character is moved by the fixed steps of the physics, if the physics is not
+        // stepped by the regular update. See [`crate::scene::graph::Graph::fixed_update`].
+        if context.switches.is_some_and(|switches| !switches.physics2d) {
+            return;
+        }
+
+        context.physics2d.move_character(
+            self,
+            // Character controller can be root node of a scene, in this case it does not have a
+            // parent.
+            context
+                .nodes
+                .try_borrow(self.parent)
+                .map(|p| p.global_transform())
+                .unwrap_or_else(Matrix4::identity),
+            context.dt,
+        );
+    }
+
+    fn validate(&self, scene: &Scene) -> Result<(), String> {
+        for &child in self.children() {
+            if scene.graph.try_get_of_type::<Collider>(child).is_some() {
+                return Ok(());
+            }
+        }
+
+        Err(
+            "The 2D character controller must have at least one 2D collider as a \
+        direct child node to work correctly!"
+                .to_string(),
+        )
+    }
+}
+
+/// Allows you to create 2D character controller in declarative manner.
+pub struct CharacterControllerBuilder {
+    base_builder: BaseBuilder,
+    offset: f32,
+    slide: bool,
+    max_slope_climb_angle: f32,
+    min_slope_slide_angle: f32,
+    autostep: CharacterAutostep,
+    snap_to_ground: f32,
+    push_dynamic_bodies: bool,
+    mass: f32,
+    inherit_platform_velocity: bool,
+}
+
+impl CharacterControllerBuilder {
+    /// Creates new character controller builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            offset: 0.01,
+            slide: true,
+            max_slope_climb_angle: 45.0f32.to_radians(),
+            min_slope_slide_angle: 30.0f32.to_radians(),
+            autostep: Default::default(),
+            snap_to_ground: 0.2,
+            push_dynamic_bodies: true,
+            mass: 80.0,
+            inherit_platform_velocity: true,
+        }
+    }
+
+    /// Sets the desired gap between the character and its surroundings.
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets whether the character should slide along obstacles or not.
+    pub fn with_slide(mut self, slide: bool) -> Self {
+        self.slide = slide;
+        self
+    }
+
+    /// Sets the desired maximum angle (in radians) of a slope, that the character is able to climb.
+    pub fn with_max_slope_climb_angle(mut self, angle: f32) -> Self {
+        self.max_slope_climb_angle = angle;
+        self
+    }
+
+    /// Sets the desired minimum angle (in radians) of a slope, at which the character starts to
+    /// slide down.
+    pub fn with_min_slope_slide_angle(mut self, angle: f32) -> Self {
+        self.min_slope_slide_angle = angle;
+        self
+    }
+
+    /// Sets the desired auto-stepping settings.
+    pub fn with_autostep(mut self, autostep: CharacterAutostep) -> Self {
+        self.autostep = autostep;
+        self
+    }
+
+    /// Sets the desired snap-to-ground distance. Zero disables snapping.
+    pub fn with_snap_to_ground(mut self, distance: f32) -> Self {
+        self.snap_to_ground = distance;
+        self
+    }
+
+    /// Sets whether the character should push dynamic rigid bodies or not.
+    pub fn with_push_dynamic_bodies(mut self, push: bool) -> Self {
+        self.push_dynamic_bodies = push;
+        self
+    }
+
+    /// Sets the desired mass of the character.
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Sets whether the character should move together with a moving platform it stands on or not.
+    pub fn with_inherit_platform_velocity(mut self, inherit: bool) -> Self {
+        self.inherit_platform_velocity = inherit;
+        self
+    }
+
+    /// Creates CharacterController node but does not add it to the graph.
+    pub fn build_character_controller(self) -> CharacterController {
+        CharacterController {
+            base: self.base_builder.build_base(),
+            offset: self.offset.into(),
+            slide: self.slide.into(),
+            max_slope_climb_angle: self.max_slope_climb_angle.into(),
+            min_slope_slide_angle: self.min_slope_slide_angle.into(),
+            autostep: self.autostep.into(),
+            snap_to_ground: self.snap_to_ground.into(),
+            push_dynamic_bodies: self.push_dynamic_bodies.into(),
+            mass: self.mass.into(),
+            inherit_platform_velocity: self.inherit_platform_velocity.into(),
+            desired_translation: Default::default(),
+            effective_translation: Default::default(),
+            grounded: false,
+            sliding_down_slope: false,
+            platform_velocity: Default::default(),
+            collisions: Default::default(),
+            native: Cell::new(RigidBodyHandle::invalid()),
+        }
+    }
+
+    /// Creates CharacterController node but does not add it to the graph.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_character_controller())
+    }
+
+    /// Creates CharacterController node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
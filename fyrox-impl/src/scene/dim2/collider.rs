@@ -41,11 +41,13 @@ use crate::{
         base::{Base, BaseBuilder},
         collider::InteractionGroups,
         dim2::{
+            character::CharacterController,
             physics::{ContactPair, IntersectionPair, PhysicsWorld},
             rigidbody::RigidBody,
         },
         graph::{physics::CoefficientCombineRule, Graph},
         node::{Node, NodeTrait, SyncContext},
+        physics_material::{PhysicsMaterial, PhysicsMaterialResource},
         Scene,
     },
 };
@@ -298,9 +300,23 @@ pub struct Collider {
     #[reflect(setter = "set_restitution_combine_rule")]
     pub(crate) restitution_combine_rule: InheritableVariable<CoefficientCombineRule>,
 
+    #[reflect(setter = "set_material")]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) material: InheritableVariable<Option<PhysicsMaterialResource>>,
+
+    #[reflect(setter = "set_report_collision_events")]
+    #[visit(optional)] // Backward compatibility
+    pub(crate) report_collision_events: InheritableVariable<bool>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) native: Cell<ColliderHandle>,
+
+    // Key and content hash of the physics material, that was applied to the native collider the
+    // last time.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) applied_material: Cell<Option<(u64, u64)>>,
 }
 
 impl Default for Collider {
@@ -316,7 +332,10 @@ impl Default for Collider {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            material: Default::default(),
+            report_collision_events: Default::default(),
             native: Cell::new(ColliderHandle::invalid()),
+            applied_material: Default::default(),
         }
     }
 }
@@ -348,8 +367,11 @@ impl Clone for Collider {
             solver_groups: self.solver_groups.clone(),
             friction_combine_rule: self.friction_combine_rule.clone(),
             restitution_combine_rule: self.restitution_combine_rule.clone(),
+            material: self.material.clone(),
+            report_collision_events: self.report_collision_events.clone(),
             // Do not copy. The copy will have its own native representation.
             native: Cell::new(ColliderHandle::invalid()),
+            applied_material: Default::default(),
         }
     }
 }
@@ -501,6 +523,20 @@ impl Collider {
         *self.is_sensor
     }
 
+    /// Defines whether the physics engine should report collision events (see
+    /// [`crate::script::ScriptTrait::on_collision`]) for the collider or not. An event is reported
+    /// if at least one of the two colliders has this flag set. Collision events are disabled by
+    /// default, because collecting them for every collider in a scene is quite expensive.
+    pub fn set_report_collision_events(&mut self, report: bool) -> bool {
+        self.report_collision_events
+            .set_value_and_mark_modified(report)
+    }
+
+    /// Returns `true` if the physics engine reports collision events for the collider.
+    pub fn is_report_collision_events(&self) -> bool {
+        *self.report_collision_events
+    }
+
     /// Sets the new friction combine rule. See [`CoefficientCombineRule`] docs for more info.
     ///
     /// # Performance
@@ -540,6 +576,58 @@ impl Collider {
         *self.restitution_combine_rule
     }
 
+    /// Sets the new physics material of the collider. The material overrides friction, restitution,
+    /// their combine rules and density (if the material defines it) of the collider. `None` means
+    /// that the collider's own properties will be used. See [`PhysicsMaterial`] docs for more info.
+    pub fn set_material(
+        &mut self,
+        material: Option<PhysicsMaterialResource>,
+    ) -> Option<PhysicsMaterialResource> {
+        self.material.set_value_and_mark_modified(material)
+    }
+
+    /// Returns current physics material of the collider.
+    pub fn material(&self) -> Option<PhysicsMaterialResource> {
+        (*self.material).clone()
+    }
+
+    /// Returns surface properties that are actually used by the physics engine. These are the
+    /// properties of the physics material (if it is assigned and loaded) or the properties of the
+    /// collider itself otherwise. If the material does not define density, the density of the
+    /// collider is used.
+    pub fn effective_material(&self) -> PhysicsMaterial {
+        if let Some(material) = self.material.as_ref() {
+            if let Some(material) = material.data_ref().as_loaded_ref() {
+                return PhysicsMaterial {
+                    density: material.density.or(*self.density),
+                    ..material.clone()
+                };
+            }
+        }
+
+        PhysicsMaterial {
+            friction: *self.friction,
+            friction_combine_rule: *self.friction_combine_rule,
+            restitution: *self.restitution,
+            restitution_combine_rule: *self.restitution_combine_rule,
+            density: *self.density,
+            surface_tag: Default::default(),
+        }
+    }
+
+    // Returns the key and the content hash of the physics material, they change if the material
+    // is replaced or its properties are changed (including hot reloading).
+    pub(crate) fn material_state(&self) -> Option<(u64, u64)> {
+        self.material.as_ref().map(|material| {
+            let hash = material
+                .data_ref()
+                .as_loaded_ref()
+                .map(PhysicsMaterial::content_hash)
+                .unwrap_or_default();
+            (material.key(), hash)
+        })
+    }
+
     /// Returns an iterator that yields contact information for the collider.
     /// Contacts checks between two regular colliders
     pub fn contacts<'a>(
@@ -568,6 +656,8 @@ impl Collider {
             || self.solver_groups.need_sync()
             || self.friction_combine_rule.need_sync()
             || self.restitution_combine_rule.need_sync()
+            || self.material.need_sync()
+            || self.report_collision_events.need_sync()
     }
 }
 
@@ -641,11 +731,14 @@ impl NodeTrait for Collider {
         if scene
             .graph
             .try_get(self.parent())
-            .and_then(|p| p.component_ref::<RigidBody>())
+            .filter(|p| {
+                p.component_ref::<RigidBody>().is_some()
+                    || p.component_ref::<CharacterController>().is_some()
+            })
             .is_none()
         {
-            message += "2D Collider must be a direct child of a 3D Rigid Body node, \
-            otherwise it will not have any effect!";
+            message += "2D Collider must be a direct child of a 2D Rigid Body or a 2D Character \
+            Controller node, otherwise it will not have any effect!";
         }
 
         match &*self.shape {
@@ -695,6 +788,8 @@ pub struct ColliderBuilder {
     solver_groups: InteractionGroups,
     friction_combine_rule: CoefficientCombineRule,
     restitution_combine_rule: CoefficientCombineRule,
+    material: Option<PhysicsMaterialResource>,
+    report_collision_events: bool,
 }
 
 impl ColliderBuilder {
@@ -711,6 +806,8 @@ impl ColliderBuilder {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            material: None,
+            report_collision_events: false,
         }
     }
 
@@ -768,6 +865,18 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets desired physics material.
+    pub fn with_material(mut self, material: Option<PhysicsMaterialResource>) -> Self {
+        self.material = material;
+        self
+    }
+
+    /// Sets whether the physics engine should report collision events for the collider or not.
+    pub fn with_report_collision_events(mut self, report: bool) -> Self {
+        self.report_collision_events = report;
+        self
+    }
+
     /// Creates collider node, but does not add it to a graph.
     pub fn build_collider(self) -> Collider {
         Collider {
@@ -781,7 +890,10 @@ impl ColliderBuilder {
             solver_groups: self.solver_groups.into(),
             friction_combine_rule: self.friction_combine_rule.into(),
             restitution_combine_rule: self.restitution_combine_rule.into(),
+            material: self.material.into(),
+            report_collision_events: self.report_collision_events.into(),
             native: Cell::new(ColliderHandle::invalid()),
+            applied_material: Default::default(),
         }
     }
 
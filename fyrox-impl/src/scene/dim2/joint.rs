@@ -51,6 +51,8 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
+pub use crate::scene::joint::{GenericJointAxis, JointMotor};
+
 /// Ball joint locks any translational moves between two objects on the axis between objects, but
 /// allows rigid bodies to perform relative rotations. The real world example is a human shoulder,
 /// pendulum, etc.
@@ -65,6 +67,11 @@ pub struct BallJoint {
     #[reflect(description = "Allowed angles range for the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits_angles: Range<f32>,
+
+    /// Motor that drives relative rotation of the bodies.
+    #[reflect(description = "Motor that drives relative rotation of the bodies.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for BallJoint {
@@ -72,6 +79,7 @@ impl Default for BallJoint {
         Self {
             limits_enabled: false,
             limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
@@ -96,6 +104,11 @@ pub struct PrismaticJoint {
     #[reflect(description = "Allowed linear distance range along local X axis of the joint.")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Motor that drives translation along local X axis of the joint.
+    #[reflect(description = "Motor that drives translation along local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for PrismaticJoint {
@@ -103,6 +116,72 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
+        }
+    }
+}
+
+/// Generic joint allows to configure every degree of freedom separately - both linear axes and the
+/// angular axis can be locked, limited, or driven by a motor. All axes are free by default.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect, Default)]
+pub struct GenericJoint {
+    /// Settings of the translation along local X axis of the joint.
+    #[reflect(description = "Settings of the translation along local X axis of the joint.")]
+    pub x: GenericJointAxis,
+
+    /// Settings of the translation along local Y axis of the joint.
+    #[reflect(description = "Settings of the translation along local Y axis of the joint.")]
+    pub y: GenericJointAxis,
+
+    /// Settings of the relative rotation of the bodies.
+    #[reflect(description = "Settings of the relative rotation of the bodies.")]
+    pub angular: GenericJointAxis,
+}
+
+/// Rope joint limits the maximum distance between the anchors of two rigid bodies, but lets them
+/// move freely as long as the distance is less than the maximum.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct RopeJoint {
+    /// Maximum distance between the anchors of the bodies.
+    #[reflect(description = "Maximum distance between the anchors of the bodies.")]
+    #[reflect(min_value = 0.0)]
+    pub max_distance: f32,
+}
+
+impl Default for RopeJoint {
+    fn default() -> Self {
+        Self { max_distance: 1.0 }
+    }
+}
+
+/// Spring joint tries to keep the distance between the anchors of two rigid bodies equal to the
+/// rest length.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct SpringJoint {
+    /// Distance between the anchors of the bodies at which the spring applies no force.
+    #[reflect(
+        description = "Distance between the anchors of the bodies at which the spring applies no force."
+    )]
+    #[reflect(min_value = 0.0)]
+    pub rest_length: f32,
+
+    /// Stiffness of the spring.
+    #[reflect(description = "Stiffness of the spring.")]
+    #[reflect(min_value = 0.0)]
+    pub stiffness: f32,
+
+    /// Damping of the spring.
+    #[reflect(description = "Damping of the spring.")]
+    #[reflect(min_value = 0.0)]
+    pub damping: f32,
+}
+
+impl Default for SpringJoint {
+    fn default() -> Self {
+        Self {
+            rest_length: 1.0,
+            stiffness: 10.0,
+            damping: 1.0,
         }
     }
 }
@@ -116,6 +195,12 @@ pub enum JointParams {
     FixedJoint(FixedJoint),
     /// See [`PrismaticJoint`] for more info.
     PrismaticJoint(PrismaticJoint),
+    /// See [`GenericJoint`] for more info.
+    GenericJoint(GenericJoint),
+    /// See [`RopeJoint`] for more info.
+    RopeJoint(RopeJoint),
+    /// See [`SpringJoint`] for more info.
+    SpringJoint(SpringJoint),
 }
 
 uuid_provider!(JointParams = "e1fa2015-3ea3-47bb-8ad3-d408559c9643");
@@ -304,6 +389,24 @@ impl ConstructorProvider<Node, Graph> for Joint {
                     .build_node()
                     .into()
             })
+            .with_variant("Generic Joint 2D", |_| {
+                JointBuilder::new(BaseBuilder::new().with_name("Generic Joint 2D"))
+                    .with_params(JointParams::GenericJoint(Default::default()))
+                    .build_node()
+                    .into()
+            })
+            .with_variant("Rope Joint 2D", |_| {
+                JointBuilder::new(BaseBuilder::new().with_name("Rope Joint 2D"))
+                    .with_params(JointParams::RopeJoint(Default::default()))
+                    .build_node()
+                    .into()
+            })
+            .with_variant("Spring Joint 2D", |_| {
+                JointBuilder::new(BaseBuilder::new().with_name("Spring Joint 2D"))
+                    .with_params(JointParams::SpringJoint(Default::default()))
+                    .build_node()
+                    .into()
+            })
             .with_group("Physics 2D")
     }
 }
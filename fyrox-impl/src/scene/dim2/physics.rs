@@ -23,7 +23,7 @@
 use crate::{
     core::{
         algebra::{
-            Isometry2, Isometry3, Matrix4, Point2, Rotation3, Translation2, Translation3,
+            Isometry2, Isometry3, Matrix4, Point2, Point3, Rotation3, Translation2, Translation3,
             UnitComplex, UnitQuaternion, UnitVector2, Vector2, Vector3,
         },
         arrayvec::ArrayVec,
@@ -44,37 +44,52 @@ use crate::{
         debug::SceneDrawingContext,
         dim2::{
             self, collider::ColliderShape, collider::TileMapShape, joint::JointLocalFrames,
-            joint::JointParams, rigidbody::ApplyAction,
+            joint::JointMotor, joint::JointParams, rigidbody::ApplyAction,
         },
         graph::{
             isometric_global_transform,
-            physics::{FeatureId, IntegrationParameters, PhysicsPerformanceStatistics},
+            physics::{
+                CollisionEvent, CollisionEventCollector, CollisionEventKind, FeatureId,
+                IntegrationParameters, PhysicsPerformanceStatistics,
+            },
+            physics_recorder::{
+                PhysicsRecorder, PhysicsRecordingFrame, RecordedBody, RecordedCollider,
+                RecordedContact,
+            },
             Graph, NodePool,
         },
         node::{Node, NodeTrait},
         tilemap::TileMap,
     },
 };
+use fxhash::FxHashMap;
 pub use rapier2d::geometry::shape::*;
+
+// Maximum distance (in addition to the offset of a character controller) between a character and
+// a platform below it, at which the character is considered to be standing on the platform.
+const PLATFORM_PROBE_DISTANCE: f32 = 0.05;
 use rapier2d::{
+    control::{CharacterAutostep, CharacterLength, KinematicCharacterController},
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
-        IslandManager, JointAxesMask, JointAxis, MultibodyJointHandle, MultibodyJointSet,
-        RigidBody, RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet,
-        RigidBodyType,
+        IslandManager, JointAxesMask, JointAxis, MotorModel, MultibodyJointHandle,
+        MultibodyJointSet, RigidBody, RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle,
+        RigidBodySet, RigidBodyType,
     },
     geometry::{
-        Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid, DefaultBroadPhase,
-        InteractionGroups, NarrowPhase, Ray, SharedShape,
+        Collider, ColliderBuilder, ColliderHandle, ColliderSet, CollisionEventFlags, Cuboid,
+        DefaultBroadPhase, InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
     parry::query::ShapeCastOptions,
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryPipeline},
+    pipeline::{ActiveEvents, DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryPipeline},
 };
+use serde::{Deserialize, Serialize};
 use std::{
     cell::RefCell,
     cmp::Ordering,
     fmt::{Debug, Formatter},
     hash::Hash,
+    io,
     num::NonZeroUsize,
     sync::Arc,
 };
@@ -156,6 +171,11 @@ pub struct Intersection {
 
     /// Distance from the ray origin.
     pub toi: f32,
+
+    /// Surface tag of the physics material of the collider (see
+    /// [`crate::scene::physics_material::PhysicsMaterial::surface_tag`]). Empty if the collider
+    /// has no material.
+    pub surface_tag: ImmutableString,
 }
 
 /// A set of options for the ray cast.
@@ -279,6 +299,28 @@ pub struct IntersectionPair {
     pub has_any_active_contact: bool,
 }
 
+impl EventHandler for CollisionEventCollector<rapier2d::geometry::CollisionEvent> {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: rapier2d::geometry::CollisionEvent,
+        _contact_pair: Option<&rapier2d::geometry::ContactPair>,
+    ) {
+        self.events.lock().push(event);
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: f32,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &rapier2d::geometry::ContactPair,
+        _total_force_magnitude: f32,
+    ) {
+    }
+}
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -287,6 +329,34 @@ where
     map: BiDirHashMap<A, Handle<Node>>,
 }
 
+fn apply_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &JointMotor) {
+    if motor.enabled {
+        joint
+            .set_motor(
+                axis,
+                motor.target_position,
+                motor.target_velocity,
+                motor.stiffness,
+                motor.damping,
+            )
+            .set_motor_max_force(axis, motor.max_force);
+    }
+}
+
+fn apply_generic_joint_axis(
+    joint: &mut GenericJoint,
+    axis: JointAxis,
+    settings: &scene::dim2::joint::GenericJointAxis,
+) {
+    if settings.locked {
+        return;
+    }
+    if settings.limits_enabled {
+        joint.set_limits(axis, [settings.limits.start, settings.limits.end]);
+    }
+    apply_joint_motor(joint, axis, &settings.motor);
+}
+
 fn convert_joint_params(
     params: scene::dim2::joint::JointParams,
     local_frame1: Isometry2<f32>,
@@ -296,6 +366,20 @@ fn convert_joint_params(
         JointParams::BallJoint(_) => JointAxesMask::LOCKED_REVOLUTE_AXES,
         JointParams::FixedJoint(_) => JointAxesMask::LOCKED_FIXED_AXES,
         JointParams::PrismaticJoint(_) => JointAxesMask::LOCKED_PRISMATIC_AXES,
+        JointParams::GenericJoint(ref v) => {
+            let mut mask = JointAxesMask::empty();
+            for (settings, axis_mask) in [
+                (&v.x, JointAxesMask::LIN_X),
+                (&v.y, JointAxesMask::LIN_Y),
+                (&v.angular, JointAxesMask::ANG_X),
+            ] {
+                if settings.locked {
+                    mask |= axis_mask;
+                }
+            }
+            mask
+        }
+        JointParams::RopeJoint(_) | JointParams::SpringJoint(_) => JointAxesMask::empty(),
     };
 
     let mut joint = GenericJointBuilder::new(locked_axis)
@@ -311,12 +395,31 @@ fn convert_joint_params(
                     [v.limits_angles.start, v.limits_angles.end],
                 );
             }
+            apply_joint_motor(&mut joint, JointAxis::AngX, &v.motor);
         }
         scene::dim2::joint::JointParams::FixedJoint(_) => {}
         scene::dim2::joint::JointParams::PrismaticJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::LinX, [v.limits.start, v.limits.end]);
             }
+            apply_joint_motor(&mut joint, JointAxis::LinX, &v.motor);
+        }
+        scene::dim2::joint::JointParams::GenericJoint(v) => {
+            apply_generic_joint_axis(&mut joint, JointAxis::LinX, &v.x);
+            apply_generic_joint_axis(&mut joint, JointAxis::LinY, &v.y);
+            apply_generic_joint_axis(&mut joint, JointAxis::AngX, &v.angular);
+        }
+        scene::dim2::joint::JointParams::RopeJoint(v) => {
+            // Coupled linear axes make the limit work with the distance between the anchors,
+            // instead of the offset along a single axis.
+            joint.coupled_axes = JointAxesMask::LIN_AXES;
+            joint.set_limits(JointAxis::LinX, [0.0, v.max_distance]);
+        }
+        scene::dim2::joint::JointParams::SpringJoint(v) => {
+            joint.coupled_axes = JointAxesMask::LIN_AXES;
+            joint
+                .set_motor_position(JointAxis::LinX, v.rest_length, v.stiffness, v.damping)
+                .set_motor_model(JointAxis::LinX, MotorModel::ForceBased);
         }
     }
 
@@ -431,6 +534,12 @@ pub struct PhysicsWorld {
     #[reflect(hidden)]
     pub performance_statistics: PhysicsPerformanceStatistics,
 
+    /// Recorder of the simulation state, it is disabled by default. See [`PhysicsRecorder`] docs for
+    /// more info.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub recorder: PhysicsRecorder,
+
     // Current physics pipeline.
     #[visit(skip)]
     #[reflect(hidden)]
@@ -471,13 +580,35 @@ pub struct PhysicsWorld {
     // Event handler collects info about contacts and proximity events.
     #[visit(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_handler: CollisionEventCollector<rapier2d::geometry::CollisionEvent>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pending_contact_events: Vec<(ColliderHandle, ColliderHandle, CollisionEvent)>,
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
     #[visit(skip)]
     #[reflect(hidden)]
     debug_render_pipeline: Mutex<DebugRenderPipeline>,
+    // Interpolation factor between the last two physics states, `None` - interpolation is disabled.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    interpolation_factor: Option<f32>,
+    // Poses of the awake dynamic bodies before the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    previous_poses: FxHashMap<RigidBodyHandle, Isometry2<f32>>,
+    // Poses, that were written to rigid body nodes during the last sync.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    interpolated_poses: FxHashMap<RigidBodyHandle, Isometry2<f32>>,
+    // Surface tags of physics materials of the colliders, only non-empty tags are stored.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    surface_tags: FxHashMap<ColliderHandle, ImmutableString>,
 }
 
 impl Clone for PhysicsWorld {
@@ -491,6 +622,106 @@ impl Clone for PhysicsWorld {
     }
 }
 
+/// A snapshot of the complete state of the 2D physics world. It is taken by
+/// [`PhysicsWorld::snapshot`] and restored by [`Graph::restore_physics2d_snapshot`]. See
+/// [`scene::graph::physics::PhysicsSnapshot`] docs for more info.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PhysicsSnapshot {
+    islands: IslandManager,
+    broad_phase: DefaultBroadPhase,
+    narrow_phase: NarrowPhase,
+    ccd_solver: CCDSolver,
+    bodies: RigidBodySet,
+    colliders: ColliderSet,
+    joints: ImpulseJointSet,
+    joint_owners: Vec<(ImpulseJointHandle, Handle<Node>)>,
+    multibody_joints: MultibodyJointSet,
+    multibody_joint_owners: Vec<(MultibodyJointHandle, Handle<Node>)>,
+}
+
+impl Debug for PhysicsSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PhysicsSnapshot - {} bodies, {} colliders, {} joints",
+            self.bodies.len(),
+            self.colliders.len(),
+            self.joints.len()
+        )
+    }
+}
+
+impl PhysicsSnapshot {
+    /// Serializes the snapshot into a compact binary form.
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        bincode::serialize(self).map_err(io::Error::other)
+    }
+
+    /// Deserializes a snapshot from the bytes produced by [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        bincode::deserialize(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    // Syncs a physical node with the state of the snapshot. Returns `true` if the local transform
+    // of the node was changed.
+    pub(crate) fn restore_node(&self, node: &mut Node, parent_transform: &Matrix4<f32>) -> bool {
+        if let Some(rigid_body) = node.cast_mut::<dim2::rigidbody::RigidBody>() {
+            let Some(native) = self.bodies.get(rigid_body.native.get()) else {
+                // The body was created after the snapshot, it will be re-created on next sync.
+                rigid_body.native.set(RigidBodyHandle::invalid());
+                return false;
+            };
+            rigid_body
+                .lin_vel
+                .set_value_with_flags(*native.linvel(), VariableFlags::MODIFIED);
+            rigid_body
+                .ang_vel
+                .set_value_with_flags(native.angvel(), VariableFlags::MODIFIED);
+            rigid_body.sleeping = native.is_sleeping();
+            set_node_pose(node, parent_transform, native.position());
+            true
+        } else if let Some(controller) = node.cast::<dim2::character::CharacterController>() {
+            let Some(native) = self.bodies.get(controller.native.get()) else {
+                controller.native.set(RigidBodyHandle::invalid());
+                return false;
+            };
+            set_node_pose(node, parent_transform, native.position());
+            true
+        } else {
+            if let Some(collider) = node.cast::<dim2::collider::Collider>() {
+                if !self.colliders.contains(collider.native.get()) {
+                    collider.native.set(ColliderHandle::invalid());
+                }
+            }
+            false
+        }
+    }
+}
+
+fn set_node_pose(node: &mut Node, parent_transform: &Matrix4<f32>, pose: &Isometry2<f32>) {
+    let local_transform: Matrix4<f32> = parent_transform
+        .try_inverse()
+        .unwrap_or_else(Matrix4::identity)
+        * isometry2_to_mat4(pose);
+
+    node.local_transform_mut()
+        .set_position(Vector3::new(local_transform[12], local_transform[13], 0.0))
+        .set_rotation(UnitQuaternion::from_matrix_eps(
+            &local_transform.basis(),
+            f32::EPSILON,
+            16,
+            UnitQuaternion::identity(),
+        ));
+}
+
+fn active_events(report_collision_events: bool) -> ActiveEvents {
+    if report_collision_events {
+        ActiveEvents::COLLISION_EVENTS
+    } else {
+        ActiveEvents::empty()
+    }
+}
+
 fn isometry_from_global_transform(transform: &Matrix4<f32>) -> Isometry2<f32> {
     Isometry2 {
         translation: Translation2::new(transform[12], transform[13]),
@@ -533,7 +764,7 @@ pub struct QueryFilter<'a> {
     /// If set, any collider attached to this rigid-body will be excluded from the scene query.
     pub exclude_rigid_body: Option<Handle<Node>>,
     /// If set, any collider for which this closure returns false will be excluded from the scene query.
-    pub predicate: Option<&'a dyn Fn(Handle<Node>, &collider::Collider) -> bool>,
+    pub predicate: Option<&'a dyn Fn(Handle<Node>, &dim2::collider::Collider) -> bool>,
 }
 
 /// The result of a time-of-impact (TOI) computation.
@@ -561,6 +792,17 @@ pub struct TOI {
     pub status: collider::TOIStatus,
 }
 
+/// The result of a point projection, see [`PhysicsWorld::project_point`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PointProjection {
+    /// A handle of the collider on which the point was projected.
+    pub collider: Handle<Node>,
+    /// The projection of the point on the collider (in world coordinates).
+    pub point: Point2<f32>,
+    /// Whether the point was inside the collider or not.
+    pub is_inside: bool,
+}
+
 impl PhysicsWorld {
     /// Creates a new instance of the physics world.
     pub(crate) fn new() -> Self {
@@ -583,10 +825,17 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_handler: Default::default(),
+            collision_events: Default::default(),
+            pending_contact_events: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
+            recorder: Default::default(),
             debug_render_pipeline: Default::default(),
+            interpolation_factor: None,
+            previous_poses: Default::default(),
+            interpolated_poses: Default::default(),
+            surface_tags: Default::default(),
         }
     }
 
@@ -625,6 +874,16 @@ impl PhysicsWorld {
                 max_ccd_substeps: self.integration_parameters.max_ccd_substeps as usize,
             };
 
+            self.previous_poses.clear();
+            if self.interpolation_factor.is_some() {
+                self.previous_poses.extend(
+                    self.bodies
+                        .iter()
+                        .filter(|(_, body)| body.is_dynamic() && !body.is_sleeping())
+                        .map(|(handle, body)| (handle, *body.position())),
+                );
+            }
+
             self.pipeline.step(
                 &self.gravity,
                 &integration_parameters,
@@ -640,19 +899,343 @@ impl PhysicsWorld {
                 // so we keep updating it manually.
                 None,
                 &(),
-                &*self.event_handler,
+                &self.event_handler,
             );
+
+            self.collect_collision_events();
+
+            if self.recorder.is_enabled() {
+                self.record_frame(integration_parameters.dt);
+            }
         }
 
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
+    // Captures the current state of the simulation and passes it to the recorder. The state is
+    // stored in the XY plane.
+    fn record_frame(&mut self, dt: f32) {
+        let to_3d = |v: Vector2<f32>| Vector3::new(v.x, v.y, 0.0);
+        let rotation_to_3d =
+            |r: UnitComplex<f32>| UnitQuaternion::from_axis_angle(&Vector3::z_axis(), r.angle());
+
+        let bodies = self
+            .bodies
+            .iter()
+            .map(|(_, body)| RecordedBody {
+                node: Handle::decode_from_u128(body.user_data),
+                position: to_3d(body.position().translation.vector),
+                rotation: rotation_to_3d(body.position().rotation),
+                lin_vel: to_3d(*body.linvel()),
+                ang_vel: Vector3::new(0.0, 0.0, body.angvel()),
+                sleeping: body.is_sleeping(),
+            })
+            .collect();
+
+        let colliders = self
+            .colliders
+            .iter()
+            .map(|(_, collider)| {
+                let aabb = collider.shape().compute_local_aabb();
+                RecordedCollider {
+                    node: Handle::decode_from_u128(collider.user_data),
+                    position: to_3d(collider.position().translation.vector),
+                    rotation: rotation_to_3d(collider.position().rotation),
+                    local_aabb_min: to_3d(aabb.mins.coords),
+                    local_aabb_max: to_3d(aabb.maxs.coords),
+                    sleeping: collider
+                        .parent()
+                        .and_then(|parent| self.bodies.get(parent))
+                        .is_some_and(|body| body.is_sleeping()),
+                }
+            })
+            .collect();
+
+        let mut contacts = Vec::new();
+        for pair in self.narrow_phase.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+            let (Some(collider1), Some(collider2)) = (
+                self.colliders.get(pair.collider1),
+                self.colliders.get(pair.collider2),
+            ) else {
+                continue;
+            };
+            for manifold in pair.manifolds.iter() {
+                for point in manifold.points.iter().filter(|p| p.dist <= 0.0) {
+                    contacts.push(RecordedContact {
+                        collider1: Handle::decode_from_u128(collider1.user_data),
+                        collider2: Handle::decode_from_u128(collider2.user_data),
+                        point: to_3d((collider1.position() * point.local_p1).coords),
+                        normal: to_3d(manifold.data.normal),
+                        impulse: point.data.impulse,
+                    });
+                }
+            }
+        }
+
+        self.recorder.push(
+            dt,
+            PhysicsRecordingFrame {
+                time: 0.0,
+                bodies,
+                colliders,
+                contacts,
+            },
+        );
+    }
+
+    fn fetch_contact_data(
+        &self,
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+        event: &mut CollisionEvent,
+    ) {
+        event.contact_points.clear();
+        event.total_impulse = 0.0;
+
+        if let Some(pair) = self.narrow_phase.contact_pair(collider1, collider2) {
+            let sign = if pair.collider1 == collider1 {
+                1.0
+            } else {
+                -1.0
+            };
+            for manifold in pair.manifolds.iter() {
+                event.contact_points.extend(
+                    manifold
+                        .data
+                        .solver_contacts
+                        .iter()
+                        .map(|contact| Vector3::new(contact.point.x, contact.point.y, 0.0)),
+                );
+                event.total_impulse += manifold.points.iter().map(|p| p.data.impulse).sum::<f32>();
+            }
+            if let Some(manifold) = pair.manifolds.first() {
+                let normal = manifold.data.normal.scale(sign);
+                event.normal = Vector3::new(normal.x, normal.y, 0.0);
+            }
+        }
+    }
+
+    fn collect_collision_events(&mut self) {
+        self.collision_events.clear();
+
+        // Speculative contacts are reported one step earlier than the actual hit, so their impulse
+        // is still zero. Such events are delayed by one step to report the actual impact impulse.
+        for (collider1, collider2, mut event) in std::mem::take(&mut self.pending_contact_events) {
+            self.fetch_contact_data(collider1, collider2, &mut event);
+            self.collision_events.push(event);
+        }
+
+        let events = std::mem::take(self.event_handler.events.get_mut());
+        for event in events {
+            let (collider1, collider2, flags, started) = match event {
+                rapier2d::geometry::CollisionEvent::Started(c1, c2, flags) => (c1, c2, flags, true),
+                rapier2d::geometry::CollisionEvent::Stopped(c1, c2, flags) => {
+                    (c1, c2, flags, false)
+                }
+            };
+
+            let collider_node = |handle: ColliderHandle| {
+                self.colliders
+                    .get(handle)
+                    .map(|c| Handle::decode_from_u128(c.user_data))
+                    .unwrap_or_default()
+            };
+
+            let mut event = CollisionEvent {
+                kind: match (flags.contains(CollisionEventFlags::SENSOR), started) {
+                    (false, true) => CollisionEventKind::ContactStarted,
+                    (false, false) => CollisionEventKind::ContactStopped,
+                    (true, true) => CollisionEventKind::IntersectionStarted,
+                    (true, false) => CollisionEventKind::IntersectionStopped,
+                },
+                collider1: collider_node(collider1),
+                collider2: collider_node(collider2),
+                contact_points: Default::default(),
+                normal: Default::default(),
+                total_impulse: 0.0,
+            };
+
+            match event.kind {
+                CollisionEventKind::ContactStarted => {
+                    self.fetch_contact_data(collider1, collider2, &mut event);
+                    if event.total_impulse == 0.0 {
+                        self.pending_contact_events
+                            .push((collider1, collider2, event));
+                        continue;
+                    }
+                }
+                CollisionEventKind::ContactStopped => {
+                    // The contact has stopped before its delayed start event was reported.
+                    if let Some(index) =
+                        self.pending_contact_events.iter().position(|(c1, c2, _)| {
+                            (*c1, *c2) == (collider1, collider2)
+                                || (*c1, *c2) == (collider2, collider1)
+                        })
+                    {
+                        let (_, _, started) = self.pending_contact_events.remove(index);
+                        self.collision_events.push(started);
+                    }
+                }
+                _ => (),
+            }
+
+            self.collision_events.push(event);
+        }
+    }
+
+    /// Returns a list of collision events, that have happened during the last physics step. 2D
+    /// contact points and normals are converted to 3D with zero Z coordinate. See
+    /// [`CollisionEvent`] docs for more info.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
+    /// Takes a snapshot of the complete state of the physics world. See [`PhysicsSnapshot`] docs for
+    /// more info.
+    pub fn snapshot(&self) -> PhysicsSnapshot {
+        PhysicsSnapshot {
+            islands: self.islands.clone(),
+            broad_phase: self.broad_phase.clone(),
+            narrow_phase: self.narrow_phase.clone(),
+            ccd_solver: self.ccd_solver.clone(),
+            bodies: self.bodies.clone(),
+            colliders: self.colliders.clone(),
+            joints: self.joints.set.clone(),
+            joint_owners: self
+                .joints
+                .map
+                .forward_map()
+                .iter()
+                .map(|(handle, owner)| (*handle, *owner))
+                .collect(),
+            multibody_joints: self.multibody_joints.set.clone(),
+            multibody_joint_owners: self
+                .multibody_joints
+                .map
+                .forward_map()
+                .iter()
+                .map(|(handle, owner)| (*handle, *owner))
+                .collect(),
+        }
+    }
+
+    // Replaces the state of the world with the snapshot. Nodes must be synced with the snapshot
+    // before this call, see `Graph::restore_physics2d_snapshot`.
+    pub(crate) fn restore_snapshot(&mut self, snapshot: &PhysicsSnapshot) {
+        let snapshot = snapshot.clone();
+        self.islands = snapshot.islands;
+        self.broad_phase = snapshot.broad_phase;
+        self.narrow_phase = snapshot.narrow_phase;
+        self.ccd_solver = snapshot.ccd_solver;
+        self.bodies = snapshot.bodies;
+        self.colliders = snapshot.colliders;
+        self.joints = Container {
+            set: snapshot.joints,
+            map: snapshot
+                .joint_owners
+                .into_iter()
+                .collect::<FxHashMap<_, _>>()
+                .into(),
+        };
+        self.multibody_joints = Container {
+            set: snapshot.multibody_joints,
+            map: snapshot
+                .multibody_joint_owners
+                .into_iter()
+                .collect::<FxHashMap<_, _>>()
+                .into(),
+        };
+        self.event_handler.events.get_mut().clear();
+        self.collision_events.clear();
+        self.pending_contact_events.clear();
+        self.previous_poses.clear();
+        self.interpolated_poses.clear();
+    }
+
+    // Removes every native entity, that does not belong to a node anymore.
+    pub(crate) fn remove_orphans(&mut self, nodes: &NodePool) {
+        let orphan_bodies = self
+            .bodies
+            .iter()
+            .filter(|(handle, body)| {
+                let owner = nodes.try_borrow(Handle::decode_from_u128(body.user_data));
+                owner.and_then(|node| {
+                    node.cast::<dim2::rigidbody::RigidBody>()
+                        .map(|rigid_body| rigid_body.native.get())
+                        .or_else(|| {
+                            node.cast::<dim2::character::CharacterController>()
+                                .map(|controller| controller.native.get())
+                        })
+                }) != Some(*handle)
+            })
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+        for handle in orphan_bodies {
+            self.remove_body(handle);
+        }
+
+        let orphan_colliders = self
+            .colliders
+            .iter()
+            .filter(|(handle, collider)| {
+                nodes
+                    .try_borrow(Handle::decode_from_u128(collider.user_data))
+                    .and_then(|node| node.cast::<dim2::collider::Collider>())
+                    .map(|collider| collider.native.get())
+                    != Some(*handle)
+            })
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+        for handle in orphan_colliders {
+            self.remove_collider(handle);
+        }
+
+        let orphan_joints = self
+            .joints
+            .map
+            .forward_map()
+            .iter()
+            .filter(|(handle, owner)| {
+                nodes
+                    .try_borrow(**owner)
+                    .and_then(|node| node.cast::<dim2::joint::Joint>())
+                    .map(|joint| joint.native.get())
+                    != Some(**handle)
+            })
+            .map(|(handle, _)| *handle)
+            .collect::<Vec<_>>();
+        for handle in orphan_joints {
+            self.remove_joint(handle);
+        }
+    }
+
+    /// Sets the interpolation factor (in `[0; 1]` range) between the last two physics states. This
+    /// is the 2D counterpart of [`scene::graph::physics::PhysicsWorld::set_interpolation_factor`],
+    /// see its docs for more info.
+    pub fn set_interpolation_factor(&mut self, factor: Option<f32>) {
+        self.interpolation_factor = factor.map(|factor| factor.clamp(0.0, 1.0));
+        if self.interpolation_factor.is_none() {
+            self.previous_poses.clear();
+            self.interpolated_poses.clear();
+        }
+    }
+
+    /// Returns current interpolation factor between the last two physics states.
+    pub fn interpolation_factor(&self) -> Option<f32> {
+        self.interpolation_factor
+    }
+
     pub(crate) fn add_body(&mut self, owner: Handle<Node>, mut body: RigidBody) -> RigidBodyHandle {
         body.user_data = owner.encode_to_u128();
         self.bodies.insert(body)
     }
 
     pub(crate) fn remove_body(&mut self, handle: RigidBodyHandle) {
+        self.previous_poses.remove(&handle);
+        self.interpolated_poses.remove(&handle);
         self.bodies.remove(
             handle,
             &mut self.islands,
@@ -675,6 +1258,7 @@ impl PhysicsWorld {
     }
 
     pub(crate) fn remove_collider(&mut self, handle: ColliderHandle) -> bool {
+        self.surface_tags.remove(&handle);
         self.colliders
             .remove(handle, &mut self.islands, &mut self.bodies, false)
             .is_some()
@@ -724,6 +1308,51 @@ impl PhysicsWorld {
         // of the frame.
         query.update(&self.colliders);
 
+        self.cast_ray_with_query(&query, &opts, query_buffer);
+
+        self.performance_statistics.total_ray_cast_time.set(
+            self.performance_statistics.total_ray_cast_time.get()
+                + (instant::Instant::now() - time),
+        );
+    }
+
+    /// Casts a batch of rays with given options. Results of each ray are written to a respective
+    /// query buffer, so `query_buffers` must have the same length as `rays`. This method is much
+    /// faster than calling [`Self::cast_ray`] for every ray, because the acceleration structure of
+    /// the physics world is updated only once for the whole batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the amount of query buffers does not match the amount of rays.
+    pub fn cast_rays<S: QueryResultsStorage>(
+        &self,
+        rays: &[RayCastOptions],
+        query_buffers: &mut [S],
+    ) {
+        assert_eq!(rays.len(), query_buffers.len());
+
+        let time = instant::Instant::now();
+
+        let mut query = self.query.borrow_mut();
+
+        query.update(&self.colliders);
+
+        for (opts, query_buffer) in rays.iter().zip(query_buffers.iter_mut()) {
+            self.cast_ray_with_query(&query, opts, query_buffer);
+        }
+
+        self.performance_statistics.total_ray_cast_time.set(
+            self.performance_statistics.total_ray_cast_time.get()
+                + (instant::Instant::now() - time),
+        );
+    }
+
+    fn cast_ray_with_query<S: QueryResultsStorage>(
+        &self,
+        query: &QueryPipeline,
+        opts: &RayCastOptions,
+        query_buffer: &mut S,
+    ) {
         query_buffer.clear();
         let ray = Ray::new(
             opts.ray_origin,
@@ -750,6 +1379,7 @@ impl PhysicsWorld {
                     position: ray.point_at(intersection.time_of_impact),
                     feature: intersection.feature.into(),
                     toi: intersection.time_of_impact,
+                    surface_tag: self.surface_tags.get(&handle).cloned().unwrap_or_default(),
                 })
             },
         );
@@ -764,11 +1394,56 @@ impl PhysicsWorld {
                 }
             })
         }
+    }
 
-        self.performance_statistics.total_ray_cast_time.set(
-            self.performance_statistics.total_ray_cast_time.get()
-                + (instant::Instant::now() - time),
-        );
+    // Creates a predicate for a native query filter, that calls the predicate of the given filter
+    // (if any) with the collider node.
+    fn query_filter_predicate<'a>(
+        &'a self,
+        graph: &'a Graph,
+        filter: QueryFilter<'a>,
+    ) -> impl Fn(ColliderHandle, &Collider) -> bool + 'a {
+        move |handle: ColliderHandle, _: &Collider| -> bool {
+            if let Some(pred) = filter.predicate {
+                let h = Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data);
+                pred(
+                    h,
+                    graph
+                        .node(h)
+                        .component_ref::<dim2::collider::Collider>()
+                        .unwrap(),
+                )
+            } else {
+                true
+            }
+        }
+    }
+
+    // Converts the given filter into a native one. The predicate must be created by
+    // [`Self::query_filter_predicate`].
+    fn native_query_filter<'a>(
+        &self,
+        graph: &Graph,
+        filter: QueryFilter,
+        predicate: &'a dyn Fn(ColliderHandle, &Collider) -> bool,
+    ) -> rapier2d::pipeline::QueryFilter<'a> {
+        rapier2d::pipeline::QueryFilter {
+            flags: rapier2d::pipeline::QueryFilterFlags::from_bits(filter.flags.bits()).unwrap(),
+            groups: filter.groups.map(|g| {
+                InteractionGroups::new(u32_to_group(g.memberships.0), u32_to_group(g.filter.0))
+            }),
+            exclude_collider: filter
+                .exclude_collider
+                .and_then(|h| graph.try_get(h))
+                .and_then(|n| n.component_ref::<dim2::collider::Collider>())
+                .map(|c| c.native.get()),
+            exclude_rigid_body: filter
+                .exclude_rigid_body
+                .and_then(|h| graph.try_get(h))
+                .and_then(|n| n.component_ref::<dim2::rigidbody::RigidBody>())
+                .map(|c| c.native.get()),
+            predicate: Some(predicate),
+        }
     }
 
     /// Casts a shape at a constant linear velocity and retrieve the first collider it hits.
@@ -800,35 +1475,8 @@ impl PhysicsWorld {
         stop_at_penetration: bool,
         filter: QueryFilter,
     ) -> Option<(Handle<Node>, TOI)> {
-        let predicate = |handle: ColliderHandle, _: &Collider| -> bool {
-            if let Some(pred) = filter.predicate {
-                let h = Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data);
-                pred(
-                    h,
-                    graph.node(h).component_ref::<collider::Collider>().unwrap(),
-                )
-            } else {
-                true
-            }
-        };
-
-        let filter = rapier2d::pipeline::QueryFilter {
-            flags: rapier2d::pipeline::QueryFilterFlags::from_bits(filter.flags.bits()).unwrap(),
-            groups: filter.groups.map(|g| {
-                InteractionGroups::new(u32_to_group(g.memberships.0), u32_to_group(g.filter.0))
-            }),
-            exclude_collider: filter
-                .exclude_collider
-                .and_then(|h| graph.try_get(h))
-                .and_then(|n| n.component_ref::<dim2::collider::Collider>())
-                .map(|c| c.native.get()),
-            exclude_rigid_body: filter
-                .exclude_collider
-                .and_then(|h| graph.try_get(h))
-                .and_then(|n| n.component_ref::<dim2::rigidbody::RigidBody>())
-                .map(|c| c.native.get()),
-            predicate: Some(&predicate),
-        };
+        let predicate = self.query_filter_predicate(graph, filter);
+        let filter = self.native_query_filter(graph, filter, &predicate);
 
         let query = self.query.borrow_mut();
 
@@ -864,14 +1512,127 @@ impl PhysicsWorld {
             })
     }
 
+    /// Finds all colliders that intersect the given shape at the given position. It could be used
+    /// to find every object in an area of effect or in a field of view of an AI agent, for example.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `shape` - the shape to test.
+    /// * `shape_pos` - the position of the shape in world coordinates.
+    /// * `filter` - set of rules used to determine which collider is taken into account by this
+    ///   scene query.
+    /// * `callback` - a function that will be called with a handle of every intersecting collider
+    ///   node. Return `false` from it to stop the query.
+    pub fn intersect_shape(
+        &self,
+        graph: &Graph,
+        shape: &dyn Shape,
+        shape_pos: &Isometry2<f32>,
+        filter: QueryFilter,
+        mut callback: impl FnMut(Handle<Node>) -> bool,
+    ) {
+        let predicate = self.query_filter_predicate(graph, filter);
+        let filter = self.native_query_filter(graph, filter, &predicate);
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        query.intersections_with_shape(
+            &self.bodies,
+            &self.colliders,
+            shape_pos,
+            shape,
+            filter,
+            |handle| {
+                callback(Handle::decode_from_u128(
+                    self.colliders.get(handle).unwrap().user_data,
+                ))
+            },
+        );
+    }
+
+    /// Finds all colliders that contain the given point (in world coordinates).
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `point` - the point to test.
+    /// * `filter` - set of rules used to determine which collider is taken into account by this
+    ///   scene query.
+    /// * `callback` - a function that will be called with a handle of every collider node that
+    ///   contains the point. Return `false` from it to stop the query.
+    pub fn intersections_with_point(
+        &self,
+        graph: &Graph,
+        point: &Point2<f32>,
+        filter: QueryFilter,
+        mut callback: impl FnMut(Handle<Node>) -> bool,
+    ) {
+        let predicate = self.query_filter_predicate(graph, filter);
+        let filter = self.native_query_filter(graph, filter, &predicate);
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        query.intersections_with_point(&self.bodies, &self.colliders, point, filter, |handle| {
+            callback(Handle::decode_from_u128(
+                self.colliders.get(handle).unwrap().user_data,
+            ))
+        });
+    }
+
+    /// Finds the projection of the given point (in world coordinates) on the closest collider.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `point` - the point to project.
+    /// * `solid` - if `true`, a point inside a collider will be projected on itself, otherwise it
+    ///   will be projected on the boundary of the collider.
+    /// * `filter` - set of rules used to determine which collider is taken into account by this
+    ///   scene query.
+    pub fn project_point(
+        &self,
+        graph: &Graph,
+        point: &Point2<f32>,
+        solid: bool,
+        filter: QueryFilter,
+    ) -> Option<PointProjection> {
+        let predicate = self.query_filter_predicate(graph, filter);
+        let filter = self.native_query_filter(graph, filter, &predicate);
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        query
+            .project_point(&self.bodies, &self.colliders, point, solid, filter)
+            .map(|(handle, projection)| PointProjection {
+                collider: Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data),
+                point: projection.point,
+                is_inside: projection.is_inside,
+            })
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::dim2::rigidbody::RigidBody,
         new_global_transform: &Matrix4<f32>,
     ) {
+        let new_position = isometry_from_global_transform(new_global_transform);
+
+        // Interpolated poses are for rendering only, the simulation must not be affected by them.
+        if let Some(interpolated) = self.interpolated_poses.get(&rigid_body.native.get()) {
+            if (interpolated.translation.vector - new_position.translation.vector).norm() <= 1.0e-4
+                && interpolated.rotation.angle_to(&new_position.rotation) <= 1.0e-4
+            {
+                return;
+            }
+        }
+
         if let Some(native) = self.bodies.get_mut(rigid_body.native.get()) {
             native.set_position(
-                isometry_from_global_transform(new_global_transform),
+                new_position,
                 // Do not wake up body, it is too expensive and must be done **only** by explicit
                 // `wake_up` call!
                 false,
@@ -883,14 +1644,31 @@ impl PhysicsWorld {
         &mut self,
         rigid_body: &mut scene::dim2::rigidbody::RigidBody,
         parent_transform: Matrix4<f32>,
+        interpolate: bool,
     ) {
         if *self.enabled {
-            if let Some(native) = self.bodies.get(rigid_body.native.get()) {
+            let handle = rigid_body.native.get();
+            if let Some(native) = self.bodies.get(handle) {
                 if native.body_type() == RigidBodyType::Dynamic {
+                    let position = match (
+                        self.interpolation_factor.filter(|_| interpolate),
+                        self.previous_poses.get(&handle),
+                    ) {
+                        (Some(factor), Some(previous)) => {
+                            let position = previous.lerp_slerp(native.position(), factor);
+                            self.interpolated_poses.insert(handle, position);
+                            position
+                        }
+                        _ => {
+                            self.interpolated_poses.remove(&handle);
+                            *native.position()
+                        }
+                    };
+
                     let local_transform: Matrix4<f32> = parent_transform
                         .try_inverse()
                         .unwrap_or_else(Matrix4::identity)
-                        * isometry2_to_mat4(native.position());
+                        * isometry2_to_mat4(&position);
 
                     let new_local_rotation = UnitQuaternion::from_matrix_eps(
                         &local_transform.basis(),
@@ -1067,6 +1845,214 @@ impl PhysicsWorld {
         }
     }
 
+    pub(crate) fn set_character_controller_position(
+        &mut self,
+        controller: &scene::dim2::character::CharacterController,
+        new_global_transform: &Matrix4<f32>,
+    ) {
+        if let Some(native) = self.bodies.get_mut(controller.native.get()) {
+            native
+                .set_next_kinematic_position(isometry_from_global_transform(new_global_transform));
+        }
+    }
+
+    pub(crate) fn sync_to_character_controller_node(
+        &mut self,
+        handle: Handle<Node>,
+        controller: &scene::dim2::character::CharacterController,
+    ) {
+        if !controller.is_globally_enabled() {
+            self.remove_body(controller.native.get());
+            controller.native.set(Default::default());
+            return;
+        }
+
+        if controller.native.get() == RigidBodyHandle::invalid() {
+            let body = RigidBodyBuilder::kinematic_position_based()
+                .position(isometry_from_global_transform(
+                    &controller.global_transform(),
+                ))
+                .build();
+
+            controller.native.set(self.add_body(handle, body));
+
+            Log::writeln(
+                MessageKind::Information,
+                format!(
+                    "Native character controller was created for node {}",
+                    controller.name()
+                ),
+            );
+        }
+    }
+
+    pub(crate) fn move_character(
+        &mut self,
+        controller: &mut scene::dim2::character::CharacterController,
+        parent_transform: Matrix4<f32>,
+        dt: f32,
+    ) {
+        let desired_translation = std::mem::take(&mut controller.desired_translation);
+
+        controller.collisions.clear();
+        controller.effective_translation = Vector2::default();
+        controller.platform_velocity = Vector2::default();
+
+        if !*self.enabled {
+            return;
+        }
+
+        let body_handle = controller.native.get();
+        let Some(collider) = self
+            .bodies
+            .get(body_handle)
+            .and_then(|body| body.colliders().first())
+            .and_then(|collider| self.colliders.get(*collider))
+        else {
+            return;
+        };
+
+        let autostep = controller.autostep();
+        let native_controller = KinematicCharacterController {
+            offset: CharacterLength::Absolute(controller.offset()),
+            slide: controller.is_slide(),
+            autostep: autostep.enabled.then_some(CharacterAutostep {
+                max_height: CharacterLength::Absolute(autostep.max_height),
+                min_width: CharacterLength::Absolute(autostep.min_width),
+                include_dynamic_bodies: autostep.include_dynamic_bodies,
+            }),
+            max_slope_climb_angle: controller.max_slope_climb_angle(),
+            min_slope_slide_angle: controller.min_slope_slide_angle(),
+            snap_to_ground: (controller.snap_to_ground() > 0.0)
+                .then(|| CharacterLength::Absolute(controller.snap_to_ground())),
+            ..Default::default()
+        };
+
+        let filter = rapier2d::pipeline::QueryFilter::default()
+            .exclude_rigid_body(body_handle)
+            .exclude_sensors()
+            .groups(collider.collision_groups());
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        // The native body of the controller is synced with the node with one frame delay, so
+        // the node is the only reliable source of the current position of the character.
+        let character_position = isometry_from_global_transform(&controller.global_transform())
+            * collider
+                .position_wrt_parent()
+                .cloned()
+                .unwrap_or_else(Isometry2::identity);
+
+        let mut native_collisions = Vec::new();
+
+        // Move the character together with the platform it stands on (if any). The platform
+        // itself is excluded from the movement, so it won't block the character.
+        let mut platform_translation = Vector2::default();
+        if controller.is_inherit_platform_velocity() {
+            let probe_distance = 2.0 * controller.offset() + PLATFORM_PROBE_DISTANCE;
+            if let Some((platform_collider, hit)) = query.cast_shape(
+                &self.bodies,
+                &self.colliders,
+                &character_position,
+                &-Vector2::y_axis(),
+                collider.shape(),
+                ShapeCastOptions {
+                    max_time_of_impact: probe_distance,
+                    target_distance: 0.0,
+                    stop_at_penetration: true,
+                    compute_impact_geometry_on_penetration: true,
+                },
+                filter,
+            ) {
+                let platform = self
+                    .colliders
+                    .get(platform_collider)
+                    .and_then(|c| c.parent())
+                    .and_then(|parent| self.bodies.get(parent).map(|body| (parent, body)));
+                if let Some((platform_handle, platform)) = platform {
+                    let is_walkable = hit.normal1.dot(&Vector2::y_axis())
+                        >= controller.max_slope_climb_angle().cos();
+                    if is_walkable && !platform.is_fixed() {
+                        controller.platform_velocity = platform.velocity_at_point(&hit.witness1);
+                        platform_translation = native_controller
+                            .move_shape(
+                                dt,
+                                &self.bodies,
+                                &self.colliders,
+                                &query,
+                                collider.shape(),
+                                &character_position,
+                                controller.platform_velocity.scale(dt),
+                                filter.exclude_rigid_body(platform_handle),
+                                |collision| native_collisions.push(collision),
+                            )
+                            .translation;
+                    }
+                }
+            }
+        }
+
+        let movement = native_controller.move_shape(
+            dt,
+            &self.bodies,
+            &self.colliders,
+            &query,
+            collider.shape(),
+            &(Translation2::from(platform_translation) * character_position),
+            desired_translation,
+            filter,
+            |collision| native_collisions.push(collision),
+        );
+
+        if controller.is_push_dynamic_bodies() {
+            native_controller.solve_character_collision_impulses(
+                dt,
+                &mut self.bodies,
+                &self.colliders,
+                &query,
+                collider.shape(),
+                controller.mass(),
+                &native_collisions,
+                filter,
+            );
+        }
+
+        controller
+            .collisions
+            .extend(native_collisions.iter().map(|collision| {
+                scene::dim2::character::CharacterCollision {
+                    collider: self
+                        .colliders
+                        .get(collision.handle)
+                        .map(|c| Handle::decode_from_u128(c.user_data))
+                        .unwrap_or_default(),
+                    character_position: collision.character_pos.translation.vector,
+                    translation_applied: collision.translation_applied,
+                    translation_remaining: collision.translation_remaining,
+                    normal: collision.hit.normal1.into_inner(),
+                    toi: collision.hit.time_of_impact,
+                }
+            }));
+        let translation = platform_translation + movement.translation;
+        controller.effective_translation = translation;
+        controller.grounded = movement.grounded;
+        controller.sliding_down_slope = movement.is_sliding_down_slope;
+
+        if translation != Vector2::default() {
+            let new_global_position =
+                controller.global_position() + Vector3::new(translation.x, translation.y, 0.0);
+            let new_local_position = parent_transform
+                .try_inverse()
+                .unwrap_or_else(Matrix4::identity)
+                .transform_point(&Point3::from(new_global_position))
+                .coords;
+            controller
+                .local_transform_mut()
+                .set_position(new_local_position);
+        }
+    }
+
     pub(crate) fn sync_to_collider_node(
         &mut self,
         nodes: &NodePool,
@@ -1089,9 +2075,6 @@ impl PhysicsWorld {
         if collider_node.native.get() != ColliderHandle::invalid() {
             if anything_changed {
                 if let Some(native) = self.colliders.get_mut(collider_node.native.get()) {
-                    collider_node
-                        .restitution
-                        .try_sync_model(|v| native.set_restitution(v));
                     collider_node.collision_groups.try_sync_model(|v| {
                         native.set_collision_groups(InteractionGroups::new(
                             u32_to_group(v.memberships.0),
@@ -1104,18 +2087,12 @@ impl PhysicsWorld {
                             u32_to_group(v.filter.0),
                         ))
                     });
-                    collider_node
-                        .friction
-                        .try_sync_model(|v| native.set_friction(v));
                     collider_node
                         .is_sensor
                         .try_sync_model(|v| native.set_sensor(v));
                     collider_node
-                        .friction_combine_rule
-                        .try_sync_model(|v| native.set_friction_combine_rule(v.into()));
-                    collider_node
-                        .restitution_combine_rule
-                        .try_sync_model(|v| native.set_restitution_combine_rule(v.into()));
+                        .report_collision_events
+                        .try_sync_model(|v| native.set_active_events(active_events(v)));
                     let mut remove_collider = false;
                     collider_node.shape.try_sync_model(|v| {
                         let inv_global_transform = isometric_global_transform(nodes, handle)
@@ -1136,12 +2113,39 @@ impl PhysicsWorld {
                     }
                 }
             }
-        } else if let Some(parent_body) = nodes
-            .try_borrow(collider_node.parent())
-            .and_then(|n| n.cast::<dim2::rigidbody::RigidBody>())
+
+            // Surface properties are applied together with the physics material.
+            let mut surface_changed = false;
+            collider_node
+                .friction
+                .try_sync_model(|_| surface_changed = true);
+            collider_node
+                .restitution
+                .try_sync_model(|_| surface_changed = true);
+            collider_node
+                .friction_combine_rule
+                .try_sync_model(|_| surface_changed = true);
+            collider_node
+                .restitution_combine_rule
+                .try_sync_model(|_| surface_changed = true);
+            collider_node
+                .density
+                .try_sync_model(|_| surface_changed = true);
+            collider_node
+                .material
+                .try_sync_model(|_| surface_changed = true);
+            self.sync_collider_material(collider_node, surface_changed);
+        } else if let Some(rigid_body_native) =
+            nodes.try_borrow(collider_node.parent()).and_then(|n| {
+                n.cast::<dim2::rigidbody::RigidBody>()
+                    .map(|body| body.native.get())
+                    .or_else(|| {
+                        n.cast::<dim2::character::CharacterController>()
+                            .map(|controller| controller.native.get())
+                    })
+            })
         {
-            if parent_body.native.get() != RigidBodyHandle::invalid() {
-                let rigid_body_native = parent_body.native.get();
+            if rigid_body_native != RigidBodyHandle::invalid() {
                 let inv_global_transform = isometric_global_transform(nodes, handle)
                     .try_inverse()
                     .unwrap();
@@ -1150,6 +2154,7 @@ impl PhysicsWorld {
                     inv_global_transform,
                     nodes,
                 ) {
+                    let material = collider_node.effective_material();
                     let mut builder = ColliderBuilder::new(shape)
                         .position(Isometry2 {
                             rotation: UnitComplex::from_angle(
@@ -1159,21 +2164,22 @@ impl PhysicsWorld {
                                 vector: collider_node.local_transform().position().xy(),
                             },
                         })
-                        .friction(collider_node.friction())
-                        .restitution(collider_node.restitution())
+                        .friction(material.friction)
+                        .restitution(material.restitution)
                         .collision_groups(InteractionGroups::new(
                             u32_to_group(collider_node.collision_groups().memberships.0),
                             u32_to_group(collider_node.collision_groups().filter.0),
                         ))
-                        .friction_combine_rule(collider_node.friction_combine_rule().into())
-                        .restitution_combine_rule(collider_node.restitution_combine_rule().into())
+                        .friction_combine_rule(material.friction_combine_rule.into())
+                        .restitution_combine_rule(material.restitution_combine_rule.into())
                         .solver_groups(InteractionGroups::new(
                             u32_to_group(collider_node.solver_groups().memberships.0),
                             u32_to_group(collider_node.solver_groups().filter.0),
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(active_events(collider_node.is_report_collision_events()));
 
-                    if let Some(density) = collider_node.density() {
+                    if let Some(density) = material.density {
                         builder = builder.density(density);
                     }
 
@@ -1181,6 +2187,13 @@ impl PhysicsWorld {
                         self.add_collider(handle, rigid_body_native, builder.build());
 
                     collider_node.native.set(native_handle);
+                    collider_node
+                        .applied_material
+                        .set(collider_node.material_state());
+                    if !material.surface_tag.is_empty() {
+                        self.surface_tags
+                            .insert(native_handle, material.surface_tag);
+                    }
 
                     Log::writeln(
                         MessageKind::Information,
@@ -1194,6 +2207,41 @@ impl PhysicsWorld {
         }
     }
 
+    // Applies the surface properties of the collider (or its physics material) to the native
+    // collider. It is done only if the properties of the collider were changed or if the material
+    // was replaced or modified (including hot reloading), because `get_mut` is expensive.
+    fn sync_collider_material(
+        &mut self,
+        collider_node: &scene::dim2::collider::Collider,
+        force: bool,
+    ) {
+        let material_state = collider_node.material_state();
+        if !force && collider_node.applied_material.get() == material_state {
+            return;
+        }
+
+        let handle = collider_node.native.get();
+        let Some(native) = self.colliders.get_mut(handle) else {
+            return;
+        };
+
+        collider_node.applied_material.set(material_state);
+
+        let material = collider_node.effective_material();
+        native.set_friction(material.friction);
+        native.set_restitution(material.restitution);
+        native.set_friction_combine_rule(material.friction_combine_rule.into());
+        native.set_restitution_combine_rule(material.restitution_combine_rule.into());
+        // Colliders without explicit density use the default density of the physics engine.
+        native.set_density(material.density.unwrap_or(1.0));
+
+        if material.surface_tag.is_empty() {
+            self.surface_tags.remove(&handle);
+        } else {
+            self.surface_tags.insert(handle, material.surface_tag);
+        }
+    }
+
     pub(crate) fn sync_to_joint_node(
         &mut self,
         nodes: &NodePool,
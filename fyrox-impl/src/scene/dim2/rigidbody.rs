@@ -484,6 +484,7 @@ impl NodeTrait for RigidBody {
                 .try_borrow(self.parent)
                 .map(|p| p.global_transform())
                 .unwrap_or_else(Matrix4::identity),
+            true,
         );
     }
 
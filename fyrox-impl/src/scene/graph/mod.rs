@@ -60,17 +60,20 @@ use crate::{
     scene::{
         base::{NodeMessage, NodeScriptMessage, SceneNodeId},
         camera::Camera,
+        character::CharacterController,
         dim2::{self},
         graph::{
             event::{GraphEvent, GraphEventBroadcaster},
-            physics::{PhysicsPerformanceStatistics, PhysicsWorld},
+            physics::{PhysicsPerformanceStatistics, PhysicsSnapshot, PhysicsWorld},
         },
         mesh::Mesh,
         navmesh,
         node::{container::NodeContainer, Node, NodeTrait, SyncContext, UpdateContext},
         pivot::Pivot,
+        rigidbody::RigidBody,
         sound::context::SoundContext,
         transform::TransformBuilder,
+        vehicle::Vehicle,
     },
     script::ScriptTrait,
     utils::lightmap::{self, Lightmap},
@@ -88,6 +91,7 @@ use std::{
 
 pub mod event;
 pub mod physics;
+pub mod physics_recorder;
 
 /// Graph performance statistics. Allows you to find out "hot" parts of the scene graph, which
 /// parts takes the most time to update.
@@ -269,6 +273,9 @@ pub struct GraphUpdateSwitches {
     /// Whether the graph update is paused or not. Paused graphs won't be updated and their sound content will be also paused
     /// so it won't emit any sounds.
     pub paused: bool,
+    /// Enables or disables streaming of the world partition cells. See
+    /// [`crate::scene::streaming::WorldPartition`] docs for more info.
+    pub streaming: bool,
 }
 
 impl Default for GraphUpdateSwitches {
@@ -279,6 +286,7 @@ impl Default for GraphUpdateSwitches {
             node_overrides: Default::default(),
             delete_dead_nodes: true,
             paused: false,
+            streaming: true,
         }
     }
 }
@@ -1073,7 +1081,7 @@ impl Graph {
         handle: Handle<Node>,
         frame_size: Vector2<f32>,
         dt: f32,
-        delete_dead_nodes: bool,
+        switches: &GraphUpdateSwitches,
     ) {
         if let Some((ticket, mut node)) = self.pool.try_take_reserve(handle) {
             let mut is_alive = node.is_alive();
@@ -1086,9 +1094,10 @@ impl Graph {
                     physics: &mut self.physics,
                     physics2d: &mut self.physics2d,
                     sound_context: &mut self.sound_context,
+                    switches: Some(switches),
                 });
 
-                if delete_dead_nodes {
+                if switches.delete_dead_nodes {
                     if let Some(lifetime) = node.lifetime.get_value_mut_silent().as_mut() {
                         *lifetime -= dt;
                         if *lifetime <= 0.0 {
@@ -1100,7 +1109,7 @@ impl Graph {
 
             self.pool.put_back(ticket, node);
 
-            if !is_alive && delete_dead_nodes {
+            if !is_alive && switches.delete_dead_nodes {
                 self.remove_node(handle);
             }
         }
@@ -1128,35 +1137,148 @@ impl Graph {
         self.sync_native(&switches);
         self.performance_statistics.sync_time = instant::Instant::now() - last_time;
 
-        if switches.physics {
-            self.physics.performance_statistics.reset();
-            self.physics.update(dt);
-            self.performance_statistics.physics = self.physics.performance_statistics.clone();
+        self.step_physics(dt, &switches);
+
+        self.performance_statistics.sound_update_time =
+            self.sound_context.state().full_render_duration();
+
+        if let Some(overrides) = switches.node_overrides.as_ref() {
+            for handle in overrides {
+                self.update_node(*handle, frame_size, dt, &switches);
+            }
+        } else {
+            for i in 0..self.pool.get_capacity() {
+                self.update_node(self.pool.handle_from_index(i), frame_size, dt, &switches);
+            }
         }
+    }
 
-        if switches.physics2d {
-            self.physics2d.performance_statistics.reset();
-            self.physics2d.update(dt);
-            self.performance_statistics.physics2d = self.physics2d.performance_statistics.clone();
+    /// Performs a single step of the physics simulation (both 3D and 2D, if enabled in the switches)
+    /// with the given time step. Unlike [`Self::update`], it updates only physical nodes: rigid
+    /// bodies receive their new (not interpolated) poses, character controllers are moved and
+    /// vehicle wheels are synced, then global transforms of the graph are updated. It is used by
+    /// the engine when fixed time step is enabled (see [`crate::engine::FixedTimeStepSettings`]),
+    /// the regular update in this case should be called with disabled physics.
+    pub fn fixed_update(&mut self, dt: f32, switches: &GraphUpdateSwitches) {
+        if switches.paused {
+            return;
         }
 
-        self.performance_statistics.sound_update_time =
-            self.sound_context.state().full_render_duration();
+        self.process_node_messages(Some(switches));
+        self.sync_native(switches);
+        self.step_physics(dt, switches);
 
         if let Some(overrides) = switches.node_overrides.as_ref() {
             for handle in overrides {
-                self.update_node(*handle, frame_size, dt, switches.delete_dead_nodes);
+                self.sync_physical_node(*handle, dt, switches);
             }
         } else {
             for i in 0..self.pool.get_capacity() {
-                self.update_node(
-                    self.pool.handle_from_index(i),
-                    frame_size,
-                    dt,
-                    switches.delete_dead_nodes,
-                );
+                self.sync_physical_node(self.pool.handle_from_index(i), dt, switches);
             }
         }
+
+        self.update_hierarchical_data();
+    }
+
+    fn sync_physical_node(
+        &mut self,
+        handle: Handle<Node>,
+        dt: f32,
+        switches: &GraphUpdateSwitches,
+    ) {
+        let Some((ticket, mut node)) = self.pool.try_take_reserve(handle) else {
+            return;
+        };
+
+        if node.is_globally_enabled() {
+            let parent_transform = self
+                .pool
+                .try_borrow(node.parent())
+                .map(|parent| parent.global_transform())
+                .unwrap_or_else(Matrix4::identity);
+
+            if switches.physics {
+                if let Some(rigid_body) = node.cast_mut::<RigidBody>() {
+                    self.physics
+                        .sync_rigid_body_node(rigid_body, parent_transform, false);
+                } else if let Some(character) = node.cast_mut::<CharacterController>() {
+                    self.physics.move_character(character, parent_transform, dt);
+                } else if let Some(vehicle) = node.cast_mut::<Vehicle>() {
+                    vehicle.sync_wheels(&self.physics, &mut self.pool);
+                }
+            }
+
+            if switches.physics2d {
+                if let Some(rigid_body) = node.cast_mut::<dim2::rigidbody::RigidBody>() {
+                    self.physics2d
+                        .sync_rigid_body_node(rigid_body, parent_transform, false);
+                } else if let Some(character) =
+                    node.cast_mut::<dim2::character::CharacterController>()
+                {
+                    self.physics2d
+                        .move_character(character, parent_transform, dt);
+                }
+            }
+        }
+
+        self.pool.put_back(ticket, node);
+    }
+
+    /// Restores the state of the 3D physics world from the given snapshot (see [`PhysicsWorld::snapshot`])
+    /// and moves every rigid body and character controller node to its captured position. Physical
+    /// entities of the nodes, that were created after the snapshot was taken, will be re-created on
+    /// next update and the entities of the deleted nodes are removed.
+    pub fn restore_physics_snapshot(&mut self, snapshot: &PhysicsSnapshot) {
+        self.restore_physical_nodes(|node, parent_transform| {
+            snapshot.restore_node(node, parent_transform)
+        });
+        self.physics.restore_snapshot(snapshot);
+        self.physics.remove_orphans(&self.pool);
+    }
+
+    /// Restores the state of the 2D physics world from the given snapshot. See
+    /// [`Self::restore_physics_snapshot`] for more info.
+    pub fn restore_physics2d_snapshot(&mut self, snapshot: &dim2::physics::PhysicsSnapshot) {
+        self.restore_physical_nodes(|node, parent_transform| {
+            snapshot.restore_node(node, parent_transform)
+        });
+        self.physics2d.restore_snapshot(snapshot);
+        self.physics2d.remove_orphans(&self.pool);
+    }
+
+    fn restore_physical_nodes<F>(&mut self, mut restore: F)
+    where
+        F: FnMut(&mut Node, &Matrix4<f32>) -> bool,
+    {
+        // Parents must be restored first, otherwise their children will be placed incorrectly.
+        let handles = self.traverse_handle_iter(self.root).collect::<Vec<_>>();
+        for handle in handles {
+            let parent_transform = self
+                .pool
+                .try_borrow(self.pool[handle].parent())
+                .map(|parent| parent.global_transform())
+                .unwrap_or_else(Matrix4::identity);
+            if restore(&mut self.pool[handle], &parent_transform) {
+                // This is done while the current state of the physics is still alive, so any
+                // changes made to it by the nodes will be discarded.
+                self.update_hierarchical_data_for_descendants(handle);
+            }
+        }
+    }
+
+    fn step_physics(&mut self, dt: f32, switches: &GraphUpdateSwitches) {
+        if switches.physics {
+            self.physics.performance_statistics.reset();
+            self.physics.update(dt);
+            self.performance_statistics.physics = self.physics.performance_statistics.clone();
+        }
+
+        if switches.physics2d {
+            self.physics2d.performance_statistics.reset();
+            self.physics2d.update(dt);
+            self.performance_statistics.physics2d = self.physics2d.performance_statistics.clone();
+        }
     }
 
     /// Returns capacity of internal pool. Can be used to iterate over all **potentially**
@@ -1803,7 +1925,7 @@ impl SceneGraph for Graph {
 #[cfg(test)]
 mod test {
     use crate::{
-        asset::{io::FsResourceIo, manager::ResourceManager},
+        asset::{event::ResourceEvent, io::FsResourceIo, manager::ResourceManager, ResourceData},
         core::{
             algebra::{Matrix4, Vector3},
             futures::executor::block_on,
@@ -1813,24 +1935,33 @@ mod test {
             visitor::prelude::*,
         },
         engine::{self, SerializationContext},
-        graph::{BaseSceneGraph, SceneGraph},
+        graph::{BaseSceneGraph, NodeMapping, SceneGraph, SceneGraphNode},
         resource::model::{Model, ModelResourceExtension},
         scene::{
             base::BaseBuilder,
-            graph::Graph,
+            character::{CharacterController, CharacterControllerBuilder},
+            collider::{ColliderBuilder, ColliderShape},
+            dim2,
+            graph::{
+                physics::{Intersection, PhysicsSnapshot, QueryFilter, RayCastOptions},
+                physics_recorder::PhysicsRecording,
+                Graph, GraphUpdateSwitches,
+            },
             mesh::{
                 surface::{SurfaceBuilder, SurfaceData, SurfaceResource},
                 MeshBuilder,
             },
             node::Node,
             pivot::{Pivot, PivotBuilder},
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
             transform::TransformBuilder,
             Scene, SceneLoader,
         },
         script::ScriptTrait,
     };
-    use fyrox_core::algebra::Vector2;
+    use fyrox_core::algebra::{Isometry3, Point3, Vector2};
     use fyrox_resource::untyped::ResourceKind;
+    use rapier3d::geometry::Ball;
     use std::{fs, path::Path, sync::Arc};
 
     #[derive(Clone, Debug, PartialEq, Reflect, Visit, TypeUuidProvider, ComponentProvider)]
@@ -2241,4 +2372,540 @@ mod test {
         assert!(!graph[c].is_globally_enabled());
         assert!(!graph[d].is_globally_enabled());
     }
+
+    #[test]
+    fn test_prefab_variant() {
+        if !Path::new("test_output").exists() {
+            fs::create_dir_all("test_output").unwrap();
+        }
+
+        let base_path = Path::new("test_output/variant_base.rgs");
+        let variant_path = Path::new("test_output/variant.rgs");
+        let position_path = "base.local_transform.local_position";
+        let scale_path = "base.local_transform.local_scale";
+
+        save_scene(&mut create_scene(), base_path);
+
+        // Create a variant with an overridden property.
+        {
+            let resource_manager = make_resource_manager();
+            let base = block_on(resource_manager.request::<Model>(base_path)).unwrap();
+
+            let mut variant = Model::new_variant(&base);
+            assert_eq!(variant.variant_base(), Some(base.clone()));
+            assert_eq!(variant.variant_chain(), vec![base.clone()]);
+
+            // A prefab that just consists of a single instance is not a variant.
+            let mut scene = Scene::new();
+            base.instantiate(&mut scene);
+            let prefab = Model::new(NodeMapping::UseHandles, scene);
+            assert!(prefab.variant_base().is_none());
+            assert!(prefab.variant_chain().is_empty());
+
+            let graph = &mut variant.scene.graph;
+            let mesh_pivot = graph.find_by_name_from_root("MeshPivot").unwrap().0;
+            assert!(graph[mesh_pivot].overridden_properties().is_empty());
+            graph[mesh_pivot]
+                .local_transform_mut()
+                .set_position(Vector3::new(1.0, 2.0, 3.0));
+            let overrides = graph[mesh_pivot].overridden_properties();
+            assert_eq!(overrides.len(), 1);
+            assert_eq!(overrides[0].path, position_path);
+
+            variant.save(variant_path).unwrap();
+        }
+
+        let resource_manager = make_resource_manager();
+        let variant = block_on(resource_manager.request::<Model>(variant_path)).unwrap();
+        assert_eq!(variant.data_ref().variant_chain().len(), 1);
+
+        let mut scene = Scene::new();
+        let instance = variant.instantiate(&mut scene);
+        let other_instance = variant.instantiate(&mut scene);
+        let graph = &mut scene.graph;
+        assert!(graph.instance_overrides(instance).is_empty());
+
+        let mesh_pivot = graph.find_by_name(instance, "MeshPivot").unwrap().0;
+        assert_eq!(
+            **graph[mesh_pivot].local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+
+        // Override a property in the instance and revert it back.
+        graph[mesh_pivot]
+            .local_transform_mut()
+            .set_scale(Vector3::new(2.0, 2.0, 2.0));
+        let overrides = graph.instance_overrides(instance);
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].0, mesh_pivot);
+        assert_eq!(overrides[0].1[0].path, scale_path);
+        assert_eq!(graph[mesh_pivot].revert_overridden_properties().len(), 1);
+        assert_eq!(
+            **graph[mesh_pivot].local_transform().scale(),
+            Vector3::new(1.0, 1.0, 1.0)
+        );
+
+        // Override the property again and apply it to the variant.
+        graph[mesh_pivot]
+            .local_transform_mut()
+            .set_scale(Vector3::new(3.0, 3.0, 3.0));
+        let (sender, receiver) = std::sync::mpsc::channel();
+        resource_manager.state().event_broadcaster.add(sender);
+        assert!(
+            graph[mesh_pivot].apply_inheritable_property_to_resource(scale_path, &resource_manager)
+        );
+        assert!(graph.instance_overrides(instance).is_empty());
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(ResourceEvent::Reloaded(resource)) if resource == variant.clone().into_untyped()
+        ));
+
+        // Other instances are synced with the variant on resolve.
+        scene.resolve();
+        let other_mesh_pivot = scene
+            .graph
+            .find_by_name(other_instance, "MeshPivot")
+            .unwrap()
+            .1;
+        assert_eq!(
+            **other_mesh_pivot.local_transform().scale(),
+            Vector3::new(3.0, 3.0, 3.0)
+        );
+
+        let variant_data = variant.data_ref();
+        let variant_graph = &variant_data.get_scene().graph;
+        let variant_mesh_pivot = variant_graph.find_by_name_from_root("MeshPivot").unwrap();
+        assert_eq!(
+            **variant_mesh_pivot.1.local_transform().scale(),
+            Vector3::new(3.0, 3.0, 3.0)
+        );
+        // The property is now overridden in the variant.
+        assert_eq!(variant_mesh_pivot.1.overridden_properties().len(), 2);
+    }
+
+    #[test]
+    fn test_physics_snapshot() {
+        let mut graph = Graph::new();
+
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(10.0, 0.5, 10.0))
+            .build(&mut graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[ground_collider]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        let ball_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.5))
+            .build(&mut graph);
+        let ball = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 3.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[ball_collider]),
+        )
+        .with_lin_vel(Vector3::new(1.0, 0.0, 0.5))
+        .build(&mut graph);
+
+        let update = |graph: &mut Graph, body: Handle<Node>| {
+            graph.update(Vector2::new(100.0, 100.0), 1.0 / 60.0, Default::default());
+            **graph[body].local_transform().position()
+        };
+
+        for _ in 0..10 {
+            update(&mut graph, ball);
+        }
+
+        let snapshot = graph.physics.snapshot();
+        let bytes = snapshot.to_bytes().unwrap();
+        let snapshot_position = **graph[ball].local_transform().position();
+        let expected = (0..60)
+            .map(|_| update(&mut graph, ball))
+            .collect::<Vec<_>>();
+
+        // A body created after the snapshot must be re-created, a deleted one must be removed.
+        let box_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(0.5, 0.5, 0.5))
+            .build(&mut graph);
+        let late_body = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(100.0, 3.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[box_collider]),
+        )
+        .build(&mut graph);
+        update(&mut graph, ball);
+
+        graph.restore_physics_snapshot(&PhysicsSnapshot::from_bytes(&bytes).unwrap());
+        assert_eq!(
+            **graph[ball].local_transform().position(),
+            snapshot_position
+        );
+
+        let actual = (0..60)
+            .map(|_| update(&mut graph, ball))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, actual);
+        assert!(graph[late_body].local_transform().position().y < 3.0);
+
+        graph.remove_node(late_body);
+        graph.restore_physics_snapshot(&snapshot);
+        update(&mut graph, ball);
+        assert_eq!(graph.physics.colliders.len(), 2);
+    }
+
+    #[test]
+    fn test_fixed_update_syncs_physical_nodes() {
+        let mut graph = Graph::new();
+
+        let marker = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let ball_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.5))
+            .build(&mut graph);
+        let ball = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 10.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[ball_collider, marker]),
+        )
+        .build(&mut graph);
+
+        let character_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.5))
+            .build(&mut graph);
+        let character = CharacterControllerBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(10.0, 10.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[character_collider]),
+        )
+        .build(&mut graph);
+
+        let move_character = |graph: &mut Graph| {
+            graph[character]
+                .cast_mut::<CharacterController>()
+                .unwrap()
+                .set_desired_translation(Vector3::new(1.0, 0.0, 0.0));
+        };
+
+        graph.physics.set_interpolation_factor(Some(0.0));
+
+        // Native colliders are attached to their bodies on the step after the bodies are created.
+        graph.fixed_update(1.0 / 60.0, &Default::default());
+        let mut height = graph[ball].global_position().y;
+        for step in 1..=3 {
+            move_character(&mut graph);
+            graph.fixed_update(1.0 / 60.0, &Default::default());
+
+            // The poses must be available right after the step, without interpolation.
+            let position = graph[ball].global_position();
+            assert!(position.y < height);
+            assert_eq!(graph[marker].global_position(), position);
+            assert_eq!(graph[character].global_position().x, 10.0 + step as f32);
+            height = position.y;
+        }
+
+        // Frame updates with disabled physics must only interpolate the bodies and must not move
+        // the characters.
+        move_character(&mut graph);
+        graph.update(
+            Vector2::new(100.0, 100.0),
+            1.0 / 30.0,
+            GraphUpdateSwitches {
+                physics: false,
+                physics2d: false,
+                ..Default::default()
+            },
+        );
+        assert!(graph[ball].local_transform().position().y > height);
+        assert_eq!(graph[character].local_transform().position().x, 13.0);
+    }
+
+    #[test]
+    fn test_physics_scene_queries() {
+        let mut graph = Graph::new();
+
+        let make_box = |graph: &mut Graph, x: f32| {
+            let collider = ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::cuboid(1.0, 1.0, 1.0))
+                .build(graph);
+            RigidBodyBuilder::new(
+                BaseBuilder::new()
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(Vector3::new(x, 0.0, 0.0))
+                            .build(),
+                    )
+                    .with_children(&[collider]),
+            )
+            .with_body_type(RigidBodyType::Static)
+            .build(graph);
+            collider
+        };
+        let a = make_box(&mut graph, 0.0);
+        let b = make_box(&mut graph, 5.0);
+
+        graph.update(Vector2::new(100.0, 100.0), 1.0 / 60.0, Default::default());
+        graph.update(Vector2::new(100.0, 100.0), 1.0 / 60.0, Default::default());
+
+        let intersect_ball = |graph: &Graph, radius: f32, filter: QueryFilter| {
+            let mut colliders = Vec::new();
+            graph.physics.intersect_shape(
+                graph,
+                &Ball::new(radius),
+                &Isometry3::translation(0.0, 0.0, 0.0),
+                filter,
+                |collider| {
+                    colliders.push(collider);
+                    true
+                },
+            );
+            colliders.sort();
+            colliders
+        };
+        assert_eq!(intersect_ball(&graph, 1.5, Default::default()), vec![a]);
+        let mut both = vec![a, b];
+        both.sort();
+        assert_eq!(intersect_ball(&graph, 4.5, Default::default()), both);
+        assert_eq!(
+            intersect_ball(
+                &graph,
+                4.5,
+                QueryFilter {
+                    exclude_collider: Some(a),
+                    ..Default::default()
+                }
+            ),
+            vec![b]
+        );
+        assert_eq!(
+            intersect_ball(
+                &graph,
+                4.5,
+                QueryFilter {
+                    predicate: Some(&|handle, _| handle == a),
+                    ..Default::default()
+                }
+            ),
+            vec![a]
+        );
+
+        let mut colliders = Vec::new();
+        graph.physics.intersections_with_point(
+            &graph,
+            &Point3::new(5.5, 0.5, 0.0),
+            Default::default(),
+            |collider| {
+                colliders.push(collider);
+                true
+            },
+        );
+        assert_eq!(colliders, vec![b]);
+
+        let projection = graph
+            .physics
+            .project_point(
+                &graph,
+                &Point3::new(0.0, 3.0, 0.0),
+                true,
+                Default::default(),
+            )
+            .unwrap();
+        assert_eq!(projection.collider, a);
+        assert!(!projection.is_inside);
+        assert!((projection.point.coords - Vector3::new(0.0, 1.0, 0.0)).norm() < 1.0e-5);
+
+        let rays = [0.0, 5.0, 10.0].map(|x| RayCastOptions {
+            ray_origin: Point3::new(x, 5.0, 0.0),
+            ray_direction: Vector3::new(0.0, -1.0, 0.0),
+            max_len: 10.0,
+            groups: Default::default(),
+            sort_results: true,
+        });
+        let mut results = vec![Vec::<Intersection>::new(); rays.len()];
+        graph.physics.cast_rays(&rays, &mut results);
+        assert_eq!(results[0][0].collider, a);
+        assert_eq!(results[1][0].collider, b);
+        assert!(results[2].is_empty());
+    }
+
+    #[test]
+    fn test_query_filter_exclude_rigid_body() {
+        let mut graph = Graph::new();
+
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(1.0, 1.0, 1.0))
+            .build(&mut graph);
+        let body = RigidBodyBuilder::new(BaseBuilder::new().with_children(&[collider]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        graph.update(Vector2::new(100.0, 100.0), 1.0 / 60.0, Default::default());
+        graph.update(Vector2::new(100.0, 100.0), 1.0 / 60.0, Default::default());
+
+        let intersections_with_point = |filter: QueryFilter| {
+            let mut colliders = Vec::new();
+            graph.physics.intersections_with_point(
+                &graph,
+                &Point3::new(0.5, 0.5, 0.0),
+                filter,
+                |collider| {
+                    colliders.push(collider);
+                    true
+                },
+            );
+            colliders
+        };
+
+        assert_eq!(intersections_with_point(Default::default()), vec![collider]);
+        assert!(intersections_with_point(QueryFilter {
+            exclude_rigid_body: Some(body),
+            ..Default::default()
+        })
+        .is_empty());
+        // Excluding a rigid body must not exclude a collider with the same handle.
+        assert_eq!(
+            intersections_with_point(QueryFilter {
+                exclude_rigid_body: Some(collider),
+                ..Default::default()
+            }),
+            vec![collider]
+        );
+    }
+
+    #[test]
+    fn test_physics_recorder() {
+        let mut graph = Graph::new();
+
+        let ground = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(10.0, 0.5, 10.0))
+            .build(&mut graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[ground]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        let ball = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.5))
+            .build(&mut graph);
+        let body = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 2.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[ball]),
+        )
+        .build(&mut graph);
+
+        graph.physics.recorder.set_enabled(true);
+        graph.physics.recorder.set_duration(1.0);
+
+        let dt = 1.0 / 60.0;
+        for _ in 0..180 {
+            graph.update(Vector2::new(100.0, 100.0), dt, Default::default());
+        }
+
+        // Only the last second of the simulation is kept.
+        let recorder = &graph.physics.recorder;
+        assert!((60..=62).contains(&recorder.frame_count()));
+
+        let first = recorder.frame(0).unwrap();
+        let last = recorder.frame(recorder.frame_count() - 1).unwrap();
+        assert!(last.time - first.time <= 1.0);
+        assert_eq!(first.bodies.len(), 2);
+        assert_eq!(first.colliders.len(), 2);
+        assert!(last.body(body).is_some());
+
+        // The ball lies on the ground at the end.
+        assert!(last
+            .contacts
+            .iter()
+            .any(|c| (c.collider1 == ball && c.collider2 == ground)
+                || (c.collider1 == ground && c.collider2 == ball)));
+        assert!(std::ptr::eq(
+            recorder.frame_at(last.time + 10.0).unwrap(),
+            last
+        ));
+
+        if !Path::new("test_output").exists() {
+            fs::create_dir_all("test_output").unwrap();
+        }
+        let path = Path::new("test_output/physics_recording.physrec");
+        let mut recording = recorder.recording();
+        recording.save(path).unwrap();
+        let loaded = block_on(PhysicsRecording::load(path)).unwrap();
+        assert_eq!(loaded, recording);
+
+        graph.physics.recorder.clear();
+        assert_eq!(graph.physics.recorder.frame_count(), 0);
+    }
+
+    #[test]
+    fn test_physics_recorder_2d() {
+        let mut graph = Graph::new();
+
+        let ground = dim2::collider::ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(dim2::collider::ColliderShape::cuboid(10.0, 0.5))
+            .build(&mut graph);
+        dim2::rigidbody::RigidBodyBuilder::new(BaseBuilder::new().with_children(&[ground]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        let ball = dim2::collider::ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(dim2::collider::ColliderShape::ball(0.5))
+            .build(&mut graph);
+        let body = dim2::rigidbody::RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 2.0, 0.0))
+                        .build(),
+                )
+                .with_children(&[ball]),
+        )
+        .build(&mut graph);
+
+        graph.physics2d.recorder.set_enabled(true);
+
+        let dt = 1.0 / 60.0;
+        for _ in 0..120 {
+            graph.update(Vector2::new(100.0, 100.0), dt, Default::default());
+        }
+
+        let recorder = &graph.physics2d.recorder;
+        let last = recorder.frame(recorder.frame_count() - 1).unwrap();
+        let recorded_body = last.body(body).unwrap();
+        assert_eq!(recorded_body.position.z, 0.0);
+        assert!(recorded_body.position.y < 2.0);
+        assert!(last
+            .contacts
+            .iter()
+            .any(|c| (c.collider1 == ball && c.collider2 == ground)
+                || (c.collider1 == ground && c.collider2 == ball)));
+
+        // The closest frame is picked from both sides of the given time.
+        let first = recorder.frame(0).unwrap();
+        let second = recorder.frame(1).unwrap();
+        assert!(std::ptr::eq(
+            recorder.frame_at(first.time + 0.4 * dt).unwrap(),
+            first
+        ));
+        assert!(std::ptr::eq(
+            recorder.frame_at(first.time + 0.6 * dt).unwrap(),
+            second
+        ));
+        assert!(std::ptr::eq(recorder.frame_at(-1.0).unwrap(), first));
+    }
 }
@@ -31,19 +31,30 @@ use crate::{
         log::{Log, MessageKind},
         math::Matrix4Ext,
         parking_lot::Mutex,
-        pool::Handle,
+        pool::{Handle, Pool},
         reflect::prelude::*,
+        task::TaskPool,
         uuid_provider,
         variable::{InheritableVariable, VariableFlags},
         visitor::prelude::*,
-        BiDirHashMap,
+        BiDirHashMap, ImmutableString,
     },
     scene::{
         self,
-        collider::{self, ColliderShape, GeometrySource},
+        collider::{
+            self, ColliderShape, CompoundShape, ConvexDecomposition, ConvexDecompositionShape,
+            ConvexHull, GeometrySource, PendingConvexDecomposition,
+        },
         debug::SceneDrawingContext,
-        graph::{isometric_global_transform, Graph, NodePool},
-        joint::{JointLocalFrames, JointParams},
+        graph::{
+            isometric_global_transform,
+            physics_recorder::{
+                PhysicsRecorder, PhysicsRecordingFrame, RecordedBody, RecordedCollider,
+                RecordedContact,
+            },
+            Graph, NodePool,
+        },
+        joint::{JointLocalFrames, JointMotor, JointParams},
         mesh::{
             buffer::{VertexAttributeUsage, VertexReadTrait},
             Mesh,
@@ -54,25 +65,37 @@ use crate::{
     },
     utils::raw_mesh::{RawMeshBuilder, RawVertex},
 };
+use fxhash::{FxHashMap, FxHasher};
 use rapier3d::{
+    control::{
+        CharacterAutostep, CharacterLength, DynamicRayCastVehicleController,
+        KinematicCharacterController, WheelTuning,
+    },
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
-        IslandManager, JointAxesMask, MultibodyJointHandle, MultibodyJointSet, RigidBody,
-        RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet, RigidBodyType,
+        IslandManager, JointAxesMask, MotorModel, MultibodyJointHandle, MultibodyJointSet,
+        RigidBody, RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet,
+        RigidBodyType,
     },
     geometry::{
-        Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid, DefaultBroadPhase,
-        InteractionGroups, NarrowPhase, Ray, SharedShape,
+        Collider, ColliderBuilder, ColliderHandle, ColliderSet, CollisionEventFlags, Cuboid,
+        DefaultBroadPhase, InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
-    parry::{query::ShapeCastOptions, shape::HeightField},
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryPipeline},
+    parry::{
+        query::ShapeCastOptions,
+        shape::HeightField,
+        transformation::vhacd::{VHACDParameters, VHACD},
+    },
+    pipeline::{ActiveEvents, DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryPipeline},
     prelude::{HeightFieldCellStatus, JointAxis},
 };
+use serde::{Deserialize, Serialize};
 use std::{
     cell::{Cell, RefCell},
     cmp::Ordering,
     fmt::{Debug, Formatter},
-    hash::Hash,
+    hash::{Hash, Hasher},
+    io,
     num::NonZeroUsize,
     sync::Arc,
     time::Duration,
@@ -82,6 +105,10 @@ use strum_macros::{AsRefStr, EnumString, VariantNames};
 use fyrox_graph::{BaseSceneGraph, SceneGraphNode};
 pub use rapier3d::geometry::shape::*;
 
+// Maximum distance (in addition to the offset of a character controller) between a character and
+// a platform below it, at which the character is considered to be standing on the platform.
+const PLATFORM_PROBE_DISTANCE: f32 = 0.05;
+
 /// Shape-dependent identifier.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub enum FeatureId {
@@ -227,6 +254,11 @@ pub struct Intersection {
 
     /// Distance from the ray origin.
     pub toi: f32,
+
+    /// Surface tag of the physics material of the collider (see
+    /// [`crate::scene::physics_material::PhysicsMaterial::surface_tag`]). Empty if the collider
+    /// has no material.
+    pub surface_tag: ImmutableString,
 }
 
 /// A set of options for the ray cast.
@@ -400,6 +432,118 @@ pub struct IntersectionPair {
     pub has_any_active_contact: bool,
 }
 
+/// A kind of a collision event. See [`CollisionEvent`] docs for more info.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CollisionEventKind {
+    /// Two colliders started touching each other. Speculative contacts (when the colliders are
+    /// just about to touch) are reported one physics step later, when the actual impact happens,
+    /// so the event could carry the impact impulse.
+    ContactStarted,
+    /// Two colliders stopped touching each other.
+    ContactStopped,
+    /// A sensor collider started intersecting with another collider.
+    IntersectionStarted,
+    /// A sensor collider stopped intersecting with another collider.
+    IntersectionStopped,
+}
+
+/// A collision event, that has happened during the last physics step. Collision events are
+/// collected by the physics world automatically and then delivered to scripts of the involved
+/// collider nodes and their parent rigid bodies (see [`crate::script::ScriptTrait::on_collision`]).
+/// They could also be polled manually by using [`PhysicsWorld::collision_events`]. An event is
+/// reported only if at least one of the colliders has collision events enabled (see
+/// [`crate::scene::collider::Collider::set_report_collision_events`]).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollisionEvent {
+    /// A kind of the event.
+    pub kind: CollisionEventKind,
+    /// The first collider involved in the event. When the event is delivered to a script, this is
+    /// always the collider of the script's node (or a collider of the script's rigid body). Could
+    /// be [`Handle::NONE`], if the collider was removed from the scene.
+    pub collider1: Handle<Node>,
+    /// The second collider involved in the event. Could be [`Handle::NONE`], if the collider was
+    /// removed from the scene.
+    pub collider2: Handle<Node>,
+    /// World-space contact points. Empty for every event kind, except
+    /// [`CollisionEventKind::ContactStarted`].
+    pub contact_points: Vec<Vector3<f32>>,
+    /// World-space contact normal, pointing from the first collider to the second. Zero for every
+    /// event kind, except [`CollisionEventKind::ContactStarted`].
+    pub normal: Vector3<f32>,
+    /// Total magnitude of the impulse, that was applied to resolve the contact during the physics
+    /// step. Zero for every event kind, except [`CollisionEventKind::ContactStarted`].
+    pub total_impulse: f32,
+}
+
+impl CollisionEvent {
+    /// Returns `true` if the event is about the beginning of a contact or intersection, `false` -
+    /// otherwise.
+    pub fn is_started(&self) -> bool {
+        matches!(
+            self.kind,
+            CollisionEventKind::ContactStarted | CollisionEventKind::IntersectionStarted
+        )
+    }
+
+    /// Returns a copy of the event with swapped colliders. The normal is flipped accordingly.
+    pub fn swapped(&self) -> Self {
+        Self {
+            kind: self.kind,
+            collider1: self.collider2,
+            collider2: self.collider1,
+            contact_points: self.contact_points.clone(),
+            normal: -self.normal,
+            total_impulse: self.total_impulse,
+        }
+    }
+}
+
+pub(crate) struct CollisionEventCollector<E> {
+    pub(crate) events: Mutex<Vec<E>>,
+}
+
+impl<E> Default for CollisionEventCollector<E> {
+    fn default() -> Self {
+        Self {
+            events: Default::default(),
+        }
+    }
+}
+
+impl EventHandler for CollisionEventCollector<rapier3d::geometry::CollisionEvent> {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: rapier3d::geometry::CollisionEvent,
+        _contact_pair: Option<&rapier3d::geometry::ContactPair>,
+    ) {
+        self.events.lock().push(event);
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: f32,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &rapier3d::geometry::ContactPair,
+        _total_force_magnitude: f32,
+    ) {
+    }
+}
+
+// Native representation of a vehicle node.
+pub(crate) struct NativeVehicle {
+    controller: DynamicRayCastVehicleController,
+}
+
+fn make_vehicle_controller(chassis: RigidBodyHandle) -> DynamicRayCastVehicleController {
+    let mut controller = DynamicRayCastVehicleController::new(chassis);
+    // Fyrox uses Z axis as forward direction.
+    controller.index_forward_axis = 2;
+    controller
+}
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -408,6 +552,34 @@ where
     map: BiDirHashMap<A, Handle<Node>>,
 }
 
+fn apply_joint_motor(joint: &mut GenericJoint, axis: JointAxis, motor: &JointMotor) {
+    if motor.enabled {
+        joint
+            .set_motor(
+                axis,
+                motor.target_position,
+                motor.target_velocity,
+                motor.stiffness,
+                motor.damping,
+            )
+            .set_motor_max_force(axis, motor.max_force);
+    }
+}
+
+fn apply_generic_joint_axis(
+    joint: &mut GenericJoint,
+    axis: JointAxis,
+    settings: &scene::joint::GenericJointAxis,
+) {
+    if settings.locked {
+        return;
+    }
+    if settings.limits_enabled {
+        joint.set_limits(axis, [settings.limits.start, settings.limits.end]);
+    }
+    apply_joint_motor(joint, axis, &settings.motor);
+}
+
 fn convert_joint_params(
     params: scene::joint::JointParams,
     local_frame1: Isometry3<f32>,
@@ -418,6 +590,23 @@ fn convert_joint_params(
         JointParams::FixedJoint(_) => JointAxesMask::LOCKED_FIXED_AXES,
         JointParams::PrismaticJoint(_) => JointAxesMask::LOCKED_PRISMATIC_AXES,
         JointParams::RevoluteJoint(_) => JointAxesMask::LOCKED_REVOLUTE_AXES,
+        JointParams::GenericJoint(ref v) => {
+            let mut mask = JointAxesMask::empty();
+            for (settings, axis_mask) in [
+                (&v.x, JointAxesMask::LIN_X),
+                (&v.y, JointAxesMask::LIN_Y),
+                (&v.z, JointAxesMask::LIN_Z),
+                (&v.angular_x, JointAxesMask::ANG_X),
+                (&v.angular_y, JointAxesMask::ANG_Y),
+                (&v.angular_z, JointAxesMask::ANG_Z),
+            ] {
+                if settings.locked {
+                    mask |= axis_mask;
+                }
+            }
+            mask
+        }
+        JointParams::RopeJoint(_) | JointParams::SpringJoint(_) => JointAxesMask::empty(),
     };
 
     let mut joint = GenericJointBuilder::new(locked_axis)
@@ -445,31 +634,55 @@ fn convert_joint_params(
                     [v.z_limits_angles.start, v.z_limits_angles.end],
                 );
             }
+            apply_joint_motor(&mut joint, JointAxis::AngX, &v.x_motor);
+            apply_joint_motor(&mut joint, JointAxis::AngY, &v.y_motor);
+            apply_joint_motor(&mut joint, JointAxis::AngZ, &v.z_motor);
         }
         scene::joint::JointParams::FixedJoint(_) => {}
         scene::joint::JointParams::PrismaticJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::LinX, [v.limits.start, v.limits.end]);
             }
+            apply_joint_motor(&mut joint, JointAxis::LinX, &v.motor);
         }
         scene::joint::JointParams::RevoluteJoint(v) => {
             if v.limits_enabled {
                 joint.set_limits(JointAxis::AngX, [v.limits.start, v.limits.end]);
             }
+            apply_joint_motor(&mut joint, JointAxis::AngX, &v.motor);
+        }
+        scene::joint::JointParams::GenericJoint(v) => {
+            apply_generic_joint_axis(&mut joint, JointAxis::LinX, &v.x);
+            apply_generic_joint_axis(&mut joint, JointAxis::LinY, &v.y);
+            apply_generic_joint_axis(&mut joint, JointAxis::LinZ, &v.z);
+            apply_generic_joint_axis(&mut joint, JointAxis::AngX, &v.angular_x);
+            apply_generic_joint_axis(&mut joint, JointAxis::AngY, &v.angular_y);
+            apply_generic_joint_axis(&mut joint, JointAxis::AngZ, &v.angular_z);
+        }
+        scene::joint::JointParams::RopeJoint(v) => {
+            // Coupled linear axes make the limit work with the distance between the anchors,
+            // instead of the offset along a single axis.
+            joint.coupled_axes = JointAxesMask::LIN_AXES;
+            joint.set_limits(JointAxis::LinX, [0.0, v.max_distance]);
+        }
+        scene::joint::JointParams::SpringJoint(v) => {
+            joint.coupled_axes = JointAxesMask::LIN_AXES;
+            joint
+                .set_motor_position(JointAxis::LinX, v.rest_length, v.stiffness, v.damping)
+                .set_motor_model(JointAxis::LinX, MotorModel::ForceBased);
         }
     }
 
     joint
 }
 
-/// Creates new trimesh collider shape from given mesh node. It also bakes scale into
-/// vertices of trimesh because rapier does not support collider scaling yet.
-fn make_trimesh(
+/// Collects triangles of given mesh nodes into a single vertex and index buffer. It also bakes
+/// scale into vertices because rapier does not support collider scaling yet.
+fn collect_geometry(
     owner_inv_transform: Matrix4<f32>,
-    owner: Handle<Node>,
     sources: &[GeometrySource],
     nodes: &NodePool,
-) -> Option<SharedShape> {
+) -> (Vec<Point3<f32>>, Vec<[u32; 3]>) {
     let mut mesh_builder = RawMeshBuilder::new(0, 0);
 
     // Create inverse transform that will discard rotation and translation, but leave scaling and
@@ -545,6 +758,19 @@ fn make_trimesh(
         .map(|t| [t.0[0], t.0[1], t.0[2]])
         .collect::<Vec<_>>();
 
+    (vertices, indices)
+}
+
+/// Creates new trimesh collider shape from given mesh node. It also bakes scale into
+/// vertices of trimesh because rapier does not support collider scaling yet.
+fn make_trimesh(
+    owner_inv_transform: Matrix4<f32>,
+    owner: Handle<Node>,
+    sources: &[GeometrySource],
+    nodes: &NodePool,
+) -> Option<SharedShape> {
+    let (vertices, indices) = collect_geometry(owner_inv_transform, sources, nodes);
+
     if indices.is_empty() {
         Log::writeln(
             MessageKind::Warning,
@@ -560,6 +786,225 @@ fn make_trimesh(
     }
 }
 
+// Convex decompositions of a collider shape. Decompositions from the previous build of the shape
+// are re-used if their parameters and source geometry are the same.
+#[derive(Default)]
+struct ConvexDecompositionCache {
+    previous: Vec<ConvexDecomposition>,
+    current: Vec<ConvexDecomposition>,
+    previous_pending: Vec<PendingConvexDecomposition>,
+    current_pending: Vec<PendingConvexDecomposition>,
+    task_pool: Option<Arc<TaskPool>>,
+}
+
+impl ConvexDecompositionCache {
+    // Returns the hulls of a decomposition with the given key, if it is finished. Otherwise the
+    // decomposition is started on the task pool (if it is not started yet), or computed right
+    // away if there's no task pool.
+    fn hulls(
+        &mut self,
+        key: u64,
+        params: VHACDParameters,
+        vertices: Vec<Point3<f32>>,
+        indices: Vec<[u32; 3]>,
+    ) -> Option<Vec<ConvexHull>> {
+        if let Some(decomposition) = self
+            .current
+            .iter()
+            .chain(self.previous.iter())
+            .find(|d| d.key == key)
+        {
+            return Some(decomposition.hulls.clone());
+        }
+
+        let pending = if let Some(pending) = self.current_pending.iter().find(|p| p.key == key) {
+            pending.clone()
+        } else {
+            let pending = self
+                .previous_pending
+                .iter()
+                .find(|p| p.key == key)
+                .cloned()
+                .unwrap_or_else(|| {
+                    spawn_convex_decomposition(
+                        self.task_pool.as_ref(),
+                        key,
+                        params,
+                        vertices,
+                        indices,
+                    )
+                });
+            self.current_pending.push(pending.clone());
+            pending
+        };
+
+        let hulls = pending.hulls.lock().clone();
+        if hulls.is_some() {
+            self.current_pending.retain(|p| p.key != key);
+        }
+        hulls
+    }
+}
+
+fn spawn_convex_decomposition(
+    task_pool: Option<&Arc<TaskPool>>,
+    key: u64,
+    params: VHACDParameters,
+    vertices: Vec<Point3<f32>>,
+    indices: Vec<[u32; 3]>,
+) -> PendingConvexDecomposition {
+    let pending = PendingConvexDecomposition {
+        key,
+        hulls: Default::default(),
+    };
+
+    let hulls = pending.hulls.clone();
+    let decompose = move || {
+        let result = VHACD::decompose(&params, &vertices, &indices, true)
+            .compute_exact_convex_hulls(&vertices, &indices)
+            .into_iter()
+            .map(|(vertices, triangles)| ConvexHull {
+                vertices: vertices.into_iter().map(|v| v.coords).collect(),
+                triangles,
+            })
+            .collect::<Vec<_>>();
+        *hulls.lock() = Some(result);
+    };
+
+    match task_pool {
+        Some(task_pool) => task_pool.spawn_task(async move { decompose() }),
+        None => decompose(),
+    }
+
+    pending
+}
+
+fn convex_decomposition_key(
+    shape: &ConvexDecompositionShape,
+    vertices: &[Point3<f32>],
+    indices: &[[u32; 3]],
+) -> u64 {
+    let mut hasher = FxHasher::default();
+    shape.resolution.hash(&mut hasher);
+    shape.concavity.to_bits().hash(&mut hasher);
+    shape.max_convex_hulls.hash(&mut hasher);
+    for vertex in vertices {
+        vertex.map(f32::to_bits).hash(&mut hasher);
+    }
+    indices.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Splits the geometry of given mesh nodes into a set of convex hulls using V-HACD and creates
+/// a compound shape out of them. Generated hulls are stored in the cache. Returns `None` while
+/// the decomposition is being computed on the task pool.
+fn make_convex_decomposition(
+    owner_inv_transform: Matrix4<f32>,
+    owner: Handle<Node>,
+    shape: &ConvexDecompositionShape,
+    nodes: &NodePool,
+    transform: Isometry3<f32>,
+    cache: &mut ConvexDecompositionCache,
+) -> Option<SharedShape> {
+    let (vertices, indices) = collect_geometry(owner_inv_transform, &shape.sources, nodes);
+
+    if indices.is_empty() {
+        Log::writeln(
+            MessageKind::Warning,
+            format!(
+                "Failed to create convex decomposition for {}, it has no vertices!",
+                nodes[owner].name()
+            ),
+        );
+
+        return None;
+    }
+
+    let key = convex_decomposition_key(shape, &vertices, &indices);
+
+    let params = VHACDParameters {
+        resolution: shape.resolution.max(1),
+        concavity: shape.concavity,
+        max_convex_hulls: shape.max_convex_hulls.max(1),
+        ..Default::default()
+    };
+
+    let hulls = cache.hulls(key, params, vertices, indices)?;
+
+    let parts = hulls
+        .iter()
+        .filter_map(|hull| {
+            SharedShape::convex_mesh(
+                hull.vertices.iter().map(|v| Point3::from(*v)).collect(),
+                &hull.triangles,
+            )
+            .map(|shape| (Isometry3::identity(), shape))
+        })
+        .collect::<Vec<_>>();
+
+    cache.current.push(ConvexDecomposition {
+        transform,
+        hulls,
+        key,
+    });
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(SharedShape::compound(parts))
+    }
+}
+
+/// Creates a compound shape from the given parts. Parts that are compound shapes themselves are
+/// flattened, because Rapier does not support nested composite shapes.
+fn make_compound(
+    compound: &CompoundShape,
+    owner_inv_global_transform: Matrix4<f32>,
+    owner_collider: Handle<Node>,
+    pool: &NodePool,
+    transform: Isometry3<f32>,
+    cache: &mut ConvexDecompositionCache,
+) -> Option<SharedShape> {
+    let mut shapes = Vec::new();
+
+    for part in compound.parts.iter() {
+        let part_transform = Isometry3 {
+            rotation: part.rotation,
+            translation: Translation3::from(part.position),
+        };
+
+        let Some(shape) = collider_shape_into_native_shape(
+            &part.shape.clone().into(),
+            owner_inv_global_transform,
+            owner_collider,
+            pool,
+            transform * part_transform,
+            cache,
+        ) else {
+            continue;
+        };
+
+        if let Some(sub_compound) = shape.as_compound() {
+            shapes.extend(
+                sub_compound
+                    .shapes()
+                    .iter()
+                    .map(|(sub_transform, sub_shape)| {
+                        (part_transform * sub_transform, sub_shape.clone())
+                    }),
+            );
+        } else {
+            shapes.push((part_transform, shape));
+        }
+    }
+
+    if shapes.is_empty() {
+        None
+    } else {
+        Some(SharedShape::compound(shapes))
+    }
+}
+
 /// Creates new convex polyhedron collider shape from given mesh node. It also bakes scale into
 /// vertices of trimesh because rapier does not support collider scaling yet.
 fn make_polyhedron_shape(owner_inv_transform: Matrix4<f32>, mesh: &Mesh) -> SharedShape {
@@ -721,12 +1166,45 @@ fn make_heightfield(terrain: &Terrain) -> Option<SharedShape> {
     )]))
 }
 
+// Converts descriptor in a shared shape and refreshes convex decompositions cached on the
+// collider.
+fn make_collider_shape(
+    collider: &scene::collider::Collider,
+    shape: &ColliderShape,
+    owner_inv_global_transform: Matrix4<f32>,
+    owner_collider: Handle<Node>,
+    pool: &NodePool,
+    task_pool: Option<&Arc<TaskPool>>,
+) -> Option<SharedShape> {
+    let mut cache = ConvexDecompositionCache {
+        previous: collider.convex_decompositions.take(),
+        previous_pending: collider.pending_convex_decompositions.take(),
+        task_pool: task_pool.cloned(),
+        ..Default::default()
+    };
+    let shape = collider_shape_into_native_shape(
+        shape,
+        owner_inv_global_transform,
+        owner_collider,
+        pool,
+        Isometry3::identity(),
+        &mut cache,
+    );
+    collider.convex_decompositions.replace(cache.current);
+    collider
+        .pending_convex_decompositions
+        .replace(cache.current_pending);
+    shape
+}
+
 // Converts descriptor in a shared shape.
 fn collider_shape_into_native_shape(
     shape: &ColliderShape,
     owner_inv_global_transform: Matrix4<f32>,
     owner_collider: Handle<Node>,
     pool: &NodePool,
+    transform: Isometry3<f32>,
+    cache: &mut ConvexDecompositionCache,
 ) -> Option<SharedShape> {
     match shape {
         ColliderShape::Ball(ball) => Some(SharedShape::ball(ball.radius)),
@@ -772,6 +1250,28 @@ fn collider_shape_into_native_shape(
             .try_borrow(polyhedron.geometry_source.0)
             .and_then(|n| n.cast::<Mesh>())
             .map(|mesh| make_polyhedron_shape(owner_inv_global_transform, mesh)),
+        ColliderShape::ConvexDecomposition(decomposition) => {
+            if decomposition.sources.is_empty() {
+                None
+            } else {
+                make_convex_decomposition(
+                    owner_inv_global_transform,
+                    owner_collider,
+                    decomposition,
+                    pool,
+                    transform,
+                    cache,
+                )
+            }
+        }
+        ColliderShape::Compound(compound) => make_compound(
+            compound,
+            owner_inv_global_transform,
+            owner_collider,
+            pool,
+            transform,
+            cache,
+        ),
     }
 }
 
@@ -967,6 +1467,12 @@ pub struct PhysicsWorld {
     #[reflect(hidden)]
     pub performance_statistics: PhysicsPerformanceStatistics,
 
+    /// Recorder of the simulation state, it is disabled by default. See [`PhysicsRecorder`] docs for
+    /// more info.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub recorder: PhysicsRecorder,
+
     // Current physics pipeline.
     #[visit(skip)]
     #[reflect(hidden)]
@@ -1007,13 +1513,43 @@ pub struct PhysicsWorld {
     // Event handler collects info about contacts and proximity events.
     #[visit(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_handler: CollisionEventCollector<rapier3d::geometry::CollisionEvent>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pending_contact_events: Vec<(ColliderHandle, ColliderHandle, CollisionEvent)>,
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
     #[visit(skip)]
     #[reflect(hidden)]
     debug_render_pipeline: Mutex<DebugRenderPipeline>,
+    // Interpolation factor between the last two physics states, `None` - interpolation is disabled.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    interpolation_factor: Option<f32>,
+    // Poses of the awake dynamic bodies before the last simulation step.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    previous_poses: FxHashMap<RigidBodyHandle, Isometry3<f32>>,
+    // Poses, that were written to rigid body nodes during the last sync. They must not be sent back
+    // to the physics engine, otherwise the simulation will be rewound.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    interpolated_poses: FxHashMap<RigidBodyHandle, Isometry3<f32>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    vehicles: Pool<NativeVehicle>,
+    // Surface tags of physics materials of the colliders, only non-empty tags are stored.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    surface_tags: FxHashMap<ColliderHandle, ImmutableString>,
+    // Task pool for convex decompositions, they're computed right away if there's no task pool.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    task_pool: Option<Arc<TaskPool>>,
 }
 
 impl Clone for PhysicsWorld {
@@ -1022,11 +1558,128 @@ impl Clone for PhysicsWorld {
             enabled: self.enabled.clone(),
             integration_parameters: self.integration_parameters.clone(),
             gravity: self.gravity.clone(),
+            task_pool: self.task_pool.clone(),
             ..Default::default()
         }
     }
 }
 
+/// A snapshot of the complete state of the 3D physics world: rigid bodies, colliders, joints,
+/// islands, broad and narrow phases. A snapshot is taken by [`PhysicsWorld::snapshot`] and restored
+/// by [`Graph::restore_physics_snapshot`], which also puts the physical nodes back to their
+/// captured positions. Taking a snapshot is a plain copy of the internal state of the physics
+/// engine, so it is cheap enough to be done every frame (for example, for rollback networking).
+/// Use [`PhysicsSnapshot::to_bytes`] to store the snapshot or to send it over the network.
+///
+/// ```rust
+/// # use fyrox_impl::scene::Scene;
+/// fn rollback(scene: &mut Scene) {
+///     let snapshot = scene.graph.physics.snapshot();
+///     // ... simulate a few frames ...
+///     scene.graph.restore_physics_snapshot(&snapshot);
+/// }
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PhysicsSnapshot {
+    islands: IslandManager,
+    broad_phase: DefaultBroadPhase,
+    narrow_phase: NarrowPhase,
+    ccd_solver: CCDSolver,
+    bodies: RigidBodySet,
+    colliders: ColliderSet,
+    joints: ImpulseJointSet,
+    joint_owners: Vec<(ImpulseJointHandle, Handle<Node>)>,
+    multibody_joints: MultibodyJointSet,
+    multibody_joint_owners: Vec<(MultibodyJointHandle, Handle<Node>)>,
+}
+
+impl Debug for PhysicsSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PhysicsSnapshot - {} bodies, {} colliders, {} joints",
+            self.bodies.len(),
+            self.colliders.len(),
+            self.joints.len()
+        )
+    }
+}
+
+impl PhysicsSnapshot {
+    /// Serializes the snapshot into a compact binary form.
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        bincode::serialize(self).map_err(io::Error::other)
+    }
+
+    /// Deserializes a snapshot from the bytes produced by [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        bincode::deserialize(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    // Syncs a physical node with the state of the snapshot. Returns `true` if the local transform
+    // of the node was changed.
+    pub(crate) fn restore_node(&self, node: &mut Node, parent_transform: &Matrix4<f32>) -> bool {
+        if let Some(rigid_body) = node.cast_mut::<scene::rigidbody::RigidBody>() {
+            let Some(native) = self.bodies.get(rigid_body.native.get()) else {
+                // The body was created after the snapshot, it will be re-created on next sync.
+                rigid_body.native.set(RigidBodyHandle::invalid());
+                return false;
+            };
+            rigid_body
+                .lin_vel
+                .set_value_with_flags(*native.linvel(), VariableFlags::MODIFIED);
+            rigid_body
+                .ang_vel
+                .set_value_with_flags(*native.angvel(), VariableFlags::MODIFIED);
+            rigid_body.sleeping = native.is_sleeping();
+            set_node_pose(node, parent_transform, native.position());
+            true
+        } else if let Some(controller) = node.cast::<scene::character::CharacterController>() {
+            let Some(native) = self.bodies.get(controller.native.get()) else {
+                controller.native.set(RigidBodyHandle::invalid());
+                return false;
+            };
+            set_node_pose(node, parent_transform, native.position());
+            true
+        } else {
+            if let Some(collider) = node.cast::<scene::collider::Collider>() {
+                if !self.colliders.contains(collider.native.get()) {
+                    collider.native.set(ColliderHandle::invalid());
+                }
+            }
+            false
+        }
+    }
+}
+
+fn set_node_pose(node: &mut Node, parent_transform: &Matrix4<f32>, pose: &Isometry3<f32>) {
+    let local_transform: Matrix4<f32> = parent_transform
+        .try_inverse()
+        .unwrap_or_else(Matrix4::identity)
+        * pose.to_homogeneous();
+
+    node.local_transform_mut()
+        .set_position(Vector3::new(
+            local_transform[12],
+            local_transform[13],
+            local_transform[14],
+        ))
+        .set_rotation(UnitQuaternion::from_matrix_eps(
+            &local_transform.basis(),
+            f32::EPSILON,
+            16,
+            UnitQuaternion::identity(),
+        ));
+}
+
+fn active_events(report_collision_events: bool) -> ActiveEvents {
+    if report_collision_events {
+        ActiveEvents::COLLISION_EVENTS
+    } else {
+        ActiveEvents::empty()
+    }
+}
+
 fn isometry_from_global_transform(transform: &Matrix4<f32>) -> Isometry3<f32> {
     Isometry3 {
         translation: Translation3::new(transform[12], transform[13], transform[14]),
@@ -1098,6 +1751,17 @@ pub struct TOI {
     pub status: collider::TOIStatus,
 }
 
+/// The result of a point projection, see [`PhysicsWorld::project_point`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PointProjection {
+    /// A handle of the collider on which the point was projected.
+    pub collider: Handle<Node>,
+    /// The projection of the point on the collider (in world coordinates).
+    pub point: Point3<f32>,
+    /// Whether the point was inside the collider or not.
+    pub is_inside: bool,
+}
+
 impl PhysicsWorld {
     /// Creates a new instance of the physics world.
     pub(super) fn new() -> Self {
@@ -1120,10 +1784,19 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_handler: Default::default(),
+            collision_events: Default::default(),
+            pending_contact_events: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
+            interpolation_factor: None,
+            previous_poses: Default::default(),
+            interpolated_poses: Default::default(),
+            vehicles: Default::default(),
+            surface_tags: Default::default(),
+            task_pool: None,
+            recorder: Default::default(),
         }
     }
 
@@ -1162,6 +1835,18 @@ impl PhysicsWorld {
                 max_ccd_substeps: self.integration_parameters.max_ccd_substeps as usize,
             };
 
+            self.update_vehicles(integration_parameters.dt);
+
+            self.previous_poses.clear();
+            if self.interpolation_factor.is_some() {
+                self.previous_poses.extend(
+                    self.bodies
+                        .iter()
+                        .filter(|(_, body)| body.is_dynamic() && !body.is_sleeping())
+                        .map(|(handle, body)| (handle, *body.position())),
+                );
+            }
+
             self.pipeline.step(
                 &self.gravity,
                 &integration_parameters,
@@ -1177,33 +1862,395 @@ impl PhysicsWorld {
                 // so we keep updating it manually.
                 None,
                 &(),
-                &*self.event_handler,
+                &self.event_handler,
             );
+
+            self.collect_collision_events();
+
+            if self.recorder.is_enabled() {
+                self.record_frame(integration_parameters.dt);
+            }
         }
 
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
-    pub(super) fn add_body(&mut self, owner: Handle<Node>, mut body: RigidBody) -> RigidBodyHandle {
-        body.user_data = owner.encode_to_u128();
-        self.bodies.insert(body)
-    }
+    // Captures the current state of the simulation and passes it to the recorder.
+    fn record_frame(&mut self, dt: f32) {
+        let bodies = self
+            .bodies
+            .iter()
+            .map(|(_, body)| RecordedBody {
+                node: Handle::decode_from_u128(body.user_data),
+                position: body.position().translation.vector,
+                rotation: body.position().rotation,
+                lin_vel: *body.linvel(),
+                ang_vel: *body.angvel(),
+                sleeping: body.is_sleeping(),
+            })
+            .collect();
+
+        let colliders = self
+            .colliders
+            .iter()
+            .map(|(_, collider)| {
+                let aabb = collider.shape().compute_local_aabb();
+                RecordedCollider {
+                    node: Handle::decode_from_u128(collider.user_data),
+                    position: collider.position().translation.vector,
+                    rotation: collider.position().rotation,
+                    local_aabb_min: aabb.mins.coords,
+                    local_aabb_max: aabb.maxs.coords,
+                    sleeping: collider
+                        .parent()
+                        .and_then(|parent| self.bodies.get(parent))
+                        .is_some_and(|body| body.is_sleeping()),
+                }
+            })
+            .collect();
 
-    pub(crate) fn remove_body(&mut self, handle: RigidBodyHandle) {
-        self.bodies.remove(
-            handle,
-            &mut self.islands,
-            &mut self.colliders,
-            &mut self.joints.set,
-            &mut self.multibody_joints.set,
-            true,
+        let mut contacts = Vec::new();
+        for pair in self.narrow_phase.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+            let (Some(collider1), Some(collider2)) = (
+                self.colliders.get(pair.collider1),
+                self.colliders.get(pair.collider2),
+            ) else {
+                continue;
+            };
+            for manifold in pair.manifolds.iter() {
+                for point in manifold.points.iter().filter(|p| p.dist <= 0.0) {
+                    contacts.push(RecordedContact {
+                        collider1: Handle::decode_from_u128(collider1.user_data),
+                        collider2: Handle::decode_from_u128(collider2.user_data),
+                        point: (collider1.position() * point.local_p1).coords,
+                        normal: manifold.data.normal,
+                        impulse: point.data.impulse,
+                    });
+                }
+            }
+        }
+
+        self.recorder.push(
+            dt,
+            PhysicsRecordingFrame {
+                time: 0.0,
+                bodies,
+                colliders,
+                contacts,
+            },
         );
     }
 
-    pub(super) fn add_collider(
-        &mut self,
-        owner: Handle<Node>,
-        parent_body: RigidBodyHandle,
+    // Applies suspension, engine and braking forces of the vehicles to their chassis.
+    fn update_vehicles(&mut self, dt: f32) {
+        if self.vehicles.alive_count() == 0 {
+            return;
+        }
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        for native in self.vehicles.iter_mut() {
+            let chassis = native.controller.chassis;
+            if self.bodies.get(chassis).is_none() {
+                continue;
+            }
+
+            native.controller.update_vehicle(
+                dt,
+                &mut self.bodies,
+                &self.colliders,
+                &query,
+                rapier3d::pipeline::QueryFilter::default()
+                    .exclude_rigid_body(chassis)
+                    .exclude_sensors(),
+            );
+        }
+    }
+
+    fn fetch_contact_data(
+        &self,
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+        event: &mut CollisionEvent,
+    ) {
+        event.contact_points.clear();
+        event.total_impulse = 0.0;
+
+        if let Some(pair) = self.narrow_phase.contact_pair(collider1, collider2) {
+            let sign = if pair.collider1 == collider1 {
+                1.0
+            } else {
+                -1.0
+            };
+            for manifold in pair.manifolds.iter() {
+                event.contact_points.extend(
+                    manifold
+                        .data
+                        .solver_contacts
+                        .iter()
+                        .map(|contact| contact.point.coords),
+                );
+                event.total_impulse += manifold.points.iter().map(|p| p.data.impulse).sum::<f32>();
+            }
+            if let Some(manifold) = pair.manifolds.first() {
+                event.normal = manifold.data.normal.scale(sign);
+            }
+        }
+    }
+
+    fn collect_collision_events(&mut self) {
+        self.collision_events.clear();
+
+        // Speculative contacts are reported one step earlier than the actual hit, so their impulse
+        // is still zero. Such events are delayed by one step to report the actual impact impulse.
+        for (collider1, collider2, mut event) in std::mem::take(&mut self.pending_contact_events) {
+            self.fetch_contact_data(collider1, collider2, &mut event);
+            self.collision_events.push(event);
+        }
+
+        let events = std::mem::take(self.event_handler.events.get_mut());
+        for event in events {
+            let (collider1, collider2, flags, started) = match event {
+                rapier3d::geometry::CollisionEvent::Started(c1, c2, flags) => (c1, c2, flags, true),
+                rapier3d::geometry::CollisionEvent::Stopped(c1, c2, flags) => {
+                    (c1, c2, flags, false)
+                }
+            };
+
+            let collider_node = |handle: ColliderHandle| {
+                self.colliders
+                    .get(handle)
+                    .map(|c| Handle::decode_from_u128(c.user_data))
+                    .unwrap_or_default()
+            };
+
+            let mut event = CollisionEvent {
+                kind: match (flags.contains(CollisionEventFlags::SENSOR), started) {
+                    (false, true) => CollisionEventKind::ContactStarted,
+                    (false, false) => CollisionEventKind::ContactStopped,
+                    (true, true) => CollisionEventKind::IntersectionStarted,
+                    (true, false) => CollisionEventKind::IntersectionStopped,
+                },
+                collider1: collider_node(collider1),
+                collider2: collider_node(collider2),
+                contact_points: Default::default(),
+                normal: Default::default(),
+                total_impulse: 0.0,
+            };
+
+            match event.kind {
+                CollisionEventKind::ContactStarted => {
+                    self.fetch_contact_data(collider1, collider2, &mut event);
+                    if event.total_impulse == 0.0 {
+                        self.pending_contact_events
+                            .push((collider1, collider2, event));
+                        continue;
+                    }
+                }
+                CollisionEventKind::ContactStopped => {
+                    // The contact has stopped before its delayed start event was reported.
+                    if let Some(index) =
+                        self.pending_contact_events.iter().position(|(c1, c2, _)| {
+                            (*c1, *c2) == (collider1, collider2)
+                                || (*c1, *c2) == (collider2, collider1)
+                        })
+                    {
+                        let (_, _, started) = self.pending_contact_events.remove(index);
+                        self.collision_events.push(started);
+                    }
+                }
+                _ => (),
+            }
+
+            self.collision_events.push(event);
+        }
+    }
+
+    /// Returns a list of collision events, that have happened during the last physics step. See
+    /// [`CollisionEvent`] docs for more info.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
+    }
+
+    /// Takes a snapshot of the complete state of the physics world. See [`PhysicsSnapshot`] docs for
+    /// more info.
+    pub fn snapshot(&self) -> PhysicsSnapshot {
+        PhysicsSnapshot {
+            islands: self.islands.clone(),
+            broad_phase: self.broad_phase.clone(),
+            narrow_phase: self.narrow_phase.clone(),
+            ccd_solver: self.ccd_solver.clone(),
+            bodies: self.bodies.clone(),
+            colliders: self.colliders.clone(),
+            joints: self.joints.set.clone(),
+            joint_owners: self
+                .joints
+                .map
+                .forward_map()
+                .iter()
+                .map(|(handle, owner)| (*handle, *owner))
+                .collect(),
+            multibody_joints: self.multibody_joints.set.clone(),
+            multibody_joint_owners: self
+                .multibody_joints
+                .map
+                .forward_map()
+                .iter()
+                .map(|(handle, owner)| (*handle, *owner))
+                .collect(),
+        }
+    }
+
+    // Replaces the state of the world with the snapshot. Nodes must be synced with the snapshot
+    // before this call, see `Graph::restore_physics_snapshot`.
+    pub(crate) fn restore_snapshot(&mut self, snapshot: &PhysicsSnapshot) {
+        let snapshot = snapshot.clone();
+        self.islands = snapshot.islands;
+        self.broad_phase = snapshot.broad_phase;
+        self.narrow_phase = snapshot.narrow_phase;
+        self.ccd_solver = snapshot.ccd_solver;
+        self.bodies = snapshot.bodies;
+        self.colliders = snapshot.colliders;
+        self.joints = Container {
+            set: snapshot.joints,
+            map: snapshot
+                .joint_owners
+                .into_iter()
+                .collect::<FxHashMap<_, _>>()
+                .into(),
+        };
+        self.multibody_joints = Container {
+            set: snapshot.multibody_joints,
+            map: snapshot
+                .multibody_joint_owners
+                .into_iter()
+                .collect::<FxHashMap<_, _>>()
+                .into(),
+        };
+        self.event_handler.events.get_mut().clear();
+        self.collision_events.clear();
+        self.pending_contact_events.clear();
+        self.previous_poses.clear();
+        self.interpolated_poses.clear();
+    }
+
+    // Removes every native entity, that does not belong to a node anymore.
+    pub(crate) fn remove_orphans(&mut self, nodes: &NodePool) {
+        let orphan_bodies = self
+            .bodies
+            .iter()
+            .filter(|(handle, body)| {
+                let owner = nodes.try_borrow(Handle::decode_from_u128(body.user_data));
+                owner.and_then(|node| {
+                    node.cast::<scene::rigidbody::RigidBody>()
+                        .map(|rigid_body| rigid_body.native.get())
+                        .or_else(|| {
+                            node.cast::<scene::character::CharacterController>()
+                                .map(|controller| controller.native.get())
+                        })
+                }) != Some(*handle)
+            })
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+        for handle in orphan_bodies {
+            self.remove_body(handle);
+        }
+
+        let orphan_colliders = self
+            .colliders
+            .iter()
+            .filter(|(handle, collider)| {
+                nodes
+                    .try_borrow(Handle::decode_from_u128(collider.user_data))
+                    .and_then(|node| node.cast::<scene::collider::Collider>())
+                    .map(|collider| collider.native.get())
+                    != Some(*handle)
+            })
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+        for handle in orphan_colliders {
+            self.remove_collider(handle);
+        }
+
+        let orphan_joints = self
+            .joints
+            .map
+            .forward_map()
+            .iter()
+            .filter(|(handle, owner)| {
+                nodes
+                    .try_borrow(**owner)
+                    .and_then(|node| node.cast::<scene::joint::Joint>())
+                    .map(|joint| joint.native.get())
+                    != Some(**handle)
+            })
+            .map(|(handle, _)| *handle)
+            .collect::<Vec<_>>();
+        for handle in orphan_joints {
+            self.remove_joint(handle);
+        }
+    }
+
+    /// Sets the interpolation factor (in `[0; 1]` range) between the last two physics states. When
+    /// set, dynamic rigid body nodes are synced with the poses interpolated between the previous and
+    /// the current simulation steps, which removes jitter when the physics is stepped with a fixed
+    /// rate that differs from the frame rate. `None` disables the interpolation. The engine sets this
+    /// value automatically when fixed time step is enabled, see
+    /// [`crate::engine::FixedTimeStepSettings`] for more info.
+    pub fn set_interpolation_factor(&mut self, factor: Option<f32>) {
+        self.interpolation_factor = factor.map(|factor| factor.clamp(0.0, 1.0));
+        if self.interpolation_factor.is_none() {
+            self.previous_poses.clear();
+            self.interpolated_poses.clear();
+        }
+    }
+
+    /// Returns current interpolation factor between the last two physics states. See
+    /// [`Self::set_interpolation_factor`] for more info.
+    pub fn interpolation_factor(&self) -> Option<f32> {
+        self.interpolation_factor
+    }
+
+    /// Sets a task pool that will be used to compute convex decompositions (see
+    /// [`crate::scene::collider::ConvexDecompositionShape`]) in background. Decompositions are
+    /// computed right away, blocking the update, if there's no task pool. The engine sets its own
+    /// task pool for every scene added to [`crate::scene::SceneContainer`].
+    pub fn set_task_pool(&mut self, task_pool: Option<Arc<TaskPool>>) {
+        self.task_pool = task_pool;
+    }
+
+    /// Returns the task pool used to compute convex decompositions. See [`Self::set_task_pool`]
+    /// for more info.
+    pub fn task_pool(&self) -> Option<&Arc<TaskPool>> {
+        self.task_pool.as_ref()
+    }
+
+    pub(super) fn add_body(&mut self, owner: Handle<Node>, mut body: RigidBody) -> RigidBodyHandle {
+        body.user_data = owner.encode_to_u128();
+        self.bodies.insert(body)
+    }
+
+    pub(crate) fn remove_body(&mut self, handle: RigidBodyHandle) {
+        self.previous_poses.remove(&handle);
+        self.interpolated_poses.remove(&handle);
+        self.bodies.remove(
+            handle,
+            &mut self.islands,
+            &mut self.colliders,
+            &mut self.joints.set,
+            &mut self.multibody_joints.set,
+            true,
+        );
+    }
+
+    pub(super) fn add_collider(
+        &mut self,
+        owner: Handle<Node>,
+        parent_body: RigidBodyHandle,
         mut collider: Collider,
     ) -> ColliderHandle {
         collider.user_data = owner.encode_to_u128();
@@ -1212,6 +2259,7 @@ impl PhysicsWorld {
     }
 
     pub(crate) fn remove_collider(&mut self, handle: ColliderHandle) -> bool {
+        self.surface_tags.remove(&handle);
         self.colliders
             .remove(handle, &mut self.islands, &mut self.bodies, false)
             .is_some()
@@ -1261,6 +2309,51 @@ impl PhysicsWorld {
         // of the frame.
         query.update(&self.colliders);
 
+        self.cast_ray_with_query(&query, &opts, query_buffer);
+
+        self.performance_statistics.total_ray_cast_time.set(
+            self.performance_statistics.total_ray_cast_time.get()
+                + (instant::Instant::now() - time),
+        );
+    }
+
+    /// Casts a batch of rays with given options. Results of each ray are written to a respective
+    /// query buffer, so `query_buffers` must have the same length as `rays`. This method is much
+    /// faster than calling [`Self::cast_ray`] for every ray, because the acceleration structure of
+    /// the physics world is updated only once for the whole batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the amount of query buffers does not match the amount of rays.
+    pub fn cast_rays<S: QueryResultsStorage>(
+        &self,
+        rays: &[RayCastOptions],
+        query_buffers: &mut [S],
+    ) {
+        assert_eq!(rays.len(), query_buffers.len());
+
+        let time = instant::Instant::now();
+
+        let mut query = self.query.borrow_mut();
+
+        query.update(&self.colliders);
+
+        for (opts, query_buffer) in rays.iter().zip(query_buffers.iter_mut()) {
+            self.cast_ray_with_query(&query, opts, query_buffer);
+        }
+
+        self.performance_statistics.total_ray_cast_time.set(
+            self.performance_statistics.total_ray_cast_time.get()
+                + (instant::Instant::now() - time),
+        );
+    }
+
+    fn cast_ray_with_query<S: QueryResultsStorage>(
+        &self,
+        query: &QueryPipeline,
+        opts: &RayCastOptions,
+        query_buffer: &mut S,
+    ) {
         query_buffer.clear();
         let ray = Ray::new(
             opts.ray_origin,
@@ -1287,6 +2380,7 @@ impl PhysicsWorld {
                     position: ray.point_at(intersection.time_of_impact),
                     feature: intersection.feature.into(),
                     toi: intersection.time_of_impact,
+                    surface_tag: self.surface_tags.get(&handle).cloned().unwrap_or_default(),
                 })
             },
         );
@@ -1301,11 +2395,53 @@ impl PhysicsWorld {
                 }
             })
         }
+    }
 
-        self.performance_statistics.total_ray_cast_time.set(
-            self.performance_statistics.total_ray_cast_time.get()
-                + (instant::Instant::now() - time),
-        );
+    // Creates a predicate for a native query filter, that calls the predicate of the given filter
+    // (if any) with the collider node.
+    fn query_filter_predicate<'a>(
+        &'a self,
+        graph: &'a Graph,
+        filter: QueryFilter<'a>,
+    ) -> impl Fn(ColliderHandle, &Collider) -> bool + 'a {
+        move |handle: ColliderHandle, _: &Collider| -> bool {
+            if let Some(pred) = filter.predicate {
+                let h = Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data);
+                pred(
+                    h,
+                    graph.node(h).component_ref::<collider::Collider>().unwrap(),
+                )
+            } else {
+                true
+            }
+        }
+    }
+
+    // Converts the given filter into a native one. The predicate must be created by
+    // [`Self::query_filter_predicate`].
+    fn native_query_filter<'a>(
+        &self,
+        graph: &Graph,
+        filter: QueryFilter,
+        predicate: &'a dyn Fn(ColliderHandle, &Collider) -> bool,
+    ) -> rapier3d::pipeline::QueryFilter<'a> {
+        rapier3d::pipeline::QueryFilter {
+            flags: rapier3d::pipeline::QueryFilterFlags::from_bits(filter.flags.bits()).unwrap(),
+            groups: filter.groups.map(|g| {
+                InteractionGroups::new(u32_to_group(g.memberships.0), u32_to_group(g.filter.0))
+            }),
+            exclude_collider: filter
+                .exclude_collider
+                .and_then(|h| graph.try_get(h))
+                .and_then(|n| n.component_ref::<collider::Collider>())
+                .map(|c| c.native.get()),
+            exclude_rigid_body: filter
+                .exclude_rigid_body
+                .and_then(|h| graph.try_get(h))
+                .and_then(|n| n.component_ref::<rigidbody::RigidBody>())
+                .map(|c| c.native.get()),
+            predicate: Some(predicate),
+        }
     }
 
     /// Casts a shape at a constant linear velocity and retrieve the first collider it hits.
@@ -1337,35 +2473,8 @@ impl PhysicsWorld {
         stop_at_penetration: bool,
         filter: QueryFilter,
     ) -> Option<(Handle<Node>, TOI)> {
-        let predicate = |handle: ColliderHandle, _: &Collider| -> bool {
-            if let Some(pred) = filter.predicate {
-                let h = Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data);
-                pred(
-                    h,
-                    graph.node(h).component_ref::<collider::Collider>().unwrap(),
-                )
-            } else {
-                true
-            }
-        };
-
-        let filter = rapier3d::pipeline::QueryFilter {
-            flags: rapier3d::pipeline::QueryFilterFlags::from_bits(filter.flags.bits()).unwrap(),
-            groups: filter.groups.map(|g| {
-                InteractionGroups::new(u32_to_group(g.memberships.0), u32_to_group(g.filter.0))
-            }),
-            exclude_collider: filter
-                .exclude_collider
-                .and_then(|h| graph.try_get(h))
-                .and_then(|n| n.component_ref::<collider::Collider>())
-                .map(|c| c.native.get()),
-            exclude_rigid_body: filter
-                .exclude_collider
-                .and_then(|h| graph.try_get(h))
-                .and_then(|n| n.component_ref::<rigidbody::RigidBody>())
-                .map(|c| c.native.get()),
-            predicate: Some(&predicate),
-        };
+        let predicate = self.query_filter_predicate(graph, filter);
+        let filter = self.native_query_filter(graph, filter, &predicate);
 
         let query = self.query.borrow_mut();
 
@@ -1401,14 +2510,127 @@ impl PhysicsWorld {
             })
     }
 
+    /// Finds all colliders that intersect the given shape at the given position. It could be used
+    /// to find every object in an area of effect or in a field of view of an AI agent, for example.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `shape` - the shape to test.
+    /// * `shape_pos` - the position of the shape in world coordinates.
+    /// * `filter` - set of rules used to determine which collider is taken into account by this
+    ///   scene query.
+    /// * `callback` - a function that will be called with a handle of every intersecting collider
+    ///   node. Return `false` from it to stop the query.
+    pub fn intersect_shape(
+        &self,
+        graph: &Graph,
+        shape: &dyn Shape,
+        shape_pos: &Isometry3<f32>,
+        filter: QueryFilter,
+        mut callback: impl FnMut(Handle<Node>) -> bool,
+    ) {
+        let predicate = self.query_filter_predicate(graph, filter);
+        let filter = self.native_query_filter(graph, filter, &predicate);
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        query.intersections_with_shape(
+            &self.bodies,
+            &self.colliders,
+            shape_pos,
+            shape,
+            filter,
+            |handle| {
+                callback(Handle::decode_from_u128(
+                    self.colliders.get(handle).unwrap().user_data,
+                ))
+            },
+        );
+    }
+
+    /// Finds all colliders that contain the given point (in world coordinates).
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `point` - the point to test.
+    /// * `filter` - set of rules used to determine which collider is taken into account by this
+    ///   scene query.
+    /// * `callback` - a function that will be called with a handle of every collider node that
+    ///   contains the point. Return `false` from it to stop the query.
+    pub fn intersections_with_point(
+        &self,
+        graph: &Graph,
+        point: &Point3<f32>,
+        filter: QueryFilter,
+        mut callback: impl FnMut(Handle<Node>) -> bool,
+    ) {
+        let predicate = self.query_filter_predicate(graph, filter);
+        let filter = self.native_query_filter(graph, filter, &predicate);
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        query.intersections_with_point(&self.bodies, &self.colliders, point, filter, |handle| {
+            callback(Handle::decode_from_u128(
+                self.colliders.get(handle).unwrap().user_data,
+            ))
+        });
+    }
+
+    /// Finds the projection of the given point (in world coordinates) on the closest collider.
+    ///
+    /// # Parameters
+    ///
+    /// * `graph` - a reference to the scene graph.
+    /// * `point` - the point to project.
+    /// * `solid` - if `true`, a point inside a collider will be projected on itself, otherwise it
+    ///   will be projected on the boundary of the collider.
+    /// * `filter` - set of rules used to determine which collider is taken into account by this
+    ///   scene query.
+    pub fn project_point(
+        &self,
+        graph: &Graph,
+        point: &Point3<f32>,
+        solid: bool,
+        filter: QueryFilter,
+    ) -> Option<PointProjection> {
+        let predicate = self.query_filter_predicate(graph, filter);
+        let filter = self.native_query_filter(graph, filter, &predicate);
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        query
+            .project_point(&self.bodies, &self.colliders, point, solid, filter)
+            .map(|(handle, projection)| PointProjection {
+                collider: Handle::decode_from_u128(self.colliders.get(handle).unwrap().user_data),
+                point: projection.point,
+                is_inside: projection.is_inside,
+            })
+    }
+
     pub(crate) fn set_rigid_body_position(
         &mut self,
         rigid_body: &scene::rigidbody::RigidBody,
         new_global_transform: &Matrix4<f32>,
     ) {
+        let new_position = isometry_from_global_transform(new_global_transform);
+
+        // Interpolated poses are for rendering only, the simulation must not be affected by them.
+        if let Some(interpolated) = self.interpolated_poses.get(&rigid_body.native.get()) {
+            if (interpolated.translation.vector - new_position.translation.vector).norm() <= 1.0e-4
+                && interpolated.rotation.angle_to(&new_position.rotation) <= 1.0e-4
+            {
+                return;
+            }
+        }
+
         if let Some(native) = self.bodies.get_mut(rigid_body.native.get()) {
             native.set_position(
-                isometry_from_global_transform(new_global_transform),
+                new_position,
                 // Do not wake up body, it is too expensive and must be done **only** by explicit
                 // `wake_up` call!
                 false,
@@ -1420,14 +2642,31 @@ impl PhysicsWorld {
         &mut self,
         rigid_body: &mut scene::rigidbody::RigidBody,
         parent_transform: Matrix4<f32>,
+        interpolate: bool,
     ) {
         if *self.enabled {
-            if let Some(native) = self.bodies.get(rigid_body.native.get()) {
+            let handle = rigid_body.native.get();
+            if let Some(native) = self.bodies.get(handle) {
                 if native.body_type() == RigidBodyType::Dynamic {
+                    let position = match (
+                        self.interpolation_factor.filter(|_| interpolate),
+                        self.previous_poses.get(&handle),
+                    ) {
+                        (Some(factor), Some(previous)) => {
+                            let position = previous.lerp_slerp(native.position(), factor);
+                            self.interpolated_poses.insert(handle, position);
+                            position
+                        }
+                        _ => {
+                            self.interpolated_poses.remove(&handle);
+                            *native.position()
+                        }
+                    };
+
                     let local_transform: Matrix4<f32> = parent_transform
                         .try_inverse()
                         .unwrap_or_else(Matrix4::identity)
-                        * native.position().to_homogeneous();
+                        * position.to_homogeneous();
 
                     let new_local_rotation = UnitQuaternion::from_matrix_eps(
                         &local_transform.basis(),
@@ -1626,6 +2865,318 @@ impl PhysicsWorld {
         }
     }
 
+    pub(crate) fn set_character_controller_position(
+        &mut self,
+        controller: &scene::character::CharacterController,
+        new_global_transform: &Matrix4<f32>,
+    ) {
+        if let Some(native) = self.bodies.get_mut(controller.native.get()) {
+            native
+                .set_next_kinematic_position(isometry_from_global_transform(new_global_transform));
+        }
+    }
+
+    pub(crate) fn sync_to_character_controller_node(
+        &mut self,
+        handle: Handle<Node>,
+        controller: &scene::character::CharacterController,
+    ) {
+        if !controller.is_globally_enabled() {
+            self.remove_body(controller.native.get());
+            controller.native.set(Default::default());
+            return;
+        }
+
+        if controller.native.get() == RigidBodyHandle::invalid() {
+            let body = RigidBodyBuilder::kinematic_position_based()
+                .position(isometry_from_global_transform(
+                    &controller.global_transform(),
+                ))
+                .build();
+
+            controller.native.set(self.add_body(handle, body));
+
+            Log::writeln(
+                MessageKind::Information,
+                format!(
+                    "Native character controller was created for node {}",
+                    controller.name()
+                ),
+            );
+        }
+    }
+
+    pub(crate) fn move_character(
+        &mut self,
+        controller: &mut scene::character::CharacterController,
+        parent_transform: Matrix4<f32>,
+        dt: f32,
+    ) {
+        let desired_translation = std::mem::take(&mut controller.desired_translation);
+
+        controller.collisions.clear();
+        controller.effective_translation = Vector3::default();
+        controller.platform_velocity = Vector3::default();
+
+        if !*self.enabled {
+            return;
+        }
+
+        let body_handle = controller.native.get();
+        let Some(collider) = self
+            .bodies
+            .get(body_handle)
+            .and_then(|body| body.colliders().first())
+            .and_then(|collider| self.colliders.get(*collider))
+        else {
+            return;
+        };
+
+        let autostep = controller.autostep();
+        let native_controller = KinematicCharacterController {
+            offset: CharacterLength::Absolute(controller.offset()),
+            slide: controller.is_slide(),
+            autostep: autostep.enabled.then_some(CharacterAutostep {
+                max_height: CharacterLength::Absolute(autostep.max_height),
+                min_width: CharacterLength::Absolute(autostep.min_width),
+                include_dynamic_bodies: autostep.include_dynamic_bodies,
+            }),
+            max_slope_climb_angle: controller.max_slope_climb_angle(),
+            min_slope_slide_angle: controller.min_slope_slide_angle(),
+            snap_to_ground: (controller.snap_to_ground() > 0.0)
+                .then(|| CharacterLength::Absolute(controller.snap_to_ground())),
+            ..Default::default()
+        };
+
+        let filter = rapier3d::pipeline::QueryFilter::default()
+            .exclude_rigid_body(body_handle)
+            .exclude_sensors()
+            .groups(collider.collision_groups());
+
+        let mut query = self.query.borrow_mut();
+        query.update(&self.colliders);
+
+        // The native body of the controller is synced with the node with one frame delay, so
+        // the node is the only reliable source of the current position of the character.
+        let character_position = isometry_from_global_transform(&controller.global_transform())
+            * collider
+                .position_wrt_parent()
+                .cloned()
+                .unwrap_or_else(Isometry3::identity);
+
+        let mut native_collisions = Vec::new();
+
+        // Move the character together with the platform it stands on (if any). The platform
+        // itself is excluded from the movement, so it won't block the character.
+        let mut platform_translation = Vector3::default();
+        if controller.is_inherit_platform_velocity() {
+            let probe_distance = 2.0 * controller.offset() + PLATFORM_PROBE_DISTANCE;
+            if let Some((platform_collider, hit)) = query.cast_shape(
+                &self.bodies,
+                &self.colliders,
+                &character_position,
+                &-Vector3::y_axis(),
+                collider.shape(),
+                ShapeCastOptions {
+                    max_time_of_impact: probe_distance,
+                    target_distance: 0.0,
+                    stop_at_penetration: true,
+                    compute_impact_geometry_on_penetration: true,
+                },
+                filter,
+            ) {
+                let platform = self
+                    .colliders
+                    .get(platform_collider)
+                    .and_then(|c| c.parent())
+                    .and_then(|parent| self.bodies.get(parent).map(|body| (parent, body)));
+                if let Some((platform_handle, platform)) = platform {
+                    let is_walkable = hit.normal1.dot(&Vector3::y_axis())
+                        >= controller.max_slope_climb_angle().cos();
+                    if is_walkable && !platform.is_fixed() {
+                        controller.platform_velocity = platform.velocity_at_point(&hit.witness1);
+                        platform_translation = native_controller
+                            .move_shape(
+                                dt,
+                                &self.bodies,
+                                &self.colliders,
+                                &query,
+                                collider.shape(),
+                                &character_position,
+                                controller.platform_velocity.scale(dt),
+                                filter.exclude_rigid_body(platform_handle),
+                                |collision| native_collisions.push(collision),
+                            )
+                            .translation;
+                    }
+                }
+            }
+        }
+
+        let movement = native_controller.move_shape(
+            dt,
+            &self.bodies,
+            &self.colliders,
+            &query,
+            collider.shape(),
+            &(Translation3::from(platform_translation) * character_position),
+            desired_translation,
+            filter,
+            |collision| native_collisions.push(collision),
+        );
+
+        if controller.is_push_dynamic_bodies() {
+            native_controller.solve_character_collision_impulses(
+                dt,
+                &mut self.bodies,
+                &self.colliders,
+                &query,
+                collider.shape(),
+                controller.mass(),
+                &native_collisions,
+                filter,
+            );
+        }
+
+        controller
+            .collisions
+            .extend(native_collisions.iter().map(|collision| {
+                scene::character::CharacterCollision {
+                    collider: self
+                        .colliders
+                        .get(collision.handle)
+                        .map(|c| Handle::decode_from_u128(c.user_data))
+                        .unwrap_or_default(),
+                    character_position: collision.character_pos.translation.vector,
+                    translation_applied: collision.translation_applied,
+                    translation_remaining: collision.translation_remaining,
+                    normal: collision.hit.normal1.into_inner(),
+                    toi: collision.hit.time_of_impact,
+                }
+            }));
+        let translation = platform_translation + movement.translation;
+        controller.effective_translation = translation;
+        controller.grounded = movement.grounded;
+        controller.sliding_down_slope = movement.is_sliding_down_slope;
+
+        if translation != Vector3::default() {
+            let new_global_position = controller.global_position() + translation;
+            let new_local_position = parent_transform
+                .try_inverse()
+                .unwrap_or_else(Matrix4::identity)
+                .transform_point(&Point3::from(new_global_position))
+                .coords;
+            controller
+                .local_transform_mut()
+                .set_position(new_local_position);
+        }
+    }
+
+    pub(crate) fn remove_vehicle(&mut self, handle: Handle<NativeVehicle>) {
+        if self.vehicles.is_valid_handle(handle) {
+            self.vehicles.free(handle);
+        }
+    }
+
+    pub(crate) fn sync_to_vehicle_node(
+        &mut self,
+        nodes: &NodePool,
+        vehicle: &scene::vehicle::Vehicle,
+    ) {
+        let chassis = nodes
+            .try_borrow(vehicle.parent())
+            .and_then(|n| n.cast::<scene::rigidbody::RigidBody>())
+            .map(|body| body.native.get())
+            .unwrap_or_else(RigidBodyHandle::invalid);
+
+        if !vehicle.is_globally_enabled() || chassis == RigidBodyHandle::invalid() {
+            self.remove_vehicle(vehicle.native.get());
+            vehicle.native.set(Handle::NONE);
+            return;
+        }
+
+        if !self.vehicles.is_valid_handle(vehicle.native.get()) {
+            vehicle.native.set(self.vehicles.spawn(NativeVehicle {
+                controller: make_vehicle_controller(chassis),
+            }));
+
+            Log::writeln(
+                MessageKind::Information,
+                format!("Native vehicle was created for node {}", vehicle.name()),
+            );
+        }
+
+        let controller = &mut self.vehicles[vehicle.native.get()].controller;
+        controller.chassis = chassis;
+
+        if controller.wheels().len() != vehicle.wheels().len() {
+            *controller = make_vehicle_controller(chassis);
+            for wheel in vehicle.wheels() {
+                controller.add_wheel(
+                    Point3::from(wheel.connection_point),
+                    wheel.direction,
+                    wheel.axle,
+                    wheel.suspension_rest_length,
+                    wheel.radius,
+                    &WheelTuning::default(),
+                );
+            }
+        }
+
+        // Wheels are defined in the local space of the vehicle node, while the physics engine
+        // needs them in the local space of the chassis.
+        let local_transform = vehicle.local_transform();
+        let rotation = **local_transform.rotation();
+        let position = **local_transform.position();
+        for (native, wheel) in controller.wheels_mut().iter_mut().zip(vehicle.wheels()) {
+            native.chassis_connection_point_cs =
+                Point3::from(rotation * wheel.connection_point + position);
+            native.direction_cs = rotation * wheel.direction;
+            native.axle_cs = rotation * wheel.axle;
+            native.suspension_rest_length = wheel.suspension_rest_length;
+            native.max_suspension_travel = wheel.max_suspension_travel;
+            native.radius = wheel.radius;
+            native.suspension_stiffness = wheel.suspension_stiffness;
+            native.damping_compression = wheel.suspension_compression;
+            native.damping_relaxation = wheel.suspension_damping;
+            native.max_suspension_force = wheel.max_suspension_force;
+            native.friction_slip = wheel.friction_slip;
+            native.side_friction_stiffness = wheel.side_friction_stiffness;
+            native.steering = wheel.steering;
+            native.engine_force = wheel.engine_force;
+            native.brake = wheel.brake;
+        }
+    }
+
+    pub(crate) fn fetch_vehicle_state(&self, vehicle: &mut scene::vehicle::Vehicle) {
+        vehicle.wheel_states.clear();
+
+        let Some(native) = self.vehicles.try_borrow(vehicle.native.get()) else {
+            vehicle.current_speed = 0.0;
+            return;
+        };
+
+        vehicle.current_speed = native.controller.current_vehicle_speed;
+        vehicle
+            .wheel_states
+            .extend(native.controller.wheels().iter().map(|wheel| {
+                let info = wheel.raycast_info();
+                scene::vehicle::VehicleWheelState {
+                    in_contact: info.is_in_contact,
+                    suspension_length: info.suspension_length,
+                    rotation: wheel.rotation,
+                    contact_point: info.contact_point_ws.coords,
+                    contact_normal: info.contact_normal_ws,
+                    ground: info
+                        .ground_object
+                        .and_then(|collider| self.colliders.get(collider))
+                        .map(|collider| Handle::decode_from_u128(collider.user_data))
+                        .unwrap_or_default(),
+                    suspension_force: wheel.wheel_suspension_force,
+                }
+            }));
+    }
+
     pub(crate) fn sync_to_collider_node(
         &mut self,
         nodes: &NodePool,
@@ -1638,7 +3189,14 @@ impl PhysicsWorld {
             return;
         }
 
-        let anything_changed = collider_node.needs_sync_model();
+        // The shape must be rebuilt as soon as a convex decomposition is finished.
+        let convex_decomposition_finished = collider_node
+            .pending_convex_decompositions
+            .borrow()
+            .iter()
+            .any(PendingConvexDecomposition::is_finished);
+
+        let anything_changed = collider_node.needs_sync_model() || convex_decomposition_finished;
 
         // Important notes!
         // 1) The collider node may lack backing native physics collider in case if it
@@ -1648,9 +3206,6 @@ impl PhysicsWorld {
         if collider_node.native.get() != ColliderHandle::invalid() {
             if anything_changed {
                 if let Some(native) = self.colliders.get_mut(collider_node.native.get()) {
-                    collider_node
-                        .restitution
-                        .try_sync_model(|v| native.set_restitution(v));
                     collider_node.collision_groups.try_sync_model(|v| {
                         native.set_collision_groups(InteractionGroups::new(
                             u32_to_group(v.memberships.0),
@@ -1663,56 +3218,84 @@ impl PhysicsWorld {
                             u32_to_group(v.filter.0),
                         ))
                     });
-                    collider_node
-                        .friction
-                        .try_sync_model(|v| native.set_friction(v));
                     collider_node
                         .is_sensor
                         .try_sync_model(|v| native.set_sensor(v));
                     collider_node
-                        .friction_combine_rule
-                        .try_sync_model(|v| native.set_friction_combine_rule(v.into()));
-                    collider_node
-                        .restitution_combine_rule
-                        .try_sync_model(|v| native.set_restitution_combine_rule(v.into()));
+                        .report_collision_events
+                        .try_sync_model(|v| native.set_active_events(active_events(v)));
+                    let mut rebuild_shape = convex_decomposition_finished;
+                    collider_node.shape.try_sync_model(|_| rebuild_shape = true);
                     let mut remove_collider = false;
-                    collider_node.shape.try_sync_model(|v| {
+                    if rebuild_shape {
                         let inv_global_transform = isometric_global_transform(nodes, handle)
                             .try_inverse()
                             .unwrap_or_default();
 
-                        if let Some(shape) = collider_shape_into_native_shape(
-                            &v,
+                        if let Some(shape) = make_collider_shape(
+                            collider_node,
+                            collider_node.shape(),
                             inv_global_transform,
                             handle,
                             nodes,
+                            self.task_pool.as_ref(),
                         ) {
                             native.set_shape(shape);
                         } else {
                             remove_collider = true;
                         }
-                    });
+                    }
                     if remove_collider {
                         self.remove_collider(collider_node.native.get());
                         collider_node.native.set(ColliderHandle::invalid());
                     }
                 }
             }
-        } else if let Some(parent_body) = nodes
-            .try_borrow(collider_node.parent())
-            .and_then(|n| n.cast::<scene::rigidbody::RigidBody>())
+
+            // Surface properties are applied together with the physics material.
+            let mut surface_changed = false;
+            collider_node
+                .friction
+                .try_sync_model(|_| surface_changed = true);
+            collider_node
+                .restitution
+                .try_sync_model(|_| surface_changed = true);
+            collider_node
+                .friction_combine_rule
+                .try_sync_model(|_| surface_changed = true);
+            collider_node
+                .restitution_combine_rule
+                .try_sync_model(|_| surface_changed = true);
+            collider_node
+                .density
+                .try_sync_model(|_| surface_changed = true);
+            collider_node
+                .material
+                .try_sync_model(|_| surface_changed = true);
+            self.sync_collider_material(collider_node, surface_changed);
+        } else if let Some(rigid_body_native) =
+            nodes.try_borrow(collider_node.parent()).and_then(|n| {
+                n.cast::<scene::rigidbody::RigidBody>()
+                    .map(|body| body.native.get())
+                    .or_else(|| {
+                        n.cast::<scene::character::CharacterController>()
+                            .map(|controller| controller.native.get())
+                    })
+            })
         {
-            if parent_body.native.get() != RigidBodyHandle::invalid() {
+            if rigid_body_native != RigidBodyHandle::invalid() {
                 let inv_global_transform = isometric_global_transform(nodes, handle)
                     .try_inverse()
                     .unwrap();
-                let rigid_body_native = parent_body.native.get();
-                if let Some(shape) = collider_shape_into_native_shape(
+                if let Some(shape) = make_collider_shape(
+                    collider_node,
                     collider_node.shape(),
                     inv_global_transform,
                     handle,
                     nodes,
+                    self.task_pool.as_ref(),
                 ) {
+                    let material = collider_node.effective_material();
                     let mut builder = ColliderBuilder::new(shape)
                         .position(Isometry3 {
                             rotation: **collider_node.local_transform().rotation(),
@@ -1720,21 +3303,22 @@ impl PhysicsWorld {
                                 vector: **collider_node.local_transform().position(),
                             },
                         })
-                        .friction(collider_node.friction())
-                        .restitution(collider_node.restitution())
+                        .friction(material.friction)
+                        .restitution(material.restitution)
                         .collision_groups(InteractionGroups::new(
                             u32_to_group(collider_node.collision_groups().memberships.0),
                             u32_to_group(collider_node.collision_groups().filter.0),
                         ))
-                        .friction_combine_rule(collider_node.friction_combine_rule().into())
-                        .restitution_combine_rule(collider_node.restitution_combine_rule().into())
+                        .friction_combine_rule(material.friction_combine_rule.into())
+                        .restitution_combine_rule(material.restitution_combine_rule.into())
                         .solver_groups(InteractionGroups::new(
                             u32_to_group(collider_node.solver_groups().memberships.0),
                             u32_to_group(collider_node.solver_groups().filter.0),
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(active_events(collider_node.is_report_collision_events()));
 
-                    if let Some(density) = collider_node.density() {
+                    if let Some(density) = material.density {
                         builder = builder.density(density);
                     }
 
@@ -1742,6 +3326,13 @@ impl PhysicsWorld {
                         self.add_collider(handle, rigid_body_native, builder.build());
 
                     collider_node.native.set(native_handle);
+                    collider_node
+                        .applied_material
+                        .set(collider_node.material_state());
+                    if !material.surface_tag.is_empty() {
+                        self.surface_tags
+                            .insert(native_handle, material.surface_tag);
+                    }
 
                     Log::writeln(
                         MessageKind::Information,
@@ -1755,6 +3346,37 @@ impl PhysicsWorld {
         }
     }
 
+    // Applies the surface properties of the collider (or its physics material) to the native
+    // collider. It is done only if the properties of the collider were changed or if the material
+    // was replaced or modified (including hot reloading), because `get_mut` is expensive.
+    fn sync_collider_material(&mut self, collider_node: &scene::collider::Collider, force: bool) {
+        let material_state = collider_node.material_state();
+        if !force && collider_node.applied_material.get() == material_state {
+            return;
+        }
+
+        let handle = collider_node.native.get();
+        let Some(native) = self.colliders.get_mut(handle) else {
+            return;
+        };
+
+        collider_node.applied_material.set(material_state);
+
+        let material = collider_node.effective_material();
+        native.set_friction(material.friction);
+        native.set_restitution(material.restitution);
+        native.set_friction_combine_rule(material.friction_combine_rule.into());
+        native.set_restitution_combine_rule(material.restitution_combine_rule.into());
+        // Colliders without explicit density use the default density of the physics engine.
+        native.set_density(material.density.unwrap_or(1.0));
+
+        if material.surface_tag.is_empty() {
+            self.surface_tags.remove(&handle);
+        } else {
+            self.surface_tags.insert(handle, material.surface_tag);
+        }
+    }
+
     pub(crate) fn sync_to_joint_node(
         &mut self,
         nodes: &NodePool,
@@ -0,0 +1,354 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Physics recorder is a debugging tool, that captures the state of the physics world (body transforms,
+//! velocities, collider bounds and contacts) for the last few seconds of simulation. See [`PhysicsRecorder`]
+//! docs for more info.
+//!
+//! The same recorder is used by both 3D and 2D physics worlds. States of 2D bodies and colliders are
+//! stored in the XY plane: their rotations are rotations around the Z axis and the Z component of
+//! their positions is always zero.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector3},
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        visitor::prelude::*,
+    },
+    scene::{
+        debug::{Line, SceneDrawingContext},
+        node::Node,
+    },
+};
+use std::{collections::VecDeque, path::Path};
+
+/// State of a rigid body at the moment of capture.
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+pub struct RecordedBody {
+    /// A handle of the rigid body node.
+    pub node: Handle<Node>,
+    /// World-space position of the body.
+    pub position: Vector3<f32>,
+    /// World-space rotation of the body.
+    pub rotation: UnitQuaternion<f32>,
+    /// Linear velocity of the body.
+    pub lin_vel: Vector3<f32>,
+    /// Angular velocity of the body.
+    pub ang_vel: Vector3<f32>,
+    /// `true` if the body was sleeping.
+    pub sleeping: bool,
+}
+
+impl RecordedBody {
+    /// Returns world-space transform of the body.
+    pub fn transform(&self) -> Matrix4<f32> {
+        Matrix4::new_translation(&self.position) * self.rotation.to_homogeneous()
+    }
+}
+
+/// State of a collider at the moment of capture. The shape of the collider is approximated by its
+/// local bounding box.
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+pub struct RecordedCollider {
+    /// A handle of the collider node.
+    pub node: Handle<Node>,
+    /// World-space position of the collider.
+    pub position: Vector3<f32>,
+    /// World-space rotation of the collider.
+    pub rotation: UnitQuaternion<f32>,
+    /// Minimal corner of the local bounding box of the collider shape.
+    pub local_aabb_min: Vector3<f32>,
+    /// Maximal corner of the local bounding box of the collider shape.
+    pub local_aabb_max: Vector3<f32>,
+    /// `true` if the collider is attached to a sleeping rigid body.
+    pub sleeping: bool,
+}
+
+impl RecordedCollider {
+    /// Returns world-space transform of the collider.
+    pub fn transform(&self) -> Matrix4<f32> {
+        Matrix4::new_translation(&self.position) * self.rotation.to_homogeneous()
+    }
+
+    /// Returns local bounding box of the collider shape.
+    pub fn local_aabb(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::from_min_max(self.local_aabb_min, self.local_aabb_max)
+    }
+}
+
+/// A single contact point between two colliders at the moment of capture.
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+pub struct RecordedContact {
+    /// A handle of the first collider node.
+    pub collider1: Handle<Node>,
+    /// A handle of the second collider node.
+    pub collider2: Handle<Node>,
+    /// World-space position of the contact point.
+    pub point: Vector3<f32>,
+    /// World-space contact normal, pointing from the first collider to the second one.
+    pub normal: Vector3<f32>,
+    /// Impulse applied by the solver at the contact point.
+    pub impulse: f32,
+}
+
+/// State of the physics world after a single simulation step.
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+pub struct PhysicsRecordingFrame {
+    /// Time (in seconds) since the beginning of the recording.
+    pub time: f32,
+    /// States of every rigid body.
+    pub bodies: Vec<RecordedBody>,
+    /// States of every collider.
+    pub colliders: Vec<RecordedCollider>,
+    /// Every active contact point.
+    pub contacts: Vec<RecordedContact>,
+}
+
+impl PhysicsRecordingFrame {
+    /// Draws the frame using the given drawing context. Colliders are drawn as oriented boxes (sleeping
+    /// bodies are gray), bodies are drawn as basis vectors with their linear velocity (yellow) and
+    /// contacts are drawn as short normals (red) at the contact points.
+    pub fn draw(&self, ctx: &mut SceneDrawingContext) {
+        for collider in self.colliders.iter() {
+            let color = if collider.sleeping {
+                Color::opaque(120, 120, 120)
+            } else {
+                Color::GREEN
+            };
+            ctx.draw_oob(&collider.local_aabb(), collider.transform(), color);
+        }
+
+        for body in self.bodies.iter() {
+            ctx.draw_transform(body.transform());
+            ctx.add_line(Line {
+                begin: body.position,
+                end: body.position + body.lin_vel,
+                color: Color::opaque(255, 255, 0),
+            });
+        }
+
+        for contact in self.contacts.iter() {
+            ctx.add_line(Line {
+                begin: contact.point,
+                end: contact.point + contact.normal.scale(0.25),
+                color: Color::RED,
+            });
+        }
+    }
+
+    /// Tries to find a state of a rigid body with the given handle.
+    pub fn body(&self, node: Handle<Node>) -> Option<&RecordedBody> {
+        self.bodies.iter().find(|body| body.node == node)
+    }
+}
+
+/// A set of captured frames, that can be saved to a file and loaded back. It is a convenient way of
+/// attaching the physics state to bug reports.
+#[derive(Clone, Debug, Default, PartialEq, Visit)]
+pub struct PhysicsRecording {
+    /// Captured frames sorted by time.
+    pub frames: Vec<PhysicsRecordingFrame>,
+}
+
+impl PhysicsRecording {
+    /// Saves the recording to a file at the given path. The editor expects recordings to have `physrec`
+    /// extension.
+    pub fn save(&mut self, path: &Path) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.visit("PhysicsRecording", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+
+    /// Loads a recording from a file at the given path.
+    pub async fn load(path: &Path) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        let mut recording = Self::default();
+        recording.visit("PhysicsRecording", &mut visitor)?;
+        Ok(recording)
+    }
+
+    /// Returns the duration of the recording in seconds.
+    pub fn duration(&self) -> f32 {
+        self.frames.last().map_or(0.0, |frame| frame.time)
+            - self.frames.first().map_or(0.0, |frame| frame.time)
+    }
+
+    /// Returns a frame, that is the closest one to the given time.
+    pub fn frame_at(&self, time: f32) -> Option<&PhysicsRecordingFrame> {
+        closest_frame(
+            self.frames
+                .binary_search_by(|frame| frame.time.total_cmp(&time)),
+            |index| self.frames.get(index),
+            time,
+        )
+    }
+}
+
+// Frames are sorted by time, so the closest frame is either the one found by the binary search or
+// one of its two neighbours at the insertion position.
+fn closest_frame<'a>(
+    search_result: Result<usize, usize>,
+    frame: impl Fn(usize) -> Option<&'a PhysicsRecordingFrame>,
+    time: f32,
+) -> Option<&'a PhysicsRecordingFrame> {
+    match search_result {
+        Ok(index) => frame(index),
+        Err(index) => {
+            let previous = index.checked_sub(1).and_then(&frame);
+            match (previous, frame(index)) {
+                (Some(previous), Some(next)) => {
+                    if time - previous.time <= next.time - time {
+                        Some(previous)
+                    } else {
+                        Some(next)
+                    }
+                }
+                (previous, next) => previous.or(next),
+            }
+        }
+    }
+}
+
+/// Physics recorder captures the state of the physics world after every simulation step and keeps it
+/// for the last [`PhysicsRecorder::duration`] seconds in a ring buffer. It is disabled by default,
+/// because capturing is not free - it copies the state of every body, collider and contact on each
+/// step.
+///
+/// Captured frames could be drawn using [`PhysicsRecordingFrame::draw`], which allows you to scrub
+/// through the last few seconds of simulation and see what has happened when a body tunnels through
+/// a wall or a joint explodes.
+///
+/// ```rust
+/// # use fyrox_impl::scene::{Scene, debug::SceneDrawingContext};
+/// fn debug_physics(scene: &mut Scene, time: f32) {
+///     let recorder = &mut scene.graph.physics.recorder;
+///     recorder.set_enabled(true);
+///     recorder.set_duration(10.0);
+///
+///     if let Some(frame) = recorder.frame_at(time) {
+///         frame.draw(&mut scene.drawing_context);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PhysicsRecorder {
+    enabled: bool,
+    duration: f32,
+    time: f32,
+    frames: VecDeque<PhysicsRecordingFrame>,
+}
+
+impl Default for PhysicsRecorder {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            duration: 5.0,
+            time: 0.0,
+            frames: Default::default(),
+        }
+    }
+}
+
+impl PhysicsRecorder {
+    /// Enables or disables the recorder. Already captured frames are kept.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns `true` if the recorder is enabled, `false` - otherwise.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Sets the amount of time (in seconds) the recorder should keep the frames for. Frames that are
+    /// older than that will be discarded.
+    pub fn set_duration(&mut self, duration: f32) {
+        self.duration = duration.max(0.0);
+        self.discard_old_frames();
+    }
+
+    /// Returns the amount of time (in seconds) the recorder keeps the frames for.
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    /// Adds a new frame to the recorder. `dt` is the duration of the simulation step, that produced
+    /// the frame. Time of the frame will be set by the recorder. Frames of zero-length steps (for
+    /// example, when the simulation is frozen) are ignored.
+    pub fn push(&mut self, dt: f32, mut frame: PhysicsRecordingFrame) {
+        if dt <= 0.0 {
+            return;
+        }
+
+        self.time += dt;
+        frame.time = self.time;
+        self.frames.push_back(frame);
+        self.discard_old_frames();
+    }
+
+    fn discard_old_frames(&mut self) {
+        while self
+            .frames
+            .front()
+            .is_some_and(|frame| self.time - frame.time > self.duration)
+        {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Returns an iterator over the captured frames, from the oldest to the newest.
+    pub fn frames(&self) -> impl Iterator<Item = &PhysicsRecordingFrame> {
+        self.frames.iter()
+    }
+
+    /// Returns the total amount of captured frames.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns a frame at the given index, where `0` is the oldest frame.
+    pub fn frame(&self, index: usize) -> Option<&PhysicsRecordingFrame> {
+        self.frames.get(index)
+    }
+
+    /// Returns a frame, that is the closest one to the given time.
+    pub fn frame_at(&self, time: f32) -> Option<&PhysicsRecordingFrame> {
+        closest_frame(
+            self.frames
+                .binary_search_by(|frame| frame.time.total_cmp(&time)),
+            |index| self.frames.get(index),
+            time,
+        )
+    }
+
+    /// Removes every captured frame.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Copies the captured frames into a recording, which can be saved to a file.
+    pub fn recording(&self) -> PhysicsRecording {
+        PhysicsRecording {
+            frames: self.frames.iter().cloned().collect(),
+        }
+    }
+}
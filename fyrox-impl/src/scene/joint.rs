@@ -54,6 +54,83 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, VariantNames};
 
+/// Joint motor drives relative motion of two rigid bodies along (or around) an axis of a joint. The
+/// motor acts like a spring-damper that tries to reach the target position and the target velocity
+/// at the same time: `acceleration = stiffness * (target_position - position) + damping *
+/// (target_velocity - velocity)`. Set `stiffness` to zero to get a pure velocity motor, or set
+/// `damping` to zero to get a pure position motor.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct JointMotor {
+    /// Whether the motor is enabled or not. Default is `false`.
+    #[reflect(description = "Whether the motor is enabled or not.")]
+    pub enabled: bool,
+
+    /// Desired relative velocity along the axis (in m/s or rad/s).
+    #[reflect(description = "Desired relative velocity along the axis (in m/s or rad/s).")]
+    pub target_velocity: f32,
+
+    /// Desired relative position along the axis (in meters or radians).
+    #[reflect(description = "Desired relative position along the axis (in meters or radians).")]
+    pub target_position: f32,
+
+    /// Defines how strongly the motor pulls the bodies towards the target position.
+    #[reflect(
+        description = "Defines how strongly the motor pulls the bodies towards the target position."
+    )]
+    #[reflect(min_value = 0.0)]
+    pub stiffness: f32,
+
+    /// Defines how strongly the motor pulls the bodies towards the target velocity.
+    #[reflect(
+        description = "Defines how strongly the motor pulls the bodies towards the target velocity."
+    )]
+    #[reflect(min_value = 0.0)]
+    pub damping: f32,
+
+    /// Maximum force (or torque) the motor can apply.
+    #[reflect(description = "Maximum force (or torque) the motor can apply.")]
+    #[reflect(min_value = 0.0)]
+    pub max_force: f32,
+}
+
+uuid_provider!(JointMotor = "dfd58459-d51e-4b2b-854b-5e22cc8cc88a");
+
+impl Default for JointMotor {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_velocity: 0.0,
+            target_position: 0.0,
+            stiffness: 0.0,
+            damping: 1.0,
+            max_force: f32::MAX,
+        }
+    }
+}
+
+impl JointMotor {
+    /// Creates new motor that tries to keep the given relative velocity.
+    pub fn velocity(target_velocity: f32, damping: f32) -> Self {
+        Self {
+            enabled: true,
+            target_velocity,
+            damping,
+            ..Default::default()
+        }
+    }
+
+    /// Creates new motor that tries to reach the given relative position.
+    pub fn position(target_position: f32, stiffness: f32, damping: f32) -> Self {
+        Self {
+            enabled: true,
+            target_position,
+            stiffness,
+            damping,
+            ..Default::default()
+        }
+    }
+}
+
 /// Ball joint locks any translational moves between two objects on the axis between objects, but
 /// allows rigid bodies to perform relative rotations. The real world example is a human shoulder,
 /// pendulum, etc.
@@ -88,6 +165,21 @@ pub struct BallJoint {
     #[reflect(description = "Allowed angle range around local Z axis of the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub z_limits_angles: Range<f32>,
+
+    /// Motor that drives rotation around local X axis of the joint.
+    #[reflect(description = "Motor that drives rotation around local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub x_motor: JointMotor,
+
+    /// Motor that drives rotation around local Y axis of the joint.
+    #[reflect(description = "Motor that drives rotation around local Y axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub y_motor: JointMotor,
+
+    /// Motor that drives rotation around local Z axis of the joint.
+    #[reflect(description = "Motor that drives rotation around local Z axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub z_motor: JointMotor,
 }
 
 impl Default for BallJoint {
@@ -99,6 +191,9 @@ impl Default for BallJoint {
             y_limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
             z_limits_enabled: false,
             z_limits_angles: -std::f32::consts::PI..std::f32::consts::PI,
+            x_motor: Default::default(),
+            y_motor: Default::default(),
+            z_motor: Default::default(),
         }
     }
 }
@@ -123,6 +218,11 @@ pub struct PrismaticJoint {
     )]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Motor that drives translation along local X axis of the joint.
+    #[reflect(description = "Motor that drives translation along local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for PrismaticJoint {
@@ -130,6 +230,7 @@ impl Default for PrismaticJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
         }
     }
 }
@@ -150,6 +251,11 @@ pub struct RevoluteJoint {
     #[reflect(description = "Allowed angle range around local X axis of the joint (in radians).")]
     #[visit(optional)] // Backward compatibility
     pub limits: Range<f32>,
+
+    /// Motor that drives rotation around local X axis of the joint.
+    #[reflect(description = "Motor that drives rotation around local X axis of the joint.")]
+    #[visit(optional)] // Backward compatibility
+    pub motor: JointMotor,
 }
 
 impl Default for RevoluteJoint {
@@ -157,6 +263,148 @@ impl Default for RevoluteJoint {
         Self {
             limits_enabled: false,
             limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
+        }
+    }
+}
+
+/// Settings of a single degree of freedom of a [`GenericJoint`].
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct GenericJointAxis {
+    /// Whether the relative motion along (or around) the axis is prohibited or not. Locked axis
+    /// ignores its limits and motor. Default is `false`.
+    #[reflect(
+        description = "Whether the relative motion along (or around) the axis is prohibited."
+    )]
+    pub locked: bool,
+
+    /// Whether the limits of the axis are enabled or not. Default is `false`.
+    #[reflect(description = "Whether the limits of the axis are enabled or not.")]
+    pub limits_enabled: bool,
+
+    /// Allowed range of relative motion along (or around) the axis (in meters or radians).
+    #[reflect(
+        description = "Allowed range of relative motion along (or around) the axis (in meters or radians)."
+    )]
+    pub limits: Range<f32>,
+
+    /// Motor that drives relative motion along (or around) the axis.
+    #[reflect(description = "Motor that drives relative motion along (or around) the axis.")]
+    pub motor: JointMotor,
+}
+
+uuid_provider!(GenericJointAxis = "cdefb557-4b31-44de-9f86-d955acef9593");
+
+impl Default for GenericJointAxis {
+    fn default() -> Self {
+        Self {
+            locked: false,
+            limits_enabled: false,
+            limits: -std::f32::consts::PI..std::f32::consts::PI,
+            motor: Default::default(),
+        }
+    }
+}
+
+impl GenericJointAxis {
+    /// Creates new locked axis.
+    pub fn locked() -> Self {
+        Self {
+            locked: true,
+            ..Default::default()
+        }
+    }
+
+    /// Creates new free axis with the given limits.
+    pub fn limited(limits: Range<f32>) -> Self {
+        Self {
+            limits_enabled: true,
+            limits,
+            ..Default::default()
+        }
+    }
+}
+
+/// Generic (6-DOF) joint allows to configure every degree of freedom separately - each linear and
+/// angular axis can be locked, limited, or driven by a motor. It could be used to build any other
+/// joint, as well as something more exotic, like a ragdoll joint with asymmetric limits. All axes
+/// are free by default.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect, Default)]
+pub struct GenericJoint {
+    /// Settings of the translation along local X axis of the joint.
+    #[reflect(description = "Settings of the translation along local X axis of the joint.")]
+    pub x: GenericJointAxis,
+
+    /// Settings of the translation along local Y axis of the joint.
+    #[reflect(description = "Settings of the translation along local Y axis of the joint.")]
+    pub y: GenericJointAxis,
+
+    /// Settings of the translation along local Z axis of the joint.
+    #[reflect(description = "Settings of the translation along local Z axis of the joint.")]
+    pub z: GenericJointAxis,
+
+    /// Settings of the rotation around local X axis of the joint.
+    #[reflect(description = "Settings of the rotation around local X axis of the joint.")]
+    pub angular_x: GenericJointAxis,
+
+    /// Settings of the rotation around local Y axis of the joint.
+    #[reflect(description = "Settings of the rotation around local Y axis of the joint.")]
+    pub angular_y: GenericJointAxis,
+
+    /// Settings of the rotation around local Z axis of the joint.
+    #[reflect(description = "Settings of the rotation around local Z axis of the joint.")]
+    pub angular_z: GenericJointAxis,
+}
+
+/// Rope joint limits the maximum distance between the anchors of two rigid bodies, but lets them
+/// move freely (and rotate) as long as the distance is less than the maximum. The real world
+/// example is a rope or a chain.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct RopeJoint {
+    /// Maximum distance between the anchors of the bodies (in meters).
+    #[reflect(description = "Maximum distance between the anchors of the bodies (in meters).")]
+    #[reflect(min_value = 0.0)]
+    pub max_distance: f32,
+}
+
+impl Default for RopeJoint {
+    fn default() -> Self {
+        Self { max_distance: 1.0 }
+    }
+}
+
+/// Spring joint pulls the anchors of two rigid bodies towards each other (or pushes them apart),
+/// trying to keep the distance between them equal to the rest length. Rotations are unrestricted.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct SpringJoint {
+    /// Distance between the anchors of the bodies at which the spring applies no force (in meters).
+    #[reflect(
+        description = "Distance between the anchors of the bodies at which the spring applies no force (in meters)."
+    )]
+    #[reflect(min_value = 0.0)]
+    pub rest_length: f32,
+
+    /// Stiffness of the spring. Larger values make the spring harder to stretch or compress.
+    #[reflect(
+        description = "Stiffness of the spring. Larger values make the spring harder to stretch or compress."
+    )]
+    #[reflect(min_value = 0.0)]
+    pub stiffness: f32,
+
+    /// Damping of the spring. Larger values make oscillations fade out faster.
+    #[reflect(
+        description = "Damping of the spring. Larger values make oscillations fade out faster."
+    )]
+    #[reflect(min_value = 0.0)]
+    pub damping: f32,
+}
+
+impl Default for SpringJoint {
+    fn default() -> Self {
+        Self {
+            rest_length: 1.0,
+            stiffness: 10.0,
+            damping: 1.0,
         }
     }
 }
@@ -172,6 +420,12 @@ pub enum JointParams {
     PrismaticJoint(PrismaticJoint),
     /// See [`RevoluteJoint`] for more info.
     RevoluteJoint(RevoluteJoint),
+    /// See [`GenericJoint`] for more info.
+    GenericJoint(GenericJoint),
+    /// See [`RopeJoint`] for more info.
+    RopeJoint(RopeJoint),
+    /// See [`SpringJoint`] for more info.
+    SpringJoint(SpringJoint),
 }
 
 uuid_provider!(JointParams = "a3e09303-9de4-4123-9492-05e27f29aaa3");
@@ -383,6 +637,24 @@ impl ConstructorProvider<Node, Graph> for Joint {
                     .build_node()
                     .into()
             })
+            .with_variant("Generic Joint", |_| {
+                JointBuilder::new(BaseBuilder::new().with_name("Generic Joint"))
+                    .with_params(JointParams::GenericJoint(Default::default()))
+                    .build_node()
+                    .into()
+            })
+            .with_variant("Rope Joint", |_| {
+                JointBuilder::new(BaseBuilder::new().with_name("Rope Joint"))
+                    .with_params(JointParams::RopeJoint(Default::default()))
+                    .build_node()
+                    .into()
+            })
+            .with_variant("Spring Joint", |_| {
+                JointBuilder::new(BaseBuilder::new().with_name("Spring Joint"))
+                    .with_params(JointParams::SpringJoint(Default::default()))
+                    .build_node()
+                    .into()
+            })
             .with_group("Physics")
     }
 }
@@ -535,3 +807,98 @@ impl JointBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::core::algebra::{Vector2, Vector3};
+    use crate::scene::{
+        base::BaseBuilder,
+        collider::{ColliderBuilder, ColliderShape},
+        graph::Graph,
+        joint::{JointBuilder, JointMotor, JointParams, RevoluteJoint, RopeJoint},
+        node::Node,
+        rigidbody::{RigidBody, RigidBodyBuilder, RigidBodyType},
+        transform::TransformBuilder,
+    };
+    use fyrox_core::pool::Handle;
+
+    fn make_body(
+        graph: &mut Graph,
+        position: Vector3<f32>,
+        body_type: RigidBodyType,
+    ) -> Handle<Node> {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.25))
+            .build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(body_type)
+        .with_gravity_scale(0.0)
+        .build(graph)
+    }
+
+    fn update(graph: &mut Graph, frames: usize) {
+        for _ in 0..frames {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+    }
+
+    #[test]
+    fn test_revolute_joint_motor() {
+        let mut graph = Graph::new();
+
+        let body1 = make_body(&mut graph, Vector3::default(), RigidBodyType::Static);
+        let body2 = make_body(&mut graph, Vector3::default(), RigidBodyType::Dynamic);
+        JointBuilder::new(BaseBuilder::new())
+            .with_params(JointParams::RevoluteJoint(RevoluteJoint {
+                motor: JointMotor::velocity(2.0, 10.0),
+                ..Default::default()
+            }))
+            .with_body1(body1)
+            .with_body2(body2)
+            .with_contacts_enabled(false)
+            .build(&mut graph);
+
+        update(&mut graph, 60);
+
+        let ang_vel = graph[body2].cast::<RigidBody>().unwrap().ang_vel();
+        assert!((ang_vel.x - 2.0).abs() < 0.05, "{ang_vel:?}");
+        assert!(ang_vel.y.abs() < 1.0e-3 && ang_vel.z.abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn test_rope_joint_limits_distance() {
+        let mut graph = Graph::new();
+
+        let body1 = make_body(&mut graph, Vector3::default(), RigidBodyType::Static);
+        let body2 = make_body(
+            &mut graph,
+            Vector3::new(0.0, -0.5, 0.0),
+            RigidBodyType::Dynamic,
+        );
+        graph[body2]
+            .cast_mut::<RigidBody>()
+            .unwrap()
+            .set_gravity_scale(1.0);
+        JointBuilder::new(BaseBuilder::new())
+            .with_params(JointParams::RopeJoint(RopeJoint { max_distance: 2.0 }))
+            .with_body1(body1)
+            .with_body2(body2)
+            .with_contacts_enabled(false)
+            .build(&mut graph);
+
+        update(&mut graph, 120);
+
+        // The body falls freely until the rope is stretched and then hangs on it. The anchor of
+        // the second body is half a meter above its center (at the joint position).
+        let y = graph[body2].local_transform().position().y;
+        assert!((-2.6..-2.4).contains(&y), "{y}");
+    }
+}
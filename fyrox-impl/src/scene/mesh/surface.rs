@@ -249,6 +249,11 @@ impl ResourceData for SurfaceData {
     fn can_be_saved(&self) -> bool {
         true
     }
+
+    fn memory_usage(&self) -> usize {
+        self.vertex_buffer.raw_data().len()
+            + self.geometry_buffer.len() * size_of::<TriangleDefinition>()
+    }
 }
 
 impl SurfaceData {
@@ -28,9 +28,11 @@ pub mod accel;
 pub mod animation;
 pub mod base;
 pub mod camera;
+pub mod character;
 pub mod collider;
 pub mod debug;
 pub mod decal;
+pub mod diff;
 pub mod dim2;
 pub mod graph;
 pub mod joint;
@@ -39,14 +41,17 @@ pub mod mesh;
 pub mod navmesh;
 pub mod node;
 pub mod particle_system;
+pub mod physics_material;
 pub mod pivot;
 pub mod ragdoll;
 pub mod rigidbody;
 pub mod sound;
 pub mod sprite;
+pub mod streaming;
 pub mod terrain;
 pub mod tilemap;
 pub mod transform;
+pub mod vehicle;
 
 use crate::renderer::framework::PolygonFillMode;
 use crate::{
@@ -58,6 +63,7 @@ use crate::{
         log::{Log, MessageKind},
         pool::{Handle, Pool, Ticket},
         reflect::prelude::*,
+        task::TaskPool,
         visitor::{Visit, VisitError, VisitResult, Visitor},
     },
     engine::SerializationContext,
@@ -71,6 +77,7 @@ use crate::{
         navmesh::NavigationalMeshBuilder,
         node::Node,
         sound::SoundEngine,
+        streaming::WorldPartition,
     },
     utils::navmesh::Navmesh,
 };
@@ -233,6 +240,10 @@ pub struct Scene {
     /// to false for menu's scene and when you need to open a menu - set it to true and
     /// set `enabled` flag to false for level's scene.
     pub enabled: InheritableVariable<bool>,
+
+    /// World partition of the scene, that allows streaming parts of the scene in and out at
+    /// runtime. See [`WorldPartition`] docs for more info.
+    pub world_partition: WorldPartition,
 }
 
 impl Default for Scene {
@@ -243,6 +254,7 @@ impl Default for Scene {
             drawing_context: Default::default(),
             performance_statistics: Default::default(),
             enabled: true.into(),
+            world_partition: Default::default(),
         }
     }
 }
@@ -402,6 +414,7 @@ impl Scene {
             drawing_context: Default::default(),
             performance_statistics: Default::default(),
             enabled: true.into(),
+            world_partition: Default::default(),
         }
     }
 
@@ -430,6 +443,15 @@ impl Scene {
         self.performance_statistics.graph = self.graph.performance_statistics.clone();
     }
 
+    /// Performs single streaming step of the world partition of the scene. See [`WorldPartition`]
+    /// docs for more info. In most cases there is no need to call it directly, engine automatically
+    /// does this for all available scenes.
+    pub fn update_world_partition(&mut self, resource_manager: &ResourceManager) {
+        let mut world_partition = std::mem::take(&mut self.world_partition);
+        world_partition.update(self, resource_manager);
+        self.world_partition = world_partition;
+    }
+
     /// Creates deep copy of a scene, filter predicate allows you to filter out nodes
     /// by your criteria.
     pub fn clone<F, Pre, Post>(
@@ -448,6 +470,9 @@ impl Scene {
             self.graph
                 .clone(root, filter, pre_process_callback, post_process_callback);
 
+        let mut world_partition = self.world_partition.clone();
+        world_partition.remap_handles(&old_new_map);
+
         (
             Self {
                 graph,
@@ -455,6 +480,7 @@ impl Scene {
                 drawing_context: self.drawing_context.clone(),
                 performance_statistics: Default::default(),
                 enabled: self.enabled.clone(),
+                world_partition,
             },
             old_new_map,
         )
@@ -479,6 +505,7 @@ impl Scene {
         let _ = self
             .rendering_options
             .visit("RenderingOptions", &mut region);
+        let _ = self.world_partition.visit("WorldPartition", &mut region);
 
         // Backward compatibility.
         let mut navmeshes = NavMeshContainer::default();
@@ -546,14 +573,16 @@ impl Scene {
 pub struct SceneContainer {
     pool: Pool<Scene>,
     sound_engine: SoundEngine,
+    task_pool: Arc<TaskPool>,
     pub(crate) destruction_list: Vec<(Handle<Scene>, Scene)>,
 }
 
 impl SceneContainer {
-    pub(crate) fn new(sound_engine: SoundEngine) -> Self {
+    pub(crate) fn new(sound_engine: SoundEngine, task_pool: Arc<TaskPool>) -> Self {
         Self {
             pool: Pool::new(),
             sound_engine,
+            task_pool,
             destruction_list: Default::default(),
         }
     }
@@ -597,10 +626,14 @@ impl SceneContainer {
 
     /// Adds new scene into container.
     #[inline]
-    pub fn add(&mut self, scene: Scene) -> Handle<Scene> {
+    pub fn add(&mut self, mut scene: Scene) -> Handle<Scene> {
         self.sound_engine
             .state()
             .add_context(scene.graph.sound_context.native.clone());
+        scene
+            .graph
+            .physics
+            .set_task_pool(Some(self.task_pool.clone()));
         self.pool.spawn(scene)
     }
 
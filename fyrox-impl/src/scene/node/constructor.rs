@@ -51,6 +51,7 @@ pub type NodeConstructorContainer = GraphNodeConstructorContainer<Node, Graph>;
 pub fn new_node_constructor_container() -> NodeConstructorContainer {
     let container = NodeConstructorContainer::default();
 
+    container.add::<dim2::character::CharacterController>();
     container.add::<dim2::collider::Collider>();
     container.add::<dim2::joint::Joint>();
     container.add::<Rectangle>();
@@ -63,9 +64,11 @@ pub fn new_node_constructor_container() -> NodeConstructorContainer {
     container.add::<Sound>();
     container.add::<Listener>();
     container.add::<Camera>();
+    container.add::<scene::character::CharacterController>();
     container.add::<scene::collider::Collider>();
     container.add::<Decal>();
     container.add::<scene::joint::Joint>();
+    container.add::<scene::vehicle::Vehicle>();
     container.add::<Pivot>();
     container.add::<scene::rigidbody::RigidBody>();
     container.add::<Sprite>();
@@ -119,7 +119,7 @@ pub struct SyncContext<'a, 'b> {
 }
 
 /// A data for update tick. See [`NodeTrait::update`] for more info.
-pub struct UpdateContext<'a> {
+pub struct UpdateContext<'a, 'b> {
     /// Size of client area of the window.
     pub frame_size: Vector2<f32>,
     /// A time that have passed since last update call.
@@ -132,6 +132,8 @@ pub struct UpdateContext<'a> {
     pub physics2d: &'a mut dim2::physics::PhysicsWorld,
     /// A mutable reference to sound context.
     pub sound_context: &'a mut SoundContext,
+    /// A reference to graph update switches. See [`GraphUpdateSwitches`] for more info.
+    pub switches: Option<&'b GraphUpdateSwitches>,
 }
 
 /// An enumeration, that contains all possible render data collection strategies.
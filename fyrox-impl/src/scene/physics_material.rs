@@ -0,0 +1,213 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Physics material is a shared resource that defines surface properties (friction, restitution,
+//! density) of colliders. See [`PhysicsMaterial`] docs for more info.
+
+use crate::{
+    asset::{
+        io::ResourceIo,
+        loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+        state::LoadError,
+        Resource, ResourceData,
+    },
+    core::{
+        io::FileLoadError, reflect::prelude::*, sstorage::ImmutableString, type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::graph::physics::CoefficientCombineRule,
+};
+use fxhash::FxHasher;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// An error that may occur during physics material resource loading.
+#[derive(Debug)]
+pub enum PhysicsMaterialError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for PhysicsMaterialError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            Self::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for PhysicsMaterialError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for PhysicsMaterialError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// Physics material defines surface properties of colliders - friction, restitution, their combine
+/// rules and density. A material is a resource, so it can be tuned once (for example "ice", "metal"
+/// or "mud") and then shared across any number of colliders (both 3D and 2D). When a collider has a
+/// material assigned, the material overrides the respective properties of the collider. Any changes
+/// to the material (including hot reloading of the resource) are applied to every collider that
+/// uses it.
+///
+/// The material also has a surface tag - an arbitrary user-defined string, that could be used by
+/// game logic to tell surfaces apart. For example, the tag is reported in ray-cast results (see
+/// [`crate::scene::graph::physics::Intersection::surface_tag`]), which is useful to pick a proper
+/// sound for footsteps.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     asset::untyped::ResourceKind,
+/// #     scene::physics_material::{PhysicsMaterial, PhysicsMaterialResource},
+/// # };
+/// fn ice() -> PhysicsMaterialResource {
+///     PhysicsMaterialResource::new_ok(
+///         ResourceKind::Embedded,
+///         PhysicsMaterial {
+///             friction: 0.02,
+///             surface_tag: "Ice".into(),
+///             ..Default::default()
+///         },
+///     )
+/// }
+/// ```
+#[derive(Visit, Reflect, Debug, Clone, PartialEq, TypeUuidProvider)]
+#[type_uuid(id = "5f09fad9-9720-4452-8b48-57df36bb6443")]
+pub struct PhysicsMaterial {
+    /// Friction coefficient of the surface.
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub friction: f32,
+
+    /// A rule that is used to combine friction coefficients of two contacting surfaces.
+    pub friction_combine_rule: CoefficientCombineRule,
+
+    /// Restitution coefficient of the surface (bounciness).
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub restitution: f32,
+
+    /// A rule that is used to combine restitution coefficients of two contacting surfaces.
+    pub restitution_combine_rule: CoefficientCombineRule,
+
+    /// Density of the material. `None` means that the density of a collider will be used.
+    pub density: Option<f32>,
+
+    /// An arbitrary user-defined tag of the surface.
+    pub surface_tag: ImmutableString,
+}
+
+impl Default for PhysicsMaterial {
+    fn default() -> Self {
+        Self {
+            friction: 0.5,
+            friction_combine_rule: Default::default(),
+            restitution: 0.0,
+            restitution_combine_rule: Default::default(),
+            density: None,
+            surface_tag: Default::default(),
+        }
+    }
+}
+
+impl PhysicsMaterial {
+    /// Load a physics material from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, PhysicsMaterialError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut material = PhysicsMaterial::default();
+        material.visit("PhysicsMaterial", &mut visitor)?;
+        Ok(material)
+    }
+
+    // Hash of the properties of the material. It is used to detect changes of the material without
+    // storing a copy of it.
+    pub(crate) fn content_hash(&self) -> u64 {
+        let mut hasher = FxHasher::default();
+        self.friction.to_bits().hash(&mut hasher);
+        (self.friction_combine_rule as u32).hash(&mut hasher);
+        self.restitution.to_bits().hash(&mut hasher);
+        (self.restitution_combine_rule as u32).hash(&mut hasher);
+        self.density.map(f32::to_bits).hash(&mut hasher);
+        self.surface_tag.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl ResourceData for PhysicsMaterial {
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut visitor = Visitor::new();
+        self.visit("PhysicsMaterial", &mut visitor)?;
+        visitor.save_binary(path)?;
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        true
+    }
+}
+
+/// A loader for physics material resource.
+pub struct PhysicsMaterialLoader;
+
+impl ResourceLoader for PhysicsMaterialLoader {
+    fn extensions(&self) -> &[&str] {
+        &["physmat"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <PhysicsMaterial as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let material = PhysicsMaterial::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(material))
+        })
+    }
+}
+
+/// Physics material resource.
+pub type PhysicsMaterialResource = Resource<PhysicsMaterial>;
@@ -588,6 +588,7 @@ impl NodeTrait for RigidBody {
                 .try_borrow(self.parent)
                 .map(|p| p.global_transform())
                 .unwrap_or_else(Matrix4::identity),
+            true,
         );
     }
 
@@ -0,0 +1,494 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! World partition allows splitting large scenes into spatial cells and streaming them in and out
+//! at runtime around a set of streaming sources. See [`WorldPartition`] docs for more info.
+
+use crate::{
+    asset::{manager::ResourceManager, state::ResourceState},
+    core::{
+        algebra::{Vector2, Vector3},
+        log::Log,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        visitor::prelude::*,
+    },
+    graph::{BaseSceneGraph, NodeHandleMap, SceneGraph},
+    resource::model::{Model, ModelResource, ModelResourceExtension},
+    scene::{base::SceneNodeId, camera::Camera, graph::Graph, node::Node, Scene},
+};
+use fxhash::FxHashMap;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// A spatial cell of a [`WorldPartition`]. Every cell is a separate scene (stored as a model
+/// resource) that is instantiated into the parent scene when any streaming source comes close
+/// enough to the bounds of the cell.
+#[derive(Clone, Debug, Default, Visit, Reflect, TypeUuidProvider)]
+#[type_uuid(id = "b0ef02f1-c7f5-43ff-8203-a17902f030a9")]
+pub struct WorldCell {
+    /// Name of the cell.
+    pub name: String,
+    /// Minimal point of the world-space bounds of the cell content.
+    pub bounds_min: Vector3<f32>,
+    /// Maximal point of the world-space bounds of the cell content.
+    pub bounds_max: Vector3<f32>,
+    /// Path to the scene of the cell.
+    pub path: PathBuf,
+    // A handle of the cell instance in the parent scene. It is serialized, so saved games will
+    // keep track of the loaded cells.
+    #[reflect(hidden)]
+    root: Handle<Node>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    loading: Option<ModelResource>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    failed: bool,
+}
+
+impl WorldCell {
+    /// Creates a new cell with the given name, bounds and path to the scene of the cell.
+    pub fn new(name: String, bounds: AxisAlignedBoundingBox, path: PathBuf) -> Self {
+        Self {
+            name,
+            bounds_min: bounds.min,
+            bounds_max: bounds.max,
+            path,
+            ..Default::default()
+        }
+    }
+
+    /// Returns world-space bounds of the cell content.
+    pub fn bounds(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::from_min_max(self.bounds_min, self.bounds_max)
+    }
+
+    /// Returns a handle of the cell instance in the parent scene, or [`Handle::NONE`] if the cell
+    /// is not loaded.
+    pub fn root(&self) -> Handle<Node> {
+        self.root
+    }
+
+    /// Returns `true` if the cell is instantiated in the parent scene.
+    pub fn is_loaded(&self) -> bool {
+        self.root.is_some()
+    }
+
+    /// Returns `true` if the scene of the cell is being loaded.
+    pub fn is_loading(&self) -> bool {
+        self.loading.is_some()
+    }
+
+    /// Returns the distance from the given point to the bounds of the cell. The distance is zero
+    /// if the point is inside the bounds.
+    pub fn distance_to(&self, point: Vector3<f32>) -> f32 {
+        let closest = Vector3::new(
+            point.x.clamp(self.bounds_min.x, self.bounds_max.x),
+            point.y.clamp(self.bounds_min.y, self.bounds_max.y),
+            point.z.clamp(self.bounds_min.z, self.bounds_max.z),
+        );
+        (point - closest).norm()
+    }
+
+    fn unload(&mut self, graph: &mut Graph) {
+        if graph.is_valid_handle(self.root) {
+            graph.remove_node(self.root);
+        }
+        self.root = Handle::NONE;
+        // Dropping the resource cancels its loading if it was not used by anything else.
+        self.loading = None;
+    }
+
+    fn instantiate(&mut self, model: ModelResource, scene: &mut Scene) {
+        // Reuse the ids of the nodes stored in the cell scene, so every instance of the cell will
+        // have the same ids and nodes from other cells could reference them by their ids.
+        let ids = model
+            .data_ref()
+            .get_scene()
+            .graph
+            .pair_iter()
+            .map(|(handle, node)| (handle, node.instance_id()))
+            .collect::<FxHashMap<Handle<Node>, SceneNodeId>>();
+
+        self.root = model.begin_instantiation(scene).with_ids(&ids).finish();
+        scene.graph[self.root].set_name(self.name.clone());
+    }
+}
+
+/// World partition splits a large scene into a set of spatial cells (see [`WorldCell`]) and
+/// streams them in and out at runtime. The cells are loaded when any streaming source comes
+/// closer than [`Self::load_distance`] to the bounds of a cell and unloaded when every source is
+/// farther than [`Self::unload_distance`]. The gap between these distances prevents cells from
+/// being reloaded over and over again when a source moves back and forth near the boundary.
+///
+/// ## Streaming sources
+///
+/// A streaming source could be either an enabled camera (if [`Self::use_cameras`] is set), a node
+/// with the [`Self::source_tag`] tag or a node explicitly listed in [`Self::sources`].
+///
+/// ## Building
+///
+/// Use [`Self::build`] to split an existing scene into cells. It moves every top-level node that
+/// passes a filter into a cell scene according to its position and saves the cell scenes to the
+/// given directory. Everything else stays in the persistent part of the scene, which could be
+/// loaded as usual (for example, with `AsyncSceneLoader`).
+///
+/// ## Cross-cell references
+///
+/// Handles are not stable across cell loading, because every cell is instantiated anew. Nodes
+/// from a cell keep the same [`SceneNodeId`] on every load though, so any reference to a node
+/// of another cell must be stored as [`SceneNodeId`] and resolved via [`Self::resolve`] when
+/// needed. The resolved handle is [`Handle::NONE`] while the cell of the node is not loaded.
+///
+/// ## Updating
+///
+/// The engine updates the world partition of every enabled scene automatically, this could be
+/// disabled for a particular scene using [`super::graph::GraphUpdateSwitches::streaming`].
+#[derive(Clone, Debug, Visit, Reflect)]
+pub struct WorldPartition {
+    /// Whether the streaming is enabled or not.
+    pub enabled: bool,
+    /// A set of cells of the world partition.
+    pub cells: Vec<WorldCell>,
+    /// A distance from a streaming source to the bounds of a cell at which the cell will be loaded.
+    #[reflect(min_value = 0.0)]
+    pub load_distance: f32,
+    /// A distance from a streaming source to the bounds of a cell at which the cell will be
+    /// unloaded. Must be greater than or equal to [`Self::load_distance`].
+    #[reflect(min_value = 0.0)]
+    pub unload_distance: f32,
+    /// Whether every enabled camera is a streaming source or not.
+    pub use_cameras: bool,
+    /// Tag of the nodes that will be used as streaming sources. Empty tag means no tagged sources.
+    pub source_tag: String,
+    /// A set of nodes that will be used as streaming sources.
+    pub sources: Vec<Handle<Node>>,
+}
+
+impl Default for WorldPartition {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cells: Default::default(),
+            load_distance: 100.0,
+            unload_distance: 120.0,
+            use_cameras: true,
+            source_tag: Default::default(),
+            sources: Default::default(),
+        }
+    }
+}
+
+impl WorldPartition {
+    /// Splits the given scene into cells of the given size (on XZ plane) and saves every cell as
+    /// a separate scene in the given directory. Only top-level nodes (direct children of the root)
+    /// that pass the filter are moved to the cells, the rest of the scene stays as is. Returns the
+    /// amount of created cells. Existing cells of the world partition of the scene are replaced.
+    pub fn build(
+        scene: &mut Scene,
+        cell_size: f32,
+        directory: &Path,
+        filter: &mut dyn FnMut(Handle<Node>, &Node) -> bool,
+    ) -> Result<usize, VisitError> {
+        assert!(cell_size > 0.0);
+
+        scene.graph.update_hierarchical_data();
+
+        let root = scene.graph.get_root();
+        let mut groups = BTreeMap::<(i32, i32), Vec<Handle<Node>>>::new();
+        for &child in scene.graph[root].children() {
+            let node = &scene.graph[child];
+            if filter(child, node) {
+                let position = node.global_position();
+                let coords = (
+                    (position.x / cell_size).floor() as i32,
+                    (position.z / cell_size).floor() as i32,
+                );
+                groups.entry(coords).or_default().push(child);
+            }
+        }
+
+        std::fs::create_dir_all(directory)?;
+
+        let mut cells = Vec::with_capacity(groups.len());
+        for ((x, z), nodes) in groups {
+            let name = format!("cell_{x}_{z}");
+            let path = directory.join(format!("{name}.rgs"));
+
+            let mut cell_scene = Scene::new();
+            let mut bounds = AxisAlignedBoundingBox::default();
+            for node in nodes {
+                for (_, descendant) in scene.graph.traverse_iter(node) {
+                    // Nodes without geometry have invalid local bounds.
+                    if descendant.local_bounding_box().is_valid() {
+                        bounds.add_box(descendant.world_bounding_box());
+                    } else {
+                        bounds.add_point(descendant.global_position());
+                    }
+                }
+
+                scene.graph.copy_node(
+                    node,
+                    &mut cell_scene.graph,
+                    &mut |_, _| true,
+                    &mut |_, _| {},
+                    &mut |_, _, _| {},
+                );
+                scene.graph.remove_node(node);
+            }
+
+            let mut visitor = Visitor::new();
+            cell_scene.save("Scene", &mut visitor)?;
+            visitor.save_binary(&path)?;
+
+            cells.push(WorldCell::new(name, bounds, path));
+        }
+
+        let count = cells.len();
+        scene.world_partition.cells = cells;
+        Ok(count)
+    }
+
+    /// Returns the coordinates of a cell of the given size, that contains the given point. It
+    /// could be used to find a cell built by [`Self::build`].
+    pub fn cell_coords(point: Vector3<f32>, cell_size: f32) -> Vector2<i32> {
+        Vector2::new(
+            (point.x / cell_size).floor() as i32,
+            (point.z / cell_size).floor() as i32,
+        )
+    }
+
+    /// Returns a handle of a node with the given id, or [`Handle::NONE`] if there's no such node
+    /// (for example, if its cell is not loaded). It is just a shortcut for
+    /// [`Graph::id_to_node_handle`], see "Cross-cell references" section of the type docs.
+    pub fn resolve(graph: &Graph, id: SceneNodeId) -> Handle<Node> {
+        graph.id_to_node_handle(id).cloned().unwrap_or_default()
+    }
+
+    /// Returns the amount of loaded cells.
+    pub fn loaded_count(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.is_loaded()).count()
+    }
+
+    /// Unloads every loaded cell.
+    pub fn unload_all(&mut self, graph: &mut Graph) {
+        for cell in self.cells.iter_mut() {
+            cell.unload(graph);
+        }
+    }
+
+    /// Remaps handles of the loaded cells using the given map. Cells, whose instances are not in
+    /// the map, are marked as unloaded.
+    pub(crate) fn remap_handles(&mut self, map: &NodeHandleMap<Node>) {
+        for cell in self.cells.iter_mut() {
+            map.map(&mut cell.root);
+        }
+        for source in self.sources.iter_mut() {
+            map.map(source);
+        }
+    }
+
+    fn collect_sources(&self, graph: &Graph) -> Vec<Vector3<f32>> {
+        let mut positions = self
+            .sources
+            .iter()
+            .filter_map(|source| graph.try_get(*source))
+            .map(|source| source.global_position())
+            .collect::<Vec<_>>();
+
+        if self.use_cameras || !self.source_tag.is_empty() {
+            for node in graph.linear_iter() {
+                let is_source = (self.use_cameras
+                    && node
+                        .cast::<Camera>()
+                        .is_some_and(|camera| camera.is_enabled()))
+                    || (!self.source_tag.is_empty() && node.tag() == self.source_tag);
+                if is_source {
+                    positions.push(node.global_position());
+                }
+            }
+        }
+
+        positions
+    }
+
+    /// Performs a single streaming step: unloads cells that are too far from every streaming
+    /// source, requests loading of cells that are close enough to any source and instantiates
+    /// the loaded ones. There's no need to call this method manually, the engine does this
+    /// automatically.
+    pub(crate) fn update(&mut self, scene: &mut Scene, resource_manager: &ResourceManager) {
+        if !self.enabled {
+            return;
+        }
+
+        let sources = self.collect_sources(&scene.graph);
+        let unload_distance = self.unload_distance.max(self.load_distance);
+
+        for cell in self.cells.iter_mut() {
+            // A cell could be deleted by something else.
+            if cell.root.is_some() && !scene.graph.is_valid_handle(cell.root) {
+                cell.root = Handle::NONE;
+            }
+
+            let distance = sources
+                .iter()
+                .map(|source| cell.distance_to(*source))
+                .fold(f32::MAX, f32::min);
+
+            if distance > unload_distance {
+                cell.unload(&mut scene.graph);
+                cell.failed = false;
+                continue;
+            }
+
+            if cell.is_loaded() || cell.failed {
+                continue;
+            }
+
+            if let Some(model) = cell.loading.clone() {
+                if model.is_ok() {
+                    cell.loading = None;
+                    cell.instantiate(model, scene);
+                } else if let ResourceState::LoadError { ref error } = model.header().state {
+                    Log::err(format!(
+                        "Unable to load world cell {} from {}. Reason: {:?}",
+                        cell.name,
+                        cell.path.display(),
+                        error
+                    ));
+                    cell.loading = None;
+                    cell.failed = true;
+                }
+            } else if distance <= self.load_distance {
+                cell.loading = Some(resource_manager.request::<Model>(&cell.path));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::manager::ResourceManager,
+        core::{algebra::Vector3, futures::executor::block_on, pool::Handle},
+        engine::{self, SerializationContext},
+        graph::SceneGraph,
+        scene::{
+            base::BaseBuilder, pivot::PivotBuilder, streaming::WorldPartition,
+            transform::TransformBuilder, Scene,
+        },
+    };
+    use std::sync::Arc;
+
+    fn make_pivot(scene: &mut Scene, name: &str, position: Vector3<f32>) {
+        PivotBuilder::new(
+            BaseBuilder::new().with_name(name).with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            ),
+        )
+        .build(&mut scene.graph);
+    }
+
+    #[test]
+    fn test_world_partition() {
+        let mut scene = Scene::new();
+        make_pivot(&mut scene, "Persistent", Vector3::new(0.0, 0.0, 0.0));
+        make_pivot(&mut scene, "A", Vector3::new(5.0, 0.0, 5.0));
+        make_pivot(&mut scene, "B", Vector3::new(105.0, 0.0, 5.0));
+        let b_id = scene
+            .graph
+            .find_by_name_from_root("B")
+            .unwrap()
+            .1
+            .instance_id();
+
+        let count = WorldPartition::build(
+            &mut scene,
+            50.0,
+            &std::env::temp_dir().join("fyrox-world-partition-test"),
+            &mut |_, node| node.name() != "Persistent",
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+        assert!(scene.graph.find_by_name_from_root("Persistent").is_some());
+        assert!(scene.graph.find_by_name_from_root("A").is_none());
+        assert!(scene.graph.find_by_name_from_root("B").is_none());
+        assert_eq!(scene.world_partition.cells[0].name, "cell_0_0");
+        assert_eq!(scene.world_partition.cells[1].name, "cell_2_0");
+
+        let resource_manager = ResourceManager::new(Arc::new(Default::default()));
+        engine::initialize_resource_manager_loaders(
+            &resource_manager,
+            Arc::new(SerializationContext::new()),
+        );
+
+        let source = scene.graph.find_by_name_from_root("Persistent").unwrap().0;
+        let partition = &mut scene.world_partition;
+        partition.use_cameras = false;
+        partition.sources.push(source);
+        partition.load_distance = 10.0;
+        partition.unload_distance = 20.0;
+
+        let update = |scene: &mut Scene| {
+            scene.graph.update_hierarchical_data();
+            scene.update_world_partition(&resource_manager);
+            for cell in scene.world_partition.cells.iter() {
+                if let Some(model) = cell.loading.clone() {
+                    block_on(model).unwrap();
+                }
+            }
+            scene.update_world_partition(&resource_manager);
+        };
+
+        // Only the first cell is close enough.
+        update(&mut scene);
+        assert!(scene.world_partition.cells[0].is_loaded());
+        assert!(!scene.world_partition.cells[1].is_loaded());
+        assert!(scene.graph.find_by_name_from_root("A").is_some());
+        assert_eq!(WorldPartition::resolve(&scene.graph, b_id), Handle::NONE);
+
+        // Hysteresis - the first cell must stay loaded.
+        scene.graph[source]
+            .local_transform_mut()
+            .set_position(Vector3::new(-10.0, 0.0, 0.0));
+        update(&mut scene);
+        assert!(scene.world_partition.cells[0].is_loaded());
+
+        // Move the source to the second cell.
+        scene.graph[source]
+            .local_transform_mut()
+            .set_position(Vector3::new(100.0, 0.0, 0.0));
+        update(&mut scene);
+        assert!(!scene.world_partition.cells[0].is_loaded());
+        assert!(scene.world_partition.cells[1].is_loaded());
+        assert!(scene.graph.find_by_name_from_root("A").is_none());
+
+        // Ids must be preserved.
+        let b = WorldPartition::resolve(&scene.graph, b_id);
+        assert_eq!(scene.graph[b].name(), "B");
+    }
+}
@@ -0,0 +1,536 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Vehicle is a physics entity, that simulates a wheeled vehicle using ray-cast suspension. See
+//! [`Vehicle`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        log::Log,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+        uuid::{uuid, Uuid},
+        uuid_provider,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        graph::{
+            physics::{NativeVehicle, PhysicsWorld},
+            Graph, NodePool,
+        },
+        node::{constructor::NodeConstructor, Node, NodeTrait, SyncContext, UpdateContext},
+        rigidbody::RigidBody,
+        Scene,
+    },
+};
+use fyrox_graph::{constructor::ConstructorProvider, BaseSceneGraph, SceneGraph};
+use std::{
+    cell::Cell,
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+};
+
+/// A wheel of a [`Vehicle`]. Every wheel is simulated by a ray cast along the suspension
+/// direction, so the wheel itself does not have any collider. All the vectors are defined in the
+/// local coordinate system of the vehicle node.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct VehicleWheel {
+    /// An optional handle of a node, that will be used to visualize the wheel. It should be a
+    /// direct child of the vehicle node, its local transform is overwritten on each update.
+    pub node: Handle<Node>,
+    /// A point (in local coordinates of the vehicle), at which the suspension is attached.
+    pub connection_point: Vector3<f32>,
+    /// Direction of the suspension, the ray cast is done along this direction.
+    pub direction: Vector3<f32>,
+    /// An axis around which the wheel rotates.
+    pub axle: Vector3<f32>,
+    /// Length of the suspension spring at rest.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub suspension_rest_length: f32,
+    /// The maximum distance, that the suspension can travel in both directions from its rest
+    /// length.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub max_suspension_travel: f32,
+    /// Radius of the wheel.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub radius: f32,
+    /// Stiffness of the suspension spring. Increase it if the suspension is not able to hold the
+    /// vehicle.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub suspension_stiffness: f32,
+    /// Damping of the suspension, when it is being compressed.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub suspension_compression: f32,
+    /// Damping of the suspension, when it is being released. Increase it if the suspension
+    /// overshoots.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub suspension_damping: f32,
+    /// The maximum force, that could be applied by the suspension.
+    #[reflect(min_value = 0.0)]
+    pub max_suspension_force: f32,
+    /// Defines the traction of the tire. Larger values make braking more instantaneous, but too
+    /// high values could flip the vehicle.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub friction_slip: f32,
+    /// A multiplier of the side friction of the tire.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub side_friction_stiffness: f32,
+    /// Steering angle of the wheel (in radians).
+    pub steering: f32,
+    /// Force, that is applied by the engine to the wheel. Positive values move the vehicle
+    /// forward (along the local Z axis of the chassis).
+    pub engine_force: f32,
+    /// Braking force applied to the wheel.
+    #[reflect(min_value = 0.0)]
+    pub brake: f32,
+}
+
+uuid_provider!(VehicleWheel = "86b4b62e-ae50-4a0d-a94a-0678ac603c39");
+
+impl Default for VehicleWheel {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            connection_point: Default::default(),
+            direction: -Vector3::y(),
+            axle: -Vector3::x(),
+            suspension_rest_length: 0.3,
+            max_suspension_travel: 0.3,
+            radius: 0.35,
+            suspension_stiffness: 20.0,
+            suspension_compression: 1.5,
+            suspension_damping: 2.0,
+            max_suspension_force: 6000.0,
+            friction_slip: 10.5,
+            side_friction_stiffness: 1.0,
+            steering: 0.0,
+            engine_force: 0.0,
+            brake: 0.0,
+        }
+    }
+}
+
+impl VehicleWheel {
+    /// Calculates the local transform of the wheel in the coordinate system of the vehicle from
+    /// the given state of the wheel.
+    pub fn local_pose(&self, state: &VehicleWheelState) -> (Vector3<f32>, UnitQuaternion<f32>) {
+        let direction = self
+            .direction
+            .try_normalize(f32::EPSILON)
+            .unwrap_or_default();
+        let axle = self.axle.try_normalize(f32::EPSILON).unwrap_or_default();
+        let position = self.connection_point + direction.scale(state.suspension_length);
+        let steering = UnitQuaternion::from_scaled_axis(-direction.scale(self.steering));
+        let spin = UnitQuaternion::from_scaled_axis(-axle.scale(state.rotation));
+        (position, steering * spin)
+    }
+}
+
+/// Simulation state of a wheel of a vehicle after the last physics step.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VehicleWheelState {
+    /// `true` if the wheel touches the ground.
+    pub in_contact: bool,
+    /// Current length of the suspension.
+    pub suspension_length: f32,
+    /// Current rotation angle (in radians) of the wheel around its axle.
+    pub rotation: f32,
+    /// World-space position of the contact point with the ground.
+    pub contact_point: Vector3<f32>,
+    /// World-space normal of the ground at the contact point.
+    pub contact_normal: Vector3<f32>,
+    /// A handle of a collider node, that the wheel touches.
+    pub ground: Handle<Node>,
+    /// Force, that was applied by the suspension.
+    pub suspension_force: f32,
+}
+
+/// Vehicle is a physics entity, that simulates a wheeled vehicle using ray-cast suspension. It is
+/// backed by the ray-cast vehicle controller of the physics engine.
+///
+/// # Usage
+///
+/// Vehicle node must be a direct child of a dynamic 3D rigid body, that will be used as the
+/// chassis. The chassis must have at least one collider, that defines its mass. Wheels are
+/// defined in the local coordinate system of the vehicle node (see [`VehicleWheel`] docs). The
+/// forward direction of the vehicle is the local Z axis of the chassis and the up direction is
+/// the local Y axis. Nodes, that visualize the wheels, should be direct children of the vehicle
+/// node - their local transforms are updated automatically after every simulation step.
+///
+/// ```rust
+/// # use fyrox_impl::{
+/// #     core::pool::Handle,
+/// #     graph::SceneGraph,
+/// #     scene::{graph::Graph, node::Node, vehicle::Vehicle},
+/// # };
+/// fn drive(graph: &mut Graph, vehicle: Handle<Node>, throttle: f32, steering: f32) {
+///     if let Some(vehicle) = graph.try_get_mut_of_type::<Vehicle>(vehicle) {
+///         // Front wheels steer, rear wheels drive.
+///         for wheel in 0..2 {
+///             vehicle.set_steering(wheel, steering);
+///         }
+///         for wheel in 2..4 {
+///             vehicle.set_engine_force(wheel, throttle * 800.0);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Visit, Reflect, ComponentProvider)]
+pub struct Vehicle {
+    base: Base,
+
+    #[reflect(setter = "set_wheels")]
+    pub(crate) wheels: InheritableVariable<Vec<VehicleWheel>>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) wheel_states: Vec<VehicleWheelState>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) current_speed: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) native: Cell<Handle<NativeVehicle>>,
+}
+
+impl Debug for Vehicle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Vehicle")
+    }
+}
+
+impl Default for Vehicle {
+    fn default() -> Self {
+        VehicleBuilder::new(BaseBuilder::new()).build_vehicle()
+    }
+}
+
+impl Deref for Vehicle {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for Vehicle {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Clone for Vehicle {
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            wheels: self.wheels.clone(),
+            wheel_states: self.wheel_states.clone(),
+            current_speed: self.current_speed,
+            // Do not copy. The copy will have its own native representation.
+            native: Cell::new(Handle::NONE),
+        }
+    }
+}
+
+impl TypeUuidProvider for Vehicle {
+    fn type_uuid() -> Uuid {
+        uuid!("66f50711-e533-4847-8837-2beb53bf9872")
+    }
+}
+
+impl Vehicle {
+    /// Sets new set of wheels of the vehicle.
+    pub fn set_wheels(&mut self, wheels: Vec<VehicleWheel>) -> Vec<VehicleWheel> {
+        self.wheels.set_value_and_mark_modified(wheels)
+    }
+
+    /// Returns a reference to the wheels of the vehicle.
+    pub fn wheels(&self) -> &[VehicleWheel] {
+        &self.wheels
+    }
+
+    /// Returns a mutable reference to the wheels of the vehicle. Use [`Self::set_steering`],
+    /// [`Self::set_engine_force`] and [`Self::set_brake`] to control the vehicle, because they do
+    /// not mark the wheels as modified and preserve property inheritance.
+    pub fn wheels_mut(&mut self) -> &mut Vec<VehicleWheel> {
+        self.wheels.get_value_mut_and_mark_modified()
+    }
+
+    /// Sets steering angle (in radians) of a wheel with the given index.
+    pub fn set_steering(&mut self, wheel: usize, angle: f32) {
+        if let Some(wheel) = self.wheels.get_value_mut_silent().get_mut(wheel) {
+            wheel.steering = angle;
+        }
+    }
+
+    /// Sets engine force applied to a wheel with the given index.
+    pub fn set_engine_force(&mut self, wheel: usize, force: f32) {
+        if let Some(wheel) = self.wheels.get_value_mut_silent().get_mut(wheel) {
+            wheel.engine_force = force;
+        }
+    }
+
+    /// Sets braking force applied to a wheel with the given index.
+    pub fn set_brake(&mut self, wheel: usize, force: f32) {
+        if let Some(wheel) = self.wheels.get_value_mut_silent().get_mut(wheel) {
+            wheel.brake = force;
+        }
+    }
+
+    /// Returns simulation state of every wheel after the last physics step. The states are in the
+    /// same order as the wheels.
+    pub fn wheel_states(&self) -> &[VehicleWheelState] {
+        &self.wheel_states
+    }
+
+    /// Returns current forward speed of the vehicle. Negative values mean that the vehicle moves
+    /// backwards.
+    pub fn current_speed(&self) -> f32 {
+        self.current_speed
+    }
+
+    // Fetches the state of the native vehicle and moves the wheel nodes accordingly.
+    pub(crate) fn sync_wheels(&mut self, physics: &PhysicsWorld, nodes: &mut NodePool) {
+        physics.fetch_vehicle_state(self);
+
+        for (wheel, state) in self.wheels.iter().zip(self.wheel_states.iter()) {
+            if let Some(wheel_node) = nodes.try_borrow_mut(wheel.node) {
+                let (position, rotation) = wheel.local_pose(state);
+                wheel_node
+                    .local_transform_mut()
+                    .set_position(position)
+                    .set_rotation(rotation);
+            }
+        }
+    }
+}
+
+impl ConstructorProvider<Node, Graph> for Vehicle {
+    fn constructor() -> NodeConstructor {
+        NodeConstructor::new::<Self>()
+            .with_variant("Vehicle", |_| {
+                VehicleBuilder::new(BaseBuilder::new().with_name("Vehicle"))
+                    .build_node()
+                    .into()
+            })
+            .with_group("Physics")
+    }
+}
+
+impl NodeTrait for Vehicle {
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn on_removed_from_graph(&mut self, graph: &mut Graph) {
+        graph.physics.remove_vehicle(self.native.get());
+        self.native.set(Handle::NONE);
+
+        Log::info(format!(
+            "Native vehicle was removed for node: {}",
+            self.name()
+        ));
+    }
+
+    fn sync_native(&self, _self_handle: Handle<Node>, context: &mut SyncContext) {
+        context.physics.sync_to_vehicle_node(context.nodes, self);
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        // The vehicle is synced by the fixed steps of the physics, if the physics is not stepped
+        // by the regular update. See [`crate::scene::graph::Graph::fixed_update`].
+        if context.switches.is_some_and(|switches| !switches.physics) {
+            return;
+        }
+
+        self.sync_wheels(context.physics, context.nodes);
+    }
+
+    fn validate(&self, scene: &Scene) -> Result<(), String> {
+        let mut message = String::new();
+
+        if scene
+            .graph
+            .try_get_of_type::<RigidBody>(self.parent())
+            .is_none()
+        {
+            message += "Vehicle must be a direct child of a 3D Rigid Body node, that will be \
+            used as the chassis!";
+        }
+
+        for (index, wheel) in self.wheels.iter().enumerate() {
+            if wheel.node.is_some() && !self.children().contains(&wheel.node) {
+                message += &format!(
+                    "Node of the wheel {index} must be a direct child of the vehicle node!"
+                );
+            }
+        }
+
+        if message.is_empty() {
+            Ok(())
+        } else {
+            Err(message)
+        }
+    }
+}
+
+/// Allows you to create a vehicle in declarative manner.
+pub struct VehicleBuilder {
+    base_builder: BaseBuilder,
+    wheels: Vec<VehicleWheel>,
+}
+
+impl VehicleBuilder {
+    /// Creates new vehicle builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            wheels: Default::default(),
+        }
+    }
+
+    /// Sets the desired wheels of the vehicle.
+    pub fn with_wheels(mut self, wheels: Vec<VehicleWheel>) -> Self {
+        self.wheels = wheels;
+        self
+    }
+
+    /// Creates Vehicle node but does not add it to the graph.
+    pub fn build_vehicle(self) -> Vehicle {
+        Vehicle {
+            base: self.base_builder.build_base(),
+            wheels: self.wheels.into(),
+            wheel_states: Default::default(),
+            current_speed: 0.0,
+            native: Cell::new(Handle::NONE),
+        }
+    }
+
+    /// Creates Vehicle node but does not add it to the graph.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_vehicle())
+    }
+
+    /// Creates Vehicle node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::algebra::{Vector2, Vector3};
+    use crate::scene::{
+        base::BaseBuilder,
+        collider::{ColliderBuilder, ColliderShape},
+        graph::Graph,
+        pivot::PivotBuilder,
+        rigidbody::{RigidBodyBuilder, RigidBodyType},
+        transform::TransformBuilder,
+        vehicle::{Vehicle, VehicleBuilder, VehicleWheel},
+    };
+
+    #[test]
+    fn test_vehicle_stands_on_wheels_and_drives() {
+        let mut graph = Graph::new();
+
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(50.0, 0.5, 50.0))
+            .build(&mut graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[ground_collider]))
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+        let mut wheels = Vec::new();
+        let mut wheel_nodes = Vec::new();
+        for (x, z) in [(0.9, 1.5), (-0.9, 1.5), (0.9, -1.5), (-0.9, -1.5)] {
+            let node = PivotBuilder::new(BaseBuilder::new()).build(&mut graph);
+            wheel_nodes.push(node);
+            wheels.push(VehicleWheel {
+                node,
+                connection_point: Vector3::new(x, 0.0, z),
+                ..Default::default()
+            });
+        }
+        let vehicle = VehicleBuilder::new(BaseBuilder::new().with_children(&wheel_nodes))
+            .with_wheels(wheels)
+            .build(&mut graph);
+
+        let chassis_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(1.0, 0.25, 2.0))
+            .build(&mut graph);
+        let chassis = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 1.5, 0.0))
+                        .build(),
+                )
+                .with_children(&[chassis_collider, vehicle]),
+        )
+        .build(&mut graph);
+
+        for _ in 0..180 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        let vehicle_ref = graph[vehicle].cast::<Vehicle>().unwrap();
+        assert_eq!(vehicle_ref.wheel_states().len(), 4);
+        assert!(vehicle_ref.wheel_states().iter().all(|s| s.in_contact));
+        assert!(vehicle_ref
+            .wheel_states()
+            .iter()
+            .all(|s| s.ground == ground_collider));
+        // Ground level + wheel radius + compressed suspension.
+        let height = graph[chassis].local_transform().position().y;
+        assert!((0.9..1.2).contains(&height), "{height}");
+        // Wheel nodes follow the suspension.
+        let suspension_length = vehicle_ref.wheel_states()[0].suspension_length;
+        let wheel_y = graph[wheel_nodes[0]].local_transform().position().y;
+        assert!((wheel_y + suspension_length).abs() < 1.0e-5);
+
+        let start_z = graph[chassis].local_transform().position().z;
+        for _ in 0..60 {
+            let vehicle_mut = graph[vehicle].cast_mut::<Vehicle>().unwrap();
+            vehicle_mut.set_engine_force(2, 10.0);
+            vehicle_mut.set_engine_force(3, 10.0);
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        let vehicle_ref = graph[vehicle].cast::<Vehicle>().unwrap();
+        assert!(vehicle_ref.current_speed() > 0.0);
+        assert!(vehicle_ref.wheel_states()[0].rotation > 0.0);
+        let end_z = graph[chassis].local_transform().position().z;
+        assert!(end_z > start_z + 0.5, "{start_z} {end_z}");
+    }
+}
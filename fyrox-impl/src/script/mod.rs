@@ -37,7 +37,7 @@ use crate::{
     event::Event,
     gui::UiContainer,
     plugin::{Plugin, PluginContainer},
-    scene::{base::NodeScriptMessage, node::Node, Scene},
+    scene::{base::NodeScriptMessage, graph::physics::CollisionEvent, node::Node, Scene},
 };
 use std::{
     any::{Any, TypeId},
@@ -604,6 +604,30 @@ pub trait ScriptTrait: BaseScript + ComponentProvider {
     /// [`crate::engine::executor::Executor::set_desired_update_rate`] method.
     fn on_update(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
 
+    /// Performs a single fixed step of the script. The method is called only if fixed time step is
+    /// enabled (see [`crate::engine::FixedTimeStepSettings`]), right after each physics step, so it
+    /// is the right place to apply forces, impulses or to move kinematic bodies. It could be called
+    /// zero or multiple times per frame, [`ScriptContext::dt`] is always equal to the fixed time step.
+    /// Only started scripts receive fixed updates.
+    fn on_fixed_update(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
+
+    /// Called when a collider of the node (or one of the colliders of the node, if it is a rigid body
+    /// or a character controller) starts or stops touching (or intersecting) some other collider.
+    /// The method is called after [`ScriptTrait::on_update`] (or after [`ScriptTrait::on_fixed_update`]
+    /// if fixed time step is enabled) for every collision event, that has happened during the last
+    /// physics step (both 3D and 2D). [`CollisionEvent::collider1`] is
+    /// always the collider, that belongs to the node of the script. See [`CollisionEvent`] docs for
+    /// more info.
+    ///
+    /// Collision events are reported only for the colliders with enabled collision events (see
+    /// [`crate::scene::collider::Collider::set_report_collision_events`]).
+    fn on_collision(
+        &mut self,
+        #[allow(unused_variables)] event: &CollisionEvent,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
     /// Allows you to react to certain script messages. It could be used for communication between scripts; to
     /// bypass borrowing issues. If you need to receive messages of a particular type, you must subscribe to a type
     /// explicitly. Usually it is done in [`ScriptTrait::on_start`] method:
@@ -0,0 +1,380 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! On-disk cache for the output of expensive resource importers. See [`ImportCache`] docs for
+//! more info.
+
+use crate::core::{
+    log::Log,
+    parking_lot::RwLock,
+    rand::{self, Rng},
+    visitor::{Visit, Visitor},
+};
+use fxhash::FxHasher64;
+use serde::Serialize;
+use std::{
+    fs,
+    hash::Hasher,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Magic number at the beginning of every cache entry.
+pub const IMPORT_CACHE_MAGIC: [u8; 4] = *b"FICE";
+
+/// Version of the layout of cache entries. Bump it if the layout changes, so old entries will be
+/// ignored.
+pub const IMPORT_CACHE_FORMAT_VERSION: u32 = 1;
+
+fn fx_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = FxHasher64::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(bytes);
+    crc.sum()
+}
+
+/// A key of an import cache entry. It uniquely identifies the output of a loader by the content
+/// of the source file, the import options and the version of the loader. Paths are intentionally
+/// not a part of the key, so moving or renaming a source file does not invalidate its entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportCacheKey {
+    loader_id: String,
+    header: Vec<u8>,
+}
+
+impl ImportCacheKey {
+    /// Creates a new key. `loader_id` is a short unique name of the loader (it is used as a part
+    /// of the file name, so it should contain only alphanumeric characters), `version` is the
+    /// version of the loader that must be increased every time the loader starts to produce
+    /// different output for the same input, `source` is the content of the source file and
+    /// `options` are the import options used to process the file. Returns `None` if the options
+    /// cannot be serialized, such output must not be cached, otherwise different options could
+    /// end up with the same key.
+    pub fn new<O>(loader_id: &str, version: u32, source: &[u8], options: &O) -> Option<Self>
+    where
+        O: Serialize + ?Sized,
+    {
+        let options = match ron::ser::to_string(options) {
+            Ok(options) => options,
+            Err(err) => {
+                Log::warn(format!(
+                    "Unable to serialize import options for {loader_id} import cache key, the \
+                    import cache will not be used. Reason: {err:?}"
+                ));
+                return None;
+            }
+        };
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&(loader_id.len() as u32).to_le_bytes());
+        header.extend_from_slice(loader_id.as_bytes());
+        header.extend_from_slice(&version.to_le_bytes());
+        header.extend_from_slice(&(source.len() as u64).to_le_bytes());
+        header.extend_from_slice(&crc32(source).to_le_bytes());
+        header.extend_from_slice(&fx_hash(source).to_le_bytes());
+        header.extend_from_slice(&(options.len() as u32).to_le_bytes());
+        header.extend_from_slice(options.as_bytes());
+
+        Some(Self {
+            loader_id: loader_id.to_string(),
+            header,
+        })
+    }
+
+    /// Returns the name of the cache file for the key.
+    pub fn file_name(&self) -> String {
+        format!("{}-{:016x}.bin", self.loader_id, fx_hash(&self.header))
+    }
+}
+
+/// Import cache stores processed output of resource loaders on disk, so the next time the same
+/// source file is loaded with the same import options, a loader could skip expensive processing
+/// (for example, texture compression or FBX parsing) and use the cached data instead.
+///
+/// ## Invalidation
+///
+/// Cache entries are keyed by [`ImportCacheKey`], which includes the hash of the source file, the
+/// import options (usually loaded from `.options` files) and the version of the loader. Any change
+/// in these will result in a different key and the old entry will simply be ignored. Every entry
+/// also stores the full key and a checksum of its payload, so hash collisions and corrupted
+/// entries are detected and treated as misses.
+///
+/// ## Concurrency
+///
+/// Entries are written to a temporary file first and then atomically renamed, so multiple
+/// processes (for example, the editor and a running game) can share the same cache directory
+/// without seeing partially written entries.
+///
+/// ## Usage
+///
+/// The cache is disabled by default, use [`Self::set_directory`] to enable it. The cache is a
+/// shared handle, so it can be cloned and given to resource loaders.
+#[derive(Clone, Default, Debug)]
+pub struct ImportCache {
+    directory: Arc<RwLock<Option<PathBuf>>>,
+}
+
+impl ImportCache {
+    /// Sets a directory in which the cache will store its entries. `None` disables the cache.
+    pub fn set_directory(&self, directory: Option<PathBuf>) {
+        *self.directory.write() = directory;
+    }
+
+    /// Returns the current cache directory, if any.
+    pub fn directory(&self) -> Option<PathBuf> {
+        self.directory.read().clone()
+    }
+
+    /// Returns `true` if the cache is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.directory.read().is_some()
+    }
+
+    /// Tries to fetch the data stored for the given key. Returns `None` if the cache is disabled,
+    /// there is no entry for the key or the entry is invalid.
+    pub fn load(&self, key: &ImportCacheKey) -> Option<Vec<u8>> {
+        let path = self.directory()?.join(key.file_name());
+        let data = fs::read(&path).ok()?;
+        let payload = Self::validate(&data, key);
+        if payload.is_none() {
+            Log::warn(format!(
+                "Import cache entry {} is invalid and will be ignored.",
+                path.display()
+            ));
+        }
+        payload.map(|payload| payload.to_vec())
+    }
+
+    fn validate<'a>(data: &'a [u8], key: &ImportCacheKey) -> Option<&'a [u8]> {
+        let mut cursor = data;
+        let mut take = |count: usize| -> Option<&'a [u8]> {
+            if cursor.len() < count {
+                return None;
+            }
+            let (head, tail) = cursor.split_at(count);
+            cursor = tail;
+            Some(head)
+        };
+        let take_u32 = |bytes: &[u8]| u32::from_le_bytes(bytes.try_into().unwrap());
+
+        if take(4)? != IMPORT_CACHE_MAGIC {
+            return None;
+        }
+        if take_u32(take(4)?) != IMPORT_CACHE_FORMAT_VERSION {
+            return None;
+        }
+        let header_len = take_u32(take(4)?) as usize;
+        if take(header_len)? != key.header.as_slice() {
+            return None;
+        }
+        let payload_len = u64::from_le_bytes(take(8)?.try_into().unwrap()) as usize;
+        let checksum = take_u32(take(4)?);
+        let payload = take(payload_len)?;
+        if crc32(payload) != checksum {
+            return None;
+        }
+        Some(payload)
+    }
+
+    /// Stores the given data for the given key. Does nothing if the cache is disabled.
+    pub fn store(&self, key: &ImportCacheKey, payload: &[u8]) -> io::Result<()> {
+        let Some(directory) = self.directory() else {
+            return Ok(());
+        };
+
+        fs::create_dir_all(&directory)?;
+
+        // Write to a unique temporary file first and then atomically replace the entry, so
+        // concurrent readers never see a partially written entry.
+        let temp_path = directory.join(format!(
+            "{}.{}-{:08x}.tmp",
+            key.file_name(),
+            std::process::id(),
+            rand::thread_rng().gen::<u32>()
+        ));
+        let result = Self::write_entry(&temp_path, key, payload)
+            .and_then(|_| fs::rename(&temp_path, directory.join(key.file_name())));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        result
+    }
+
+    fn write_entry(path: &Path, key: &ImportCacheKey, payload: &[u8]) -> io::Result<()> {
+        let mut file = io::BufWriter::new(fs::File::create(path)?);
+        file.write_all(&IMPORT_CACHE_MAGIC)?;
+        file.write_all(&IMPORT_CACHE_FORMAT_VERSION.to_le_bytes())?;
+        file.write_all(&(key.header.len() as u32).to_le_bytes())?;
+        file.write_all(&key.header)?;
+        file.write_all(&(payload.len() as u64).to_le_bytes())?;
+        file.write_all(&crc32(payload).to_le_bytes())?;
+        file.write_all(payload)?;
+        file.into_inner()?.sync_all()
+    }
+
+    /// Tries to fetch and deserialize an object stored for the given key using [`Self::store_data`].
+    pub fn load_data<T>(&self, key: &ImportCacheKey) -> Option<T>
+    where
+        T: Visit + Default,
+    {
+        let data = self.load(key)?;
+        let mut visitor = Visitor::load_binary_from_memory(&data).ok()?;
+        let mut object = T::default();
+        object.visit("Data", &mut visitor).ok()?;
+        Some(object)
+    }
+
+    /// Serializes the given object and stores it for the given key. Errors are logged, because
+    /// the cache is an optimization and a failure to write an entry should not be fatal.
+    pub fn store_data<T>(&self, key: &ImportCacheKey, object: &mut T)
+    where
+        T: Visit,
+    {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut visitor = Visitor::new();
+        let result = object
+            .visit("Data", &mut visitor)
+            .and_then(|_| visitor.save_binary_to_vec());
+        match result {
+            Ok(data) => {
+                if let Err(err) = self.store(key, &data) {
+                    Log::warn(format!("Unable to write import cache entry. Reason: {err}"));
+                }
+            }
+            Err(err) => Log::warn(format!(
+                "Unable to serialize import cache entry. Reason: {err:?}"
+            )),
+        }
+    }
+
+    /// Removes all entries from the cache directory.
+    pub fn clear(&self) -> io::Result<()> {
+        let Some(directory) = self.directory() else {
+            return Ok(());
+        };
+        match fs::remove_dir_all(&directory) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn make_cache(name: &str) -> ImportCache {
+        let directory = std::env::temp_dir().join(format!(
+            "fyrox-import-cache-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let cache = ImportCache::default();
+        cache.set_directory(Some(directory));
+        cache.clear().unwrap();
+        cache
+    }
+
+    #[test]
+    fn test_disabled_cache() {
+        let cache = ImportCache::default();
+        let key = ImportCacheKey::new("test", 1, b"source", &()).unwrap();
+        assert!(!cache.is_enabled());
+        cache.store(&key, b"payload").unwrap();
+        assert_eq!(cache.load(&key), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cache = make_cache("round-trip");
+        let key = ImportCacheKey::new("test", 1, b"source", &(1, "options")).unwrap();
+        assert_eq!(cache.load(&key), None);
+        cache.store(&key, b"payload").unwrap();
+        assert_eq!(cache.load(&key).as_deref(), Some(b"payload".as_slice()));
+
+        let mut value = 123u32;
+        cache.store_data(&key, &mut value);
+        assert_eq!(cache.load_data::<u32>(&key), Some(123));
+
+        cache.clear().unwrap();
+        assert_eq!(cache.load(&key), None);
+    }
+
+    #[test]
+    fn test_invalidation() {
+        let cache = make_cache("invalidation");
+        let key = ImportCacheKey::new("test", 1, b"source", &(1, "options")).unwrap();
+        cache.store(&key, b"payload").unwrap();
+
+        for other in [
+            ImportCacheKey::new("test", 2, b"source", &(1, "options")).unwrap(),
+            ImportCacheKey::new("test", 1, b"source2", &(1, "options")).unwrap(),
+            ImportCacheKey::new("test", 1, b"source", &(2, "options")).unwrap(),
+            ImportCacheKey::new("other", 1, b"source", &(1, "options")).unwrap(),
+        ] {
+            assert_ne!(other, key);
+            assert_eq!(cache.load(&other), None);
+        }
+
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_corrupted_entry() {
+        let cache = make_cache("corrupted");
+        let key = ImportCacheKey::new("test", 1, b"source", &()).unwrap();
+        cache.store(&key, b"payload").unwrap();
+
+        let path = cache.directory().unwrap().join(key.file_name());
+        let mut data = fs::read(&path).unwrap();
+        *data.last_mut().unwrap() ^= 0xFF;
+        fs::write(&path, &data).unwrap();
+        assert_eq!(cache.load(&key), None);
+
+        fs::write(&path, &data[..data.len() / 2]).unwrap();
+        assert_eq!(cache.load(&key), None);
+
+        cache.clear().unwrap();
+    }
+    #[test]
+    fn test_unserializable_options() {
+        struct Unserializable;
+
+        impl Serialize for Unserializable {
+            fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("unserializable"))
+            }
+        }
+
+        assert_eq!(
+            ImportCacheKey::new("test", 1, b"source", &Unserializable),
+            None
+        );
+    }
+}
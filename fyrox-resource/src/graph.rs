@@ -20,8 +20,12 @@
 
 //! Resource dependency graph. See [`ResourceDependencyGraph`] docs for more info.
 
-use crate::{collect_used_resources, state::ResourceState, untyped::UntypedResource};
+use crate::{
+    collect_used_resources, io::ResourceIo, manager::ResourceManager, options::OPTIONS_EXTENSION,
+    pack::normalize_path, state::ResourceState, untyped::UntypedResource,
+};
 use fxhash::FxHashSet;
+use std::path::PathBuf;
 
 /// A node of [`ResourceDependencyGraph`].
 pub struct ResourceGraphNode {
@@ -113,13 +117,321 @@ impl ResourceDependencyGraph {
         out
     }
 }
+
+/// A flat set of every resource that is reachable from a set of root resources. Unlike
+/// [`ResourceDependencyGraph`], it waits until every resource is loaded before looking into its
+/// content, so it could be used on resources that were just requested. It is useful to figure out
+/// which assets are actually used by a game, for example to trim a shipped build.
+#[derive(Default, Debug)]
+pub struct ResourceDependencies {
+    /// Every resource reachable from the roots (including the roots themselves) in discovery order.
+    /// Embedded resources are included as well, since they could reference external resources.
+    pub resources: Vec<UntypedResource>,
+    /// Resources that failed to load with the reason of failure. Their dependencies (if any) are
+    /// unknown.
+    pub failed: Vec<(UntypedResource, String)>,
+}
+
+impl ResourceDependencies {
+    /// Requests resources at the given paths (usually scenes) and collects every resource that
+    /// is reachable from them. Built-in resources are skipped, since they are not backed by files.
+    pub async fn collect(resource_manager: &ResourceManager, roots: &[PathBuf]) -> Self {
+        let roots = roots
+            .iter()
+            .map(|path| resource_manager.request_untyped(path))
+            .collect::<Vec<_>>();
+        let mut dependencies = Self::collect_from_resources(&roots).await;
+
+        let state = resource_manager.state();
+        dependencies.resources.retain(|resource| {
+            resource
+                .kind()
+                .path()
+                .map_or(true, |path| !state.built_in_resources.contains_key(path))
+        });
+
+        dependencies
+    }
+
+    /// Collects every resource that is reachable from the given resources.
+    pub async fn collect_from_resources(roots: &[UntypedResource]) -> Self {
+        let mut dependencies = Self::default();
+        let mut visited = FxHashSet::default();
+        let mut queue = roots.to_vec();
+
+        while let Some(resource) = queue.pop() {
+            if !visited.insert(resource.clone()) {
+                continue;
+            }
+
+            if let Err(err) = resource.clone().await {
+                let reason = err
+                    .0
+                    .map_or_else(|| "Unknown error.".to_string(), |err| format!("{err:?}"));
+                dependencies.failed.push((resource, reason));
+                continue;
+            }
+
+            let mut children = FxHashSet::default();
+            {
+                let header = resource.0.lock();
+                if let ResourceState::Ok(ref resource_data) = header.state {
+                    (**resource_data).as_reflect(&mut |entity| {
+                        collect_used_resources(entity, &mut children);
+                    });
+                }
+            }
+            queue.extend(children.into_iter().filter(|r| !visited.contains(r)));
+
+            dependencies.resources.push(resource);
+        }
+
+        dependencies
+    }
+
+    /// Returns a set of normalized paths (see [`normalize_path`]) of every external resource in
+    /// the set.
+    pub fn paths(&self) -> FxHashSet<String> {
+        self.resources
+            .iter()
+            .chain(self.failed.iter().map(|(resource, _)| resource))
+            .filter_map(|resource| resource.kind().into_path())
+            .map(|path| normalize_path(&path))
+            .collect()
+    }
+
+    /// Walks the given folders and returns every file that is not used by any resource in the set,
+    /// sorted by path. Import options files (`.options`) are considered used if their resource is
+    /// used.
+    pub async fn find_unused_files(
+        &self,
+        io: &dyn ResourceIo,
+        folders: &[PathBuf],
+    ) -> Vec<PathBuf> {
+        let used = self.paths();
+        let mut unused = Vec::new();
+
+        for folder in folders {
+            let Ok(iter) = io.walk_directory(folder).await else {
+                continue;
+            };
+
+            for path in iter {
+                if !io.is_file(&path).await {
+                    continue;
+                }
+
+                let mut key = normalize_path(&path);
+                if path.extension().is_some_and(|ext| ext == OPTIONS_EXTENSION) {
+                    key.truncate(key.len() - OPTIONS_EXTENSION.len() - 1);
+                }
+
+                if !used.contains(&key) {
+                    unused.push(path);
+                }
+            }
+        }
+
+        unused.sort();
+        unused.dedup();
+        unused
+    }
+}
+
+/// A report about used and unused files of a project. See [`ResourceUsageReport::new`] docs for
+/// more info.
+#[derive(Default, Debug, Clone)]
+pub struct ResourceUsageReport {
+    /// Sorted list of paths of every external resource reachable from the roots.
+    pub used: Vec<PathBuf>,
+    /// Sorted list of files in the searched folders that nothing reaches.
+    pub unused: Vec<PathBuf>,
+    /// Resources that failed to load with the reason of failure.
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl ResourceUsageReport {
+    /// Collects every resource reachable from the given roots (usually scenes) and then searches
+    /// the given folders (usually `data`) for files that nothing reaches. Keep in mind, that
+    /// resources that are requested from code (rather than referenced by some other resource)
+    /// cannot be found this way, so such resources must be added to the list of roots.
+    pub async fn new(
+        resource_manager: &ResourceManager,
+        roots: &[PathBuf],
+        folders: &[PathBuf],
+    ) -> Self {
+        let dependencies = ResourceDependencies::collect(resource_manager, roots).await;
+        let io = resource_manager.resource_io();
+        let unused = dependencies.find_unused_files(&*io, folders).await;
+
+        let mut used = dependencies
+            .resources
+            .iter()
+            .filter_map(|resource| resource.kind().into_path())
+            .collect::<Vec<_>>();
+        used.sort();
+
+        Self {
+            used,
+            unused,
+            failed: dependencies
+                .failed
+                .iter()
+                .map(|(resource, reason)| {
+                    (
+                        resource.kind().into_path().unwrap_or_default(),
+                        reason.clone(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Prints the report into a string in a human-readable form.
+    pub fn pretty_print(&self) -> String {
+        let mut out = String::new();
+
+        out += &format!("Used resources ({}):\n", self.used.len());
+        for path in self.used.iter() {
+            out += &format!("\t{}\n", path.display());
+        }
+
+        out += &format!("Unused files ({}):\n", self.unused.len());
+        for path in self.unused.iter() {
+            out += &format!("\t{}\n", path.display());
+        }
+
+        if !self.failed.is_empty() {
+            out += &format!("Failed to load ({}):\n", self.failed.len());
+            for (path, reason) in self.failed.iter() {
+                out += &format!("\t{} - {}\n", path.display(), reason);
+            }
+        }
+
+        out
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use std::path::PathBuf;
+    use std::{
+        error::Error,
+        io::Cursor,
+        path::{Path, PathBuf},
+    };
 
-    use fyrox_core::uuid::Uuid;
+    use fyrox_core::{
+        futures::executor::block_on,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    };
 
     use super::*;
+    use crate::{
+        pack::{PackWriter, PackedResourceIo},
+        untyped::ResourceKind,
+        ResourceData,
+    };
+
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct Container {
+        children: Vec<UntypedResource>,
+    }
+
+    impl TypeUuidProvider for Container {
+        fn type_uuid() -> Uuid {
+            uuid!("4c1d3b8a-7a0e-4d7e-9a55-2f2b2d1f6c11")
+        }
+    }
+
+    impl ResourceData for Container {
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
+        }
+
+        fn save(&mut self, _path: &Path) -> Result<(), Box<dyn Error>> {
+            Err("Saving is not supported!".to_string().into())
+        }
+
+        fn can_be_saved(&self) -> bool {
+            false
+        }
+    }
+
+    fn container(kind: ResourceKind, children: Vec<UntypedResource>) -> UntypedResource {
+        UntypedResource::new_ok(kind, Container { children })
+    }
+
+    fn external(path: &str, children: Vec<UntypedResource>) -> UntypedResource {
+        container(PathBuf::from(path).into(), children)
+    }
+
+    fn make_dependencies() -> ResourceDependencies {
+        let c = external("data/c.png", vec![]);
+        let embedded = container(ResourceKind::Embedded, vec![c]);
+        let b = external("data/b.png", vec![]);
+        let d = UntypedResource::new_load_error(
+            PathBuf::from("data/d.png").into(),
+            Default::default(),
+            Uuid::default(),
+        );
+        let a = external("./data/a.rgs", vec![b.clone(), embedded, d]);
+        // Shared dependency must be reported once.
+        let root2 = external("data/root2.rgs", vec![b]);
+        block_on(ResourceDependencies::collect_from_resources(&[a, root2]))
+    }
+
+    #[test]
+    fn resource_dependencies_collect() {
+        let dependencies = make_dependencies();
+
+        assert_eq!(dependencies.resources.len(), 5);
+        assert_eq!(dependencies.failed.len(), 1);
+
+        let mut paths = dependencies.paths().into_iter().collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "data/a.rgs",
+                "data/b.png",
+                "data/c.png",
+                "data/d.png",
+                "data/root2.rgs"
+            ]
+        );
+    }
+
+    #[test]
+    fn resource_dependencies_find_unused_files() {
+        let mut writer = PackWriter::new(Cursor::new(Vec::new())).unwrap();
+        for path in [
+            "data/a.rgs",
+            "data/b.png",
+            "data/b.png.options",
+            "data/c.png",
+            "data/d.png",
+            "data/root2.rgs",
+            "data/e.png",
+            "data/e.png.options",
+            "data/sub/f.wav",
+        ] {
+            writer.add_file(Path::new(path), &[]).unwrap();
+        }
+        let io = PackedResourceIo::from_bytes(writer.finish().unwrap().into_inner()).unwrap();
+
+        let unused = block_on(make_dependencies().find_unused_files(&io, &["data".into()]));
+        assert_eq!(
+            unused,
+            [
+                PathBuf::from("data/e.png"),
+                PathBuf::from("data/e.png.options"),
+                PathBuf::from("data/sub/f.wav")
+            ]
+        );
+    }
 
     #[test]
     fn resource_graph_node_new() {
@@ -21,6 +21,8 @@
 //! Provides an interface for IO operations that a resource loader will use, this facilliates
 //! things such as loading assets within archive files
 
+use crate::pack::normalize_path;
+use fxhash::FxHashSet;
 use fyrox_core::io::FileLoadError;
 use std::future::{ready, Future};
 use std::iter::empty;
@@ -29,6 +31,7 @@ use std::{
     fmt::Debug,
     io::{Cursor, Read, Seek},
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 /// Trait for files readers ensuring they implement the required traits
@@ -228,3 +231,193 @@ impl ResourceIo for FsResourceIo {
         Box::pin(fyrox_core::io::is_dir(path))
     }
 }
+
+/// Resource IO that combines multiple other resource IOs into a single one. Layers are checked in
+/// order, the first layer that has a requested file wins. Directory listings are merged from every
+/// layer. It could be used, for example, to put loose files on the file system over a packed
+/// archive (see [`crate::pack::PackedResourceIo`]) so patches and mods could replace some of the
+/// packed assets without rebuilding the pack.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use fyrox_resource::{
+///     io::{FsResourceIo, OverlayResourceIo},
+///     pack::PackedResourceIo,
+/// };
+/// use std::sync::Arc;
+///
+/// let io = OverlayResourceIo::new(vec![
+///     Arc::new(FsResourceIo),
+///     Arc::new(PackedResourceIo::open("data.pak").unwrap()),
+/// ]);
+/// ```
+#[derive(Default, Clone)]
+pub struct OverlayResourceIo {
+    layers: Vec<Arc<dyn ResourceIo>>,
+}
+
+impl OverlayResourceIo {
+    /// Creates new overlay from the given layers. The first layer has the highest priority.
+    pub fn new(layers: Vec<Arc<dyn ResourceIo>>) -> Self {
+        Self { layers }
+    }
+
+    /// Adds a new layer with the lowest priority.
+    pub fn push_layer(&mut self, layer: Arc<dyn ResourceIo>) {
+        self.layers.push(layer);
+    }
+
+    /// Returns a slice with all the layers of the overlay.
+    pub fn layers(&self) -> &[Arc<dyn ResourceIo>] {
+        &self.layers
+    }
+
+    async fn find_layer(&self, path: &Path) -> Option<&dyn ResourceIo> {
+        for layer in self.layers.iter() {
+            if layer.exists(path).await {
+                return Some(&**layer);
+            }
+        }
+        None
+    }
+
+    async fn merge_paths<'a, F>(
+        &'a self,
+        path: &'a Path,
+        func: F,
+    ) -> Result<PathIter, FileLoadError>
+    where
+        F: Fn(&'a dyn ResourceIo) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>>,
+    {
+        let mut paths = Vec::new();
+        let mut unique_paths = FxHashSet::default();
+        let mut last_error = None;
+        let mut any_ok = false;
+        for layer in self.layers.iter() {
+            if !layer.is_dir(path).await {
+                continue;
+            }
+            match func(&**layer).await {
+                Ok(iter) => {
+                    any_ok = true;
+                    for path in iter {
+                        if unique_paths.insert(normalize_path(&path)) {
+                            paths.push(path);
+                        }
+                    }
+                }
+                Err(err) => last_error = Some(err),
+            }
+        }
+        match last_error {
+            Some(err) if !any_ok => Err(err),
+            _ => {
+                let iter: PathIter = Box::new(paths.into_iter());
+                Ok(iter)
+            }
+        }
+    }
+
+    fn not_found(path: &Path) -> FileLoadError {
+        FileLoadError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} does not exist in any layer.", path.display()),
+        ))
+    }
+}
+
+impl ResourceIo for OverlayResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            match self.find_layer(path).await {
+                Some(layer) => layer.load_file(path).await,
+                None => Err(Self::not_found(path)),
+            }
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(async move {
+            match self.find_layer(source).await {
+                Some(layer) => layer.move_file(source, dest).await,
+                None => Err(Self::not_found(source)),
+            }
+        })
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        Box::pin(async move {
+            match self
+                .find_layer(path)
+                .await
+                .or(self.layers.first().map(|l| &**l))
+            {
+                Some(layer) => layer.canonicalize_path(path).await,
+                None => Ok(path.to_owned()),
+            }
+        })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(self.merge_paths(path, move |layer| layer.read_directory(path)))
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(self.merge_paths(path, move |layer| layer.walk_directory(path)))
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        Box::pin(async move {
+            match self.find_layer(path).await {
+                Some(layer) => layer.file_reader(path).await,
+                None => Err(Self::not_found(path)),
+            }
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move { self.find_layer(path).await.is_some() })
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            for layer in self.layers.iter() {
+                if layer.is_file(path).await {
+                    return true;
+                }
+            }
+            false
+        })
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            for layer in self.layers.iter() {
+                if layer.is_dir(path).await {
+                    return true;
+                }
+            }
+            false
+        })
+    }
+}
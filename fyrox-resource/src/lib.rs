@@ -55,6 +55,7 @@ pub use fyrox_core as core;
 use fyrox_core::log::Log;
 use fyrox_core::{combine_uuids, Downcast};
 
+pub mod cache;
 pub mod constructor;
 pub mod entry;
 pub mod event;
@@ -62,7 +63,10 @@ pub mod graph;
 pub mod io;
 pub mod loader;
 pub mod manager;
+pub mod memory;
 pub mod options;
+pub mod queue;
+pub mod pack;
 pub mod state;
 pub mod untyped;
 
@@ -94,6 +98,14 @@ pub trait ResourceData: Downcast + Debug + Visit + Send + Reflect {
     /// resource type supports saving, for example there might be temporary resource type that is
     /// used only at runtime which does not need saving at all.
     fn can_be_saved(&self) -> bool;
+
+    /// Returns an estimate of the amount of memory (in bytes) that the resource data occupies. It
+    /// is used by the resource manager to enforce memory budgets (see
+    /// [`manager::ResourceManagerState::set_memory_budget`]). Default implementation returns zero,
+    /// which means that resources of the type are never evicted because of a memory budget.
+    fn memory_usage(&self) -> usize {
+        0
+    }
 }
 
 /// Extension trait for a resource data of a particular type, which adds additional functionality,
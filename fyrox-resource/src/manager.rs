@@ -22,6 +22,7 @@
 //! docs for more info.
 
 use crate::{
+    cache::ImportCache,
     collect_used_resources,
     constructor::ResourceConstructorContainer,
     core::{
@@ -32,6 +33,7 @@ use crate::{
         make_relative_path, notify,
         parking_lot::{Mutex, MutexGuard},
         task::TaskPool,
+        uuid::Uuid,
         watcher::FileSystemWatcher,
         TypeUuidProvider,
     },
@@ -39,7 +41,9 @@ use crate::{
     event::{ResourceEvent, ResourceEventBroadcaster},
     io::{FsResourceIo, ResourceIo},
     loader::{ResourceLoader, ResourceLoadersContainer},
+    memory::{ResourceMemoryStatistics, ResourceTypeMemoryUsage},
     options::OPTIONS_EXTENSION,
+    queue::{LoadCommit, LoadQueue, ResourceLoadPriority},
     state::{LoadError, ResourceState},
     untyped::ResourceKind,
     Resource, ResourceData, TypedResourceData, UntypedResource,
@@ -52,7 +56,10 @@ use std::{
     fmt::{Debug, Display, Formatter},
     marker::PhantomData,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
 };
 
 /// A set of resources that can be waited for.
@@ -211,10 +218,19 @@ pub struct ResourceManagerState {
     pub built_in_resources: BuiltInResourcesContainer,
     /// File system abstraction interface. Could be used to support virtual file systems.
     pub resource_io: Arc<dyn ResourceIo>,
+    /// On-disk cache for the output of resource importers. It is disabled by default, see
+    /// [`ImportCache`] docs for more info.
+    pub import_cache: ImportCache,
 
     resources: Vec<TimedEntry<UntypedResource>>,
     task_pool: Arc<TaskPool>,
     watcher: Option<FileSystemWatcher>,
+    memory_budgets: FxHashMap<Uuid, usize>,
+    memory_statistics: ResourceMemoryStatistics,
+    // Set when the set of resources or their data has changed, so the memory usage must be
+    // recalculated. Shared with loading tasks.
+    memory_usage_changed: Arc<AtomicBool>,
+    load_queue: LoadQueue,
 }
 
 /// Resource manager controls loading and lifetime of resource in the engine. Resource manager can hold
@@ -288,6 +304,22 @@ impl ResourceManager {
         state.resource_io.clone()
     }
 
+    /// Sets memory budget (in bytes) for resources of the given type. See
+    /// [`ResourceManagerState::set_memory_budget`] for more info.
+    pub fn set_memory_budget<T>(&self, budget: Option<usize>)
+    where
+        T: TypedResourceData,
+    {
+        self.state()
+            .set_memory_budget(<T as TypeUuidProvider>::type_uuid(), budget)
+    }
+
+    /// Returns memory statistics of the resource manager. See [`ResourceMemoryStatistics`] for
+    /// more info.
+    pub fn memory_statistics(&self) -> ResourceMemoryStatistics {
+        self.state().memory_statistics().clone()
+    }
+
     /// Returns the task pool used by this resource manager.
     pub fn task_pool(&self) -> Arc<TaskPool> {
         let state = self.state();
@@ -368,6 +400,88 @@ impl ResourceManager {
         self.state().request(path)
     }
 
+    /// Same as [`Self::request`], but allows you to specify loading priority of the resource. See
+    /// [`ResourceLoadPriority`] docs for more info.
+    ///
+    /// ## Panic
+    ///
+    /// This method will panic, if type UUID of `T` does not match the actual type UUID of the resource.
+    pub fn request_with_priority<T>(
+        &self,
+        path: impl AsRef<Path>,
+        priority: ResourceLoadPriority,
+    ) -> Resource<T>
+    where
+        T: TypedResourceData,
+    {
+        let untyped = self.state().request_with_priority(path, priority);
+        let actual_type_uuid = untyped.type_uuid();
+        assert_eq!(actual_type_uuid, <T as TypeUuidProvider>::type_uuid());
+        Resource {
+            untyped,
+            phantom: PhantomData::<T>,
+        }
+    }
+
+    /// Same as [`Self::request_with_priority`], but returns untyped resource.
+    pub fn request_untyped_with_priority<P>(
+        &self,
+        path: P,
+        priority: ResourceLoadPriority,
+    ) -> UntypedResource
+    where
+        P: AsRef<Path>,
+    {
+        self.state().request_with_priority(path, priority)
+    }
+
+    /// Same as [`Self::request_with_priority`], but the loading will be cancelled if every
+    /// handle of the resource is dropped before the loading has started. This is useful for
+    /// requests that could become stale quickly (for example, streaming of a level). See
+    /// [`ResourceManagerState::request_cancellable`] for more info.
+    ///
+    /// ## Panic
+    ///
+    /// This method will panic, if type UUID of `T` does not match the actual type UUID of the resource.
+    pub fn request_cancellable<T>(
+        &self,
+        path: impl AsRef<Path>,
+        priority: ResourceLoadPriority,
+    ) -> Resource<T>
+    where
+        T: TypedResourceData,
+    {
+        let untyped = self.state().request_cancellable(path, priority);
+        let actual_type_uuid = untyped.type_uuid();
+        assert_eq!(actual_type_uuid, <T as TypeUuidProvider>::type_uuid());
+        Resource {
+            untyped,
+            phantom: PhantomData::<T>,
+        }
+    }
+
+    /// Same as [`Self::request_cancellable`], but returns untyped resource.
+    pub fn request_untyped_cancellable<P>(
+        &self,
+        path: P,
+        priority: ResourceLoadPriority,
+    ) -> UntypedResource
+    where
+        P: AsRef<Path>,
+    {
+        self.state().request_cancellable(path, priority)
+    }
+
+    /// Sets the maximum amount of resources of the given type that could be loaded at the same
+    /// time. See [`ResourceManagerState::set_concurrency_limit`] for more info.
+    pub fn set_concurrency_limit<T>(&self, limit: Option<usize>)
+    where
+        T: TypedResourceData,
+    {
+        self.state()
+            .set_concurrency_limit(<T as TypeUuidProvider>::type_uuid(), limit)
+    }
+
     /// Saves given resources in the specified path and registers it in resource manager, so
     /// it will be accessible through it later.
     pub fn register<P, F>(
@@ -530,6 +644,11 @@ impl ResourceManagerState {
             built_in_resources: Default::default(),
             // Use the file system resource io by default
             resource_io: Arc::new(FsResourceIo),
+            import_cache: Default::default(),
+            memory_budgets: Default::default(),
+            memory_statistics: Default::default(),
+            memory_usage_changed: Default::default(),
+            load_queue: Default::default(),
         }
     }
 
@@ -552,6 +671,242 @@ impl ResourceManagerState {
         self.watcher = watcher;
     }
 
+    /// Sets memory budget (in bytes) for resources of the given type. When the estimated memory
+    /// usage of resources of the type exceeds the budget, the manager will unload least recently
+    /// used resources of the type, that are not used anywhere else (i.e. held only by the manager),
+    /// without waiting until their time-to-live runs out. Keep in mind, that resources that are
+    /// still in use are never unloaded, so the actual memory usage could still exceed the budget.
+    /// `None` removes the budget. See [`ResourceData::memory_usage`] for more info about memory
+    /// usage estimation.
+    pub fn set_memory_budget(&mut self, type_uuid: Uuid, budget: Option<usize>) {
+        match budget {
+            Some(budget) => {
+                self.memory_budgets.insert(type_uuid, budget);
+            }
+            None => {
+                self.memory_budgets.remove(&type_uuid);
+            }
+        }
+        self.mark_memory_usage_changed();
+    }
+
+    /// Returns memory budget (in bytes) for resources of the given type, if any.
+    pub fn memory_budget(&self, type_uuid: Uuid) -> Option<usize> {
+        self.memory_budgets.get(&type_uuid).cloned()
+    }
+
+    /// Returns memory statistics calculated during the last enforcement of memory budgets. The
+    /// statistics is updated automatically in [`Self::update`] whether there are memory budgets
+    /// or not.
+    pub fn memory_statistics(&self) -> &ResourceMemoryStatistics {
+        &self.memory_statistics
+    }
+
+    fn mark_memory_usage_changed(&self) {
+        self.memory_usage_changed
+            .store(true, atomic::Ordering::Relaxed);
+    }
+
+    /// Evicts least recently used resources of every type whose memory usage exceeds its budget
+    /// (see [`Self::set_memory_budget`]) and updates memory statistics. Returns the amount of
+    /// evicted resources. This method is called automatically in [`Self::update`], but only if
+    /// a resource was added, loaded, removed or became unused since the last call. Nothing is
+    /// evicted if there are no memory budgets, only the statistics is updated.
+    pub fn enforce_memory_budgets(&mut self) -> usize {
+        self.memory_usage_changed
+            .store(false, atomic::Ordering::Relaxed);
+
+        let mut types = FxHashMap::<Uuid, ResourceTypeMemoryUsage>::default();
+        for (&type_uuid, &budget) in self.memory_budgets.iter() {
+            types.insert(
+                type_uuid,
+                ResourceTypeMemoryUsage {
+                    type_uuid,
+                    budget: Some(budget),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let mut entries = Vec::with_capacity(self.resources.len());
+        for entry in self.resources.iter() {
+            let header = entry.0.lock();
+            let memory_usage = match header.state {
+                ResourceState::Ok(ref data) => data.memory_usage(),
+                _ => 0,
+            };
+            let usage = types
+                .entry(header.type_uuid)
+                .or_insert_with(|| ResourceTypeMemoryUsage {
+                    type_uuid: header.type_uuid,
+                    ..Default::default()
+                });
+            usage.count += 1;
+            usage.memory_usage += memory_usage;
+            entries.push((header.type_uuid, memory_usage));
+        }
+
+        let mut evict = vec![false; entries.len()];
+        let mut evicted = 0;
+        for usage in types.values_mut() {
+            if !usage.is_over_budget() {
+                continue;
+            }
+
+            // Only resources that are held by the manager alone could be evicted. Time-to-live of
+            // such resources is decreasing since the moment they were used the last time, so the
+            // least recently used resources have the lowest time-to-live.
+            let mut candidates = entries
+                .iter()
+                .enumerate()
+                .filter(|(i, (type_uuid, memory_usage))| {
+                    *type_uuid == usage.type_uuid
+                        && *memory_usage > 0
+                        && self.resources[*i].value.use_count() <= 1
+                })
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+            candidates.sort_by(|a, b| {
+                self.resources[*a]
+                    .time_to_live
+                    .total_cmp(&self.resources[*b].time_to_live)
+            });
+
+            for i in candidates {
+                if !usage.is_over_budget() {
+                    break;
+                }
+                evict[i] = true;
+                usage.count -= 1;
+                usage.memory_usage -= entries[i].1;
+                evicted += 1;
+            }
+        }
+
+        if evicted > 0 {
+            let mut evict = evict.into_iter();
+            let event_broadcaster = &self.event_broadcaster;
+            self.resources.retain(|resource| {
+                if !evict.next().unwrap_or_default() {
+                    return true;
+                }
+
+                if let Some(path) = resource.0.lock().kind.path_owned() {
+                    Log::info(format!(
+                        "Resource {} evicted because its type exceeds memory budget!",
+                        path.display()
+                    ));
+
+                    event_broadcaster.broadcast(ResourceEvent::Removed(path));
+                }
+
+                false
+            });
+        }
+
+        let mut types = types.into_values().collect::<Vec<_>>();
+        types.sort_by_key(|usage| usage.type_uuid);
+        self.memory_statistics = ResourceMemoryStatistics {
+            total_memory_usage: types.iter().map(|usage| usage.memory_usage).sum(),
+            types,
+            evicted,
+            total_evicted: self.memory_statistics.total_evicted + evicted,
+        };
+
+        evicted
+    }
+
+    /// Sets the maximum amount of resources of the given type that could be loaded at the same
+    /// time, `None` removes the limit. Requests that exceed the limit are put in a queue and
+    /// started in the order of their priority (see [`ResourceLoadPriority`]) once other loading
+    /// tasks are finished. By default, there is no limit and every request starts immediately.
+    ///
+    /// ## Important
+    ///
+    /// Resource loaders that wait for other resources of the same type (for example, a model
+    /// that waits for other models) could get stuck, if every loading slot is occupied by such
+    /// waiting loaders. Do not set limits for such resource types.
+    pub fn set_concurrency_limit(&mut self, type_uuid: Uuid, limit: Option<usize>) {
+        self.load_queue.set_limit(type_uuid, limit);
+        self.load_queue.dispatch(&self.task_pool);
+    }
+
+    /// Returns the maximum amount of resources of the given type that could be loaded at the same
+    /// time.
+    pub fn concurrency_limit(&self, type_uuid: Uuid) -> Option<usize> {
+        self.load_queue.limit(type_uuid)
+    }
+
+    /// Sets the maximum amount of resources (of any type) that could be loaded at the same time,
+    /// `None` removes the limit. See [`Self::set_concurrency_limit`] for more info.
+    pub fn set_total_concurrency_limit(&mut self, limit: Option<usize>) {
+        self.load_queue.set_total_limit(limit);
+        self.load_queue.dispatch(&self.task_pool);
+    }
+
+    /// Returns the maximum amount of resources (of any type) that could be loaded at the same time.
+    pub fn total_concurrency_limit(&self) -> Option<usize> {
+        self.load_queue.total_limit()
+    }
+
+    /// Returns the amount of resources that are waiting in the loading queue. This amount could
+    /// be non-zero only if there are concurrency limits.
+    pub fn count_queued_resources(&self) -> usize {
+        self.load_queue.queued_count()
+    }
+
+    /// Returns the amount of resources that are loading right now.
+    pub fn count_loading_resources(&self) -> usize {
+        self.load_queue.running_count()
+    }
+
+    // Loading of a cancellable resource is cancelled when no one except the manager holds the
+    // resource. Such resources are removed from the manager, so the next request of the resource
+    // will start loading from scratch. Resources that were picked up again in the meantime are
+    // restarted with the priority of the cancelled task.
+    fn handle_cancelled_loading(&mut self) {
+        let cancelled = self.load_queue.drain_cancelled();
+        if cancelled.is_empty() {
+            return;
+        }
+
+        let mut restart = Vec::new();
+        let memory_usage_changed = &self.memory_usage_changed;
+        self.resources.retain(|resource| {
+            if !resource
+                .kind()
+                .path()
+                .is_some_and(|path| cancelled.contains_key(path))
+            {
+                return true;
+            }
+
+            // Both branches below change the set of resources whose memory is counted.
+            memory_usage_changed.store(true, atomic::Ordering::Relaxed);
+
+            if resource.value.use_count() <= 1 {
+                if let Some(path) = resource.0.lock().kind.path() {
+                    Log::info(format!(
+                        "Loading of {} was cancelled, because the resource is not used anymore!",
+                        path.display()
+                    ));
+                }
+                false
+            } else {
+                // Someone has picked up the resource in the meantime.
+                if let Some(priority) = resource.kind().path().and_then(|path| cancelled.get(path))
+                {
+                    restart.push((resource.value.clone(), *priority));
+                }
+                true
+            }
+        });
+
+        for (resource, priority) in restart {
+            self.restart_loading(&resource, priority, true);
+        }
+    }
+
     /// Returns total amount of registered resources.
     pub fn count_registered_resources(&self) -> usize {
         self.resources.len()
@@ -578,11 +933,20 @@ impl ResourceManagerState {
     /// Normally, this is called from `Engine::update()`.
     /// You should only call this manually if you don't use that method.
     pub fn update(&mut self, dt: f32) {
+        self.handle_cancelled_loading();
+
+        let memory_usage_changed = &self.memory_usage_changed;
         self.resources.retain_mut(|resource| {
             // One usage means that the resource has single owner, and that owner
             // is this container. Such resources have limited life time, if the time
             // runs out before it gets shared again, the resource will be deleted.
             if resource.value.use_count() <= 1 {
+                if resource.time_to_live == DEFAULT_RESOURCE_LIFETIME {
+                    // The resource has just became unused, which makes it a candidate for
+                    // eviction.
+                    memory_usage_changed.store(true, atomic::Ordering::Relaxed);
+                }
+
                 resource.time_to_live -= dt;
                 if resource.time_to_live <= 0.0 {
                     if let Some(path) = resource.0.lock().kind.path_owned() {
@@ -595,6 +959,8 @@ impl ResourceManagerState {
                             .broadcast(ResourceEvent::Removed(path));
                     }
 
+                    memory_usage_changed.store(true, atomic::Ordering::Relaxed);
+
                     false
                 } else {
                     // Keep resource alive for short period of time.
@@ -609,6 +975,10 @@ impl ResourceManagerState {
             }
         });
 
+        if self.memory_usage_changed.load(atomic::Ordering::Relaxed) {
+            self.enforce_memory_budgets();
+        }
+
         if let Some(watcher) = self.watcher.as_ref() {
             if let Some(evt) = watcher.try_get_event() {
                 if let notify::EventKind::Modify(_) = evt.kind {
@@ -638,6 +1008,8 @@ impl ResourceManagerState {
             value: resource,
             time_to_live: DEFAULT_RESOURCE_LIFETIME,
         });
+
+        self.mark_memory_usage_changed();
     }
 
     /// Tries to find a resources by its path. Returns None if no resource was found.
@@ -675,6 +1047,7 @@ impl ResourceManagerState {
     pub fn destroy_unused_resources(&mut self) {
         self.resources
             .retain(|resource| resource.value.use_count() > 1);
+        self.mark_memory_usage_changed();
     }
 
     /// Returns total amount of resources that still loading.
@@ -709,19 +1082,78 @@ impl ResourceManagerState {
     where
         P: AsRef<Path>,
     {
-        if let Some(built_in_resource) = self.built_in_resources.get(path.as_ref()) {
+        self.request_with_priority(path, ResourceLoadPriority::Normal)
+    }
+
+    /// Tries to load a resources at a given path with the given priority. If the resource is
+    /// already in the loading queue with lower priority, its priority will be raised.
+    pub fn request_with_priority<P>(
+        &mut self,
+        path: P,
+        priority: ResourceLoadPriority,
+    ) -> UntypedResource
+    where
+        P: AsRef<Path>,
+    {
+        self.request_internal(path.as_ref(), priority, false)
+    }
+
+    /// Same as [`Self::request_with_priority`], but the loading will be cancelled if no one except
+    /// the manager holds the resource by the time the loading should start (for example, when the
+    /// loading was delayed because of concurrency limits) or by the time the loaded data should be
+    /// stored in the resource, in the latter case the loaded data is discarded. Cancelled
+    /// resources are removed from the manager on the next update, so the next request will start
+    /// loading from scratch. If the resource is already queued as non-cancellable, it stays
+    /// non-cancellable. Likewise, a non-cancellable request of a queued cancellable resource makes
+    /// it non-cancellable.
+    pub fn request_cancellable<P>(
+        &mut self,
+        path: P,
+        priority: ResourceLoadPriority,
+    ) -> UntypedResource
+    where
+        P: AsRef<Path>,
+    {
+        self.request_internal(path.as_ref(), priority, true)
+    }
+
+    fn request_internal(
+        &mut self,
+        path: &Path,
+        priority: ResourceLoadPriority,
+        cancellable: bool,
+    ) -> UntypedResource {
+        if let Some(built_in_resource) = self.built_in_resources.get(path) {
             return built_in_resource.resource.clone();
         }
 
-        match self.find(path.as_ref()) {
-            Some(existing) => existing.clone(),
+        match self.find(path) {
+            Some(existing) => {
+                let existing = existing.clone();
+                if let Some(path) = existing.kind().into_path() {
+                    if self.load_queue.take_cancelled(&path) {
+                        // Loading was cancelled, because no one needed the resource, restart it.
+                        self.restart_loading(&existing, priority, cancellable);
+                    } else {
+                        self.load_queue.raise_priority(&path, priority, cancellable);
+                    }
+                }
+                existing
+            }
             None => {
-                let path = path.as_ref().to_owned();
+                let path = path.to_owned();
                 let kind = ResourceKind::External(path.clone());
 
-                if let Some(loader) = self.find_loader(path.as_ref()) {
+                if let Some(loader) = self.find_loader(&path) {
                     let resource = UntypedResource::new_pending(kind, loader.data_type_uuid());
-                    self.spawn_loading_task(path, resource.clone(), loader, false);
+                    self.spawn_loading_task(
+                        path,
+                        resource.clone(),
+                        loader,
+                        false,
+                        priority,
+                        cancellable,
+                    );
                     self.push(resource.clone());
                     resource
                 } else {
@@ -741,46 +1173,85 @@ impl ResourceManagerState {
         })
     }
 
+    fn restart_loading(
+        &self,
+        resource: &UntypedResource,
+        priority: ResourceLoadPriority,
+        cancellable: bool,
+    ) {
+        let Some(path) = resource.kind().into_path() else {
+            return;
+        };
+        if let Some(loader) = self.find_loader(&path) {
+            self.spawn_loading_task(path, resource.clone(), loader, false, priority, cancellable);
+        }
+    }
+
     fn spawn_loading_task(
         &self,
         path: PathBuf,
         resource: UntypedResource,
         loader: &dyn ResourceLoader,
         reload: bool,
+        priority: ResourceLoadPriority,
+        cancellable: bool,
     ) {
+        let task_path = path.clone();
         let event_broadcaster = self.event_broadcaster.clone();
+        let memory_usage_changed = self.memory_usage_changed.clone();
         let loader_future = loader.load(path.clone(), self.resource_io.clone());
-        self.task_pool.spawn_task(async move {
-            match loader_future.await {
-                Ok(data) => {
-                    let data = data.0;
+        let weak_resource = Arc::downgrade(&resource.0);
+        let future = Box::pin(async move {
+            let result = loader_future.await;
 
-                    Log::info(format!(
-                        "Resource {} was loaded successfully!",
-                        path.display()
-                    ));
+            Box::new(move || {
+                // The resource could be destroyed while it was loading.
+                let Some(resource) = weak_resource.upgrade().map(UntypedResource) else {
+                    return;
+                };
 
-                    // Separate scope to keep mutex locking time at minimum.
-                    {
-                        let mut mutex_guard = resource.0.lock();
-                        assert_eq!(mutex_guard.type_uuid, data.type_uuid());
-                        assert!(mutex_guard.kind.is_external());
-                        mutex_guard.state.commit(ResourceState::Ok(data));
-                    }
+                match result {
+                    Ok(data) => {
+                        let data = data.0;
 
-                    event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
-                }
-                Err(error) => {
-                    Log::info(format!(
-                        "Resource {} failed to load. Reason: {:?}",
-                        path.display(),
-                        error
-                    ));
+                        Log::info(format!(
+                            "Resource {} was loaded successfully!",
+                            path.display()
+                        ));
 
-                    resource.commit_error(error);
+                        // Separate scope to keep mutex locking time at minimum.
+                        {
+                            let mut mutex_guard = resource.0.lock();
+                            assert_eq!(mutex_guard.type_uuid, data.type_uuid());
+                            assert!(mutex_guard.kind.is_external());
+                            mutex_guard.state.commit(ResourceState::Ok(data));
+                        }
+
+                        memory_usage_changed.store(true, atomic::Ordering::Relaxed);
+
+                        event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
+                    }
+                    Err(error) => {
+                        Log::info(format!(
+                            "Resource {} failed to load. Reason: {:?}",
+                            path.display(),
+                            error
+                        ));
+
+                        resource.commit_error(error);
+                    }
                 }
-            }
+            }) as LoadCommit
         });
+        self.load_queue.push(
+            &self.task_pool,
+            &resource.0,
+            task_path,
+            loader.data_type_uuid(),
+            priority,
+            cancellable,
+            future,
+        );
     }
 
     /// Reloads a single resource.
@@ -793,7 +1264,14 @@ impl ResourceManagerState {
                     header.state.switch_to_pending_state();
                     drop(header);
 
-                    self.spawn_loading_task(path, resource, loader, true);
+                    self.spawn_loading_task(
+                        path,
+                        resource,
+                        loader,
+                        true,
+                        ResourceLoadPriority::Normal,
+                        false,
+                    );
                 } else {
                     let msg = format!(
                         "There's no resource loader for {} resource!",
@@ -854,6 +1332,7 @@ impl ResourceManagerState {
             .position(|r| r.kind().path() == Some(path))
         {
             self.resources.remove(position);
+            self.mark_memory_usage_changed();
         }
     }
 }
@@ -911,6 +1390,35 @@ mod test {
         }
     }
 
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct Heavy {
+        size: usize,
+    }
+
+    impl TypeUuidProvider for Heavy {
+        fn type_uuid() -> Uuid {
+            uuid!("0b3c8b3e-52a4-4a54-a6f2-2b8a2e0b5a8d")
+        }
+    }
+
+    impl ResourceData for Heavy {
+        fn type_uuid(&self) -> Uuid {
+            <Self as TypeUuidProvider>::type_uuid()
+        }
+
+        fn save(&mut self, _path: &Path) -> Result<(), Box<dyn Error>> {
+            Err("Saving is not supported!".to_string().into())
+        }
+
+        fn can_be_saved(&self) -> bool {
+            false
+        }
+
+        fn memory_usage(&self) -> usize {
+            self.size
+        }
+    }
+
     fn new_resource_manager() -> ResourceManagerState {
         ResourceManagerState::new(Arc::new(Default::default()))
     }
@@ -1180,4 +1688,100 @@ mod test {
             "UnableToRegister"
         );
     }
+
+    #[test]
+    fn resource_manager_state_memory_budget() {
+        let mut state = new_resource_manager();
+        let type_uuid = <Heavy as TypeUuidProvider>::type_uuid();
+
+        state.set_memory_budget(type_uuid, Some(250));
+        assert_eq!(state.memory_budget(type_uuid), Some(250));
+
+        for path in ["old", "recent"] {
+            state.push(UntypedResource::new_ok(
+                PathBuf::from(path).into(),
+                Heavy { size: 100 },
+            ));
+        }
+        let used = UntypedResource::new_ok(PathBuf::from("used").into(), Heavy { size: 100 });
+        state.push(used.clone());
+        state.push(UntypedResource::new_ok(
+            PathBuf::from("stub").into(),
+            Stub::default(),
+        ));
+
+        // The first resource was used long time ago.
+        state.resources[0].time_to_live = 10.0;
+        state.resources[1].time_to_live = 50.0;
+
+        assert_eq!(state.enforce_memory_budgets(), 1);
+        assert!(state.find("old").is_none());
+        assert!(state.find("recent").is_some());
+
+        let statistics = state.memory_statistics();
+        let usage = statistics.type_usage(type_uuid).unwrap();
+        assert_eq!(usage.count, 2);
+        assert_eq!(usage.memory_usage, 200);
+        assert!(!usage.is_over_budget());
+        assert_eq!(statistics.total_memory_usage, 200);
+        assert_eq!(statistics.evicted, 1);
+
+        // Resources that are still in use must never be evicted.
+        state.set_memory_budget(type_uuid, Some(50));
+        assert_eq!(state.enforce_memory_budgets(), 1);
+        assert!(state.find("used").is_some());
+        assert!(state.find("stub").is_some());
+
+        let statistics = state.memory_statistics();
+        assert!(statistics.type_usage(type_uuid).unwrap().is_over_budget());
+        assert_eq!(statistics.total_evicted, 2);
+
+        state.set_memory_budget(type_uuid, None);
+        assert_eq!(state.memory_budget(type_uuid), None);
+        assert_eq!(state.enforce_memory_budgets(), 0);
+        drop(used);
+    }
+
+    #[test]
+    fn resource_manager_state_memory_budget_update() {
+        let mut state = new_resource_manager();
+        let type_uuid = <Heavy as TypeUuidProvider>::type_uuid();
+
+        let first = UntypedResource::new_ok(PathBuf::from("first").into(), Heavy { size: 100 });
+        let second = UntypedResource::new_ok(PathBuf::from("second").into(), Heavy { size: 100 });
+        state.push(first.clone());
+        state.push(second.clone());
+
+        // No budgets - nothing is evicted, but the statistics is still calculated.
+        state.update(0.0);
+        assert_eq!(state.memory_statistics().total_memory_usage, 200);
+        assert_eq!(state.memory_statistics().evicted, 0);
+        assert_eq!(
+            state
+                .memory_statistics()
+                .type_usage(type_uuid)
+                .unwrap()
+                .count,
+            2
+        );
+
+        state.set_memory_budget(type_uuid, Some(150));
+        state.update(0.0);
+        let usage = state.memory_statistics().type_usage(type_uuid).unwrap();
+        assert_eq!(usage.memory_usage, 200);
+        assert!(usage.is_over_budget());
+
+        // Nothing has changed, the statistics must stay the same.
+        state.update(0.0);
+        assert_eq!(state.memory_statistics().evicted, 0);
+
+        // The resource became unused and must be evicted on the next update.
+        drop(first);
+        state.update(0.0);
+        assert!(state.find("first").is_none());
+        assert!(state.find("second").is_some());
+        assert_eq!(state.memory_statistics().total_evicted, 1);
+        assert_eq!(state.memory_statistics().total_memory_usage, 100);
+        drop(second);
+    }
 }
@@ -0,0 +1,94 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Resource memory statistics. See [`ResourceMemoryStatistics`] docs for more info.
+
+use crate::core::uuid::Uuid;
+use std::fmt::{Display, Formatter};
+
+/// Memory usage of resources of a single type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourceTypeMemoryUsage {
+    /// Type UUID of the resources.
+    pub type_uuid: Uuid,
+    /// Amount of registered resources of the type.
+    pub count: usize,
+    /// Estimated amount of memory (in bytes) occupied by the resources of the type. See
+    /// [`crate::ResourceData::memory_usage`] for more info.
+    pub memory_usage: usize,
+    /// Memory budget (in bytes) of the type, if any.
+    pub budget: Option<usize>,
+}
+
+impl ResourceTypeMemoryUsage {
+    /// Returns `true` if the memory usage exceeds the budget. It is possible even after eviction,
+    /// because only resources that are not used anywhere could be evicted.
+    pub fn is_over_budget(&self) -> bool {
+        self.budget.is_some_and(|budget| self.memory_usage > budget)
+    }
+}
+
+/// Memory statistics of the resource manager. It is updated on every call of
+/// [`crate::manager::ResourceManagerState::update`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourceMemoryStatistics {
+    /// Memory usage of every resource type that has registered resources or a memory budget,
+    /// sorted by type UUID.
+    pub types: Vec<ResourceTypeMemoryUsage>,
+    /// Estimated amount of memory (in bytes) occupied by all resources.
+    pub total_memory_usage: usize,
+    /// Amount of resources that were evicted during the last update because of memory budgets.
+    pub evicted: usize,
+    /// Total amount of resources that were evicted because of memory budgets.
+    pub total_evicted: usize,
+}
+
+impl ResourceMemoryStatistics {
+    /// Returns memory usage of resources of the given type, if any.
+    pub fn type_usage(&self, type_uuid: Uuid) -> Option<&ResourceTypeMemoryUsage> {
+        self.types.iter().find(|usage| usage.type_uuid == type_uuid)
+    }
+}
+
+impl Display for ResourceMemoryStatistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Resources: {} KiB (evicted {}, total {})",
+            self.total_memory_usage / 1024,
+            self.evicted,
+            self.total_evicted
+        )?;
+        for usage in self.types.iter() {
+            write!(
+                f,
+                "\t{}: {} resources, {} KiB",
+                usage.type_uuid,
+                usage.count,
+                usage.memory_usage / 1024
+            )?;
+            if let Some(budget) = usage.budget {
+                write!(f, " of {} KiB", budget / 1024)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
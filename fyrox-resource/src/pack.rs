@@ -0,0 +1,759 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Packed asset archives. See [`PackedResourceIo`] docs for more info.
+
+use crate::{
+    core::{
+        byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt},
+        io::FileLoadError,
+        parking_lot::Mutex,
+    },
+    io::{FileReader, PathIter, ResourceIo, ResourceIoFuture},
+};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use fxhash::{FxHashMap, FxHashSet};
+use std::{
+    fmt::{Debug, Formatter},
+    future::ready,
+    io::{self, Cursor, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+/// Magic number at the beginning of every pack file.
+pub const PACK_MAGIC: [u8; 4] = *b"FPAK";
+
+/// Current version of the pack file format.
+pub const PACK_VERSION: u32 = 1;
+
+/// Compression method of a single entry in a pack file.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum PackCompression {
+    /// The entry is stored as is.
+    None = 0,
+    /// The entry is compressed using Deflate algorithm.
+    #[default]
+    Deflate = 1,
+}
+
+impl PackCompression {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::Deflate),
+            _ => None,
+        }
+    }
+}
+
+/// Location and size of a single file stored in a pack file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PackEntry {
+    /// Offset (in bytes) of the entry data from the beginning of the pack file.
+    pub offset: u64,
+    /// Size (in bytes) of the entry data in the pack file.
+    pub packed_size: u64,
+    /// Size (in bytes) of the entry data after decompression.
+    pub size: u64,
+    /// Compression method of the entry.
+    pub compression: PackCompression,
+}
+
+/// Converts a path to the form that is used as a key in pack files: all `.` components are
+/// removed, `..` components are resolved and `/` is used as a separator on every platform. For
+/// example, `./data/../data/textures\\foo.png` becomes `data/textures/foo.png`.
+pub fn normalize_path(path: &Path) -> String {
+    let mut components: Vec<String> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                if components.last().is_some_and(|last| last != "..") {
+                    components.pop();
+                } else {
+                    components.push("..".to_string());
+                }
+            }
+            Component::Prefix(prefix) => {
+                components.push(prefix.as_os_str().to_string_lossy().to_string())
+            }
+            Component::RootDir => components.push(String::new()),
+            Component::Normal(name) => {
+                for part in name.to_string_lossy().split('\\') {
+                    if !part.is_empty() {
+                        components.push(part.to_string());
+                    }
+                }
+            }
+        }
+    }
+    components.join("/")
+}
+
+/// Writes pack files. Files are written one after another and the index is written at the very
+/// end by [`PackWriter::finish`], so the writer does not need to keep file data in memory.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use fyrox_resource::pack::PackWriter;
+/// use std::{fs::File, io::BufWriter, path::Path};
+///
+/// let file = BufWriter::new(File::create("data.pak").unwrap());
+/// let mut writer = PackWriter::new(file).unwrap();
+/// writer.add_directory(Path::new("data"), &|path| {
+///     path.extension().map_or(true, |ext| ext != "log")
+/// }).unwrap();
+/// writer.finish().unwrap();
+/// ```
+pub struct PackWriter<W: Write + Seek> {
+    dest: W,
+    compression: PackCompression,
+    entries: Vec<(String, PackEntry)>,
+    paths: FxHashSet<String>,
+}
+
+impl<W: Write + Seek> PackWriter<W> {
+    /// Creates new pack writer that writes its data to the given destination. Every file added
+    /// to the pack will be compressed using [`PackCompression::Deflate`].
+    pub fn new(mut dest: W) -> io::Result<Self> {
+        dest.write_all(&PACK_MAGIC)?;
+        dest.write_u32::<LittleEndian>(PACK_VERSION)?;
+        // Placeholder for the index offset, it will be overwritten in `finish`.
+        dest.write_u64::<LittleEndian>(0)?;
+        Ok(Self {
+            dest,
+            compression: PackCompression::default(),
+            entries: Default::default(),
+            paths: Default::default(),
+        })
+    }
+
+    /// Sets compression method for every file added after this call.
+    pub fn set_compression(&mut self, compression: PackCompression) {
+        self.compression = compression;
+    }
+
+    /// Returns current compression method.
+    pub fn compression(&self) -> PackCompression {
+        self.compression
+    }
+
+    /// Adds a file with the given path and content to the pack. The file is stored uncompressed
+    /// if compression does not make it any smaller. Returns an error if a file with the same
+    /// path was added before.
+    pub fn add_file(&mut self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let key = normalize_path(path);
+        if !self.paths.insert(key.clone()) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{key} is already in the pack."),
+            ));
+        }
+
+        let compressed = match self.compression {
+            PackCompression::None => None,
+            PackCompression::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data)?;
+                Some(encoder.finish()?).filter(|compressed| compressed.len() < data.len())
+            }
+        };
+
+        let offset = self.dest.stream_position()?;
+        let (compression, packed) = match compressed.as_deref() {
+            Some(compressed) => (self.compression, compressed),
+            None => (PackCompression::None, data),
+        };
+        self.dest.write_all(packed)?;
+
+        self.entries.push((
+            key,
+            PackEntry {
+                offset,
+                packed_size: packed.len() as u64,
+                size: data.len() as u64,
+                compression,
+            },
+        ));
+
+        Ok(())
+    }
+
+    /// Adds every file from the given directory (recursively) to the pack. Files are stored
+    /// with their paths as is, so if the directory is `./data` then a file `./data/foo.png` will
+    /// be accessible as `data/foo.png`. The filter is called for every file and directory; a file
+    /// is skipped if the filter returns `false` for it, a directory is skipped together with its
+    /// content. Returns the amount of added files.
+    pub fn add_directory(
+        &mut self,
+        dir: &Path,
+        filter: &dyn Fn(&Path) -> bool,
+    ) -> io::Result<usize> {
+        let mut count = 0;
+        let mut iter = walkdir::WalkDir::new(dir).sort_by_file_name().into_iter();
+        while let Some(entry) = iter.next() {
+            let entry = entry.map_err(io::Error::other)?;
+            if entry.depth() > 0 && !filter(entry.path()) {
+                if entry.file_type().is_dir() {
+                    iter.skip_current_dir();
+                }
+                continue;
+            }
+            if entry.file_type().is_file() {
+                let data = std::fs::read(entry.path())?;
+                self.add_file(entry.path(), &data)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Writes the index and returns the destination back.
+    pub fn finish(mut self) -> io::Result<W> {
+        let index_offset = self.dest.stream_position()?;
+
+        self.dest
+            .write_u32::<LittleEndian>(self.entries.len() as u32)?;
+        for (path, entry) in self.entries.iter() {
+            self.dest.write_u32::<LittleEndian>(path.len() as u32)?;
+            self.dest.write_all(path.as_bytes())?;
+            self.dest.write_u8(entry.compression as u8)?;
+            self.dest.write_u64::<LittleEndian>(entry.offset)?;
+            self.dest.write_u64::<LittleEndian>(entry.packed_size)?;
+            self.dest.write_u64::<LittleEndian>(entry.size)?;
+        }
+
+        self.dest.seek(SeekFrom::Start(
+            (PACK_MAGIC.len() + size_of::<u32>()) as u64,
+        ))?;
+        self.dest.write_u64::<LittleEndian>(index_offset)?;
+        self.dest.seek(SeekFrom::End(0))?;
+        self.dest.flush()?;
+
+        Ok(self.dest)
+    }
+}
+
+/// Read-only resource IO that fetches files from a pack file created by [`PackWriter`]. It is
+/// meant to be used in shipping builds of a game, where a single archive is much easier to
+/// distribute than thousands of loose files. Only the index of the pack is kept in memory, file
+/// data is read on demand.
+///
+/// Use [`crate::io::OverlayResourceIo`] to combine a pack with other sources, for example to let
+/// loose files on the file system override the content of the pack (useful for patches and mods).
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use fyrox_resource::{manager::ResourceManager, pack::PackedResourceIo};
+/// use std::sync::Arc;
+///
+/// fn mount(resource_manager: &ResourceManager) {
+///     let io = PackedResourceIo::open("data.pak").unwrap();
+///     resource_manager.state().set_resource_io(Arc::new(io));
+/// }
+/// ```
+pub struct PackedResourceIo {
+    reader: Mutex<Box<dyn FileReader>>,
+    entries: FxHashMap<String, PackEntry>,
+    directories: FxHashMap<String, Vec<String>>,
+}
+
+impl Debug for PackedResourceIo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PackedResourceIo")
+            .field("entries", &self.entries.len())
+            .field("directories", &self.directories.len())
+            .finish()
+    }
+}
+
+fn invalid_data(reason: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.into())
+}
+
+fn parent_of(path: &str) -> &str {
+    path.rfind('/').map_or("", |pos| &path[..pos])
+}
+
+impl PackedResourceIo {
+    /// Default name of a pack file that is produced by the editor when exporting a game.
+    pub const DEFAULT_FILE_NAME: &'static str = "data.pak";
+
+    /// Opens a pack file at the given path.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Self::new(Box::new(io::BufReader::new(file)))
+    }
+
+    /// Creates new pack IO from the pack data in memory.
+    pub fn from_bytes(bytes: Vec<u8>) -> io::Result<Self> {
+        Self::new(Box::new(Cursor::new(bytes)))
+    }
+
+    /// Creates new pack IO from an arbitrary reader. The reader must point to the beginning of
+    /// a pack file.
+    pub fn new(mut reader: Box<dyn FileReader>) -> io::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != PACK_MAGIC {
+            return Err(invalid_data("Not a pack file."));
+        }
+
+        let version = reader.read_u32::<LittleEndian>()?;
+        if version != PACK_VERSION {
+            return Err(invalid_data(format!(
+                "Unsupported pack file version {version}, expected {PACK_VERSION}."
+            )));
+        }
+
+        let index_offset = reader.read_u64::<LittleEndian>()?;
+        let data_offset = reader.stream_position()?;
+
+        // Every size in the pack is checked against the actual length of the data, so a corrupted
+        // pack can't force huge allocations.
+        let pack_len = reader.seek(SeekFrom::End(0))?;
+        if index_offset < data_offset || index_offset > pack_len {
+            return Err(invalid_data(format!(
+                "Index offset {index_offset} is out of bounds."
+            )));
+        }
+        reader.seek(SeekFrom::Start(index_offset))?;
+
+        let count = reader.read_u32::<LittleEndian>()?;
+        let mut entries = FxHashMap::default();
+        let mut directories = FxHashMap::<String, Vec<String>>::default();
+        for _ in 0..count {
+            let len = reader.read_u32::<LittleEndian>()? as u64;
+            if len > pack_len - reader.stream_position()? {
+                return Err(invalid_data(format!("Path length {len} is out of bounds.")));
+            }
+            let mut path = Vec::new();
+            reader.by_ref().take(len).read_to_end(&mut path)?;
+            let path = String::from_utf8(path).map_err(|e| invalid_data(e.to_string()))?;
+
+            let compression = reader.read_u8()?;
+            let entry = PackEntry {
+                compression: PackCompression::from_u8(compression).ok_or_else(|| {
+                    invalid_data(format!("Unknown compression {compression} of {path}."))
+                })?,
+                offset: reader.read_u64::<LittleEndian>()?,
+                packed_size: reader.read_u64::<LittleEndian>()?,
+                size: reader.read_u64::<LittleEndian>()?,
+            };
+
+            if entry
+                .offset
+                .checked_add(entry.packed_size)
+                .map_or(true, |end| entry.offset < data_offset || end > index_offset)
+            {
+                return Err(invalid_data(format!("Data of {path} is out of bounds.")));
+            }
+
+            // Register every parent directory of the entry.
+            let mut child = path.clone();
+            loop {
+                let parent = parent_of(&child).to_string();
+                let children = directories.entry(parent.clone()).or_default();
+                let is_new_dir = children.is_empty();
+                children.push(child);
+                if !is_new_dir || parent.is_empty() {
+                    break;
+                }
+                child = parent;
+            }
+
+            entries.insert(path, entry);
+        }
+
+        Ok(Self {
+            reader: Mutex::new(reader),
+            entries,
+            directories,
+        })
+    }
+
+    /// Returns an entry for the given path, if any.
+    pub fn entry(&self, path: &Path) -> Option<&PackEntry> {
+        self.entries.get(&normalize_path(path))
+    }
+
+    /// Returns an iterator over paths of every file in the pack.
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|path| path.as_str())
+    }
+
+    /// Returns `true` if the pack contains a file at the given path.
+    pub fn contains_file(&self, path: &Path) -> bool {
+        self.entry(path).is_some()
+    }
+
+    /// Returns `true` if the pack contains a directory at the given path.
+    pub fn contains_directory(&self, path: &Path) -> bool {
+        self.directories.contains_key(&normalize_path(path))
+    }
+
+    /// Reads and decompresses the content of the file at the given path.
+    pub fn read_file(&self, path: &Path) -> Result<Vec<u8>, FileLoadError> {
+        let Some(entry) = self.entry(path) else {
+            return Err(FileLoadError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} does not exist in the pack.", path.display()),
+            )));
+        };
+
+        let mut packed = vec![0; entry.packed_size as usize];
+        {
+            let mut reader = self.reader.lock();
+            reader.seek(SeekFrom::Start(entry.offset))?;
+            reader.read_exact(&mut packed)?;
+        }
+
+        match entry.compression {
+            PackCompression::None => Ok(packed),
+            PackCompression::Deflate => {
+                // Decompress at most one byte more than expected, it is enough to detect corruption.
+                let mut data = Vec::new();
+                DeflateDecoder::new(packed.as_slice())
+                    .take(entry.size.saturating_add(1))
+                    .read_to_end(&mut data)?;
+                if data.len() as u64 != entry.size {
+                    return Err(FileLoadError::Custom(format!(
+                        "{} is corrupted: expected {} bytes, got {}.",
+                        path.display(),
+                        entry.size,
+                        data.len()
+                    )));
+                }
+                Ok(data)
+            }
+        }
+    }
+
+    fn walk(&self, dir: &str, paths: &mut Vec<PathBuf>) {
+        if let Some(children) = self.directories.get(dir) {
+            for child in children {
+                paths.push(PathBuf::from(child));
+                self.walk(child, paths);
+            }
+        }
+    }
+}
+
+impl ResourceIo for PackedResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(ready(self.read_file(path)))
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        _dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(ready(Err(FileLoadError::Custom(format!(
+            "Unable to move {}, packed resources are read-only.",
+            source.display()
+        )))))
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        Box::pin(ready(Ok(PathBuf::from(normalize_path(path)))))
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        let paths = self
+            .directories
+            .get(&normalize_path(path))
+            .map(|children| children.iter().map(PathBuf::from).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let iter: PathIter = Box::new(paths.into_iter());
+        Box::pin(ready(Ok(iter)))
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        let dir = normalize_path(path);
+        let mut paths = Vec::new();
+        if self.directories.contains_key(&dir) {
+            // Mimic `WalkDir` which yields the root directory too.
+            paths.push(PathBuf::from(&dir));
+            self.walk(&dir, &mut paths);
+        }
+        let iter: PathIter = Box::new(paths.into_iter());
+        Box::pin(ready(Ok(iter)))
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(ready(
+            self.contains_file(path) || self.contains_directory(path),
+        ))
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(ready(self.contains_file(path)))
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(ready(self.contains_directory(path)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{core::futures::executor::block_on, io::OverlayResourceIo};
+    use std::sync::Arc;
+
+    fn make_pack(compression: PackCompression) -> Vec<u8> {
+        let mut writer = PackWriter::new(Cursor::new(Vec::new())).unwrap();
+        writer.set_compression(compression);
+        writer
+            .add_file(Path::new("./data/textures/foo.png"), &[1, 2, 3])
+            .unwrap();
+        writer
+            .add_file(Path::new("data/scene.rgs"), "scene ".repeat(100).as_bytes())
+            .unwrap();
+        writer.add_file(Path::new("readme.txt"), b"hello").unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path(Path::new("./data/foo.png")), "data/foo.png");
+        assert_eq!(
+            normalize_path(Path::new("data/../data/./a\\b.png")),
+            "data/a/b.png"
+        );
+        assert_eq!(normalize_path(Path::new("../foo")), "../foo");
+        assert_eq!(normalize_path(Path::new("data/")), "data");
+    }
+
+    #[test]
+    fn test_pack_round_trip() {
+        for compression in [PackCompression::None, PackCompression::Deflate] {
+            let io = PackedResourceIo::from_bytes(make_pack(compression)).unwrap();
+
+            assert_eq!(
+                block_on(io.load_file(Path::new("data/textures/foo.png"))).unwrap(),
+                vec![1, 2, 3]
+            );
+            assert_eq!(
+                block_on(io.load_file(Path::new("./data/scene.rgs"))).unwrap(),
+                "scene ".repeat(100).into_bytes()
+            );
+            assert_eq!(
+                block_on(io.load_file(Path::new("readme.txt"))).unwrap(),
+                b"hello"
+            );
+            assert!(block_on(io.load_file(Path::new("data/missing.png"))).is_err());
+
+            // Tiny files never get bigger because of compression.
+            assert_eq!(
+                io.entry(Path::new("data/textures/foo.png"))
+                    .unwrap()
+                    .compression,
+                PackCompression::None
+            );
+            assert_eq!(
+                io.entry(Path::new("data/scene.rgs")).unwrap().compression,
+                compression
+            );
+        }
+    }
+
+    #[test]
+    fn test_pack_queries() {
+        let io = PackedResourceIo::from_bytes(make_pack(PackCompression::Deflate)).unwrap();
+
+        assert!(block_on(io.exists(Path::new("data"))));
+        assert!(block_on(io.is_dir(Path::new("data/textures"))));
+        assert!(!block_on(io.is_file(Path::new("data/textures"))));
+        assert!(block_on(io.is_file(Path::new("data/textures/foo.png"))));
+        assert!(!block_on(io.exists(Path::new("data/foo"))));
+
+        let mut dir = block_on(io.read_directory(Path::new("./data")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        dir.sort();
+        assert_eq!(
+            dir,
+            vec![
+                PathBuf::from("data/scene.rgs"),
+                PathBuf::from("data/textures")
+            ]
+        );
+
+        let mut all = block_on(io.walk_directory(Path::new("data")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        all.sort();
+        assert_eq!(
+            all,
+            vec![
+                PathBuf::from("data"),
+                PathBuf::from("data/scene.rgs"),
+                PathBuf::from("data/textures"),
+                PathBuf::from("data/textures/foo.png"),
+            ]
+        );
+
+        assert!(block_on(io.move_file(Path::new("readme.txt"), Path::new("a.txt"))).is_err());
+    }
+
+    #[test]
+    fn test_pack_rejects_duplicates_and_garbage() {
+        let mut writer = PackWriter::new(Cursor::new(Vec::new())).unwrap();
+        writer.add_file(Path::new("a.txt"), b"a").unwrap();
+        assert!(writer.add_file(Path::new("./a.txt"), b"b").is_err());
+
+        assert!(PackedResourceIo::from_bytes(b"RG3D0000".to_vec()).is_err());
+        assert!(PackedResourceIo::from_bytes(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_pack_rejects_out_of_bounds_sizes() {
+        let pack = make_pack(PackCompression::None);
+        let index_offset = u64::from_le_bytes(pack[8..16].try_into().unwrap()) as usize;
+        let path_len_offset = index_offset + 4;
+        let path_len = u32::from_le_bytes(
+            pack[path_len_offset..path_len_offset + 4]
+                .try_into()
+                .unwrap(),
+        );
+        let packed_size_offset = path_len_offset + 4 + path_len as usize + 1 + 8;
+
+        let mut bad_index = pack.clone();
+        bad_index[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(PackedResourceIo::from_bytes(bad_index).is_err());
+
+        let mut bad_path = pack.clone();
+        bad_path[path_len_offset..path_len_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(PackedResourceIo::from_bytes(bad_path).is_err());
+
+        let mut bad_entry = pack;
+        bad_entry[packed_size_offset..packed_size_offset + 8]
+            .copy_from_slice(&(u64::MAX / 2).to_le_bytes());
+        assert!(PackedResourceIo::from_bytes(bad_entry).is_err());
+    }
+
+    #[test]
+    fn test_overlay() {
+        let mut patch = PackWriter::new(Cursor::new(Vec::new())).unwrap();
+        patch.add_file(Path::new("readme.txt"), b"patched").unwrap();
+        patch.add_file(Path::new("data/mod.rgs"), b"mod").unwrap();
+        let patch = PackedResourceIo::from_bytes(patch.finish().unwrap().into_inner()).unwrap();
+        let base = PackedResourceIo::from_bytes(make_pack(PackCompression::Deflate)).unwrap();
+
+        let io = OverlayResourceIo::new(vec![Arc::new(patch), Arc::new(base)]);
+
+        assert_eq!(
+            block_on(io.load_file(Path::new("readme.txt"))).unwrap(),
+            b"patched"
+        );
+        assert_eq!(
+            block_on(io.load_file(Path::new("data/textures/foo.png"))).unwrap(),
+            vec![1, 2, 3]
+        );
+        assert!(block_on(io.is_file(Path::new("data/mod.rgs"))));
+
+        let mut dir = block_on(io.read_directory(Path::new("data")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        dir.sort();
+        assert_eq!(
+            dir,
+            vec![
+                PathBuf::from("data/mod.rgs"),
+                PathBuf::from("data/scene.rgs"),
+                PathBuf::from("data/textures")
+            ]
+        );
+    }
+    /// A layer that lists the same files using a different (but equivalent) form of the paths.
+    struct LooseFilesIo(Vec<PathBuf>);
+
+    impl ResourceIo for LooseFilesIo {
+        fn load_file<'a>(
+            &'a self,
+            _path: &'a Path,
+        ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+            Box::pin(async move { Err(FileLoadError::Io(std::io::ErrorKind::NotFound.into())) })
+        }
+
+        fn move_file<'a>(
+            &'a self,
+            _source: &'a Path,
+            _dest: &'a Path,
+        ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn read_directory<'a>(
+            &'a self,
+            _path: &'a Path,
+        ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+            let iter: PathIter = Box::new(self.0.clone().into_iter());
+            Box::pin(async move { Ok(iter) })
+        }
+
+        fn exists<'a>(&'a self, _path: &'a Path) -> ResourceIoFuture<'a, bool> {
+            Box::pin(async move { true })
+        }
+
+        fn is_file<'a>(&'a self, _path: &'a Path) -> ResourceIoFuture<'a, bool> {
+            Box::pin(async move { false })
+        }
+
+        fn is_dir<'a>(&'a self, _path: &'a Path) -> ResourceIoFuture<'a, bool> {
+            Box::pin(async move { true })
+        }
+    }
+
+    #[test]
+    fn test_overlay_merges_equivalent_paths() {
+        let loose = LooseFilesIo(vec![
+            PathBuf::from("./data/scene.rgs"),
+            PathBuf::from("data/textures/../mod.rgs"),
+        ]);
+        let base = PackedResourceIo::from_bytes(make_pack(PackCompression::None)).unwrap();
+
+        let io = OverlayResourceIo::new(vec![Arc::new(loose), Arc::new(base)]);
+
+        let mut dir = block_on(io.read_directory(Path::new("data")))
+            .unwrap()
+            .map(|path| normalize_path(&path))
+            .collect::<Vec<_>>();
+        dir.sort();
+        assert_eq!(dir, vec!["data/mod.rgs", "data/scene.rgs", "data/textures"]);
+    }
+}
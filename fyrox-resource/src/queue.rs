@@ -0,0 +1,500 @@
+// Copyright (c) 2019-present Dmitry Stepanov and Fyrox Engine contributors.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Resource loading queue. It orders loading tasks by their priority, limits the amount of tasks
+//! running at the same time and cancels cancellable tasks of resources that are not needed
+//! anymore, both before they start and before their results are committed. See [`ResourceLoadPriority`] docs for more info.
+
+use crate::{
+    core::{parking_lot::Mutex, task::TaskPool, uuid::Uuid},
+    untyped::ResourceHeader,
+};
+use fxhash::FxHashMap;
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Weak},
+};
+
+/// Priority of a resource loading request. Requests with higher priority are started first, this
+/// is important when the amount of simultaneous loading tasks is limited (see
+/// [`crate::manager::ResourceManagerState::set_concurrency_limit`]), otherwise every request is
+/// started immediately. Requests with the same priority are started in the order they were made.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ResourceLoadPriority {
+    /// Resources that are not needed right now, but will likely be needed soon (for example,
+    /// streaming of nearby parts of a level).
+    Background,
+    /// Default priority.
+    #[default]
+    Normal,
+    /// Resources that are needed as soon as possible (for example, a level the player is being
+    /// teleported to).
+    Critical,
+}
+
+/// Stores results of a finished loading task in the resource. It is called only if the task was
+/// not cancelled while it was running.
+#[cfg(target_arch = "wasm32")]
+pub(crate) type LoadCommit = Box<dyn FnOnce()>;
+/// Stores results of a finished loading task in the resource. It is called only if the task was
+/// not cancelled while it was running.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) type LoadCommit = Box<dyn FnOnce() + Send>;
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) type LoadFuture = Pin<Box<dyn Future<Output = LoadCommit>>>;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) type LoadFuture = Pin<Box<dyn Future<Output = LoadCommit> + Send>>;
+
+struct QueuedTask {
+    priority: ResourceLoadPriority,
+    sequence: u64,
+    type_uuid: Uuid,
+    path: PathBuf,
+    cancellable: bool,
+    resource: Weak<Mutex<ResourceHeader>>,
+    future: LoadFuture,
+}
+
+impl QueuedTask {
+    // The queue holds only a weak reference to a resource and the resource manager holds a
+    // strong one, so a single strong reference means that no one else needs the resource. Only
+    // the tasks that were explicitly requested as cancellable are cancelled, otherwise requests
+    // made just to preload a resource would be dropped.
+    fn is_cancelled(&self) -> bool {
+        is_unused(self.cancellable, &self.resource)
+    }
+}
+
+fn is_unused(cancellable: bool, resource: &Weak<Mutex<ResourceHeader>>) -> bool {
+    cancellable && resource.strong_count() <= 1
+}
+
+impl PartialEq for QueuedTask {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedTask {}
+
+impl PartialOrd for QueuedTask {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedTask {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Max-heap: higher priority first, then older requests first.
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+#[derive(Default)]
+struct LoadQueueState {
+    queued: BinaryHeap<QueuedTask>,
+    running: FxHashMap<Uuid, usize>,
+    total_running: usize,
+    limits: FxHashMap<Uuid, usize>,
+    total_limit: Option<usize>,
+    cancelled: FxHashMap<PathBuf, ResourceLoadPriority>,
+    sequence: u64,
+}
+
+impl LoadQueueState {
+    fn can_run(&self, type_uuid: &Uuid) -> bool {
+        self.total_limit
+            .map_or(true, |limit| self.total_running < limit)
+            && self.limits.get(type_uuid).map_or(true, |limit| {
+                self.running.get(type_uuid).cloned().unwrap_or_default() < *limit
+            })
+    }
+
+    fn next_task(&mut self) -> Option<QueuedTask> {
+        let mut blocked = Vec::new();
+        let mut result = None;
+        while let Some(task) = self.queued.pop() {
+            if task.is_cancelled() {
+                self.cancelled.insert(task.path, task.priority);
+            } else if self.can_run(&task.type_uuid) {
+                result = Some(task);
+                break;
+            } else {
+                blocked.push(task);
+            }
+        }
+        self.queued.extend(blocked);
+        result
+    }
+}
+
+/// A shared queue of loading tasks.
+#[derive(Default, Clone)]
+pub(crate) struct LoadQueue {
+    state: Arc<Mutex<LoadQueueState>>,
+}
+
+impl LoadQueue {
+    /// Adds a new task to the queue and starts as many tasks as the limits allow. Cancellable
+    /// tasks are cancelled if no one except the resource manager holds the resource by the time
+    /// the task should be started or by the time its results should be committed.
+    #[allow(clippy::too_many_arguments)]
+    pub fn push(
+        &self,
+        task_pool: &Arc<TaskPool>,
+        resource: &Arc<Mutex<ResourceHeader>>,
+        path: PathBuf,
+        type_uuid: Uuid,
+        priority: ResourceLoadPriority,
+        cancellable: bool,
+        future: LoadFuture,
+    ) {
+        {
+            let mut state = self.state.lock();
+            let sequence = state.sequence;
+            state.sequence += 1;
+            state.cancelled.remove(&path);
+            state.queued.push(QueuedTask {
+                priority,
+                sequence,
+                type_uuid,
+                path,
+                cancellable,
+                resource: Arc::downgrade(resource),
+                future,
+            });
+        }
+        self.dispatch(task_pool);
+    }
+
+    /// Raises priority of a queued task of a resource at the given path and makes the task
+    /// non-cancellable if `cancellable` is `false`. Does nothing if the task has higher priority
+    /// already or if it is not in the queue.
+    pub fn raise_priority(&self, path: &Path, priority: ResourceLoadPriority, cancellable: bool) {
+        let mut state = self.state.lock();
+        if state.queued.iter().any(|task| {
+            task.path == path && (task.priority < priority || task.cancellable && !cancellable)
+        }) {
+            let mut tasks = std::mem::take(&mut state.queued).into_vec();
+            for task in tasks.iter_mut() {
+                if task.path == path {
+                    task.priority = task.priority.max(priority);
+                    task.cancellable &= cancellable;
+                }
+            }
+            state.queued = tasks.into();
+        }
+    }
+
+    /// Starts as many queued tasks as the limits allow.
+    pub fn dispatch(&self, task_pool: &Arc<TaskPool>) {
+        loop {
+            let task = {
+                let mut state = self.state.lock();
+                let Some(task) = state.next_task() else {
+                    break;
+                };
+                *state.running.entry(task.type_uuid).or_default() += 1;
+                state.total_running += 1;
+                task
+            };
+
+            let queue = self.clone();
+            let pool = task_pool.clone();
+            task_pool.spawn_task(async move {
+                let QueuedTask {
+                    priority,
+                    type_uuid,
+                    path,
+                    cancellable,
+                    resource,
+                    future,
+                    ..
+                } = task;
+
+                // The thread pool could be busy for a while, so check if the resource is still
+                // needed before doing any actual work.
+                if is_unused(cancellable, &resource) {
+                    queue.state.lock().cancelled.insert(path, priority);
+                } else {
+                    let commit = future.await;
+                    // The resource could become unused while it was loading, in this case the
+                    // results are discarded and the resource is handled as any other cancelled one.
+                    if is_unused(cancellable, &resource) {
+                        queue.state.lock().cancelled.insert(path, priority);
+                    } else {
+                        commit();
+                    }
+                }
+
+                {
+                    let mut state = queue.state.lock();
+                    if let Some(running) = state.running.get_mut(&type_uuid) {
+                        *running = running.saturating_sub(1);
+                    }
+                    state.total_running = state.total_running.saturating_sub(1);
+                }
+
+                queue.dispatch(&pool);
+            });
+        }
+    }
+
+    /// Sets the maximum amount of simultaneously running tasks for resources of the given type.
+    pub fn set_limit(&self, type_uuid: Uuid, limit: Option<usize>) {
+        let mut state = self.state.lock();
+        match limit {
+            Some(limit) => {
+                state.limits.insert(type_uuid, limit.max(1));
+            }
+            None => {
+                state.limits.remove(&type_uuid);
+            }
+        }
+    }
+
+    /// Returns the maximum amount of simultaneously running tasks for resources of the given type.
+    pub fn limit(&self, type_uuid: Uuid) -> Option<usize> {
+        self.state.lock().limits.get(&type_uuid).cloned()
+    }
+
+    /// Sets the maximum amount of simultaneously running tasks.
+    pub fn set_total_limit(&self, limit: Option<usize>) {
+        self.state.lock().total_limit = limit.map(|limit| limit.max(1));
+    }
+
+    /// Returns the maximum amount of simultaneously running tasks.
+    pub fn total_limit(&self) -> Option<usize> {
+        self.state.lock().total_limit
+    }
+
+    /// Returns the amount of tasks that are waiting in the queue.
+    pub fn queued_count(&self) -> usize {
+        self.state.lock().queued.len()
+    }
+
+    /// Returns the amount of tasks that are running right now.
+    pub fn running_count(&self) -> usize {
+        self.state.lock().total_running
+    }
+
+    /// Returns `true` if loading of a resource at the given path was cancelled and clears the
+    /// flag.
+    pub fn take_cancelled(&self, path: &Path) -> bool {
+        self.state.lock().cancelled.remove(path).is_some()
+    }
+
+    /// Returns paths of every resource whose loading was cancelled, along with the priority of the
+    /// cancelled task, and clears the list.
+    pub fn drain_cancelled(&self) -> FxHashMap<PathBuf, ResourceLoadPriority> {
+        std::mem::take(&mut self.state.lock().cancelled)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{core::futures::channel::oneshot, untyped::UntypedResource};
+    use std::time::{Duration, Instant};
+
+    fn wait_until(mut condition: impl FnMut() -> bool) {
+        let start = Instant::now();
+        while !condition() {
+            assert!(start.elapsed() < Duration::from_secs(10), "Timed out!");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn no_commit() -> LoadCommit {
+        Box::new(|| ())
+    }
+
+    fn push_blocker(
+        queue: &LoadQueue,
+        pool: &Arc<TaskPool>,
+        resource: &UntypedResource,
+    ) -> oneshot::Sender<()> {
+        let (sender, receiver) = oneshot::channel::<()>();
+        queue.push(
+            pool,
+            &resource.0,
+            PathBuf::from("blocker"),
+            Uuid::default(),
+            ResourceLoadPriority::Critical,
+            false,
+            Box::pin(async move {
+                let _ = receiver.await;
+                no_commit()
+            }),
+        );
+        sender
+    }
+
+    #[test]
+    fn test_load_queue_priority() {
+        let pool = Arc::new(TaskPool::new());
+        let queue = LoadQueue::default();
+        queue.set_total_limit(Some(1));
+        assert_eq!(queue.total_limit(), Some(1));
+
+        let resources = (0..4)
+            .map(|_| UntypedResource::default())
+            .collect::<Vec<_>>();
+
+        let sender = push_blocker(&queue, &pool, &resources[0]);
+        wait_until(|| queue.running_count() == 1);
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        for (i, (resource, priority)) in resources[1..]
+            .iter()
+            .zip([
+                ResourceLoadPriority::Background,
+                ResourceLoadPriority::Normal,
+                ResourceLoadPriority::Background,
+            ])
+            .enumerate()
+        {
+            let order = order.clone();
+            let key = resource.key();
+            queue.push(
+                &pool,
+                &resource.0,
+                PathBuf::from(format!("{}", i + 1)),
+                Uuid::default(),
+                priority,
+                false,
+                Box::pin(async move {
+                    order.lock().push(key);
+                    no_commit()
+                }),
+            );
+        }
+        queue.raise_priority(Path::new("3"), ResourceLoadPriority::Critical, false);
+        assert_eq!(queue.queued_count(), 3);
+
+        sender.send(()).unwrap();
+        wait_until(|| order.lock().len() == 3);
+
+        assert_eq!(
+            *order.lock(),
+            [resources[3].key(), resources[2].key(), resources[1].key()]
+        );
+    }
+
+    #[test]
+    fn test_load_queue_cancellation() {
+        let pool = Arc::new(TaskPool::new());
+        let queue = LoadQueue::default();
+        let type_uuid = Uuid::new_v4();
+        queue.set_limit(type_uuid, Some(1));
+        assert_eq!(queue.limit(type_uuid), Some(1));
+
+        let blocker = UntypedResource::default();
+        let _holder = blocker.clone();
+        let (sender, receiver) = oneshot::channel::<()>();
+        queue.push(
+            &pool,
+            &blocker.0,
+            PathBuf::from("blocker"),
+            type_uuid,
+            ResourceLoadPriority::Normal,
+            false,
+            Box::pin(async move {
+                let _ = receiver.await;
+                no_commit()
+            }),
+        );
+
+        // Only one strong reference (like the one held by the resource manager), so no one needs
+        // the resources.
+        let mut flags = Vec::new();
+        let mut resources = Vec::new();
+        for (path, cancellable) in [("unused", true), ("preloaded", false)] {
+            let resource = UntypedResource::default();
+            let loaded = Arc::new(Mutex::new(false));
+            let flag = loaded.clone();
+            queue.push(
+                &pool,
+                &resource.0,
+                PathBuf::from(path),
+                type_uuid,
+                ResourceLoadPriority::Normal,
+                cancellable,
+                Box::pin(async move { Box::new(move || *flag.lock() = true) as LoadCommit }),
+            );
+            flags.push(loaded);
+            resources.push(resource);
+        }
+
+        sender.send(()).unwrap();
+        wait_until(|| queue.running_count() == 0 && queue.queued_count() == 0);
+
+        // Only the cancellable request must be cancelled.
+        assert!(!*flags[0].lock());
+        assert!(*flags[1].lock());
+        assert!(queue.take_cancelled(Path::new("unused")));
+        assert!(!queue.take_cancelled(Path::new("unused")));
+        assert!(!queue.take_cancelled(Path::new("preloaded")));
+    }
+
+    #[test]
+    fn test_load_queue_cancellation_of_running_task() {
+        let pool = Arc::new(TaskPool::new());
+        let queue = LoadQueue::default();
+
+        let resource = UntypedResource::default();
+        let holder = resource.clone();
+        let committed = Arc::new(Mutex::new(false));
+        let flag = committed.clone();
+        let (sender, receiver) = oneshot::channel::<()>();
+        queue.push(
+            &pool,
+            &resource.0,
+            PathBuf::from("running"),
+            Uuid::default(),
+            ResourceLoadPriority::Background,
+            true,
+            Box::pin(async move {
+                let _ = receiver.await;
+                Box::new(move || *flag.lock() = true) as LoadCommit
+            }),
+        );
+        wait_until(|| queue.running_count() == 1);
+
+        // No one needs the resource anymore while it is loading.
+        drop(holder);
+        sender.send(()).unwrap();
+        wait_until(|| queue.running_count() == 0);
+
+        assert!(!*committed.lock());
+        let cancelled = queue.drain_cancelled();
+        assert_eq!(
+            cancelled.get(Path::new("running")),
+            Some(&ResourceLoadPriority::Background)
+        );
+    }
+}
@@ -414,6 +414,15 @@ impl UntypedResource {
         self.0.lock().kind.is_embedded()
     }
 
+    /// Returns an estimate of the amount of memory (in bytes) occupied by the resource data (see
+    /// [`ResourceData::memory_usage`]). Returns zero if the resource is not loaded.
+    pub fn memory_usage(&self) -> usize {
+        match self.0.lock().state {
+            ResourceState::Ok(ref data) => data.memory_usage(),
+            _ => 0,
+        }
+    }
+
     /// Returns exact amount of users of the resource.
     #[inline]
     pub fn use_count(&self) -> usize {
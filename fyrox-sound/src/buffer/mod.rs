@@ -289,6 +289,10 @@ impl ResourceData for SoundBuffer {
         SOUND_BUFFER_RESOURCE_UUID
     }
 
+    fn memory_usage(&self) -> usize {
+        size_of_val(self.samples())
+    }
+
     fn save(&mut self, _path: &Path) -> Result<(), Box<dyn Error>> {
         Err("Saving is not supported!".to_string().into())
     }
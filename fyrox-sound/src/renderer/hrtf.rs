@@ -226,6 +226,19 @@ impl ResourceData for HrirSphereResourceData {
         <Self as TypeUuidProvider>::type_uuid()
     }
 
+    fn memory_usage(&self) -> usize {
+        self.hrir_sphere.as_ref().map_or(0, |sphere| {
+            sphere
+                .points()
+                .iter()
+                .map(|point| {
+                    std::mem::size_of_val(point.left_hrir())
+                        + std::mem::size_of_val(point.right_hrir())
+                })
+                .sum()
+        })
+    }
+
     fn save(&mut self, _path: &Path) -> Result<(), Box<dyn Error>> {
         Err("Saving is not supported!".to_string().into())
     }
@@ -297,6 +297,10 @@ impl ResourceData for Texture {
         <Self as TypeUuidProvider>::type_uuid()
     }
 
+    fn memory_usage(&self) -> usize {
+        self.bytes.len()
+    }
+
     fn save(&mut self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
         let color_type = match self.pixel_kind {
             TexturePixelKind::R8 => ColorType::L8,
@@ -360,7 +364,7 @@ impl Visit for Texture {
         self.anisotropy.visit("Anisotropy", &mut region)?;
         self.s_wrap_mode.visit("SWrapMode", &mut region)?;
         self.t_wrap_mode.visit("TWrapMode", &mut region)?;
-        let _ = self.t_wrap_mode.visit("RWrapMode", &mut region);
+        let _ = self.r_wrap_mode.visit("RWrapMode", &mut region);
         self.mip_count.visit("MipCount", &mut region)?;
         self.kind.visit("Kind", &mut region)?;
         let mut bytes_view = PodVecView::from_pod_vec(&mut self.bytes);
@@ -1993,3 +1997,29 @@ impl TextureDataRefMut<'_> {
         None
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{Texture, TextureWrapMode};
+    use fyrox_core::visitor::{Visit, Visitor};
+
+    #[test]
+    fn test_texture_wrap_modes_serialization() {
+        let mut texture = Texture::default();
+        texture.set_s_wrap_mode(TextureWrapMode::MirroredRepeat);
+        texture.set_t_wrap_mode(TextureWrapMode::Repeat);
+        texture.set_r_wrap_mode(TextureWrapMode::ClampToEdge);
+
+        let mut visitor = Visitor::new();
+        texture.visit("Texture", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let mut visitor = Visitor::load_from_memory(&data).unwrap();
+        let mut loaded = Texture::default();
+        loaded.visit("Texture", &mut visitor).unwrap();
+
+        assert_eq!(loaded.s_wrap_mode(), TextureWrapMode::MirroredRepeat);
+        assert_eq!(loaded.t_wrap_mode(), TextureWrapMode::Repeat);
+        assert_eq!(loaded.r_wrap_mode(), TextureWrapMode::ClampToEdge);
+    }
+}
@@ -23,9 +23,16 @@
 use crate::{Texture, TextureImportOptions};
 use fyrox_core::{uuid::Uuid, TypeUuidProvider};
 use fyrox_resource::{
-    io::ResourceIo, loader::BoxedImportOptionsLoaderFuture, loader::BoxedLoaderFuture,
-    loader::LoaderPayload, loader::ResourceLoader, options::try_get_import_settings,
-    options::try_get_import_settings_opaque, options::BaseImportOptions, state::LoadError,
+    cache::{ImportCache, ImportCacheKey},
+    io::ResourceIo,
+    loader::BoxedImportOptionsLoaderFuture,
+    loader::BoxedLoaderFuture,
+    loader::LoaderPayload,
+    loader::ResourceLoader,
+    options::try_get_import_settings,
+    options::try_get_import_settings_opaque,
+    options::BaseImportOptions,
+    state::LoadError,
 };
 use std::{path::PathBuf, sync::Arc};
 
@@ -33,6 +40,15 @@ use std::{path::PathBuf, sync::Arc};
 pub struct TextureLoader {
     /// Default import options for textures.
     pub default_import_options: TextureImportOptions,
+    /// Cache for processed (decoded, compressed, with generated mips) textures. See
+    /// [`ImportCache`] docs for more info.
+    pub import_cache: ImportCache,
+}
+
+impl TextureLoader {
+    /// Version of the texture import pipeline. Must be increased every time when the loader starts
+    /// to produce different output for the same input, to invalidate stale import cache entries.
+    pub const IMPORT_CACHE_VERSION: u32 = 1;
 }
 
 impl ResourceLoader for TextureLoader {
@@ -48,6 +64,7 @@ impl ResourceLoader for TextureLoader {
 
     fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
         let default_import_options = self.default_import_options.clone();
+        let import_cache = self.import_cache.clone();
         Box::pin(async move {
             let io = io.as_ref();
 
@@ -55,9 +72,37 @@ impl ResourceLoader for TextureLoader {
                 .await
                 .unwrap_or(default_import_options);
 
-            let raw_texture = Texture::load_from_file(&path, io, import_options)
-                .await
-                .map_err(LoadError::new)?;
+            // DDS textures are already processed, so there's nothing to cache.
+            let is_dds = path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("dds"));
+            if !import_cache.is_enabled() || is_dds {
+                let raw_texture = Texture::load_from_file(&path, io, import_options)
+                    .await
+                    .map_err(LoadError::new)?;
+
+                return Ok(LoaderPayload::new(raw_texture));
+            }
+
+            let data = io.load_file(&path).await.map_err(LoadError::new)?;
+            let key = ImportCacheKey::new(
+                "texture",
+                Self::IMPORT_CACHE_VERSION,
+                &data,
+                &import_options,
+            );
+            if let Some(texture) = key
+                .as_ref()
+                .and_then(|key| import_cache.load_data::<Texture>(key))
+            {
+                return Ok(LoaderPayload::new(texture));
+            }
+
+            let mut raw_texture =
+                Texture::load_from_memory(&data, import_options).map_err(LoadError::new)?;
+            if let Some(key) = key {
+                import_cache.store_data(&key, &mut raw_texture);
+            }
 
             Ok(LoaderPayload::new(raw_texture))
         })
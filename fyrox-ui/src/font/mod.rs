@@ -230,6 +230,21 @@ impl ResourceData for Font {
     fn can_be_saved(&self) -> bool {
         false
     }
+
+    fn memory_usage(&self) -> usize {
+        // Rasterized glyphs are the only part that grows over time.
+        self.atlases
+            .values()
+            .map(|atlas| {
+                std::mem::size_of_val(atlas.glyphs.as_slice())
+                    + atlas
+                        .pages
+                        .iter()
+                        .map(|page| page.pixels.len())
+                        .sum::<usize>()
+            })
+            .sum()
+    }
 }
 
 #[derive(Copy, Clone, Default, Debug)]
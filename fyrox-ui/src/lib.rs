@@ -322,7 +322,7 @@ use fyrox_core::futures::future::join_all;
 use fyrox_core::log::Log;
 use fyrox_graph::{
     AbstractSceneGraph, AbstractSceneNode, BaseSceneGraph, NodeHandleMap, NodeMapping, PrefabData,
-    SceneGraph, SceneGraphNode,
+    PrefabDataMut, SceneGraph, SceneGraphNode,
 };
 pub use node::*;
 pub use thickness::*;
@@ -3101,6 +3101,13 @@ impl PrefabData for UserInterface {
     }
 }
 
+impl PrefabDataMut for UserInterface {
+    #[inline]
+    fn graph_mut(&mut self) -> &mut Self::Graph {
+        self
+    }
+}
+
 impl AbstractSceneGraph for UserInterface {
     fn try_get_node_untyped(&self, handle: ErasedHandle) -> Option<&dyn AbstractSceneNode> {
         self.nodes